@@ -83,6 +83,7 @@ use crate::utils::{
     proposal_status_from,
     verify_block_input,
     ProposalResult,
+    ProposalRole,
     ProposalTask,
 };
 
@@ -108,11 +109,11 @@ pub struct Batcher {
     /// All proposals are considered to be at this height.
     active_height: Option<BlockNumber>,
 
-    /// The block proposal that is currently being built, if any.
-    /// At any given time, there can be only one proposal being actively executed (either proposed
-    /// or validated).
-    active_proposal: Arc<Mutex<Option<ProposalId>>>,
-    active_proposal_task: Option<ProposalTask>,
+    /// The block proposals that are currently being built (either proposed or validated), along
+    /// with their roles. Up to `max_concurrent_proposals` proposals are executed concurrently,
+    /// each by its own block builder, on top of its own state.
+    active_proposals: Arc<Mutex<HashMap<ProposalId, ProposalRole>>>,
+    active_proposal_tasks: HashMap<ProposalId, ProposalTask>,
 
     /// Holds all the proposals that completed execution in the current height.
     executed_proposals: Arc<Mutex<HashMap<ProposalId, ProposalResult<BlockExecutionArtifacts>>>>,
@@ -150,8 +151,8 @@ impl Batcher {
             block_builder_factory,
             pre_confirmed_block_writer_factory,
             active_height: None,
-            active_proposal: Arc::new(Mutex::new(None)),
-            active_proposal_task: None,
+            active_proposals: Arc::new(Mutex::new(HashMap::new())),
+            active_proposal_tasks: HashMap::new(),
             executed_proposals: Arc::new(Mutex::new(HashMap::new())),
             propose_tx_streams: HashMap::new(),
             validate_tx_streams: HashMap::new(),
@@ -194,14 +195,28 @@ impl Batcher {
             propose_block_input.retrospective_block_hash,
         )?;
 
+        // A proposal that starts while another proposal holds the L1 provider session is built
+        // speculatively.
+        let active_roles: Vec<ProposalRole> =
+            self.active_proposals.lock().await.values().copied().collect();
+        let role = if active_roles.iter().any(ProposalRole::holds_l1_provider_session) {
+            ProposalRole::SpeculativePropose
+        } else {
+            ProposalRole::Propose
+        };
+
         // TODO(yair): extract function for the following calls, use join_all.
-        self.mempool_client.commit_block(CommitBlockArgs::default()).await.map_err(|err| {
-            error!(
-                "Mempool is not ready to start proposal {}: {}.",
-                propose_block_input.proposal_id, err
-            );
-            BatcherError::NotReady
-        })?;
+        // Rewinding the mempool while another block is proposed would hand the other proposal the
+        // same transactions again.
+        if !active_roles.iter().any(ProposalRole::is_propose) {
+            self.mempool_client.commit_block(CommitBlockArgs::default()).await.map_err(|err| {
+                error!(
+                    "Mempool is not ready to start proposal {}: {}.",
+                    propose_block_input.proposal_id, err
+                );
+                BatcherError::NotReady
+            })?;
+        }
         self.mempool_client
             .update_gas_price(
                 propose_block_input.block_info.gas_prices.strk_gas_prices.l2_gas_price.get(),
@@ -211,34 +226,54 @@ impl Batcher {
                 error!("Failed to update gas price in mempool: {}", err);
                 BatcherError::InternalError
             })?;
-        self.l1_provider_client
-            .start_block(SessionState::Propose, propose_block_input.block_info.block_number)
-            .await
-            .map_err(|err| {
-                error!(
-                    "L1 provider is not ready to start proposing block {}: {}. ",
-                    propose_block_input.block_info.block_number, err
+        let max_l1_handler_txs_per_block = match role {
+            ProposalRole::Propose => {
+                self.l1_provider_client
+                    .start_block(SessionState::Propose, propose_block_input.block_info.block_number)
+                    .await
+                    .map_err(|err| {
+                        error!(
+                            "L1 provider is not ready to start proposing block {}: {}. ",
+                            propose_block_input.block_info.block_number, err
+                        );
+                        BatcherError::NotReady
+                    })?;
+                self.config.max_l1_handler_txs_per_block_proposal
+            }
+            _ => {
+                info!(
+                    "Building proposal {} speculatively, without L1 handler transactions.",
+                    propose_block_input.proposal_id
                 );
-                BatcherError::NotReady
-            })?;
+                0
+            }
+        };
 
         let tx_provider = ProposeTransactionProvider::new(
             self.mempool_client.clone(),
             self.l1_provider_client.clone(),
-            self.config.max_l1_handler_txs_per_block_proposal,
+            max_l1_handler_txs_per_block,
             propose_block_input.block_info.block_number,
         );
 
         // A channel to receive the transactions included in the proposed block.
         let (output_tx_sender, output_tx_receiver) = tokio::sync::mpsc::unbounded_channel();
 
-        let cende_block_metadata = CendeBlockMetadata::new(propose_block_input.block_info.clone());
+        // Speculative proposals are not published as pre-confirmed blocks.
         let (pre_confirmed_block_writer, candidate_tx_sender, pre_confirmed_tx_sender) =
-            self.pre_confirmed_block_writer_factory.create(
-                propose_block_input.block_info.block_number,
-                propose_block_input.proposal_round,
-                cende_block_metadata,
-            );
+            if role == ProposalRole::Propose {
+                let cende_block_metadata =
+                    CendeBlockMetadata::new(propose_block_input.block_info.clone());
+                let (writer, candidate_tx_sender, pre_confirmed_tx_sender) =
+                    self.pre_confirmed_block_writer_factory.create(
+                        propose_block_input.block_info.block_number,
+                        propose_block_input.proposal_round,
+                        cende_block_metadata,
+                    );
+                (Some(writer), Some(candidate_tx_sender), Some(pre_confirmed_tx_sender))
+            } else {
+                (None, None, None)
+            };
 
         let (block_builder, abort_signal_sender) = self
             .block_builder_factory
@@ -253,8 +288,8 @@ impl Batcher {
                 },
                 Box::new(tx_provider),
                 Some(output_tx_sender),
-                Some(candidate_tx_sender),
-                Some(pre_confirmed_tx_sender),
+                candidate_tx_sender,
+                pre_confirmed_tx_sender,
                 tokio::runtime::Handle::current(),
             )
             .map_err(|err| {
@@ -264,10 +299,11 @@ impl Batcher {
 
         self.spawn_proposal(
             propose_block_input.proposal_id,
+            role,
             block_builder,
             abort_signal_sender,
             None,
-            Some(pre_confirmed_block_writer),
+            pre_confirmed_block_writer,
             proposal_metrics_handle,
         )
         .await?;
@@ -296,6 +332,14 @@ impl Batcher {
             validate_block_input.retrospective_block_hash,
         )?;
 
+        // Starting a new L1 provider session would break the session of the other proposal.
+        if let Some(active_proposal_id) = self.l1_provider_session_holder().await {
+            return Err(BatcherError::AnotherProposalInProgress {
+                active_proposal_id,
+                new_proposal_id: validate_block_input.proposal_id,
+            });
+        }
+
         self.l1_provider_client
             .start_block(SessionState::Validate, validate_block_input.block_info.block_number)
             .await
@@ -344,6 +388,7 @@ impl Batcher {
 
         self.spawn_proposal(
             validate_block_input.proposal_id,
+            ProposalRole::Validate,
             block_builder,
             abort_signal_sender,
             Some(final_n_executed_txs_sender),
@@ -386,7 +431,7 @@ impl Batcher {
 
     /// Clear all the proposals from the previous height.
     async fn abort_active_height(&mut self) {
-        self.abort_active_proposal().await;
+        self.abort_active_proposals().await;
         self.executed_proposals.lock().await.clear();
         self.propose_tx_streams.clear();
         self.validate_tx_streams.clear();
@@ -431,7 +476,7 @@ impl Batcher {
 
         self.validate_tx_streams.remove(&proposal_id).expect("validate tx stream should exist.");
         if self.is_active(proposal_id).await {
-            self.await_active_proposal(final_n_executed_txs).await?;
+            self.await_proposal(proposal_id, final_n_executed_txs).await?;
         }
 
         let proposal_result =
@@ -448,7 +493,7 @@ impl Batcher {
         proposal_id: ProposalId,
     ) -> BatcherResult<SendProposalContentResponse> {
         if self.is_active(proposal_id).await {
-            self.abort_active_proposal().await;
+            self.abort_proposal(proposal_id).await;
 
             let proposal_already_exists = self
                 .executed_proposals
@@ -521,6 +566,7 @@ impl Batcher {
             account_transaction_hashes,
             l1_transaction_hashes,
             block_header_without_hash: BlockHeaderWithoutHash { block_number, .. },
+            commit_certificate: _,
         } = sync_block;
 
         let height = self.get_height_from_storage()?;
@@ -567,6 +613,9 @@ impl Batcher {
                 error!("Failed to get block execution artifacts: {}", err);
                 BatcherError::InternalError
             })?;
+        // The proposals of this height that are still in progress lost.
+        self.abort_losing_proposals().await;
+
         let state_diff = block_execution_artifacts.thin_state_diff();
         let n_txs = u64::try_from(block_execution_artifacts.tx_hashes().len())
             .expect("Number of transactions should fit in u64");
@@ -687,45 +736,71 @@ impl Batcher {
     }
 
     async fn is_active(&self, proposal_id: ProposalId) -> bool {
-        *self.active_proposal.lock().await == Some(proposal_id)
+        self.active_proposals.lock().await.contains_key(&proposal_id)
+    }
+
+    // Returns the active proposal that holds the L1 provider session, if any.
+    async fn l1_provider_session_holder(&self) -> Option<ProposalId> {
+        self.active_proposals
+            .lock()
+            .await
+            .iter()
+            .find(|(_, role)| role.holds_l1_provider_session())
+            .map(|(proposal_id, _)| *proposal_id)
     }
 
     // Sets a new active proposal task.
-    // Fails if there is another proposal being currently generated, or a proposal with the same ID
-    // already exists.
-    async fn set_active_proposal(&mut self, proposal_id: ProposalId) -> BatcherResult<()> {
+    // Fails if the maximal number of proposals are being currently generated, or a proposal with
+    // the same ID already exists.
+    async fn set_active_proposal(
+        &mut self,
+        proposal_id: ProposalId,
+        role: ProposalRole,
+    ) -> BatcherResult<()> {
         if self.executed_proposals.lock().await.contains_key(&proposal_id) {
             return Err(BatcherError::ProposalAlreadyExists { proposal_id });
         }
 
-        let mut active_proposal = self.active_proposal.lock().await;
-        if let Some(active_proposal_id) = *active_proposal {
+        let mut active_proposals = self.active_proposals.lock().await;
+        if active_proposals.contains_key(&proposal_id) {
+            return Err(BatcherError::ProposalAlreadyExists { proposal_id });
+        }
+        if active_proposals.len() >= self.config.max_concurrent_proposals {
+            let active_proposal_id = *active_proposals
+                .keys()
+                .min()
+                .expect("The maximal number of concurrent proposals should be positive.");
             return Err(BatcherError::AnotherProposalInProgress {
                 active_proposal_id,
                 new_proposal_id: proposal_id,
             });
         }
 
-        debug!("Set proposal {} as the one being generated.", proposal_id);
-        *active_proposal = Some(proposal_id);
+        debug!(
+            "Set proposal {} as one of the proposals being generated, as {:?}.",
+            proposal_id, role
+        );
+        active_proposals.insert(proposal_id, role);
         Ok(())
     }
 
     // Starts a new block proposal generation task for the given proposal_id.
     // Uses the given block_builder to generate the proposal.
+    #[allow(clippy::too_many_arguments)]
     async fn spawn_proposal(
         &mut self,
         proposal_id: ProposalId,
+        role: ProposalRole,
         mut block_builder: Box<dyn BlockBuilderTrait>,
         abort_signal_sender: tokio::sync::oneshot::Sender<()>,
         final_n_executed_txs_sender: Option<tokio::sync::oneshot::Sender<usize>>,
         pre_confirmed_block_writer: Option<Box<dyn PreconfirmedBlockWriterTrait>>,
         mut proposal_metrics_handle: ProposalMetricsHandle,
     ) -> BatcherResult<()> {
-        self.set_active_proposal(proposal_id).await?;
+        self.set_active_proposal(proposal_id, role).await?;
         info!("Starting generation of a new proposal with id {}.", proposal_id);
 
-        let active_proposal = self.active_proposal.clone();
+        let active_proposals = self.active_proposals.clone();
         let executed_proposals = self.executed_proposals.clone();
 
         let execution_join_handle = tokio::spawn(
@@ -743,12 +818,11 @@ impl Batcher {
                 }
                 .map_err(Arc::new);
 
-                // The proposal is done, clear the active proposal.
-                // Keep the proposal result only if it is the same as the active proposal.
-                // The active proposal might have changed if this proposal was aborted.
-                let mut active_proposal = active_proposal.lock().await;
-                if *active_proposal == Some(proposal_id) {
-                    active_proposal.take();
+                // The proposal is done, remove it from the active proposals.
+                // Keep the proposal result only if it is still active; it is no longer active if
+                // it was aborted.
+                let mut active_proposals = active_proposals.lock().await;
+                if active_proposals.remove(&proposal_id).is_some() {
                     let proposal_already_exists =
                         executed_proposals.lock().await.insert(proposal_id, result);
                     assert!(
@@ -768,12 +842,15 @@ impl Batcher {
                 })
             });
 
-        self.active_proposal_task = Some(ProposalTask {
-            abort_signal_sender,
-            final_n_executed_txs_sender,
-            execution_join_handle,
-            writer_join_handle,
-        });
+        self.active_proposal_tasks.insert(
+            proposal_id,
+            ProposalTask {
+                abort_signal_sender,
+                final_n_executed_txs_sender,
+                execution_join_handle,
+                writer_join_handle,
+            },
+        );
         Ok(())
     }
 
@@ -795,25 +872,72 @@ impl Batcher {
         }
     }
 
-    // Ends the current active proposal.
+    // Ends the given active proposal.
+    // This call is non-blocking.
+    async fn abort_proposal(&mut self, proposal_id: ProposalId) {
+        self.active_proposals.lock().await.remove(&proposal_id);
+        if let Some(proposal_task) = self.active_proposal_tasks.remove(&proposal_id) {
+            proposal_task.abort_signal_sender.send(()).ok();
+        }
+    }
+
+    // Ends all the active proposals.
     // This call is non-blocking.
-    async fn abort_active_proposal(&mut self) {
-        self.active_proposal.lock().await.take();
-        if let Some(proposal_task) = self.active_proposal_task.take() {
+    async fn abort_active_proposals(&mut self) {
+        self.active_proposals.lock().await.clear();
+        for (_, proposal_task) in self.active_proposal_tasks.drain() {
             proposal_task.abort_signal_sender.send(()).ok();
         }
     }
 
+    // Ends all the active proposals once a decision is reached on another proposal, and marks
+    // them as aborted.
+    // This call is non-blocking.
+    async fn abort_losing_proposals(&mut self) {
+        let losing_proposal_ids: Vec<ProposalId> = self
+            .active_proposals
+            .lock()
+            .await
+            .drain()
+            .map(|(proposal_id, _)| proposal_id)
+            .collect();
+
+        let mut executed_proposals = self.executed_proposals.lock().await;
+        for proposal_id in losing_proposal_ids {
+            info!("Aborting proposal {proposal_id}, since a decision was reached on another one.");
+            if let Some(proposal_task) = self.active_proposal_tasks.remove(&proposal_id) {
+                proposal_task.abort_signal_sender.send(()).ok();
+            }
+            let proposal_already_exists =
+                executed_proposals.insert(proposal_id, Err(Arc::new(BlockBuilderError::Aborted)));
+            assert!(proposal_already_exists.is_none(), "Duplicate proposal: {proposal_id}.");
+        }
+    }
+
+    // Waits for all the proposals that were started to finish. Validated proposals are first given
+    // the final number of transactions.
     pub async fn await_active_proposal(
         &mut self,
         final_n_executed_txs: usize,
+    ) -> BatcherResult<()> {
+        let proposal_ids: Vec<ProposalId> = self.active_proposal_tasks.keys().copied().collect();
+        for proposal_id in proposal_ids {
+            self.await_proposal(proposal_id, final_n_executed_txs).await?;
+        }
+        Ok(())
+    }
+
+    async fn await_proposal(
+        &mut self,
+        proposal_id: ProposalId,
+        final_n_executed_txs: usize,
     ) -> BatcherResult<()> {
         if let Some(ProposalTask {
             execution_join_handle,
             writer_join_handle,
             final_n_executed_txs_sender,
             ..
-        }) = self.active_proposal_task.take()
+        }) = self.active_proposal_tasks.remove(&proposal_id)
         {
             if let Some(final_n_executed_txs_sender) = final_n_executed_txs_sender {
                 final_n_executed_txs_sender.send(final_n_executed_txs).map_err(|err| {
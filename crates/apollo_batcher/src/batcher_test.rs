@@ -161,8 +161,19 @@ impl Default for MockDependencies {
 }
 
 async fn create_batcher(mock_dependencies: MockDependencies) -> Batcher {
-    let mut batcher = Batcher::new(
+    create_batcher_with_config(
         BatcherConfig { outstream_content_buffer_size: STREAMING_CHUNK_SIZE, ..Default::default() },
+        mock_dependencies,
+    )
+    .await
+}
+
+async fn create_batcher_with_config(
+    config: BatcherConfig,
+    mock_dependencies: MockDependencies,
+) -> Batcher {
+    let mut batcher = Batcher::new(
+        config,
         Arc::new(mock_dependencies.storage_reader),
         Box::new(mock_dependencies.storage_writer),
         Arc::new(mock_dependencies.l1_provider_client),
@@ -798,6 +809,107 @@ async fn concurrent_proposals_generation_fail() {
     assert_proposal_metrics(&metrics, 2, 1, 1, 0);
 }
 
+#[rstest]
+#[tokio::test]
+async fn speculative_proposal_while_validating() {
+    let expected_artifacts = BlockExecutionArtifacts::create_for_testing();
+    let mut mock_dependencies = MockDependencies::default();
+
+    mock_create_builder_for_validate_block(
+        &mut mock_dependencies.block_builder_factory,
+        Ok(BlockExecutionArtifacts::create_for_testing()),
+    );
+    // The speculative proposal is not streamed to the pre-confirmed block writer.
+    mock_dependencies
+        .block_builder_factory
+        .expect_create_block_builder()
+        .times(1)
+        .withf(|_, _, _, _, candidate_tx_sender, pre_confirmed_tx_sender, _| {
+            candidate_tx_sender.is_none() && pre_confirmed_tx_sender.is_none()
+        })
+        .return_once(|_, _, _, output_content_sender, _, _, _| {
+            let block_builder = FakeProposeBlockBuilder {
+                output_content_sender: output_content_sender.unwrap(),
+                output_txs: vec![],
+                build_block_result: Some(Ok(BlockExecutionArtifacts::create_for_testing())),
+            };
+            Ok((Box::new(block_builder), abort_signal_sender()))
+        });
+    let mut pre_confirmed_block_writer_factory = MockPreconfirmedBlockWriterFactoryTrait::new();
+    pre_confirmed_block_writer_factory.expect_create().never();
+    mock_dependencies.pre_confirmed_block_writer_factory = pre_confirmed_block_writer_factory;
+
+    // Only the validated proposal starts an L1 provider session, and the mempool is not rewound.
+    mock_dependencies
+        .l1_provider_client
+        .expect_start_block()
+        .times(1)
+        .with(eq(SessionState::Validate), eq(INITIAL_HEIGHT))
+        .returning(|_, _| Ok(()));
+    mock_dependencies.l1_provider_client.expect_commit_block().times(1).returning(|_, _, _| Ok(()));
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_update_gas_price().returning(|_| Ok(()));
+    mempool_client
+        .expect_commit_block()
+        .times(1)
+        .with(eq(CommitBlockArgs {
+            address_to_nonce: expected_artifacts.address_to_nonce(),
+            rejected_tx_hashes: expected_artifacts.execution_data.rejected_tx_hashes.clone(),
+        }))
+        .returning(|_| Ok(()));
+    mock_dependencies.mempool_client = mempool_client;
+    mock_dependencies.storage_writer.expect_commit_proposal().times(1).returning(|_, _| Ok(()));
+
+    let mut batcher = create_batcher_with_config(
+        BatcherConfig { max_concurrent_proposals: 2, ..Default::default() },
+        mock_dependencies,
+    )
+    .await;
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher.validate_block(validate_block_input(ProposalId(0))).await.unwrap();
+    batcher.propose_block(propose_block_input(ProposalId(1))).await.unwrap();
+
+    let content = batcher
+        .get_proposal_content(GetProposalContentInput { proposal_id: ProposalId(1) })
+        .await
+        .unwrap()
+        .content;
+    assert_matches!(content, GetProposalContent::Finished { .. });
+
+    // Reaching a decision on the speculative proposal aborts the validation.
+    batcher.decision_reached(DecisionReachedInput { proposal_id: ProposalId(1) }).await.unwrap();
+    let result = batcher
+        .send_proposal_content(SendProposalContentInput {
+            proposal_id: ProposalId(0),
+            content: SendProposalContent::Txs(test_txs(0..1)),
+        })
+        .await;
+    assert_eq!(result, Err(BatcherError::ProposalAborted));
+}
+
+#[rstest]
+#[tokio::test]
+async fn concurrent_validations_fail() {
+    let mut block_builder_factory = MockBlockBuilderFactoryTrait::new();
+    mock_create_builder_for_validate_block(
+        &mut block_builder_factory,
+        Ok(BlockExecutionArtifacts::create_for_testing()),
+    );
+    let mut batcher = start_batcher_with_active_validate(block_builder_factory).await;
+
+    // The L1 provider session is held by the active validation, regardless of the capacity.
+    batcher.config.max_concurrent_proposals = 2;
+    let result = batcher.validate_block(validate_block_input(ProposalId(1))).await;
+
+    assert_eq!(
+        result,
+        Err(BatcherError::AnotherProposalInProgress {
+            active_proposal_id: PROPOSAL_ID,
+            new_proposal_id: ProposalId(1),
+        })
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn proposal_startup_failure_allows_new_proposals() {
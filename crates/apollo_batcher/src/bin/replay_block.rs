@@ -0,0 +1,115 @@
+//! Rebuilds a historical block offline and compares the result with the stored block.
+//!
+//! The storage must hold the block headers, bodies and state diffs, i.e., it should be a copy of
+//! the state sync storage of a stopped node. The classes are fetched from a running class manager.
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use apollo_batcher::block_builder::{BlockBuilderConfig, BlockBuilderFactory};
+use apollo_batcher::block_replay::{read_stored_block, replay_block};
+use apollo_class_manager_types::RemoteClassManagerClient;
+use apollo_infra::component_client::RemoteClientConfig;
+use apollo_infra::metrics::{RemoteClientMetrics, CLASS_MANAGER_REMOTE_CLIENT_SEND_ATTEMPTS};
+use apollo_infra::trace_util::configure_tracing;
+use apollo_storage::db::DbConfig;
+use apollo_storage::StorageConfig;
+use blockifier::blockifier::config::ContractClassManagerConfig;
+use blockifier::bouncer::BouncerWeights;
+use blockifier::concurrency::worker_pool::WorkerPool;
+use blockifier::state::contract_class_manager::ContractClassManager;
+use clap::Parser;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::ChainId;
+use tracing::{error, info};
+
+#[derive(Parser, Debug)]
+#[command(about = "Rebuilds a historical block and compares it with the stored block.")]
+struct Args {
+    #[arg(long, help = "The path prefix of the storage")]
+    db_path: PathBuf,
+    #[arg(long, help = "The chain id of the storage")]
+    chain_id: String,
+    #[arg(long, help = "The number of the block to replay")]
+    height: u64,
+    #[arg(long, help = "A JSON file with the block's bouncer weights, to compare against")]
+    bouncer_weights_file: Option<PathBuf>,
+    #[arg(long, help = "A JSON file with the block builder config; defaults are used if missing")]
+    block_builder_config_file: Option<PathBuf>,
+    #[arg(long, default_value = "127.0.0.1")]
+    class_manager_url: String,
+    #[arg(long)]
+    class_manager_port: u16,
+    #[arg(long, default_value_t = 60, help = "The block building timeout, in seconds")]
+    timeout_secs: u64,
+}
+
+fn read_json<T: for<'a> serde::Deserialize<'a>>(path: &PathBuf) -> T {
+    let file =
+        File::open(path).unwrap_or_else(|err| panic!("Failed to open {}: {err}", path.display()));
+    serde_json::from_reader(file)
+        .unwrap_or_else(|err| panic!("Failed to parse {}: {err}", path.display()))
+}
+
+#[tokio::main]
+async fn main() {
+    configure_tracing().await;
+    let args = Args::parse();
+
+    let chain_id = ChainId::from(args.chain_id);
+    let db_config = DbConfig {
+        path_prefix: args.db_path,
+        chain_id: chain_id.clone(),
+        enforce_file_exists: true,
+        ..Default::default()
+    };
+    let (storage_reader, _storage_writer) =
+        apollo_storage::open_storage(StorageConfig { db_config, ..Default::default() })
+            .expect("Failed to open storage");
+
+    let height = BlockNumber(args.height);
+    let mut stored_block =
+        read_stored_block(&storage_reader, height).expect("Failed to read the stored block");
+    stored_block.artifacts.bouncer_weights =
+        args.bouncer_weights_file.as_ref().map(read_json::<BouncerWeights>);
+
+    let mut block_builder_config: BlockBuilderConfig =
+        args.block_builder_config_file.as_ref().map(read_json).unwrap_or_default();
+    block_builder_config.chain_info.chain_id = chain_id;
+
+    let class_manager_client = Arc::new(RemoteClassManagerClient::new(
+        RemoteClientConfig::default(),
+        &args.class_manager_url,
+        args.class_manager_port,
+        RemoteClientMetrics::new(&CLASS_MANAGER_REMOTE_CLIENT_SEND_ATTEMPTS),
+    ));
+    let block_builder_factory = BlockBuilderFactory {
+        worker_pool: Arc::new(WorkerPool::start(&block_builder_config.execute_config)),
+        block_builder_config,
+        storage_reader,
+        contract_class_manager: ContractClassManager::start(ContractClassManagerConfig::default()),
+        class_manager_client,
+    };
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(args.timeout_secs);
+    let report = replay_block(
+        &block_builder_factory,
+        stored_block.block_metadata,
+        stored_block.txs,
+        &stored_block.artifacts,
+        deadline,
+    )
+    .await
+    .expect("Failed to replay the block");
+
+    if report.is_match() {
+        info!("Block {height} was replayed successfully; the artifacts match the stored block.");
+        return;
+    }
+    for mismatch in &report.mismatches {
+        error!("Block {height} replay mismatch: {mismatch:#?}");
+    }
+    std::process::exit(1);
+}
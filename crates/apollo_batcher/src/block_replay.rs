@@ -0,0 +1,293 @@
+use apollo_batcher_types::batcher_types::ProposalCommitment;
+use apollo_storage::body::BodyStorageReader;
+use apollo_storage::header::HeaderStorageReader;
+use apollo_storage::state::StateStorageReader;
+use apollo_storage::{StorageError, StorageReader};
+use blockifier::abi::constants::STORED_BLOCK_HASH_BUFFER;
+use blockifier::bouncer::BouncerWeights;
+use starknet_api::block::{
+    BlockHashAndNumber,
+    BlockHeaderWithoutHash,
+    BlockInfo,
+    BlockNumber,
+    GasPriceVector,
+    GasPrices,
+    NonzeroGasPrice,
+};
+use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::rpc_transaction::{
+    InternalRpcDeclareTransactionV3,
+    InternalRpcDeployAccountTransaction,
+    InternalRpcTransaction,
+    InternalRpcTransactionWithoutTxHash,
+    RpcDeployAccountTransaction,
+    RpcInvokeTransaction,
+};
+use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::fields::{Fee, ValidResourceBounds};
+use starknet_api::transaction::{
+    CalculateContractAddress,
+    DeclareTransaction,
+    DeployAccountTransaction,
+    InvokeTransaction,
+    Transaction,
+    TransactionHash,
+};
+use starknet_api::StarknetApiError;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::block_builder::{
+    BlockBuilderError,
+    BlockBuilderExecutionParams,
+    BlockBuilderFactoryTrait,
+    BlockExecutionArtifacts,
+    BlockMetadata,
+};
+use crate::transaction_provider::FixedTransactionProvider;
+
+#[derive(Debug, Error)]
+pub enum BlockReplayError {
+    #[error(transparent)]
+    BlockBuilderError(#[from] BlockBuilderError),
+    #[error(transparent)]
+    InvalidTransaction(#[from] StarknetApiError),
+    #[error("Block {0} is missing from the storage.")]
+    MissingBlock(BlockNumber),
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
+    #[error("Transaction {0} can't be replayed; only V3 account transactions are supported.")]
+    UnsupportedTransaction(TransactionHash),
+}
+
+pub type BlockReplayResult<T> = Result<T, BlockReplayError>;
+
+/// The artifacts of a stored block, against which a replay of the block is compared.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredBlockArtifacts {
+    pub state_diff: ThinStateDiff,
+    // Missing for blocks that were stored without their state diff commitment.
+    pub proposal_commitment: Option<ProposalCommitment>,
+    // The bouncer weights are not kept in the storage, so they are compared only if given.
+    pub bouncer_weights: Option<BouncerWeights>,
+}
+
+/// A block as read from the storage: everything needed to replay it and to check the replay.
+pub struct StoredBlock {
+    pub block_metadata: BlockMetadata,
+    pub txs: Vec<InternalConsensusTransaction>,
+    pub artifacts: StoredBlockArtifacts,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockReplayMismatch {
+    StateDiff { stored: ThinStateDiff, replayed: ThinStateDiff },
+    ProposalCommitment { stored: ProposalCommitment, replayed: ProposalCommitment },
+    BouncerWeights { stored: BouncerWeights, replayed: BouncerWeights },
+}
+
+#[derive(Debug)]
+pub struct BlockReplayReport {
+    pub artifacts: BlockExecutionArtifacts,
+    pub mismatches: Vec<BlockReplayMismatch>,
+}
+
+impl BlockReplayReport {
+    /// Returns true if the replayed block matches the stored one.
+    pub fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Rebuilds a block from its exact list of transactions, and compares the resulting artifacts with
+/// the stored ones.
+/// The block is built in validate mode, so exactly the given transactions are executed; nothing is
+/// streamed or written to the storage.
+pub async fn replay_block(
+    block_builder_factory: &dyn BlockBuilderFactoryTrait,
+    block_metadata: BlockMetadata,
+    txs: Vec<InternalConsensusTransaction>,
+    stored_artifacts: &StoredBlockArtifacts,
+    deadline: tokio::time::Instant,
+) -> BlockReplayResult<BlockReplayReport> {
+    let height = block_metadata.block_info.block_number;
+    info!("Replaying block {height} with {} transactions.", txs.len());
+
+    let execution_params = BlockBuilderExecutionParams { deadline, is_validator: true };
+    let (mut block_builder, _abort_signal_sender) = block_builder_factory.create_block_builder(
+        block_metadata,
+        execution_params,
+        Box::new(FixedTransactionProvider::new(txs)),
+        None,
+        None,
+        None,
+        tokio::runtime::Handle::current(),
+    )?;
+    let artifacts = block_builder.build_block().await?;
+
+    let mismatches = compare_artifacts(stored_artifacts, &artifacts);
+    if mismatches.is_empty() {
+        info!("Replayed block {height} matches the stored block.");
+    } else {
+        warn!("Replayed block {height} diverges from the stored block: {mismatches:?}");
+    }
+
+    Ok(BlockReplayReport { artifacts, mismatches })
+}
+
+/// Reads the metadata, the transactions and the artifacts of the block at the given height from the
+/// storage.
+pub fn read_stored_block(
+    storage_reader: &StorageReader,
+    height: BlockNumber,
+) -> BlockReplayResult<StoredBlock> {
+    let txn = storage_reader.begin_ro_txn()?;
+    let header = txn.get_block_header(height)?.ok_or(BlockReplayError::MissingBlock(height))?;
+    let state_diff = txn.get_state_diff(height)?.ok_or(BlockReplayError::MissingBlock(height))?;
+    let txs = txn
+        .get_block_transactions_with_hash(height)?
+        .ok_or(BlockReplayError::MissingBlock(height))?
+        .into_iter()
+        .map(|(tx, tx_hash)| into_consensus_transaction(tx, tx_hash))
+        .collect::<BlockReplayResult<_>>()?;
+
+    let retrospective_block_hash = match height.0.checked_sub(STORED_BLOCK_HASH_BUFFER) {
+        Some(block_number) => {
+            let number = BlockNumber(block_number);
+            let retrospective_header =
+                txn.get_block_header(number)?.ok_or(BlockReplayError::MissingBlock(number))?;
+            Some(BlockHashAndNumber { number, hash: retrospective_header.block_hash })
+        }
+        None => None,
+    };
+    let block_metadata = BlockMetadata {
+        block_info: block_info_from_header(&header.block_header_without_hash),
+        retrospective_block_hash,
+    };
+
+    let artifacts = StoredBlockArtifacts {
+        state_diff,
+        proposal_commitment: header
+            .state_diff_commitment
+            .map(|state_diff_commitment| ProposalCommitment { state_diff_commitment }),
+        bouncer_weights: None,
+    };
+
+    Ok(StoredBlock { block_metadata, txs, artifacts })
+}
+
+pub(crate) fn block_info_from_header(header: &BlockHeaderWithoutHash) -> BlockInfo {
+    // Historical blocks may have zero gas prices (e.g., the L2 gas price before it was
+    // introduced); like the RPC execution, use the minimal price for them.
+    let nonzero = |price| NonzeroGasPrice::new(price).unwrap_or(NonzeroGasPrice::MIN);
+    BlockInfo {
+        block_number: header.block_number,
+        block_timestamp: header.timestamp,
+        sequencer_address: header.sequencer.0,
+        gas_prices: GasPrices {
+            eth_gas_prices: GasPriceVector {
+                l1_gas_price: nonzero(header.l1_gas_price.price_in_wei),
+                l1_data_gas_price: nonzero(header.l1_data_gas_price.price_in_wei),
+                l2_gas_price: nonzero(header.l2_gas_price.price_in_wei),
+            },
+            strk_gas_prices: GasPriceVector {
+                l1_gas_price: nonzero(header.l1_gas_price.price_in_fri),
+                l1_data_gas_price: nonzero(header.l1_data_gas_price.price_in_fri),
+                l2_gas_price: nonzero(header.l2_gas_price.price_in_fri),
+            },
+        },
+        use_kzg_da: header.l1_da_mode == L1DataAvailabilityMode::Blob,
+    }
+}
+
+/// Converts a stored transaction into the form the block builder executes.
+/// Only L1 handlers and V3 account transactions with all resource bounds can be converted.
+fn into_consensus_transaction(
+    tx: Transaction,
+    tx_hash: TransactionHash,
+) -> BlockReplayResult<InternalConsensusTransaction> {
+    let tx_without_hash = match tx {
+        Transaction::L1Handler(tx) => {
+            return Ok(InternalConsensusTransaction::L1Handler(L1HandlerTransaction {
+                tx,
+                tx_hash,
+                // The paid fee is not kept in the storage; it only has to be nonzero.
+                paid_fee_on_l1: Fee(1),
+            }));
+        }
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => {
+            InternalRpcTransactionWithoutTxHash::Invoke(RpcInvokeTransaction::V3(tx.try_into()?))
+        }
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => {
+            let contract_address =
+                DeployAccountTransaction::V3(tx.clone()).calculate_contract_address()?;
+            InternalRpcTransactionWithoutTxHash::DeployAccount(
+                InternalRpcDeployAccountTransaction {
+                    tx: RpcDeployAccountTransaction::V3(tx.try_into()?),
+                    contract_address,
+                },
+            )
+        }
+        Transaction::Declare(DeclareTransaction::V3(tx)) => {
+            let ValidResourceBounds::AllResources(resource_bounds) = tx.resource_bounds else {
+                return Err(BlockReplayError::UnsupportedTransaction(tx_hash));
+            };
+            InternalRpcTransactionWithoutTxHash::Declare(InternalRpcDeclareTransactionV3 {
+                sender_address: tx.sender_address,
+                compiled_class_hash: tx.compiled_class_hash,
+                signature: tx.signature,
+                nonce: tx.nonce,
+                class_hash: tx.class_hash,
+                resource_bounds,
+                tip: tx.tip,
+                paymaster_data: tx.paymaster_data,
+                account_deployment_data: tx.account_deployment_data,
+                nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                fee_data_availability_mode: tx.fee_data_availability_mode,
+            })
+        }
+        _ => return Err(BlockReplayError::UnsupportedTransaction(tx_hash)),
+    };
+    Ok(InternalConsensusTransaction::RpcTransaction(InternalRpcTransaction {
+        tx: tx_without_hash,
+        tx_hash,
+    }))
+}
+
+fn compare_artifacts(
+    stored_artifacts: &StoredBlockArtifacts,
+    artifacts: &BlockExecutionArtifacts,
+) -> Vec<BlockReplayMismatch> {
+    let mut mismatches = Vec::new();
+
+    let replayed_state_diff = artifacts.thin_state_diff();
+    if replayed_state_diff != stored_artifacts.state_diff {
+        mismatches.push(BlockReplayMismatch::StateDiff {
+            stored: stored_artifacts.state_diff.clone(),
+            replayed: replayed_state_diff,
+        });
+    }
+
+    let replayed_commitment = artifacts.commitment();
+    if let Some(stored_commitment) = stored_artifacts.proposal_commitment {
+        if replayed_commitment != stored_commitment {
+            mismatches.push(BlockReplayMismatch::ProposalCommitment {
+                stored: stored_commitment,
+                replayed: replayed_commitment,
+            });
+        }
+    }
+
+    if let Some(stored_bouncer_weights) = stored_artifacts.bouncer_weights {
+        if artifacts.bouncer_weights != stored_bouncer_weights {
+            mismatches.push(BlockReplayMismatch::BouncerWeights {
+                stored: stored_bouncer_weights,
+                replayed: artifacts.bouncer_weights,
+            });
+        }
+    }
+
+    mismatches
+}
@@ -0,0 +1,230 @@
+use apollo_storage::body::BodyStorageWriter;
+use apollo_storage::header::HeaderStorageWriter;
+use apollo_storage::state::StateStorageWriter;
+use apollo_storage::test_utils::get_test_storage;
+use apollo_storage::StorageWriter;
+use assert_matches::assert_matches;
+use blockifier::bouncer::BouncerWeights;
+use pretty_assertions::assert_eq;
+use rstest::{fixture, rstest};
+use starknet_api::block::{
+    BlockBody,
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockInfo,
+    BlockNumber,
+    GasPrice,
+    GasPricePerToken,
+    NonzeroGasPrice,
+};
+use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::test_utils::invoke::{internal_invoke_tx, invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{
+    InvokeTransactionOutput,
+    L1HandlerTransactionOutput,
+    Transaction,
+    TransactionHash,
+    TransactionOutput,
+    TransactionVersion,
+};
+use starknet_api::{felt, nonce};
+use starknet_types_core::felt::Felt;
+
+use crate::block_builder::{BlockExecutionArtifacts, BlockMetadata, MockBlockBuilderFactoryTrait};
+use crate::block_replay::{
+    block_info_from_header,
+    read_stored_block,
+    replay_block,
+    BlockReplayError,
+    BlockReplayMismatch,
+    StoredBlockArtifacts,
+};
+use crate::test_utils::{test_txs, FakeValidateBlockBuilder};
+
+#[fixture]
+fn artifacts() -> BlockExecutionArtifacts {
+    BlockExecutionArtifacts::create_for_testing()
+}
+
+fn block_metadata() -> BlockMetadata {
+    BlockMetadata { block_info: BlockInfo::create_for_testing(), retrospective_block_hash: None }
+}
+
+fn stored_artifacts_of(artifacts: &BlockExecutionArtifacts) -> StoredBlockArtifacts {
+    StoredBlockArtifacts {
+        state_diff: artifacts.thin_state_diff(),
+        proposal_commitment: Some(artifacts.commitment()),
+        bouncer_weights: Some(artifacts.bouncer_weights),
+    }
+}
+
+fn mock_block_builder_factory(artifacts: BlockExecutionArtifacts) -> MockBlockBuilderFactoryTrait {
+    let mut block_builder_factory = MockBlockBuilderFactoryTrait::new();
+    block_builder_factory
+        .expect_create_block_builder()
+        .times(1)
+        .withf(|_, execution_params, _, output_content_sender, _, _, _| {
+            execution_params.is_validator && output_content_sender.is_none()
+        })
+        .return_once(|_, _, tx_provider, _, _, _, _| {
+            let block_builder =
+                FakeValidateBlockBuilder { tx_provider, build_block_result: Some(Ok(artifacts)) };
+            Ok((Box::new(block_builder), tokio::sync::oneshot::channel().0))
+        });
+    block_builder_factory
+}
+
+#[rstest]
+#[tokio::test]
+async fn replay_matches_stored_block(artifacts: BlockExecutionArtifacts) {
+    let stored_artifacts = stored_artifacts_of(&artifacts);
+    let block_builder_factory = mock_block_builder_factory(artifacts.clone());
+
+    let report = replay_block(
+        &block_builder_factory,
+        block_metadata(),
+        test_txs(0..3),
+        &stored_artifacts,
+        tokio::time::Instant::now(),
+    )
+    .await
+    .unwrap();
+
+    assert!(report.is_match());
+    assert_eq!(report.artifacts, artifacts);
+}
+
+#[rstest]
+#[tokio::test]
+async fn replay_reports_mismatches(artifacts: BlockExecutionArtifacts) {
+    let stored_artifacts = StoredBlockArtifacts {
+        state_diff: ThinStateDiff::default(),
+        bouncer_weights: Some(BouncerWeights { n_txs: 1, ..artifacts.bouncer_weights }),
+        ..stored_artifacts_of(&artifacts)
+    };
+    let block_builder_factory = mock_block_builder_factory(artifacts.clone());
+
+    let report = replay_block(
+        &block_builder_factory,
+        block_metadata(),
+        test_txs(0..3),
+        &stored_artifacts,
+        tokio::time::Instant::now(),
+    )
+    .await
+    .unwrap();
+
+    // The stored commitment was computed from the replayed state diff, so it matches.
+    assert_matches!(
+        report.mismatches.as_slice(),
+        [BlockReplayMismatch::StateDiff { .. }, BlockReplayMismatch::BouncerWeights { .. }]
+    );
+}
+
+#[test]
+fn block_info_with_zero_gas_prices() {
+    let header = BlockHeaderWithoutHash {
+        l1_gas_price: GasPricePerToken { price_in_fri: GasPrice(7), price_in_wei: GasPrice(8) },
+        ..Default::default()
+    };
+
+    let gas_prices = block_info_from_header(&header).gas_prices;
+
+    assert_eq!(
+        gas_prices.strk_gas_prices.l1_gas_price,
+        NonzeroGasPrice::new_unchecked(GasPrice(7))
+    );
+    assert_eq!(gas_prices.eth_gas_prices.l1_gas_price, NonzeroGasPrice::new_unchecked(GasPrice(8)));
+    for gas_price_vector in [gas_prices.strk_gas_prices, gas_prices.eth_gas_prices] {
+        assert_eq!(gas_price_vector.l1_data_gas_price, NonzeroGasPrice::MIN);
+        assert_eq!(gas_price_vector.l2_gas_price, NonzeroGasPrice::MIN);
+    }
+}
+
+fn write_block(storage_writer: &mut StorageWriter, transactions: Vec<Transaction>) {
+    let transaction_outputs = transactions
+        .iter()
+        .map(|tx| match tx {
+            Transaction::L1Handler(_) => {
+                TransactionOutput::L1Handler(L1HandlerTransactionOutput::default())
+            }
+            _ => TransactionOutput::Invoke(InvokeTransactionOutput::default()),
+        })
+        .collect();
+    let transaction_hashes =
+        (0..transactions.len()).map(|i| TransactionHash(Felt::from(i))).collect();
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &BlockHeader::default())
+        .unwrap()
+        .append_body(
+            BlockNumber(0),
+            BlockBody { transactions, transaction_outputs, transaction_hashes },
+        )
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            ThinStateDiff {
+                nonces: [(Default::default(), nonce!(1_u8))].into(),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+}
+
+#[test]
+fn read_stored_block_from_storage() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let l1_handler_tx = starknet_api::transaction::L1HandlerTransaction::default();
+    let invoke_tx_args =
+        InvokeTxArgs { tx_hash: TransactionHash(felt!(1_u8)), ..Default::default() };
+    write_block(
+        &mut storage_writer,
+        vec![
+            Transaction::L1Handler(l1_handler_tx.clone()),
+            Transaction::Invoke(invoke_tx(invoke_tx_args.clone())),
+        ],
+    );
+
+    let stored_block = read_stored_block(&storage_reader, BlockNumber(0)).unwrap();
+
+    // The stored header has zero gas prices.
+    assert_eq!(
+        stored_block.block_metadata.block_info.gas_prices.strk_gas_prices.l2_gas_price,
+        NonzeroGasPrice::MIN
+    );
+    assert_eq!(stored_block.block_metadata.retrospective_block_hash, None);
+    assert_eq!(
+        stored_block.txs,
+        vec![
+            InternalConsensusTransaction::L1Handler(L1HandlerTransaction {
+                tx: l1_handler_tx,
+                tx_hash: TransactionHash(felt!(0_u8)),
+                paid_fee_on_l1: Fee(1),
+            }),
+            InternalConsensusTransaction::RpcTransaction(internal_invoke_tx(invoke_tx_args)),
+        ]
+    );
+    assert_eq!(stored_block.artifacts.state_diff.nonces.len(), 1);
+    assert_eq!(stored_block.artifacts.proposal_commitment, None);
+}
+
+#[test]
+fn read_stored_block_with_unsupported_transaction() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let invoke_tx_args = InvokeTxArgs { version: TransactionVersion::ONE, ..Default::default() };
+    write_block(&mut storage_writer, vec![Transaction::Invoke(invoke_tx(invoke_tx_args))]);
+
+    let tx_hash = TransactionHash(felt!(0_u8));
+    assert_matches!(
+        read_stored_block(&storage_reader, BlockNumber(0)).err(),
+        Some(BlockReplayError::UnsupportedTransaction(unsupported_tx_hash))
+            if unsupported_tx_hash == tx_hash
+    );
+}
@@ -21,6 +21,9 @@ pub struct BatcherConfig {
     pub pre_confirmed_block_writer_config: PreconfirmedBlockWriterConfig,
     pub contract_class_manager_config: ContractClassManagerConfig,
     pub max_l1_handler_txs_per_block_proposal: usize,
+    // The maximum number of proposals that are built concurrently in the same height; proposals
+    // that start while another proposal is in progress are built speculatively.
+    pub max_concurrent_proposals: usize,
     pub pre_confirmed_cende_config: PreconfirmedCendeConfig,
 }
 
@@ -47,6 +50,14 @@ impl SerializeConfig for BatcherConfig {
                 "The maximum number of L1 handler transactions to include in a block proposal.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_concurrent_proposals",
+                &self.max_concurrent_proposals,
+                "The maximum number of proposals that are built or validated concurrently. A block \
+                 proposed while another proposal is in progress is built speculatively, without \
+                 L1 handler transactions.",
+                ParamPrivacyInput::Public,
+            ),
         ]);
         dump.append(&mut prepend_sub_config_name(self.storage.dump(), "storage"));
         dump.append(&mut prepend_sub_config_name(
@@ -88,6 +99,7 @@ impl Default for BatcherConfig {
             pre_confirmed_block_writer_config: PreconfirmedBlockWriterConfig::default(),
             contract_class_manager_config: ContractClassManagerConfig::default(),
             max_l1_handler_txs_per_block_proposal: 3,
+            max_concurrent_proposals: 1,
             pre_confirmed_cende_config: PreconfirmedCendeConfig::default(),
         }
     }
@@ -101,5 +113,8 @@ fn validate_batcher_config(batcher_config: &BatcherConfig) -> Result<(), Validat
             "input_stream_content_buffer_size must be at least n_concurrent_txs",
         ));
     }
+    if batcher_config.max_concurrent_proposals == 0 {
+        return Err(ValidationError::new("max_concurrent_proposals must be positive"));
+    }
     Ok(())
 }
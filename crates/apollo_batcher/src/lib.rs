@@ -4,6 +4,9 @@ mod batcher_test;
 pub mod block_builder;
 #[cfg(test)]
 mod block_builder_test;
+pub mod block_replay;
+#[cfg(test)]
+mod block_replay_test;
 pub mod cende_client_types;
 pub mod communication;
 pub mod config;
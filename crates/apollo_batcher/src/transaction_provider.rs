@@ -41,8 +41,9 @@ pub type NextTxs = Vec<InternalConsensusTransaction>;
 #[async_trait]
 pub trait TransactionProvider: Send {
     async fn get_txs(&mut self, n_txs: usize) -> TransactionProviderResult<NextTxs>;
-    /// In validate mode ([ValidateTransactionProvider]) returns the final number of transactions
-    /// in the block once it is known, or `None` if it is not known yet.
+    /// In validate mode ([ValidateTransactionProvider], [FixedTransactionProvider]) returns the
+    /// final number of transactions in the block once it is known, or `None` if it is not known
+    /// yet.
     /// Once `Some()` is returned for the first time, future calls to this method may return `None`.
     /// Returns `None` in propose mode ([ProposeTransactionProvider]).
     async fn get_final_n_executed_txs(&mut self) -> Option<usize>;
@@ -77,7 +78,12 @@ impl ProposeTransactionProvider {
             l1_provider_client,
             max_l1_handler_txs_per_block,
             height,
-            phase: TxProviderPhase::L1,
+            // Skip the L1 provider altogether if no L1 handler transactions are allowed.
+            phase: if max_l1_handler_txs_per_block == 0 {
+                TxProviderPhase::Mempool
+            } else {
+                TxProviderPhase::L1
+            },
             n_l1handler_txs_so_far: 0,
         }
     }
@@ -195,3 +201,29 @@ impl TransactionProvider for ValidateTransactionProvider {
         self.final_n_executed_txs_receiver.try_recv().ok()
     }
 }
+
+/// Provides a fixed list of transactions, e.g., for replaying a historical block. The final number
+/// of transactions in the block is known in advance, so it is used in validate mode.
+pub struct FixedTransactionProvider {
+    txs: vec::IntoIter<InternalConsensusTransaction>,
+    n_txs: usize,
+}
+
+impl FixedTransactionProvider {
+    pub fn new(txs: Vec<InternalConsensusTransaction>) -> Self {
+        let n_txs = txs.len();
+        Self { txs: txs.into_iter(), n_txs }
+    }
+}
+
+#[async_trait]
+impl TransactionProvider for FixedTransactionProvider {
+    async fn get_txs(&mut self, n_txs: usize) -> TransactionProviderResult<NextTxs> {
+        assert!(n_txs > 0, "The number of transactions requested must be greater than zero.");
+        Ok(self.txs.by_ref().take(n_txs).collect())
+    }
+
+    async fn get_final_n_executed_txs(&mut self) -> Option<usize> {
+        Some(self.n_txs)
+    }
+}
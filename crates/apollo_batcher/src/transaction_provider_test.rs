@@ -15,7 +15,9 @@ use starknet_api::executable_transaction::L1HandlerTransaction;
 use starknet_api::test_utils::invoke::{internal_invoke_tx, InvokeTxArgs};
 use starknet_api::tx_hash;
 
+use crate::test_utils::test_txs;
 use crate::transaction_provider::{
+    FixedTransactionProvider,
     ProposeTransactionProvider,
     TransactionProvider,
     TransactionProviderError,
@@ -203,6 +205,24 @@ async fn no_more_l1_handler(mut mock_dependencies: MockDependencies) {
     assert!(data.iter().all(|tx| matches!(tx, InternalConsensusTransaction::RpcTransaction(_))));
 }
 
+#[rstest]
+#[tokio::test]
+async fn no_l1_handler_txs_allowed(mut mock_dependencies: MockDependencies) {
+    // The L1 provider is not queried at all.
+    mock_dependencies.expect_get_mempool_txs(MAX_TXS_PER_FETCH);
+
+    let mut tx_provider = ProposeTransactionProvider::new(
+        Arc::new(mock_dependencies.mempool_client),
+        Arc::new(mock_dependencies.l1_provider_client),
+        0,
+        HEIGHT,
+    );
+
+    let txs = tx_provider.get_txs(MAX_TXS_PER_FETCH).await.unwrap();
+    let data = assert_matches!(txs, txs if txs.len() == MAX_TXS_PER_FETCH => txs);
+    assert!(data.iter().all(|tx| matches!(tx, InternalConsensusTransaction::RpcTransaction(_))));
+}
+
 #[rstest]
 #[tokio::test]
 async fn validate_flow(mut mock_dependencies: MockDependencies) {
@@ -275,3 +295,16 @@ async fn validate_fails(
         if validation_status == expected_validation_status
     );
 }
+
+#[tokio::test]
+async fn fixed_flow() {
+    let txs = test_txs(0..15);
+    let mut fixed_tx_provider = FixedTransactionProvider::new(txs.clone());
+
+    // The final number of transactions is known in advance.
+    assert_eq!(fixed_tx_provider.get_final_n_executed_txs().await, Some(txs.len()));
+
+    assert_eq!(fixed_tx_provider.get_txs(MAX_TXS_PER_FETCH).await.unwrap(), txs[..10]);
+    assert_eq!(fixed_tx_provider.get_txs(MAX_TXS_PER_FETCH).await.unwrap(), txs[10..]);
+    assert_eq!(fixed_tx_provider.get_txs(MAX_TXS_PER_FETCH).await.unwrap(), vec![]);
+}
@@ -11,6 +11,27 @@ use crate::block_builder::BlockBuilderError;
 // BlockBuilderError is wrapped in an Arc since it doesn't implement Clone.
 pub(crate) type ProposalResult<T> = Result<T, Arc<BlockBuilderError>>;
 
+// The role of an active proposal, which determines the shared resources it uses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ProposalRole {
+    Propose,
+    // A proposal built while another proposal holds the L1 provider session. It includes no L1
+    // handler transactions, and is not streamed to the pre-confirmed block writer.
+    SpeculativePropose,
+    Validate,
+}
+
+impl ProposalRole {
+    pub fn is_propose(&self) -> bool {
+        matches!(self, ProposalRole::Propose | ProposalRole::SpeculativePropose)
+    }
+
+    // The L1 provider serves a single block session at a time.
+    pub fn holds_l1_provider_session(&self) -> bool {
+        matches!(self, ProposalRole::Propose | ProposalRole::Validate)
+    }
+}
+
 // Represents a spawned task of building new block proposal.
 pub(crate) struct ProposalTask {
     pub abort_signal_sender: tokio::sync::oneshot::Sender<()>,
@@ -103,6 +103,8 @@ impl From<ClassHashStorageConfig> for StorageConfig {
             },
             scope: value.scope,
             mmap_file_config: value.mmap_file_config,
+            state_history_length: None,
+            index_event_keys: false,
         }
     }
 }
@@ -3,13 +3,19 @@
 //! such as the validator ID, the network topic of the consensus, and the starting block height.
 
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use apollo_config::converters::{
     deserialize_float_seconds_to_duration,
     deserialize_seconds_to_duration,
 };
-use apollo_config::dumping::{prepend_sub_config_name, ser_param, SerializeConfig};
+use apollo_config::dumping::{
+    prepend_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
 use serde::{Deserialize, Serialize};
@@ -36,6 +42,9 @@ pub struct ConsensusConfig {
     pub future_round_limit: u32,
     /// How many rounds should we cache for future heights.
     pub future_height_round_limit: u32,
+    /// If set, own votes and the locked value are written to this file before being acted upon, so
+    /// that a restarted node doesn't contradict them.
+    pub wal_path: Option<PathBuf>,
 }
 
 impl SerializeConfig for ConsensusConfig {
@@ -78,6 +87,14 @@ impl SerializeConfig for ConsensusConfig {
                 ParamPrivacyInput::Public,
             ),
         ]);
+        config.extend(ser_optional_param(
+            &self.wal_path,
+            PathBuf::new(),
+            "wal_path",
+            "Path of the write-ahead log in which own votes and the locked value are recorded, to \
+             be restored after a restart. If not set, the consensus state is not persisted.",
+            ParamPrivacyInput::Public,
+        ));
         config.extend(prepend_sub_config_name(self.timeouts.dump(), "timeouts"));
         config
     }
@@ -93,6 +110,7 @@ impl Default for ConsensusConfig {
             future_height_limit: 10,
             future_round_limit: 10,
             future_height_round_limit: 1,
+            wal_path: None,
         }
     }
 }
@@ -0,0 +1,140 @@
+//! Detection of equivocating validators, i.e. validators which sent two different votes for the
+//! same height, round and vote type.
+//!
+//! [`EquivocationEvidence`] holds both conflicting votes, so that the offence can be proven to a
+//! third party (e.g. for slashing). Evidence is collected in an [`EquivocationEvidenceStore`],
+//! which can be queried while consensus is running.
+//!
+//! Votes are remembered for the heights retained in the store, so that votes which arrive after
+//! their height was decided are checked as well.
+
+#[cfg(test)]
+#[path = "equivocation_test.rs"]
+mod equivocation_test;
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use apollo_protobuf::consensus::{Vote, VoteType};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+
+use crate::metrics::CONSENSUS_CONFLICTING_VOTES;
+use crate::types::{Round, ValidatorId};
+
+/// Evidence heights older than this (relative to the newest evidence) are dropped from the store.
+const EVIDENCE_RETENTION_HEIGHTS: u64 = 1000;
+
+/// Two conflicting votes sent by the same validator.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EquivocationEvidence {
+    /// The vote which was received first.
+    pub first_vote: Vote,
+    /// A vote for the same height, round and vote type, but for a different block.
+    pub second_vote: Vote,
+}
+
+impl EquivocationEvidence {
+    /// The equivocating validator.
+    pub fn voter(&self) -> ValidatorId {
+        self.first_vote.voter
+    }
+
+    /// The height of the conflicting votes.
+    pub fn height(&self) -> BlockNumber {
+        BlockNumber(self.first_vote.height)
+    }
+}
+
+/// Shared handle to the evidence collected by consensus, kept for the most recent heights.
+#[derive(Clone, Debug, Default)]
+pub struct EquivocationEvidenceStore {
+    evidence: Arc<Mutex<BTreeMap<BlockNumber, Vec<EquivocationEvidence>>>>,
+}
+
+impl EquivocationEvidenceStore {
+    /// Returns the evidence collected for the given height.
+    pub fn get(&self, height: BlockNumber) -> Vec<EquivocationEvidence> {
+        self.lock().get(&height).cloned().unwrap_or_default()
+    }
+
+    /// Returns all the evidence in the store, ordered by height.
+    pub fn get_all(&self) -> Vec<EquivocationEvidence> {
+        self.lock().values().flatten().cloned().collect()
+    }
+
+    fn insert(&self, evidence: EquivocationEvidence) {
+        let mut evidence_by_height = self.lock();
+        evidence_by_height.entry(evidence.height()).or_default().push(evidence);
+        let newest_height = *evidence_by_height.keys().next_back().expect("Evidence was inserted");
+        while let Some(entry) = evidence_by_height.first_entry() {
+            if entry.key().0 + EVIDENCE_RETENTION_HEIGHTS > newest_height.0 {
+                break;
+            }
+            entry.remove();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<BlockNumber, Vec<EquivocationEvidence>>> {
+        self.evidence.lock().expect("Equivocation evidence lock should not be poisoned")
+    }
+}
+
+/// Remembers the first vote of each validator per (height, round, vote type), in order to detect
+/// conflicting votes.
+#[derive(Debug, Default)]
+pub(crate) struct EquivocationDetector {
+    // {height: {(round, vote_type, voter): (first_vote, is_evidence_stored)}}
+    votes: BTreeMap<u64, HashMap<(Round, VoteType, ValidatorId), (Vote, bool)>>,
+    // Votes of lower heights are no longer remembered, so they can't be checked.
+    min_height: u64,
+    store: EquivocationEvidenceStore,
+}
+
+impl EquivocationDetector {
+    pub(crate) fn new(store: EquivocationEvidenceStore) -> Self {
+        Self { votes: BTreeMap::new(), min_height: 0, store }
+    }
+
+    /// Records the vote and returns evidence if it conflicts with a vote previously received from
+    /// the same voter. Only the first conflict per (height, round, vote type, voter) is added to
+    /// the store.
+    ///
+    /// The caller must verify the vote's signature first. Votes of heights which fell out of the
+    /// retention window are ignored.
+    pub(crate) fn check_vote(&mut self, vote: &Vote) -> Option<EquivocationEvidence> {
+        if vote.height < self.min_height {
+            return None;
+        }
+        let key = (vote.round, vote.vote_type.clone(), vote.voter);
+        let (first_vote, is_evidence_stored) =
+            match self.votes.entry(vote.height).or_default().entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert((vote.clone(), false));
+                    return None;
+                }
+                Entry::Occupied(entry) => entry.into_mut(),
+            };
+        if first_vote.block_hash == vote.block_hash {
+            // Replay.
+            return None;
+        }
+
+        CONSENSUS_CONFLICTING_VOTES.increment(1);
+        let evidence =
+            EquivocationEvidence { first_vote: first_vote.clone(), second_vote: vote.clone() };
+        if !*is_evidence_stored {
+            *is_evidence_stored = true;
+            self.store.insert(evidence.clone());
+        }
+        Some(evidence)
+    }
+
+    /// Forgets the votes of the heights which fall out of the retention window once consensus
+    /// reaches `height`. Evidence already collected is retained.
+    pub(crate) fn advance_to_height(&mut self, height: BlockNumber) {
+        self.min_height = height.0.saturating_sub(EVIDENCE_RETENTION_HEIGHTS);
+        self.votes = self.votes.split_off(&self.min_height);
+    }
+}
@@ -0,0 +1,95 @@
+use apollo_protobuf::consensus::{Vote, DEFAULT_VALIDATOR_ID};
+use starknet_api::block::BlockNumber;
+use starknet_types_core::felt::Felt;
+use test_case::test_case;
+
+use crate::equivocation::{
+    EquivocationDetector,
+    EquivocationEvidence,
+    EquivocationEvidenceStore,
+    EVIDENCE_RETENTION_HEIGHTS,
+};
+use crate::test_utils::{signed_precommit, signed_prevote};
+
+#[test]
+fn conflicting_votes_produce_evidence() {
+    let store = EquivocationEvidenceStore::default();
+    let mut detector = EquivocationDetector::new(store.clone());
+    let voter = DEFAULT_VALIDATOR_ID.into();
+    let first_vote = signed_precommit(Some(Felt::ONE), 1, 0, voter);
+
+    assert_eq!(detector.check_vote(&first_vote), None);
+    // Repeats and votes of a different round don't conflict.
+    assert_eq!(detector.check_vote(&first_vote), None);
+    assert_eq!(detector.check_vote(&signed_precommit(Some(Felt::TWO), 1, 1, voter)), None);
+
+    let second_vote = signed_precommit(Some(Felt::TWO), 1, 0, voter);
+    let evidence = EquivocationEvidence { first_vote: first_vote.clone(), second_vote };
+    assert_eq!(detector.check_vote(&evidence.second_vote), Some(evidence.clone()));
+    // Further conflicts are detected, but only the first one is stored.
+    let third_vote = signed_precommit(Some(Felt::THREE), 1, 0, voter);
+    assert_eq!(
+        detector.check_vote(&third_vote),
+        Some(EquivocationEvidence { first_vote, second_vote: third_vote })
+    );
+    assert_eq!(store.get_all(), vec![evidence]);
+}
+
+#[test_case(
+    signed_prevote(Some(Felt::ONE), 1, 0, DEFAULT_VALIDATOR_ID.into()),
+    signed_prevote(Some(Felt::TWO), 1, 0, DEFAULT_VALIDATOR_ID.into());
+    "prevotes_for_different_blocks"
+)]
+#[test_case(
+    signed_prevote(None, 1, 0, DEFAULT_VALIDATOR_ID.into()),
+    signed_prevote(Some(Felt::ONE), 1, 0, DEFAULT_VALIDATOR_ID.into());
+    "prevote_for_nil_and_a_block"
+)]
+#[test_case(
+    signed_precommit(Some(Felt::ONE), 1, 0, DEFAULT_VALIDATOR_ID.into()),
+    signed_precommit(None, 1, 0, DEFAULT_VALIDATOR_ID.into());
+    "precommit_for_a_block_and_nil"
+)]
+fn all_vote_types_are_checked(first_vote: Vote, second_vote: Vote) {
+    let store = EquivocationEvidenceStore::default();
+    let mut detector = EquivocationDetector::new(store.clone());
+
+    assert_eq!(detector.check_vote(&first_vote), None);
+    let evidence = EquivocationEvidence { first_vote, second_vote };
+    assert_eq!(detector.check_vote(&evidence.second_vote), Some(evidence.clone()));
+    assert_eq!(store.get_all(), vec![evidence]);
+}
+
+#[test]
+fn votes_of_retained_past_heights_are_checked() {
+    let store = EquivocationEvidenceStore::default();
+    let mut detector = EquivocationDetector::new(store.clone());
+    let voter = DEFAULT_VALIDATOR_ID.into();
+    let old_height = 1;
+    let past_height = 2;
+    for height in [old_height, past_height] {
+        detector.check_vote(&signed_precommit(Some(Felt::ONE), height, 0, voter));
+    }
+    detector.advance_to_height(BlockNumber(EVIDENCE_RETENTION_HEIGHTS + past_height));
+
+    let past_conflict = signed_precommit(Some(Felt::TWO), past_height, 0, voter);
+    assert!(detector.check_vote(&past_conflict).is_some());
+    // Votes of heights which fell out of the retention window are forgotten.
+    assert_eq!(detector.check_vote(&signed_precommit(Some(Felt::TWO), old_height, 0, voter)), None);
+    assert_eq!(store.get_all().len(), 1);
+}
+
+#[test]
+fn store_retains_recent_heights() {
+    let store = EquivocationEvidenceStore::default();
+    let mut detector = EquivocationDetector::new(store.clone());
+    let voter = DEFAULT_VALIDATOR_ID.into();
+    for height in [1, EVIDENCE_RETENTION_HEIGHTS, EVIDENCE_RETENTION_HEIGHTS + 1] {
+        detector.check_vote(&signed_precommit(Some(Felt::ONE), height, 0, voter));
+        detector.check_vote(&signed_precommit(Some(Felt::TWO), height, 0, voter));
+    }
+
+    assert!(store.get(BlockNumber(1)).is_empty());
+    assert_eq!(store.get(BlockNumber(EVIDENCE_RETENTION_HEIGHTS)).len(), 1);
+    assert_eq!(store.get_all().len(), 2);
+}
@@ -25,6 +25,7 @@
 //! 2. It generates and runs its own events (e.g. timeouts).
 
 pub mod config;
+pub mod equivocation;
 #[allow(missing_docs)]
 pub mod types;
 pub use manager::{run_consensus, RunConsensusArguments};
@@ -41,6 +42,7 @@ mod single_height_consensus;
 mod state_machine;
 #[allow(missing_docs)]
 pub mod votes_threshold;
+mod wal;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
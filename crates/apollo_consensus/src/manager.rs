@@ -10,20 +10,23 @@
 mod manager_test;
 
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use apollo_network::network_manager::BroadcastTopicClientTrait;
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
 use apollo_protobuf::consensus::{ProposalInit, Vote};
 use apollo_protobuf::converters::ProtobufConversionError;
+use apollo_signature_manager::signature_manager::verify_vote_signature;
 use apollo_time::time::{sleep_until, Clock, DefaultClock};
 use futures::channel::mpsc;
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt, StreamExt};
 use starknet_api::block::BlockNumber;
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::config::TimeoutsConfig;
+use crate::equivocation::{EquivocationDetector, EquivocationEvidenceStore};
 use crate::metrics::{
     register_metrics,
     CONSENSUS_BLOCK_NUMBER,
@@ -34,8 +37,16 @@ use crate::metrics::{
     CONSENSUS_PROPOSALS_RECEIVED,
 };
 use crate::single_height_consensus::{ShcReturn, SingleHeightConsensus};
-use crate::types::{BroadcastVoteChannel, ConsensusContext, ConsensusError, Decision, ValidatorId};
+use crate::types::{
+    signed_vote_content,
+    BroadcastVoteChannel,
+    ConsensusContext,
+    ConsensusError,
+    Decision,
+    ValidatorId,
+};
 use crate::votes_threshold::QuorumType;
+use crate::wal::WriteAheadLog;
 
 /// Arguments for running consensus.
 #[derive(Clone, Debug)]
@@ -54,6 +65,11 @@ pub struct RunConsensusArguments {
     pub sync_retry_interval: Duration,
     /// Set to Byzantine by default. Using Honest means we trust all validators. Use with caution!
     pub quorum_type: QuorumType,
+    /// The file in which own votes are recorded before being broadcast. If set, a restarted node
+    /// restores them so it never contradicts a vote it has already cast.
+    pub wal_path: Option<PathBuf>,
+    /// Collects evidence of equivocating validators, which can be queried while consensus runs.
+    pub equivocation_evidence_store: EquivocationEvidenceStore,
 }
 
 /// Run consensus indefinitely.
@@ -91,6 +107,8 @@ where
         run_consensus_args.sync_retry_interval,
         run_consensus_args.quorum_type,
         run_consensus_args.timeouts,
+        run_consensus_args.wal_path,
+        run_consensus_args.equivocation_evidence_store,
     );
     loop {
         let must_observer = current_height < run_consensus_args.start_active_height;
@@ -148,6 +166,8 @@ struct MultiHeightManager<ContextT: ConsensusContext> {
     // Mapping: { Height : { Round : (Init, Receiver)}}
     cached_proposals: BTreeMap<u64, BTreeMap<u32, ProposalReceiverTuple<ContextT::ProposalPart>>>,
     timeouts: TimeoutsConfig,
+    wal_path: Option<PathBuf>,
+    equivocation_detector: EquivocationDetector,
 }
 
 impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
@@ -157,6 +177,8 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         sync_retry_interval: Duration,
         quorum_type: QuorumType,
         timeouts: TimeoutsConfig,
+        wal_path: Option<PathBuf>,
+        equivocation_evidence_store: EquivocationEvidenceStore,
     ) -> Self {
         Self {
             validator_id,
@@ -165,6 +187,8 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             future_votes: BTreeMap::new(),
             cached_proposals: BTreeMap::new(),
             timeouts,
+            wal_path,
+            equivocation_detector: EquivocationDetector::new(equivocation_evidence_store),
         }
     }
 
@@ -224,6 +248,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         while let Ok(content_receiver) = proposals_receiver.try_next() {
             self.handle_proposal(context, height, None, content_receiver).await?;
         }
+        self.equivocation_detector.advance_to_height(height.unchecked_next());
 
         Ok(res)
     }
@@ -315,6 +340,12 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         shc: &mut SingleHeightConsensus,
     ) -> Result<ShcReturn, ConsensusError> {
         CONSENSUS_CACHED_VOTES.set_lossy(self.future_votes.entry(height.0).or_default().len());
+        if let Some(wal_path) = &self.wal_path {
+            // Replay the votes cast before a restart, so that SHC doesn't contradict them.
+            let (wal, entries) = WriteAheadLog::open(wal_path, height)
+                .map_err(|err| ConsensusError::WriteAheadLog(err.to_string()))?;
+            shc.restore_from_wal(wal, entries);
+        }
         let mut tasks = match shc.start(context).await? {
             decision @ ShcReturn::Decision(_) => {
                 // Start should generate either TimeoutProposal (validator) or GetProposal
@@ -421,6 +452,18 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
                 "NetworkReceiver should never be closed".to_string(),
             )),
             Some((Ok(msg), metadata)) => {
+                if !Self::is_vote_signature_valid(context, &msg).await {
+                    warn!("Dropping vote with an invalid signature. {:?}", msg);
+                    report_peer(broadcast_channels, metadata);
+                    return Ok(ShcReturn::Tasks(Vec::new()));
+                }
+                // Votes from past heights can no longer affect consensus, but still prove an
+                // equivocation.
+                if let Some(evidence) = self.equivocation_detector.check_vote(&msg) {
+                    warn!("Dropping conflicting vote, reporting the voter. {:?}", evidence);
+                    report_peer(broadcast_channels, metadata);
+                    return Ok(ShcReturn::Tasks(Vec::new()));
+                }
                 // TODO(matan): Hold onto report_sender for use in later errors by SHC.
                 if broadcast_channels
                     .broadcast_topic_client
@@ -434,14 +477,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             }
             Some((Err(e), metadata)) => {
                 // Failed to parse consensus message
-                if broadcast_channels
-                    .broadcast_topic_client
-                    .report_peer(metadata.clone())
-                    .now_or_never()
-                    .is_none()
-                {
-                    error!("Unable to send report_peer. {:?}", metadata)
-                }
+                report_peer(broadcast_channels, metadata);
                 Err(e.into())
             }
         }?;
@@ -469,6 +505,27 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         }
     }
 
+    /// Every vote must be signed by its voter.
+    async fn is_vote_signature_valid(context: &ContextT, vote: &Vote) -> bool {
+        let Some(signature) = vote.signature else {
+            debug!("Vote is missing a signature. {:?}", vote);
+            return false;
+        };
+        let Some(public_key) =
+            context.validator_public_key(BlockNumber(vote.height), vote.voter).await
+        else {
+            debug!("No public key is known for the voter. {:?}", vote);
+            return false;
+        };
+        match verify_vote_signature(signed_vote_content(vote), signature.into(), public_key) {
+            Ok(is_valid) => is_valid,
+            Err(e) => {
+                debug!("Failed to verify vote signature: {e:?}. {:?}", vote);
+                false
+            }
+        }
+    }
+
     /// Checks if a cached proposal already exists (with correct height)
     /// - returns the proposals for the height if they exist and removes them from the cache.
     /// - cleans up any proposals from earlier heights.
@@ -519,3 +576,17 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         CONSENSUS_MAX_CACHED_BLOCK_NUMBER.set_lossy(*max_cached_block_number);
     }
 }
+
+fn report_peer(
+    broadcast_channels: &mut BroadcastVoteChannel,
+    metadata: BroadcastedMessageMetadata,
+) {
+    if broadcast_channels
+        .broadcast_topic_client
+        .report_peer(metadata.clone())
+        .now_or_never()
+        .is_none()
+    {
+        error!("Unable to send report_peer. {:?}", metadata)
+    }
+}
@@ -10,14 +10,24 @@ use apollo_network_types::network_types::BroadcastedMessageMetadata;
 use apollo_protobuf::consensus::{Vote, DEFAULT_VALIDATOR_ID};
 use apollo_test_utils::{get_rng, GetTestInstance};
 use futures::channel::{mpsc, oneshot};
-use futures::{FutureExt, SinkExt};
+use futures::{FutureExt, SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_types_core::felt::Felt;
 
 use super::{run_consensus, MultiHeightManager, RunHeightRes};
 use crate::config::TimeoutsConfig;
-use crate::test_utils::{precommit, prevote, proposal_init, MockTestContext, TestProposalPart};
+use crate::equivocation::{EquivocationEvidence, EquivocationEvidenceStore};
+use crate::test_utils::{
+    precommit,
+    prevote,
+    proposal_init,
+    signed_precommit,
+    signed_prevote,
+    test_public_key,
+    MockTestContext,
+    TestProposalPart,
+};
 use crate::types::ValidatorId;
 use crate::votes_threshold::QuorumType;
 use crate::RunConsensusArguments;
@@ -87,23 +97,25 @@ async fn manager_multiple_heights_unordered() {
         vec![TestProposalPart::Init(proposal_init(2, 0, *PROPOSER_ID))],
     )
     .await;
-    send(&mut sender, prevote(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
-    send(&mut sender, precommit(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
+    send(&mut sender, signed_prevote(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
+    send(&mut sender, signed_precommit(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
 
     send_proposal(
         &mut proposal_receiver_sender,
         vec![TestProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID))],
     )
     .await;
-    send(&mut sender, prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
-    send(&mut sender, precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+    send(&mut sender, signed_prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+    send(&mut sender, signed_precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
 
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
     // Run the manager for height 1.
     context.expect_try_sync().returning(|_| false);
     expect_validate_proposal(&mut context, Felt::ONE, 1);
     context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_validator_public_key().returning(|_, _| Some(test_public_key()));
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
 
@@ -112,6 +124,8 @@ async fn manager_multiple_heights_unordered() {
         SYNC_RETRY_INTERVAL,
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
+        None,
+        EquivocationEvidenceStore::default(),
     );
     let mut subscriber_channels = subscriber_channels.into();
     let decision = manager
@@ -145,6 +159,7 @@ async fn manager_multiple_heights_unordered() {
 async fn run_consensus_sync() {
     // Set expectations.
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
     let (decision_tx, decision_rx) = oneshot::channel();
 
     let (mut proposal_receiver_sender, proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
@@ -152,6 +167,7 @@ async fn run_consensus_sync() {
     expect_validate_proposal(&mut context, Felt::TWO, 1);
     context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_validator_public_key().returning(|_, _| Some(test_public_key()));
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
     context
@@ -177,8 +193,8 @@ async fn run_consensus_sync() {
     let TestSubscriberChannels { mock_network, subscriber_channels } =
         mock_register_broadcast_topic().unwrap();
     let mut network_sender = mock_network.broadcasted_messages_sender;
-    send(&mut network_sender, prevote(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
-    send(&mut network_sender, precommit(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
+    send(&mut network_sender, signed_prevote(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
+    send(&mut network_sender, signed_precommit(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
     let run_consensus_args = RunConsensusArguments {
         start_active_height: BlockNumber(1),
         start_observe_height: BlockNumber(1),
@@ -187,6 +203,8 @@ async fn run_consensus_sync() {
         timeouts: TIMEOUTS.clone(),
         sync_retry_interval: SYNC_RETRY_INTERVAL,
         quorum_type: QuorumType::Byzantine,
+        wal_path: None,
+        equivocation_evidence_store: EquivocationEvidenceStore::default(),
     };
     // Start at height 1.
     tokio::spawn(async move {
@@ -217,18 +235,20 @@ async fn test_timeouts() {
         vec![TestProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID))],
     )
     .await;
-    send(&mut sender, prevote(None, 1, 0, *VALIDATOR_ID_2)).await;
-    send(&mut sender, prevote(None, 1, 0, *VALIDATOR_ID_3)).await;
-    send(&mut sender, precommit(None, 1, 0, *VALIDATOR_ID_2)).await;
-    send(&mut sender, precommit(None, 1, 0, *VALIDATOR_ID_3)).await;
+    send(&mut sender, signed_prevote(None, 1, 0, *VALIDATOR_ID_2)).await;
+    send(&mut sender, signed_prevote(None, 1, 0, *VALIDATOR_ID_3)).await;
+    send(&mut sender, signed_precommit(None, 1, 0, *VALIDATOR_ID_2)).await;
+    send(&mut sender, signed_precommit(None, 1, 0, *VALIDATOR_ID_3)).await;
 
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
     context.expect_set_height_and_round().returning(move |_, _| ());
     expect_validate_proposal(&mut context, Felt::ONE, 2);
     context
         .expect_validators()
         .returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3]);
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_validator_public_key().returning(|_, _| Some(test_public_key()));
     context.expect_try_sync().returning(|_| false);
 
     let (timeout_send, timeout_receive) = oneshot::channel();
@@ -248,6 +268,8 @@ async fn test_timeouts() {
         SYNC_RETRY_INTERVAL,
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
+        None,
+        EquivocationEvidenceStore::default(),
     );
     let manager_handle = tokio::spawn(async move {
         let decision = manager
@@ -272,11 +294,11 @@ async fn test_timeouts() {
         vec![TestProposalPart::Init(proposal_init(1, 1, *PROPOSER_ID))],
     )
     .await;
-    send(&mut sender, prevote(Some(Felt::ONE), 1, 1, *PROPOSER_ID)).await;
-    send(&mut sender, prevote(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_2)).await;
-    send(&mut sender, prevote(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_3)).await;
-    send(&mut sender, precommit(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_2)).await;
-    send(&mut sender, precommit(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_3)).await;
+    send(&mut sender, signed_prevote(Some(Felt::ONE), 1, 1, *PROPOSER_ID)).await;
+    send(&mut sender, signed_prevote(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_2)).await;
+    send(&mut sender, signed_prevote(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_3)).await;
+    send(&mut sender, signed_precommit(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_2)).await;
+    send(&mut sender, signed_precommit(Some(Felt::ONE), 1, 1, *VALIDATOR_ID_3)).await;
 
     manager_handle.await.unwrap();
 }
@@ -309,6 +331,8 @@ async fn timely_message_handling() {
         SYNC_RETRY_INTERVAL,
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
+        None,
+        EquivocationEvidenceStore::default(),
     );
     let res = manager
         .run_height(
@@ -326,3 +350,191 @@ async fn timely_message_handling() {
     proposal_receiver_sender.try_send(mpsc::channel(1).1).unwrap();
     assert!(vote_sender.send((vote.clone(), metadata.clone())).now_or_never().is_some());
 }
+
+#[tokio::test]
+async fn forged_vote_is_dropped_and_reported() {
+    let mut context = MockTestContext::new();
+    context.expect_try_sync().returning(|_| true);
+    context.expect_validator_public_key().returning(|_, _| Some(test_public_key()));
+
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut subscriber_channels = subscriber_channels.into();
+    let mut vote_sender = mock_network.broadcasted_messages_sender;
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let mut continue_propagation_receiver = mock_network.continue_propagation_receiver;
+
+    let mut rng = get_rng();
+    // A precommit for block 1 carrying a signature over block 2.
+    let mut forged_vote = signed_precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID);
+    forged_vote.signature = signed_precommit(Some(Felt::TWO), 1, 0, *PROPOSER_ID).signature;
+    let forged_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((forged_vote, forged_metadata.clone())).await.unwrap();
+    // A precommit carrying the signature of another voter for the same block.
+    let mut replayed_voter_vote = signed_precommit(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_2);
+    replayed_voter_vote.signature =
+        signed_precommit(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_3).signature;
+    let replayed_voter_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((replayed_voter_vote, replayed_voter_metadata.clone())).await.unwrap();
+    // A precommit carrying the voter's signature from another round.
+    let mut replayed_round_vote = signed_precommit(Some(Felt::ONE), 1, 1, *PROPOSER_ID);
+    replayed_round_vote.signature = signed_precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID).signature;
+    let replayed_round_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((replayed_round_vote, replayed_round_metadata.clone())).await.unwrap();
+    // A prevote carrying the voter's precommit signature.
+    let mut replayed_type_vote = signed_prevote(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_2);
+    replayed_type_vote.signature =
+        signed_precommit(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_2).signature;
+    let replayed_type_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((replayed_type_vote, replayed_type_metadata.clone())).await.unwrap();
+    // A nil precommit carrying the voter's signature for a block.
+    let mut replayed_nil_vote = signed_precommit(None, 1, 0, *VALIDATOR_ID_2);
+    replayed_nil_vote.signature =
+        signed_precommit(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_2).signature;
+    let replayed_nil_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((replayed_nil_vote, replayed_nil_metadata.clone())).await.unwrap();
+    // Votes without any signature.
+    let unsigned_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender
+        .send((precommit(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_2), unsigned_metadata.clone()))
+        .await
+        .unwrap();
+    let unsigned_prevote_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender
+        .send((prevote(None, 1, 0, *VALIDATOR_ID_2), unsigned_prevote_metadata.clone()))
+        .await
+        .unwrap();
+    let valid_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender
+        .send((signed_precommit(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_3), valid_metadata.clone()))
+        .await
+        .unwrap();
+
+    let (_proposal_receiver_sender, mut proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        SYNC_RETRY_INTERVAL,
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+        None,
+        EquivocationEvidenceStore::default(),
+    );
+    let res = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await;
+    assert_eq!(res, Ok(RunHeightRes::Sync));
+
+    for metadata in [
+        forged_metadata,
+        replayed_voter_metadata,
+        replayed_round_metadata,
+        replayed_type_metadata,
+        replayed_nil_metadata,
+        unsigned_metadata,
+        unsigned_prevote_metadata,
+    ] {
+        assert_eq!(
+            reported_messages_receiver.next().now_or_never(),
+            Some(Some(metadata.originator_id.private_get_peer_id()))
+        );
+    }
+    assert!(reported_messages_receiver.next().now_or_never().is_none());
+    assert_eq!(continue_propagation_receiver.next().now_or_never(), Some(Some(valid_metadata)));
+    assert!(continue_propagation_receiver.next().now_or_never().is_none());
+}
+
+#[tokio::test]
+async fn equivocating_vote_is_dropped_and_reported() {
+    let mut context = MockTestContext::new();
+    context.expect_try_sync().returning(|_| true);
+    context.expect_validator_public_key().returning(|_, _| Some(test_public_key()));
+
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut subscriber_channels = subscriber_channels.into();
+    let mut vote_sender = mock_network.broadcasted_messages_sender;
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let mut continue_propagation_receiver = mock_network.continue_propagation_receiver;
+
+    let mut rng = get_rng();
+    let first_vote = signed_precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID);
+    let first_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((first_vote.clone(), first_metadata.clone())).await.unwrap();
+    let conflicting_vote = signed_precommit(Some(Felt::TWO), 1, 0, *PROPOSER_ID);
+    let conflicting_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((conflicting_vote.clone(), conflicting_metadata.clone())).await.unwrap();
+    let prevote = signed_prevote(None, 1, 0, *PROPOSER_ID);
+    let prevote_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((prevote.clone(), prevote_metadata.clone())).await.unwrap();
+    let conflicting_prevote = signed_prevote(Some(Felt::TWO), 1, 0, *PROPOSER_ID);
+    let conflicting_prevote_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender
+        .send((conflicting_prevote.clone(), conflicting_prevote_metadata.clone()))
+        .await
+        .unwrap();
+    let next_round_precommit = signed_precommit(Some(Felt::ONE), 1, 1, *PROPOSER_ID);
+    let next_round_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((next_round_precommit.clone(), next_round_metadata.clone())).await.unwrap();
+
+    let (_proposal_receiver_sender, mut proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
+    let equivocation_evidence_store = EquivocationEvidenceStore::default();
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        SYNC_RETRY_INTERVAL,
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+        None,
+        equivocation_evidence_store.clone(),
+    );
+    let res = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await;
+    assert_eq!(res, Ok(RunHeightRes::Sync));
+
+    // Votes arriving after their height was completed are checked as well.
+    let late_precommit = signed_precommit(None, 1, 1, *PROPOSER_ID);
+    let late_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    vote_sender.send((late_precommit.clone(), late_metadata.clone())).await.unwrap();
+    let res = manager
+        .run_height(
+            &mut context,
+            BlockNumber(2),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await;
+    assert_eq!(res, Ok(RunHeightRes::Sync));
+
+    for metadata in [first_metadata, prevote_metadata, next_round_metadata] {
+        assert_eq!(continue_propagation_receiver.next().now_or_never(), Some(Some(metadata)));
+    }
+    assert!(continue_propagation_receiver.next().now_or_never().is_none());
+    for metadata in [conflicting_metadata, conflicting_prevote_metadata, late_metadata] {
+        assert_eq!(
+            reported_messages_receiver.next().now_or_never(),
+            Some(Some(metadata.originator_id.private_get_peer_id()))
+        );
+    }
+    assert!(reported_messages_receiver.next().now_or_never().is_none());
+    assert_eq!(
+        equivocation_evidence_store.get(BlockNumber(1)),
+        vec![
+            EquivocationEvidence { first_vote, second_vote: conflicting_vote },
+            EquivocationEvidence { first_vote: prevote, second_vote: conflicting_prevote },
+            EquivocationEvidence { first_vote: next_round_precommit, second_vote: late_precommit },
+        ]
+    );
+}
@@ -42,6 +42,7 @@ use crate::types::{
     ValidatorId,
 };
 use crate::votes_threshold::QuorumType;
+use crate::wal::{WalEntry, WriteAheadLog};
 
 /// The SHC can either update the manager of a decision or return tasks that should be run without
 /// blocking further calls to itself.
@@ -173,6 +174,9 @@ pub(crate) struct SingleHeightConsensus {
     precommits: HashMap<(Round, ValidatorId), Vote>,
     last_prevote: Option<Vote>,
     last_precommit: Option<Vote>,
+    // Persists own votes and the locked and valid values, so that they survive a restart.
+    #[serde(skip)]
+    wal: Option<WriteAheadLog>,
 }
 
 impl SingleHeightConsensus {
@@ -199,9 +203,72 @@ impl SingleHeightConsensus {
             precommits: HashMap::new(),
             last_prevote: None,
             last_precommit: None,
+            wal: None,
         }
     }
 
+    /// Restores the votes and the locked and valid values recorded in the WAL before a restart, and
+    /// records all further ones to it. Must be called before `start`.
+    ///
+    /// The node resumes from the round following its last vote, so it never votes twice in the
+    /// same round.
+    pub(crate) fn restore_from_wal(&mut self, wal: WriteAheadLog, entries: Vec<WalEntry>) {
+        let mut locked_value_round = None;
+        let mut valid_value_round = None;
+        let mut own_votes = Vec::new();
+        for entry in entries {
+            let vote = match entry {
+                WalEntry::Lock(lock) => {
+                    locked_value_round = lock;
+                    continue;
+                }
+                WalEntry::Valid(valid_value, valid_round) => {
+                    valid_value_round = Some((valid_value, valid_round));
+                    continue;
+                }
+                WalEntry::Vote(vote) => vote,
+            };
+            let (votes, last_vote, sm_vote) = match vote.vote_type {
+                VoteType::Prevote => (
+                    &mut self.prevotes,
+                    &mut self.last_prevote,
+                    StateMachineEvent::Prevote(vote.block_hash, vote.round),
+                ),
+                VoteType::Precommit => (
+                    &mut self.precommits,
+                    &mut self.last_precommit,
+                    StateMachineEvent::Precommit(vote.block_hash, vote.round),
+                ),
+            };
+            if last_vote.as_ref().is_none_or(|last_vote| vote.round > last_vote.round) {
+                *last_vote = Some(vote.clone());
+            }
+            votes.insert((vote.round, vote.voter), vote);
+            own_votes.push(sm_vote);
+        }
+
+        let round = [&self.last_prevote, &self.last_precommit]
+            .into_iter()
+            .flatten()
+            .map(|vote| vote.round + 1)
+            .max()
+            .unwrap_or(0);
+        if round > 0 || locked_value_round.is_some() || valid_value_round.is_some() {
+            info!(
+                "Restored consensus state from WAL. round: {round}, locked_value_round: \
+                 {locked_value_round:?}, valid_value_round: {valid_value_round:?}, last_prevote: \
+                 {:?}, last_precommit: {:?}",
+                self.last_prevote, self.last_precommit
+            );
+        }
+        if let Some((valid_value, valid_round)) = valid_value_round {
+            // A reproposal of the valid value refers to the proposal of its round.
+            self.proposals.insert(valid_round, Some(valid_value));
+        }
+        self.state_machine.restore(round, locked_value_round, valid_value_round, own_votes);
+        self.wal = Some(wal);
+    }
+
     #[instrument(skip_all)]
     pub(crate) async fn start<ContextT: ConsensusContext>(
         &mut self,
@@ -422,6 +489,11 @@ impl SingleHeightConsensus {
         context: &mut ContextT,
         mut events: VecDeque<StateMachineEvent>,
     ) -> Result<ShcReturn, ConsensusError> {
+        if let Some(wal) = &mut self.wal {
+            // The state machine may have updated the valid value while producing `events`.
+            wal.update_valid(self.state_machine.valid_value_round())
+                .map_err(|err| ConsensusError::WriteAheadLog(err.to_string()))?;
+        }
         let mut ret_val = Vec::new();
         while let Some(event) = events.pop_front() {
             trace!("Handling sm event: {:?}", event);
@@ -560,7 +632,10 @@ impl SingleHeightConsensus {
             round,
             block_hash: proposal_id,
             voter: self.id,
+            signature: None,
         };
+        // Signed before it is recorded, so the decision's precommits include this node's signature.
+        let vote = context.sign_vote(vote).await?;
         if let Some(old) = votes.insert((round, self.id), vote.clone()) {
             return Err(ConsensusError::InternalInconsistency(format!(
                 "State machine should not send repeat votes: old={old:?}, new={vote:?}"
@@ -580,6 +655,13 @@ impl SingleHeightConsensus {
             }
         };
 
+        if let Some(wal) = &mut self.wal {
+            // The lock is only updated together with a precommit, so it must be written first.
+            let wal_error = |err: std::io::Error| ConsensusError::WriteAheadLog(err.to_string());
+            wal.update_lock(self.state_machine.locked_value_round()).map_err(wal_error)?;
+            wal.append_vote(&vote).map_err(wal_error)?;
+        }
+
         info!("Broadcasting {vote:?}");
         context.broadcast(vote).await?;
         Ok(vec![task])
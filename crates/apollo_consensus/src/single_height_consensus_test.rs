@@ -10,9 +10,19 @@ use super::SingleHeightConsensus;
 use crate::config::TimeoutsConfig;
 use crate::single_height_consensus::{ShcEvent, ShcReturn, ShcTask};
 use crate::state_machine::StateMachineEvent;
-use crate::test_utils::{precommit, prevote, MockTestContext, TestBlock, TestProposalPart};
+use crate::test_utils::{
+    precommit,
+    prevote,
+    signed_precommit,
+    signed_prevote,
+    signed_vote,
+    MockTestContext,
+    TestBlock,
+    TestProposalPart,
+};
 use crate::types::ValidatorId;
 use crate::votes_threshold::QuorumType;
+use crate::wal::WriteAheadLog;
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = DEFAULT_VALIDATOR_ID.into();
@@ -72,6 +82,7 @@ async fn handle_proposal(
 #[tokio::test]
 async fn proposer() {
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(signed_vote(vote)));
 
     let mut shc = SingleHeightConsensus::new(
         BlockNumber(0),
@@ -92,7 +103,7 @@ async fn proposer() {
     context
         .expect_broadcast()
         .times(1)
-        .withf(move |msg: &Vote| msg == &prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID))
+        .withf(move |msg: &Vote| msg == &signed_prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID))
         .returning(move |_| Ok(()));
     // Sends proposal and prevote.
     let shc_ret = shc.start(&mut context).await.unwrap();
@@ -113,7 +124,7 @@ async fn proposer() {
     context
         .expect_broadcast()
         .times(1)
-        .withf(move |msg: &Vote| msg == &precommit(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID))
+        .withf(move |msg: &Vote| msg == &signed_precommit(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID))
         .returning(move |_| Ok(()));
     // The Node got a Prevote quorum.
     assert_eq!(
@@ -121,11 +132,12 @@ async fn proposer() {
         Ok(ShcReturn::Tasks(vec![timeout_prevote_task(0), precommit_task(Some(BLOCK.id.0), 0),]))
     );
 
+    let own_precommit = signed_precommit(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID);
     let precommits = vec![
         precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1),
         precommit(Some(Felt::TWO), 0, 0, *VALIDATOR_ID_3),
         precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_2),
-        precommit(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID),
+        own_precommit.clone(),
     ];
     assert_eq!(
         shc.handle_vote(&mut context, precommits[0].clone()).await,
@@ -151,6 +163,7 @@ async fn proposer() {
 #[tokio::test]
 async fn validator(repeat_proposal: bool) {
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
 
     // Creation calls to `context.validators`.
     let mut shc = SingleHeightConsensus::new(
@@ -224,6 +237,7 @@ async fn validator(repeat_proposal: bool) {
 #[tokio::test]
 async fn vote_twice(same_vote: bool) {
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
 
     let mut shc = SingleHeightConsensus::new(
         BlockNumber(0),
@@ -290,6 +304,7 @@ async fn vote_twice(same_vote: bool) {
 #[tokio::test]
 async fn rebroadcast_votes() {
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
 
     let mut shc = SingleHeightConsensus::new(
         BlockNumber(0),
@@ -354,6 +369,7 @@ async fn rebroadcast_votes() {
 #[tokio::test]
 async fn repropose() {
     let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
 
     let mut shc = SingleHeightConsensus::new(
         BlockNumber(0),
@@ -434,5 +450,113 @@ async fn repropose() {
         panic!("Expected decision");
     };
     assert_eq!(decision.block, BLOCK.id);
+    // The node's own precommit is included with its signature.
+    assert!(decision.precommits.contains(&own_precommit));
     assert!(decision.precommits.into_iter().all(|item| precommits.contains(&item)));
 }
+
+#[tokio::test]
+async fn restore_from_wal() {
+    let mut context = MockTestContext::new();
+    context.expect_sign_vote().returning(|vote| Ok(vote));
+    let wal_dir = tempfile::tempdir().unwrap();
+    let wal_path = wal_dir.path().join("consensus.wal");
+    // Votes cast for round 0 before a restart.
+    let (mut wal, _) = WriteAheadLog::open(&wal_path, BlockNumber(0)).unwrap();
+    wal.append_vote(&prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)).unwrap();
+    wal.update_lock(Some((BLOCK.id, 0))).unwrap();
+    wal.append_vote(&precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)).unwrap();
+    drop(wal);
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.to_vec(),
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+    );
+    let (wal, entries) = WriteAheadLog::open(&wal_path, BlockNumber(0)).unwrap();
+    shc.restore_from_wal(wal, entries);
+
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    // The node resumes from the round after its last vote.
+    assert_eq!(
+        shc.start(&mut context).await,
+        Ok(ShcReturn::Tasks(vec![ShcTask::TimeoutPropose(
+            TIMEOUTS.proposal_timeout,
+            StateMachineEvent::TimeoutPropose(1)
+        )]))
+    );
+
+    let other_block = BlockHash(Felt::TWO);
+    context.expect_validate_proposal().times(1).returning(move |_, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send(other_block).unwrap();
+        block_receiver
+    });
+    let init = ProposalInit { round: 1, proposer: *PROPOSER_ID, ..Default::default() };
+    let (_content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    shc.handle_proposal(&mut context, init, content_receiver).await.unwrap();
+    // The node is still locked on the block from round 0.
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &Vote| msg == &prevote(None, 0, 1, *VALIDATOR_ID_1))
+        .returning(move |_| Ok(()));
+    assert_eq!(
+        shc.handle_event(
+            &mut context,
+            ShcEvent::ValidateProposal(StateMachineEvent::Proposal(Some(other_block), 1, None)),
+        )
+        .await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(None, 1)]))
+    );
+    // The new prevote is recorded, the unchanged lock isn't.
+    let (_wal, entries) = WriteAheadLog::open(&wal_path, BlockNumber(0)).unwrap();
+    assert_eq!(entries.len(), 4);
+}
+
+#[tokio::test]
+async fn restore_valid_value_from_wal() {
+    let mut context = MockTestContext::new();
+    let wal_dir = tempfile::tempdir().unwrap();
+    let wal_path = wal_dir.path().join("consensus.wal");
+    // The node saw a prevote quorum for the block in round 0 after it had already precommitted nil.
+    let (mut wal, _) = WriteAheadLog::open(&wal_path, BlockNumber(0)).unwrap();
+    wal.append_vote(&prevote(None, 0, 0, *VALIDATOR_ID_1)).unwrap();
+    wal.append_vote(&precommit(None, 0, 0, *VALIDATOR_ID_1)).unwrap();
+    wal.update_valid(Some((BLOCK.id, 0))).unwrap();
+    drop(wal);
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.to_vec(),
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+    );
+    let (wal, entries) = WriteAheadLog::open(&wal_path, BlockNumber(0)).unwrap();
+    shc.restore_from_wal(wal, entries);
+
+    // As the proposer of the next round, the node reproposes its valid value.
+    context
+        .expect_proposer()
+        .returning(move |_, round| if round == 1 { *VALIDATOR_ID_1 } else { *PROPOSER_ID });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_repropose().times(1).returning(move |id, init| {
+        assert_eq!(id, BLOCK.id);
+        assert_eq!(
+            init,
+            ProposalInit {
+                height: BlockNumber(0),
+                round: 1,
+                proposer: *VALIDATOR_ID_1,
+                valid_round: Some(0),
+            }
+        );
+    });
+    shc.start(&mut context).await.unwrap();
+}
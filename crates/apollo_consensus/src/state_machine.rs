@@ -131,14 +131,53 @@ impl StateMachine {
         &self.quorum
     }
 
-    /// Starts the state machine, effectively calling `StartRound(0)` from the paper. This is
-    /// needed to trigger the first leader to propose.
+    pub fn locked_value_round(&self) -> Option<(ProposalCommitment, Round)> {
+        self.locked_value_round
+    }
+
+    pub fn valid_value_round(&self) -> Option<(ProposalCommitment, Round)> {
+        self.valid_value_round
+    }
+
+    /// Restores the state of a node which restarted mid-height. Must be called before `start`,
+    /// which then starts `round` instead of round 0.
+    /// - `valid_value_round`: the latest value this node saw a prevote quorum for, which it
+    ///   reproposes when it is the proposer.
+    /// - `own_votes`: the prevotes and precommits this node cast before restarting. They are
+    ///   counted, but no upon rule is triggered by them.
+    pub fn restore(
+        &mut self,
+        round: Round,
+        locked_value_round: Option<(ProposalCommitment, Round)>,
+        valid_value_round: Option<(ProposalCommitment, Round)>,
+        own_votes: impl IntoIterator<Item = StateMachineEvent>,
+    ) {
+        self.round = round;
+        self.locked_value_round = locked_value_round;
+        self.valid_value_round = valid_value_round;
+        for vote in own_votes {
+            let (votes, proposal_id, vote_round) = match vote {
+                StateMachineEvent::Prevote(proposal_id, vote_round) => {
+                    (&mut self.prevotes, proposal_id, vote_round)
+                }
+                StateMachineEvent::Precommit(proposal_id, vote_round) => {
+                    (&mut self.precommits, proposal_id, vote_round)
+                }
+                _ => panic!("Only votes can be restored, got: {vote:?}"),
+            };
+            // TODO(matan): Use variable weight.
+            *votes.entry(vote_round).or_default().entry(proposal_id).or_insert(0) += 1;
+        }
+    }
+
+    /// Starts the state machine, effectively calling `StartRound(0)` from the paper (or the round
+    /// set by [`restore`](Self::restore)). This is needed to trigger the first leader to propose.
     /// See [`GetProposal`](StateMachineEvent::GetProposal)
     pub fn start<LeaderFn>(&mut self, leader_fn: &LeaderFn) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
-        self.advance_to_round(0, leader_fn)
+        self.advance_to_round(self.round, leader_fn)
     }
 
     /// Process the incoming event.
@@ -2,13 +2,23 @@ use std::time::Duration;
 
 use apollo_protobuf::consensus::{ProposalInit, Vote, VoteType};
 use apollo_protobuf::converters::ProtobufConversionError;
+use apollo_signature_manager::SignatureManager;
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
+use futures::FutureExt;
 use mockall::mock;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::crypto::utils::PublicKey;
 use starknet_types_core::felt::Felt;
 
-use crate::types::{ConsensusContext, ConsensusError, ProposalCommitment, Round, ValidatorId};
+use crate::types::{
+    signed_vote_content,
+    ConsensusContext,
+    ConsensusError,
+    ProposalCommitment,
+    Round,
+    ValidatorId,
+};
 
 /// Define a consensus block which can be used to enable auto mocking Context.
 #[derive(Debug, PartialEq, Clone)]
@@ -82,6 +92,14 @@ mock! {
 
         fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
+        async fn validator_public_key(
+            &self,
+            height: BlockNumber,
+            validator: ValidatorId,
+        ) -> Option<PublicKey>;
+
+        async fn sign_vote(&mut self, vote: Vote) -> Result<Vote, ConsensusError>;
+
         async fn broadcast(&mut self, message: Vote) -> Result<(), ConsensusError>;
 
         async fn decision_reached(
@@ -98,13 +116,49 @@ mock! {
 
 pub fn prevote(block_felt: Option<Felt>, height: u64, round: u32, voter: ValidatorId) -> Vote {
     let block_hash = block_felt.map(BlockHash);
-    Vote { vote_type: VoteType::Prevote, height, round, block_hash, voter }
+    Vote { vote_type: VoteType::Prevote, height, round, block_hash, voter, signature: None }
 }
 
 pub fn precommit(block_felt: Option<Felt>, height: u64, round: u32, voter: ValidatorId) -> Vote {
     let block_hash = block_felt.map(BlockHash);
-    Vote { vote_type: VoteType::Precommit, height, round, block_hash, voter }
+    Vote { vote_type: VoteType::Precommit, height, round, block_hash, voter, signature: None }
+}
+
+/// Signs `vote` with the testing key store (see [`test_public_key`]), as its voter would.
+pub fn signed_vote(mut vote: Vote) -> Vote {
+    let signature = SignatureManager::new()
+        .sign_vote(signed_vote_content(&vote))
+        .now_or_never()
+        .expect("The testing key store should sign immediately")
+        .expect("Failed to sign vote");
+    vote.signature = Some(signature.try_into().expect("Failed to convert signature"));
+    vote
+}
+
+/// A prevote as received from the network, signed by the testing key store.
+pub fn signed_prevote(
+    block_felt: Option<Felt>,
+    height: u64,
+    round: u32,
+    voter: ValidatorId,
+) -> Vote {
+    signed_vote(prevote(block_felt, height, round, voter))
 }
+
+/// A precommit as received from the network, signed by the testing key store.
+pub fn signed_precommit(
+    block_felt: Option<Felt>,
+    height: u64,
+    round: u32,
+    voter: ValidatorId,
+) -> Vote {
+    signed_vote(precommit(block_felt, height, round, voter))
+}
+
+pub fn test_public_key() -> PublicKey {
+    SignatureManager::new().keystore.public_key
+}
+
 pub fn proposal_init(height: u64, round: u32, proposer: ValidatorId) -> ProposalInit {
     ProposalInit { height: BlockNumber(height), round, proposer, ..Default::default() }
 }
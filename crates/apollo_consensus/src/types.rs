@@ -8,12 +8,14 @@ use apollo_network::network_manager::{
     GenericReceiver,
 };
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
-use apollo_protobuf::consensus::{ProposalInit, Vote};
+use apollo_protobuf::consensus::{ProposalInit, Vote, VoteType};
 use apollo_protobuf::converters::ProtobufConversionError;
+use apollo_signature_manager_types::{ConsensusVote, ConsensusVoteType};
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::PublicKey;
 
 /// Used to identify the node by consensus.
 /// 1. This ID is derived from the id registered with Starknet's L2 staking contract.
@@ -24,6 +26,21 @@ pub type ValidatorId = ContractAddress;
 pub type Round = u32;
 pub type ProposalCommitment = BlockHash;
 
+/// Returns the fields of `vote` covered by the voter's signature. Every vote is signed.
+pub fn signed_vote_content(vote: &Vote) -> ConsensusVote {
+    let vote_type = match vote.vote_type {
+        VoteType::Prevote => ConsensusVoteType::Prevote,
+        VoteType::Precommit => ConsensusVoteType::Precommit,
+    };
+    ConsensusVote {
+        vote_type,
+        height: BlockNumber(vote.height),
+        round: vote.round,
+        block_hash: vote.block_hash,
+        voter: vote.voter,
+    }
+}
+
 /// Interface for consensus to call out to the node.
 ///
 /// Function calls should be assumed to not be cancel safe.
@@ -100,6 +117,18 @@ pub trait ConsensusContext {
     // TODO(matan): Consider passing the validator set in order to keep this sync.
     fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
+    /// Get the public key used to verify the votes of `validator` at the given height. Returns
+    /// None if the key is unknown, in which case the validator's votes are rejected.
+    async fn validator_public_key(
+        &self,
+        height: BlockNumber,
+        validator: ValidatorId,
+    ) -> Option<PublicKey>;
+
+    /// Signs this node's `vote` (see [`signed_vote_content`]). Called before the vote is recorded
+    /// and broadcast.
+    async fn sign_vote(&mut self, vote: Vote) -> Result<Vote, ConsensusError>;
+
     async fn broadcast(&mut self, message: Vote) -> Result<(), ConsensusError>;
 
     /// Update the context that a decision has been reached for a given height.
@@ -161,6 +190,8 @@ pub enum ConsensusError {
     // For example the state machine and SHC are out of sync.
     #[error("{0}")]
     InternalInconsistency(String),
+    #[error("Failed to access the consensus write-ahead log: {0}")]
+    WriteAheadLog(String),
     #[error("Block info conversion error: {0}")]
     BlockInfoConversion(#[from] starknet_api::StarknetApiError),
     #[error("{0}")]
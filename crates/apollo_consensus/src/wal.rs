@@ -0,0 +1,117 @@
+//! Write-ahead log (WAL) of the votes cast by this node and of its locked and valid values.
+//!
+//! Entries are written (and synced to disk) before the corresponding vote is broadcast, so that a
+//! validator which crashes mid-height and restarts never contradicts a vote it has already cast.
+
+#[cfg(test)]
+#[path = "wal_test.rs"]
+mod wal_test;
+
+use std::io;
+use std::path::Path;
+
+use apollo_infra_utils::record_file::RecordFile;
+use apollo_protobuf::consensus::Vote;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+
+use crate::types::{ProposalCommitment, Round};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) enum WalEntry {
+    /// A vote cast by this node.
+    Vote(Vote),
+    /// The value this node is locked on and the round it locked in, superseding any previous
+    /// entry.
+    Lock(Option<(ProposalCommitment, Round)>),
+    /// The latest value this node saw a prevote quorum for and the round of that quorum,
+    /// superseding any previous entry.
+    Valid(ProposalCommitment, Round),
+}
+
+/// A single record of the WAL file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct WalRecord {
+    height: BlockNumber,
+    entry: WalEntry,
+}
+
+/// An append-only, on-disk log of the entries written during the current height.
+pub(crate) struct WriteAheadLog {
+    file: RecordFile,
+    height: BlockNumber,
+    locked_value_round: Option<(ProposalCommitment, Round)>,
+    valid_value_round: Option<(ProposalCommitment, Round)>,
+}
+
+impl WriteAheadLog {
+    /// Opens the WAL at the given path for `height`, creating it if needed, and returns the entries
+    /// previously written for this height. Entries of other heights are discarded.
+    /// A trailing record that cannot be parsed (e.g., a partial write before a crash) ends the
+    /// replay.
+    pub(crate) fn open(path: &Path, height: BlockNumber) -> io::Result<(Self, Vec<WalEntry>)> {
+        let (mut file, records) = RecordFile::open::<WalRecord>(path.to_path_buf())?;
+        let n_records = records.len();
+        let entries: Vec<WalEntry> = records
+            .into_iter()
+            .filter(|record| record.height == height)
+            .map(|record| record.entry)
+            .collect();
+        if entries.len() < n_records {
+            file.rewrite(entries.iter().map(|entry| WalRecord { height, entry: entry.clone() }))?;
+        }
+
+        let locked_value_round = entries
+            .iter()
+            .rev()
+            .find_map(|entry| match entry {
+                WalEntry::Lock(locked_value_round) => Some(*locked_value_round),
+                WalEntry::Vote(_) | WalEntry::Valid(..) => None,
+            })
+            .flatten();
+        let valid_value_round = entries.iter().rev().find_map(|entry| match entry {
+            WalEntry::Valid(valid_value, valid_round) => Some((*valid_value, *valid_round)),
+            WalEntry::Vote(_) | WalEntry::Lock(_) => None,
+        });
+
+        Ok((WriteAheadLog { file, height, locked_value_round, valid_value_round }, entries))
+    }
+
+    /// Durably writes a vote cast by this node. Must be called before the vote is broadcast.
+    pub(crate) fn append_vote(&mut self, vote: &Vote) -> io::Result<()> {
+        self.append(WalEntry::Vote(vote.clone()))
+    }
+
+    /// Durably writes the locked value, if it changed since it was last written.
+    pub(crate) fn update_lock(
+        &mut self,
+        locked_value_round: Option<(ProposalCommitment, Round)>,
+    ) -> io::Result<()> {
+        if locked_value_round == self.locked_value_round {
+            return Ok(());
+        }
+        self.append(WalEntry::Lock(locked_value_round))?;
+        self.locked_value_round = locked_value_round;
+        Ok(())
+    }
+
+    /// Durably writes the valid value, if it changed since it was last written.
+    pub(crate) fn update_valid(
+        &mut self,
+        valid_value_round: Option<(ProposalCommitment, Round)>,
+    ) -> io::Result<()> {
+        let Some((valid_value, valid_round)) = valid_value_round else {
+            return Ok(());
+        };
+        if valid_value_round == self.valid_value_round {
+            return Ok(());
+        }
+        self.append(WalEntry::Valid(valid_value, valid_round))?;
+        self.valid_value_round = valid_value_round;
+        Ok(())
+    }
+
+    fn append(&mut self, entry: WalEntry) -> io::Result<()> {
+        self.file.append(&WalRecord { height: self.height, entry })
+    }
+}
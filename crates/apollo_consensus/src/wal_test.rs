@@ -0,0 +1,87 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use apollo_infra_utils::record_file::RecordFile;
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+
+use crate::test_utils::{precommit, prevote};
+use crate::wal::{WalEntry, WalRecord, WriteAheadLog};
+
+const HEIGHT: BlockNumber = BlockNumber(1);
+
+#[test]
+fn entries_are_restored_in_order() {
+    let wal_dir = tempfile::tempdir().unwrap();
+    let wal_path = wal_dir.path().join("consensus.wal");
+    let prevote = prevote(Some(Felt::ONE), HEIGHT.0, 0, DEFAULT_VALIDATOR_ID.into());
+    let precommit = precommit(Some(Felt::ONE), HEIGHT.0, 0, DEFAULT_VALIDATOR_ID.into());
+    let lock = Some((BlockHash(Felt::ONE), 0));
+
+    let (mut wal, entries) = WriteAheadLog::open(&wal_path, HEIGHT).unwrap();
+    assert!(entries.is_empty());
+    wal.append_vote(&prevote).unwrap();
+    // No valid value is written until there is one.
+    wal.update_valid(None).unwrap();
+    wal.update_valid(lock).unwrap();
+    wal.update_lock(lock).unwrap();
+    wal.append_vote(&precommit).unwrap();
+    // Unchanged locked and valid values are not written again.
+    wal.update_lock(lock).unwrap();
+    wal.update_valid(lock).unwrap();
+    drop(wal);
+
+    let (_wal, entries) = WriteAheadLog::open(&wal_path, HEIGHT).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            WalEntry::Vote(prevote),
+            WalEntry::Valid(BlockHash(Felt::ONE), 0),
+            WalEntry::Lock(lock),
+            WalEntry::Vote(precommit),
+        ]
+    );
+}
+
+#[test]
+fn entries_of_other_heights_are_discarded() {
+    let wal_dir = tempfile::tempdir().unwrap();
+    let wal_path = wal_dir.path().join("consensus.wal");
+    let (mut wal, _) = WriteAheadLog::open(&wal_path, HEIGHT).unwrap();
+    wal.append_vote(&prevote(Some(Felt::ONE), HEIGHT.0, 0, DEFAULT_VALIDATOR_ID.into())).unwrap();
+    drop(wal);
+
+    let next_height = HEIGHT.unchecked_next();
+    let next_prevote = prevote(None, next_height.0, 0, DEFAULT_VALIDATOR_ID.into());
+    let (mut wal, entries) = WriteAheadLog::open(&wal_path, next_height).unwrap();
+    assert!(entries.is_empty());
+    wal.append_vote(&next_prevote).unwrap();
+    drop(wal);
+
+    let (_wal, entries) = WriteAheadLog::open(&wal_path, next_height).unwrap();
+    assert_eq!(entries, vec![WalEntry::Vote(next_prevote)]);
+    let (_file, records) = RecordFile::open::<WalRecord>(wal_path).unwrap();
+    assert_eq!(records.len(), 1);
+}
+
+#[test]
+fn torn_record_ends_replay() {
+    let wal_dir = tempfile::tempdir().unwrap();
+    let wal_path = wal_dir.path().join("consensus.wal");
+    let prevote = prevote(Some(Felt::ONE), HEIGHT.0, 0, DEFAULT_VALIDATOR_ID.into());
+    let (mut wal, _) = WriteAheadLog::open(&wal_path, HEIGHT).unwrap();
+    wal.append_vote(&prevote).unwrap();
+    drop(wal);
+    // Simulate a crash in the middle of writing a record.
+    OpenOptions::new().append(true).open(&wal_path).unwrap().write_all(b"{\"height\":").unwrap();
+
+    let precommit = precommit(None, HEIGHT.0, 0, DEFAULT_VALIDATOR_ID.into());
+    let (mut wal, entries) = WriteAheadLog::open(&wal_path, HEIGHT).unwrap();
+    assert_eq!(entries, vec![WalEntry::Vote(prevote.clone())]);
+    wal.append_vote(&precommit).unwrap();
+    drop(wal);
+
+    let (_wal, entries) = WriteAheadLog::open(&wal_path, HEIGHT).unwrap();
+    assert_eq!(entries, vec![WalEntry::Vote(prevote), WalEntry::Vote(precommit)]);
+}
@@ -8,6 +8,7 @@ use apollo_consensus_orchestrator::config::ContextConfig;
 use apollo_l1_gas_price::eth_to_strk_oracle::EthToStrkOracleConfig;
 use apollo_network::NetworkConfig;
 use apollo_reverts::RevertConfig;
+use apollo_staking::staking_manager::StakingManagerConfig;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
 use validator::Validate;
@@ -16,13 +17,17 @@ use validator::Validate;
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct ConsensusManagerConfig {
     pub consensus_manager_config: ConsensusConfig,
+    #[validate]
     pub context_config: ContextConfig,
+    #[validate]
     pub eth_to_strk_oracle_config: EthToStrkOracleConfig,
     pub stream_handler_config: StreamHandlerConfig,
     #[validate]
     pub network_config: NetworkConfig,
     pub cende_config: CendeConfig,
     pub revert_config: RevertConfig,
+    #[validate]
+    pub staking_manager_config: StakingManagerConfig,
     pub votes_topic: String,
     pub proposals_topic: String,
     pub broadcast_buffer_size: usize,
@@ -82,6 +87,10 @@ impl SerializeConfig for ConsensusManagerConfig {
         config.extend(prepend_sub_config_name(self.cende_config.dump(), "cende_config"));
         config.extend(prepend_sub_config_name(self.network_config.dump(), "network_config"));
         config.extend(prepend_sub_config_name(self.revert_config.dump(), "revert_config"));
+        config.extend(prepend_sub_config_name(
+            self.staking_manager_config.dump(),
+            "staking_manager_config",
+        ));
         config
     }
 }
@@ -96,6 +105,7 @@ impl Default for ConsensusManagerConfig {
             cende_config: CendeConfig::default(),
             network_config: NetworkConfig::default(),
             revert_config: RevertConfig::default(),
+            staking_manager_config: StakingManagerConfig::default(),
             votes_topic: "consensus_votes".to_string(),
             proposals_topic: "consensus_proposals".to_string(),
             broadcast_buffer_size: 10000,
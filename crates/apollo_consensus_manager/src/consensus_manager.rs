@@ -9,6 +9,7 @@ use apollo_batcher_types::batcher_types::RevertBlockInput;
 use apollo_batcher_types::communication::SharedBatcherClient;
 use apollo_class_manager_types::transaction_converter::TransactionConverter;
 use apollo_class_manager_types::SharedClassManagerClient;
+use apollo_consensus::equivocation::EquivocationEvidenceStore;
 use apollo_consensus::stream_handler::StreamHandler;
 use apollo_consensus::types::ConsensusError;
 use apollo_consensus::votes_threshold::QuorumType;
@@ -21,12 +22,18 @@ use apollo_infra::component_definitions::ComponentStarter;
 use apollo_infra_utils::type_name::short_type_name;
 use apollo_l1_gas_price::eth_to_strk_oracle::EthToStrkOracleClient;
 use apollo_l1_gas_price_types::L1GasPriceProviderClient;
+use apollo_network::authentication::staker_negotiator::StakerNegotiator;
 use apollo_network::gossipsub_impl::Topic;
 use apollo_network::network_manager::metrics::{BroadcastNetworkMetrics, NetworkMetrics};
 use apollo_network::network_manager::{BroadcastTopicChannels, NetworkManager};
 use apollo_protobuf::consensus::{HeightAndRound, ProposalPart, StreamMessage, Vote};
 use apollo_reverts::revert_blocks_and_eternal_pending;
 use apollo_signature_manager_types::SharedSignatureManagerClient;
+use apollo_staking::committee_provider::{CommitteeProvider, SharedCommittee};
+use apollo_staking::contract_types::epoch_of_height;
+use apollo_staking::staking_manager::StakingManager;
+use apollo_staking::sync_state_reader::latest_block_execution_context;
+use apollo_staking::utils::BlockPseudorandomGenerator;
 use apollo_state_sync_types::communication::SharedStateSyncClient;
 use apollo_time::time::DefaultClock;
 use async_trait::async_trait;
@@ -52,6 +59,7 @@ pub struct ConsensusManager {
     pub class_manager_client: SharedClassManagerClient,
     pub signature_manager_client: SharedSignatureManagerClient,
     l1_gas_price_provider: Arc<dyn L1GasPriceProviderClient>,
+    equivocation_evidence_store: EquivocationEvidenceStore,
 }
 
 impl ConsensusManager {
@@ -70,9 +78,16 @@ impl ConsensusManager {
             class_manager_client,
             signature_manager_client,
             l1_gas_price_provider,
+            equivocation_evidence_store: EquivocationEvidenceStore::default(),
         }
     }
 
+    /// Returns a handle for querying the evidence of equivocating validators collected by
+    /// consensus.
+    pub fn equivocation_evidence_store(&self) -> EquivocationEvidenceStore {
+        self.equivocation_evidence_store.clone()
+    }
+
     pub async fn run(&self) -> Result<(), ConsensusError> {
         if self.config.revert_config.should_revert {
             self.revert_batcher_blocks(self.config.revert_config.revert_up_to_and_including).await;
@@ -99,18 +114,49 @@ impl ConsensusManager {
             broadcast_metrics_by_topic: Some(broadcast_metrics_by_topic),
             sqmr_metrics: None,
         });
-        let mut network_manager =
-            NetworkManager::new(self.config.network_config.clone(), None, network_manager_metrics);
+        let observer_height = self
+            .batcher_client
+            .get_height()
+            .await
+            .expect("Failed to get observer_height from batcher")
+            .height;
+        let active_height = if self.config.immediate_active_height == observer_height {
+            // Setting `start_height` is only used to enable consensus starting immediately without
+            // observing the first height. This means consensus may return to a height
+            // it has already voted on, risking equivocation. This is only safe to do if we
+            // restart all nodes at this height.
+            observer_height
+        } else {
+            BlockNumber(observer_height.0 + 1)
+        };
+
+        let committee = self.fetch_committee(active_height).await;
+        // The peers are authenticated as the stakers of the committee, so consensus can't run
+        // without one.
+        assert!(!committee.is_empty(), "The committee of height {active_height} is empty.");
+        let public_key = committee
+            .public_key(&self.config.consensus_manager_config.validator_id)
+            .expect("The validator must be a member of the committee.");
+        let mut network_manager = NetworkManager::new_with_staker_authentication(
+            self.config.network_config.clone(),
+            None,
+            network_manager_metrics,
+            StakerNegotiator::new(
+                Arc::clone(&self.signature_manager_client),
+                public_key,
+                Arc::new(committee.clone()),
+            ),
+        );
 
         let proposals_broadcast_channels = network_manager
-            .register_broadcast_topic::<StreamMessage<ProposalPart, HeightAndRound>>(
+            .register_authenticated_broadcast_topic::<StreamMessage<ProposalPart, HeightAndRound>>(
                 Topic::new(self.config.proposals_topic.clone()),
                 self.config.broadcast_buffer_size,
             )
             .expect("Failed to register broadcast topic");
 
         let votes_broadcast_channels = network_manager
-            .register_broadcast_topic::<Vote>(
+            .register_authenticated_broadcast_topic::<Vote>(
                 Topic::new(self.config.votes_topic.clone()),
                 self.config.broadcast_buffer_size,
             )
@@ -133,22 +179,6 @@ impl ConsensusManager {
             outbound_network_sender,
         );
 
-        let observer_height = self
-            .batcher_client
-            .get_height()
-            .await
-            .expect("Failed to get observer_height from batcher")
-            .height;
-        let active_height = if self.config.immediate_active_height == observer_height {
-            // Setting `start_height` is only used to enable consensus starting immediately without
-            // observing the first height. This means consensus may return to a height
-            // it has already voted on, risking equivocation. This is only safe to do if we
-            // restart all nodes at this height.
-            observer_height
-        } else {
-            BlockNumber(observer_height.0 + 1)
-        };
-
         let context = SequencerConsensusContext::new(
             self.config.context_config.clone(),
             SequencerConsensusContextDeps {
@@ -169,6 +199,8 @@ impl ConsensusManager {
                 clock: Arc::new(DefaultClock),
                 outbound_proposal_sender: outbound_internal_sender,
                 vote_broadcast_client: votes_broadcast_channels.broadcast_topic_client.clone(),
+                signature_manager_client: Arc::clone(&self.signature_manager_client),
+                committee,
             },
         );
 
@@ -188,6 +220,8 @@ impl ConsensusManager {
             timeouts: self.config.consensus_manager_config.timeouts.clone(),
             sync_retry_interval: self.config.consensus_manager_config.sync_retry_interval,
             quorum_type,
+            wal_path: self.config.consensus_manager_config.wal_path.clone(),
+            equivocation_evidence_store: self.equivocation_evidence_store.clone(),
         };
         let consensus_fut = apollo_consensus::run_consensus(
             run_consensus_args,
@@ -212,6 +246,35 @@ impl ConsensusManager {
         }
     }
 
+    // Fetches the committee of the epoch of the given height from the staking contract, in the
+    // state of the latest synced block. Panics if the committee can't be fetched.
+    // TODO(Matan): Keep the committee up to date with the epochs of the following heights.
+    async fn fetch_committee(&self, height: BlockNumber) -> SharedCommittee {
+        let mut staking_manager = StakingManager::new(
+            Box::new(BlockPseudorandomGenerator),
+            self.config.staking_manager_config.clone(),
+        );
+        let committee = staking_manager.shared_committee();
+        let execution_context = latest_block_execution_context(
+            Arc::clone(&self.state_sync_client),
+            Arc::clone(&self.class_manager_client),
+            self.config.context_config.chain_id.clone(),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("Failed to fetch the committee of height {height}: {e}"));
+
+        // Executing the staking contract blocks on the state sync client.
+        let epoch = epoch_of_height(height);
+        tokio::task::spawn_blocking(move || {
+            staking_manager.get_committee(epoch, execution_context)
+        })
+        .await
+        .expect("The committee fetching task panicked")
+        .unwrap_or_else(|e| panic!("Failed to fetch the committee of height {height}: {e}"));
+
+        committee
+    }
+
     // Performs reverts to the batcher.
     async fn revert_batcher_blocks(&self, revert_up_to_and_including: BlockNumber) {
         // If we revert all blocks up to height X (including), the new height marker will be X.
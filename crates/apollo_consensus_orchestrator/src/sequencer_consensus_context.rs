@@ -19,6 +19,7 @@ use apollo_batcher_types::batcher_types::{
 use apollo_batcher_types::communication::{BatcherClient, BatcherClientError};
 use apollo_class_manager_types::transaction_converter::TransactionConverterTrait;
 use apollo_consensus::types::{
+    signed_vote_content,
     ConsensusContext,
     ConsensusError,
     ProposalCommitment,
@@ -41,6 +42,8 @@ use apollo_protobuf::consensus::{
     Vote,
     DEFAULT_VALIDATOR_ID,
 };
+use apollo_signature_manager_types::SignatureManagerClient;
+use apollo_staking::committee_provider::SharedCommittee;
 use apollo_state_sync_types::communication::{StateSyncClient, StateSyncClientError};
 use apollo_state_sync_types::errors::StateSyncError;
 use apollo_state_sync_types::state_sync_types::SyncBlock;
@@ -53,12 +56,15 @@ use starknet_api::block::{
     BlockHeaderWithoutHash,
     BlockNumber,
     BlockTimestamp,
+    CommitCertificate,
     GasPrice,
     GasPricePerToken,
+    SignedPrecommit,
     WEI_PER_ETH,
 };
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
 use starknet_api::core::SequencerContractAddress;
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::transaction::TransactionHash;
 use tokio::task::JoinHandle;
@@ -118,6 +124,14 @@ impl BuiltProposals {
             .unwrap_or_else(|| panic!("No proposal found for height {height} and id {commitment}"))
     }
 
+    fn find_proposal(
+        &self,
+        height: &BlockNumber,
+        commitment: &ProposalCommitment,
+    ) -> Option<&(ConsensusBlockInfo, Vec<Vec<InternalConsensusTransaction>>, ProposalId)> {
+        self.data.get(height)?.get(commitment)
+    }
+
     fn remove_proposals_below_or_at_height(&mut self, height: &BlockNumber) {
         self.data.retain(|&h, _| h > *height);
     }
@@ -177,6 +191,10 @@ pub struct SequencerConsensusContextDeps {
     pub outbound_proposal_sender: mpsc::Sender<(HeightAndRound, mpsc::Receiver<ProposalPart>)>,
     // Used to broadcast votes to other consensus nodes.
     pub vote_broadcast_client: BroadcastTopicClient<Vote>,
+    // Used to sign precommits before they are broadcast.
+    pub signature_manager_client: Arc<dyn SignatureManagerClient>,
+    // Provides the validators' public keys, used to verify their votes.
+    pub committee: SharedCommittee,
 }
 
 impl SequencerConsensusContext {
@@ -356,12 +374,18 @@ impl ConsensusContext for SequencerConsensusContext {
     async fn repropose(&mut self, id: ProposalCommitment, init: ProposalInit) {
         info!(?id, ?init, "Reproposing.");
         let height = init.height;
-        let (block_info, txs, _) = self
+        let Some((block_info, txs, _)) = self
             .valid_proposals
             .lock()
             .expect("Lock on active proposals was poisoned due to a previous panic")
-            .get_proposal(&height, &id)
-            .clone();
+            .find_proposal(&height, &id)
+            .cloned()
+        else {
+            // Proposals aren't persisted, so a valid value restored by consensus after a restart
+            // may be unknown.
+            warn!(?id, ?init, "Cannot repropose an unknown proposal.");
+            return;
+        };
 
         let transaction_converter = self.deps.transaction_converter.clone();
         let mut stream_sender = self.start_stream(HeightAndRound(height.0, init.round)).await;
@@ -427,6 +451,32 @@ impl ConsensusContext for SequencerConsensusContext {
             .expect("There should be at least one validator")
     }
 
+    async fn validator_public_key(
+        &self,
+        _height: BlockNumber,
+        validator: ValidatorId,
+    ) -> Option<PublicKey> {
+        if !self.validators.contains(&validator) {
+            return None;
+        }
+        self.deps.committee.public_key(&validator)
+    }
+
+    async fn sign_vote(&mut self, mut vote: Vote) -> Result<Vote, ConsensusError> {
+        let signature = self
+            .deps
+            .signature_manager_client
+            .sign_vote(signed_vote_content(&vote))
+            .await
+            .map_err(|e| ConsensusError::Other(format!("Failed to sign vote: {e}")))?;
+        vote.signature = Some(
+            signature
+                .try_into()
+                .map_err(|e| ConsensusError::Other(format!("Invalid vote signature: {e}")))?,
+        );
+        Ok(vote)
+    }
+
     async fn broadcast(&mut self, message: Vote) -> Result<(), ConsensusError> {
         trace!("Broadcasting message: {message:?}");
         self.deps.vote_broadcast_client.broadcast_message(message).await?;
@@ -441,6 +491,24 @@ impl ConsensusContext for SequencerConsensusContext {
         let height = precommits[0].height;
         info!("Finished consensus for height: {height}. Agreed on block: {:#064x}", block.0);
 
+        // Precommits of other validators are verified on receipt, and this node signs its own.
+        let signed_precommits = precommits
+            .iter()
+            .map(|vote| {
+                let signature = vote.signature.ok_or_else(|| {
+                    ConsensusError::InternalInconsistency(format!(
+                        "Decision precommit is not signed: {vote:?}"
+                    ))
+                })?;
+                Ok(SignedPrecommit { voter: vote.voter, signature })
+            })
+            .collect::<Result<_, ConsensusError>>()?;
+        let commit_certificate = CommitCertificate {
+            round: precommits[0].round,
+            block_hash: block,
+            precommits: signed_precommits,
+        };
+
         self.interrupt_active_proposal().await;
         let proposal_id;
         let transactions;
@@ -530,6 +598,7 @@ impl ConsensusContext for SequencerConsensusContext {
             account_transaction_hashes,
             l1_transaction_hashes,
             block_header_without_hash,
+            commit_certificate: Some(commit_certificate),
         };
         self.sync_add_new_block(sync_block).await;
 
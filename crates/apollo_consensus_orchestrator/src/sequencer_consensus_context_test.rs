@@ -5,7 +5,7 @@ use std::vec;
 use apollo_batcher_types::batcher_types::{CentralObjects, DecisionReachedResponse};
 use apollo_batcher_types::communication::BatcherClientError;
 use apollo_batcher_types::errors::BatcherError;
-use apollo_consensus::types::{ConsensusContext, Round};
+use apollo_consensus::types::{ConsensusContext, ConsensusError, Round};
 use apollo_l1_gas_price_types::errors::{
     EthToStrkOracleClientError,
     L1GasPriceClientError,
@@ -17,8 +17,19 @@ use apollo_l1_gas_price_types::{
     PriceInfo,
     DEFAULT_ETH_TO_FRI_RATE,
 };
-use apollo_protobuf::consensus::{ProposalFin, ProposalInit, ProposalPart, TransactionBatch, Vote};
+use apollo_protobuf::consensus::{
+    ProposalFin,
+    ProposalInit,
+    ProposalPart,
+    TransactionBatch,
+    Vote,
+    VoteType,
+    DEFAULT_VALIDATOR_ID,
+};
+use apollo_signature_manager_types::{ConsensusVote, ConsensusVoteType};
+use apollo_staking::committee_provider::{SharedCommittee, Staker};
 use apollo_time::time::MockClock;
+use assert_matches::assert_matches;
 use chrono::{TimeZone, Utc};
 use futures::channel::mpsc;
 use futures::channel::oneshot::Canceled;
@@ -29,12 +40,18 @@ use rstest::rstest;
 use starknet_api::block::{
     BlockHash,
     BlockNumber,
+    CommitCertificate,
     GasPrice,
+    SignedPrecommit,
     TEMP_ETH_BLOB_GAS_FEE_IN_WEI,
     TEMP_ETH_GAS_FEE_IN_WEI,
 };
+use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::{PublicKey, RawSignature, Signature};
 use starknet_api::execution_resources::GasAmount;
+use starknet_api::staking::StakingWeight;
 use starknet_api::state::ThinStateDiff;
+use starknet_types_core::felt::Felt;
 
 use crate::cende::MockCendeContext;
 use crate::config::ContextConfig;
@@ -45,6 +62,7 @@ use crate::test_utils::{
     create_test_and_network_deps,
     ETH_TO_FRI_RATE,
     INTERNAL_TX_BATCH,
+    NUM_VALIDATORS,
     STATE_DIFF_COMMITMENT,
     TIMEOUT,
     TX_BATCH,
@@ -98,6 +116,68 @@ async fn validate_proposal_success() {
     assert_eq!(fin_receiver.await.unwrap().0, STATE_DIFF_COMMITMENT.0.0);
 }
 
+#[rstest]
+#[case::prevote(VoteType::Prevote, ConsensusVoteType::Prevote, Some(BlockHash(Felt::ONE)))]
+#[case::precommit(VoteType::Precommit, ConsensusVoteType::Precommit, Some(BlockHash(Felt::ONE)))]
+#[case::nil_precommit(VoteType::Precommit, ConsensusVoteType::Precommit, None)]
+#[tokio::test]
+async fn sign_vote_signs_every_vote(
+    #[case] vote_type: VoteType,
+    #[case] consensus_vote_type: ConsensusVoteType,
+    #[case] block_hash: Option<BlockHash>,
+) {
+    let (mut deps, _network) = create_test_and_network_deps();
+    deps.signature_manager_client
+        .expect_sign_vote()
+        .times(1)
+        .withf(move |vote| {
+            *vote
+                == ConsensusVote {
+                    vote_type: consensus_vote_type,
+                    height: BlockNumber(3),
+                    round: 1,
+                    block_hash,
+                    voter: ContractAddress::from(DEFAULT_VALIDATOR_ID),
+                }
+        })
+        .return_once(|_| Ok(RawSignature(vec![Felt::ONE, Felt::TWO])));
+    let mut context = deps.build_context();
+
+    let vote = Vote {
+        vote_type,
+        height: 3,
+        round: 1,
+        block_hash,
+        voter: ContractAddress::from(DEFAULT_VALIDATOR_ID),
+        signature: None,
+    };
+    assert_eq!(
+        context.sign_vote(vote.clone()).await,
+        Ok(Vote { signature: Some(Signature { r: Felt::ONE, s: Felt::TWO }), ..vote })
+    );
+}
+
+#[tokio::test]
+async fn validator_public_key_is_taken_from_committee() {
+    let (mut deps, _network) = create_test_and_network_deps();
+    let validator = ContractAddress::from(DEFAULT_VALIDATOR_ID);
+    let validator_without_key = ContractAddress::from(DEFAULT_VALIDATOR_ID + 1);
+    let non_validator = ContractAddress::from(DEFAULT_VALIDATOR_ID + NUM_VALIDATORS);
+    deps.committee = SharedCommittee::new(vec![
+        Staker { address: validator, weight: StakingWeight(1), public_key: Felt::ONE },
+        Staker { address: non_validator, weight: StakingWeight(1), public_key: Felt::TWO },
+    ]);
+    let context = deps.build_context();
+
+    assert_eq!(
+        context.validator_public_key(BlockNumber(0), validator).await,
+        Some(PublicKey(Felt::ONE))
+    );
+    // Fails closed for validators without a known key, and for keys of non validators.
+    assert_eq!(context.validator_public_key(BlockNumber(0), validator_without_key).await, None);
+    assert_eq!(context.validator_public_key(BlockNumber(0), non_validator).await, None);
+}
+
 #[tokio::test]
 async fn dont_send_block_info() {
     let (mut deps, _network) = create_test_and_network_deps();
@@ -545,10 +625,25 @@ async fn decision_reached_sends_correct_values() {
         .times(1)
         .return_once(move |_| Ok(DecisionReachedResponse::default()));
 
+    let vote = Vote {
+        // Currently these are the only fields used by decision_reached.
+        height: 0,
+        round: 1,
+        voter: 2_u128.into(),
+        signature: Some(Signature { r: Felt::ONE, s: Felt::TWO }),
+        ..Default::default()
+    };
+    let expected_commit_certificate = CommitCertificate {
+        round: vote.round,
+        block_hash: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        precommits: vec![SignedPrecommit { voter: vote.voter, signature: vote.signature.unwrap() }],
+    };
+
     // This is the actual part of the test that checks the values are correct.
     // TODO(guy.f): Add expectations and validations for all the other values being written.
     deps.state_sync_client.expect_add_new_block().times(1).return_once(|block_info| {
         assert_eq!(block_info.block_header_without_hash.timestamp.0, BLOCK_TIME_STAMP_SECONDS);
+        assert_eq!(block_info.commit_certificate, Some(expected_commit_certificate));
         Ok(())
     });
 
@@ -563,12 +658,6 @@ async fn decision_reached_sends_correct_values() {
     let _fin = context.build_proposal(ProposalInit::default(), TIMEOUT).await.await;
     // At this point we should have a valid proposal in the context which contains the timestamp.
 
-    let vote = Vote {
-        // Currently this is the only field used by decision_reached.
-        height: 0,
-        ..Default::default()
-    };
-
     context.decision_reached(BlockHash(STATE_DIFF_COMMITMENT.0.0), vec![vote]).await.unwrap();
 
     let metrics = recorder.handle().render();
@@ -576,6 +665,24 @@ async fn decision_reached_sends_correct_values() {
         .assert_eq(&metrics, VersionedConstants::latest_constants().min_gas_price.0);
 }
 
+#[tokio::test]
+async fn decision_reached_fails_on_unsigned_precommit() {
+    let (mut deps, _network) = create_test_and_network_deps();
+    deps.batcher.expect_decision_reached().never();
+    deps.state_sync_client.expect_add_new_block().never();
+    let mut context = deps.build_context();
+
+    let unsigned_precommit = Vote {
+        vote_type: VoteType::Precommit,
+        block_hash: Some(BlockHash(Felt::ONE)),
+        ..Default::default()
+    };
+    assert_matches!(
+        context.decision_reached(BlockHash(Felt::ONE), vec![unsigned_precommit]).await,
+        Err(ConsensusError::InternalInconsistency(_))
+    );
+}
+
 #[rstest]
 #[case::l1_price_oracle_failure(true)]
 #[case::eth_to_strk_rate_oracle_failure(false)]
@@ -724,7 +831,11 @@ async fn oracle_fails_on_second_block(#[case] l1_oracle_failure: bool) {
     context
         .decision_reached(
             BlockHash(block_hash),
-            vec![Vote { block_hash: Some(BlockHash(block_hash)), ..Default::default() }],
+            vec![Vote {
+                block_hash: Some(BlockHash(block_hash)),
+                signature: Some(Signature::default()),
+                ..Default::default()
+            }],
         )
         .await
         .unwrap();
@@ -803,7 +914,10 @@ async fn constant_l2_gas_price_behavior(
     // Run proposal and decision logic.
     let _fin_receiver = context.build_proposal(ProposalInit::default(), TIMEOUT).await.await;
     context
-        .decision_reached(BlockHash(STATE_DIFF_COMMITMENT.0.0), vec![Vote::default()])
+        .decision_reached(
+            BlockHash(STATE_DIFF_COMMITMENT.0.0),
+            vec![Vote { signature: Some(Signature::default()), ..Default::default() }],
+        )
         .await
         .unwrap();
 
@@ -32,6 +32,8 @@ use apollo_network::network_manager::test_utils::{
 };
 use apollo_network::network_manager::{BroadcastTopicChannels, BroadcastTopicClient};
 use apollo_protobuf::consensus::{ConsensusBlockInfo, HeightAndRound, ProposalPart, Vote};
+use apollo_signature_manager_types::MockSignatureManagerClient;
+use apollo_staking::committee_provider::SharedCommittee;
 use apollo_state_sync_types::communication::MockStateSyncClient;
 use apollo_time::time::{Clock, DefaultClock};
 use futures::channel::mpsc;
@@ -98,6 +100,8 @@ pub(crate) struct TestDeps {
     pub clock: Arc<dyn Clock>,
     pub outbound_proposal_sender: mpsc::Sender<(HeightAndRound, mpsc::Receiver<ProposalPart>)>,
     pub vote_broadcast_client: BroadcastTopicClient<Vote>,
+    pub signature_manager_client: MockSignatureManagerClient,
+    pub committee: SharedCommittee,
 }
 
 impl From<TestDeps> for SequencerConsensusContextDeps {
@@ -112,6 +116,8 @@ impl From<TestDeps> for SequencerConsensusContextDeps {
             clock: deps.clock,
             outbound_proposal_sender: deps.outbound_proposal_sender,
             vote_broadcast_client: deps.vote_broadcast_client,
+            signature_manager_client: Arc::new(deps.signature_manager_client),
+            committee: deps.committee,
         }
     }
 }
@@ -264,6 +270,7 @@ pub(crate) fn create_test_and_network_deps() -> (TestDeps, NetworkDependencies)
     let eth_to_strk_oracle_client = MockEthToStrkOracleClientTrait::new();
     let l1_gas_price_provider = MockL1GasPriceProviderClient::new();
     let clock = Arc::new(DefaultClock);
+    let signature_manager_client = MockSignatureManagerClient::new();
 
     let test_deps = TestDeps {
         transaction_converter,
@@ -275,10 +282,12 @@ pub(crate) fn create_test_and_network_deps() -> (TestDeps, NetworkDependencies)
         clock,
         outbound_proposal_sender,
         vote_broadcast_client: votes_topic_client,
+        signature_manager_client,
+        committee: SharedCommittee::default(),
     };
 
     let network_deps =
-        NetworkDependencies { _vote_network: mock_vote_network, outbound_proposal_receiver };
+        NetworkDependencies { vote_network: mock_vote_network, outbound_proposal_receiver };
 
     (test_deps, network_deps)
 }
@@ -307,6 +316,6 @@ pub(crate) fn block_info(height: BlockNumber) -> ConsensusBlockInfo {
 }
 // Structs which aren't utilized but should not be dropped.
 pub(crate) struct NetworkDependencies {
-    _vote_network: BroadcastNetworkMock<Vote>,
+    pub vote_network: BroadcastNetworkMock<Vote>,
     pub outbound_proposal_receiver: mpsc::Receiver<(HeightAndRound, mpsc::Receiver<ProposalPart>)>,
 }
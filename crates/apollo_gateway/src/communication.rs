@@ -25,6 +25,11 @@ impl ComponentRequestHandler<GatewayRequest, GatewayResponse> for Gateway {
                         }),
                 )
             }
+            GatewayRequest::SimulateTransaction(rpc_tx) => GatewayResponse::SimulateTransaction(
+                self.simulate_tx(rpc_tx).await.map_err(|source| {
+                    GatewayError::DeprecatedGatewayError { source, p2p_message_metadata: None }
+                }),
+            ),
         }
     }
 }
@@ -193,7 +193,8 @@ pub fn mempool_client_result_to_gw_spec_result(
             match mempool_error {
                 MempoolError::DuplicateNonce { .. }
                 | MempoolError::NonceTooLarge { .. }
-                | MempoolError::NonceTooOld { .. } => {
+                | MempoolError::NonceTooOld { .. }
+                | MempoolError::NonceTooFarAhead { .. } => {
                     Err(GatewaySpecError::InvalidTransactionNonce)
                 }
                 MempoolError::DuplicateTransaction { .. } => Err(GatewaySpecError::DuplicateTx),
@@ -201,6 +202,13 @@ pub fn mempool_client_result_to_gw_spec_result(
                 MempoolError::MempoolFull => {
                     Err(GatewaySpecError::UnexpectedError { data: "Mempool full".to_owned() })
                 }
+                // The transaction is rejected due to the state of its sender account, which the
+                // client can act on.
+                MempoolError::SuspendedPoolFull { .. }
+                | MempoolError::AccountTxLimitExceeded { .. }
+                | MempoolError::AccountSizeLimitExceeded { .. } => {
+                    Err(GatewaySpecError::ValidationFailure { data: mempool_error.to_string() })
+                }
                 MempoolError::P2pPropagatorClientError { .. } => {
                     // Not an error from the gateway's perspective.
                     warn!("P2p propagator client error: {}", mempool_error);
@@ -236,15 +244,22 @@ pub fn mempool_client_err_to_deprecated_gw_err(err: MempoolClientError) -> Stark
                         "StarknetErrorCode.NONCE_TOO_LARGE".to_string(),
                     )
                 }
-                MempoolError::NonceTooOld { .. } => StarknetErrorCode::KnownErrorCode(
-                    KnownStarknetErrorCode::InvalidTransactionNonce,
-                ),
+                MempoolError::NonceTooOld { .. } | MempoolError::NonceTooFarAhead { .. } => {
+                    StarknetErrorCode::KnownErrorCode(
+                        KnownStarknetErrorCode::InvalidTransactionNonce,
+                    )
+                }
                 MempoolError::DuplicateTransaction { .. } => {
                     StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::DuplicatedTransaction)
                 }
-                MempoolError::MempoolFull => StarknetErrorCode::KnownErrorCode(
-                    KnownStarknetErrorCode::TransactionLimitExceeded,
-                ),
+                MempoolError::MempoolFull
+                | MempoolError::SuspendedPoolFull { .. }
+                | MempoolError::AccountTxLimitExceeded { .. }
+                | MempoolError::AccountSizeLimitExceeded { .. } => {
+                    StarknetErrorCode::KnownErrorCode(
+                        KnownStarknetErrorCode::TransactionLimitExceeded,
+                    )
+                }
                 MempoolError::P2pPropagatorClientError { .. } => {
                     // Not an error from the gateway's perspective.
                     return StarknetError::internal(&message);
@@ -17,6 +17,8 @@ use apollo_gateway_types::gateway_types::{
     DeployAccountGatewayOutput,
     GatewayOutput,
     InvokeGatewayOutput,
+    SimulateTxOutput,
+    SimulatedTxResources,
 };
 use apollo_infra::component_definitions::ComponentStarter;
 use apollo_mempool_types::communication::{AddTransactionArgsWrapper, SharedMempoolClient};
@@ -26,20 +28,34 @@ use apollo_proc_macros::sequencer_latency_histogram;
 use apollo_state_sync_types::communication::SharedStateSyncClient;
 use axum::async_trait;
 use blockifier::context::ChainInfo;
-use starknet_api::executable_transaction::ValidateCompiledClassHashError;
+use blockifier::fee::receipt::TransactionReceipt;
+use starknet_api::core::Nonce;
+use starknet_api::executable_transaction::{
+    AccountTransaction as ExecutableTransaction,
+    ValidateCompiledClassHashError,
+};
 use starknet_api::rpc_transaction::{
     InternalRpcTransaction,
     InternalRpcTransactionWithoutTxHash,
     RpcDeclareTransaction,
     RpcTransaction,
 };
+use starknet_api::transaction::TransactionHash;
 use tracing::{debug, error, info, instrument, warn, Span};
 
 use crate::config::GatewayConfig;
 use crate::errors::{mempool_client_result_to_deprecated_gw_result, GatewayResult};
-use crate::metrics::{register_metrics, GatewayMetricHandle, GATEWAY_ADD_TX_LATENCY};
+use crate::metrics::{
+    register_metrics,
+    GatewayMetricHandle,
+    GATEWAY_ADD_TX_LATENCY,
+    GATEWAY_SIMULATE_TX_LATENCY,
+};
 use crate::state_reader::StateReaderFactory;
-use crate::stateful_transaction_validator::StatefulTransactionValidator;
+use crate::stateful_transaction_validator::{
+    BlockifierStatefulValidator,
+    StatefulTransactionValidator,
+};
 use crate::stateless_transaction_validator::StatelessTransactionValidator;
 use crate::sync_state_reader::SyncStateReaderFactory;
 
@@ -126,6 +142,60 @@ impl Gateway {
         Ok(gateway_output)
     }
 
+    /// Runs the validations of `add_tx` on the transaction, including `__validate__`, without
+    /// adding it to the mempool. Transactions that fail the validations are rejected with the error
+    /// that `add_tx` would return.
+    /// Declare transactions are rejected, since validating them requires adding their class to the
+    /// class manager.
+    #[instrument(skip_all, ret)]
+    #[sequencer_latency_histogram(GATEWAY_SIMULATE_TX_LATENCY, true)]
+    pub async fn simulate_tx(&self, tx: RpcTransaction) -> GatewayResult<SimulateTxOutput> {
+        debug!("Simulating tx: {:?}", tx);
+
+        let mut metric_counters = GatewayMetricHandle::new_for_simulation(&tx);
+        metric_counters.count_transaction_received();
+
+        match self.run_simulation(tx).await {
+            Ok((transaction_hash, receipt)) => {
+                metric_counters.transaction_simulated();
+                Ok(SimulateTxOutput::Accepted {
+                    transaction_hash,
+                    resources: receipt.map(simulated_tx_resources),
+                })
+            }
+            Err(error) if error.is_internal() => Err(error),
+            Err(error) => {
+                debug!("Simulated tx would be rejected with error: {}", error);
+                Ok(SimulateTxOutput::Rejected { error })
+            }
+        }
+    }
+
+    async fn run_simulation(
+        &self,
+        tx: RpcTransaction,
+    ) -> GatewayResult<(TransactionHash, Option<TransactionReceipt>)> {
+        if let RpcTransaction::Declare(ref declare_tx) = tx {
+            self.check_declare_permissions(declare_tx)?;
+            return Err(StarknetError {
+                code: StarknetErrorCode::UnknownErrorCode(
+                    "StarknetErrorCode.BLOCKED_TRANSACTION_TYPE".to_string(),
+                ),
+                message: "Declare transactions cannot be simulated.".to_string(),
+            });
+        }
+
+        let blocking_task = ProcessTxBlockingTask::new(self, tx, tokio::runtime::Handle::current());
+        // Run the blocking task in the current span.
+        let curr_span = Span::current();
+        tokio::task::spawn_blocking(move || curr_span.in_scope(|| blocking_task.simulate_tx()))
+            .await
+            .map_err(|join_err| {
+                error!("Failed to simulate tx: {}", join_err);
+                StarknetError::internal(&join_err.to_string())
+            })?
+    }
+
     fn check_declare_permissions(
         &self,
         declare_tx: &RpcDeclareTransaction,
@@ -182,9 +252,52 @@ impl ProcessTxBlockingTask {
         }
     }
 
+    fn process_tx(self) -> GatewayResult<AddTransactionArgs> {
+        let stateful_tx_validator = self.stateful_tx_validator.clone();
+        let mempool_client = self.mempool_client.clone();
+        let runtime = self.runtime.clone();
+        let PreparedTx { internal_tx, executable_tx, nonce, validator } = self.prepare_tx()?;
+
+        stateful_tx_validator
+            .run_transaction_validations(&executable_tx, nonce, mempool_client, validator, runtime)
+            .map_err(|e| StarknetError {
+                code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+                message: e.to_string(),
+            })?;
+
+        // TODO(Arni): Add the Sierra and the Casm to the mempool input.
+        let address = executable_tx.contract_address();
+        Ok(AddTransactionArgs { tx: internal_tx, account_state: AccountState { address, nonce } })
+    }
+
+    // Runs the validations of `process_tx`, and returns the hash of the transaction and the
+    // receipt of its validation.
+    fn simulate_tx(self) -> GatewayResult<(TransactionHash, Option<TransactionReceipt>)> {
+        let stateful_tx_validator = self.stateful_tx_validator.clone();
+        let mempool_client = self.mempool_client.clone();
+        let runtime = self.runtime.clone();
+        let PreparedTx { internal_tx, executable_tx, nonce, validator } = self.prepare_tx()?;
+
+        let receipt = stateful_tx_validator
+            .simulate_transaction_validations(
+                &executable_tx,
+                nonce,
+                mempool_client,
+                validator,
+                runtime,
+            )
+            .map_err(|e| StarknetError {
+                code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+                message: e.to_string(),
+            })?;
+
+        Ok((internal_tx.tx_hash, receipt))
+    }
+
     // TODO(Arni): Make into async function and remove all block_on calls once we manage removing
     // the spawn_blocking call.
-    fn process_tx(self) -> GatewayResult<AddTransactionArgs> {
+    // Runs the stateless validations, converts the transaction and prepares the stateful validator.
+    fn prepare_tx(self) -> GatewayResult<PreparedTx> {
         // TODO(Arni, 1/5/2024): Perform congestion control.
 
         // Perform stateless validations.
@@ -232,21 +345,25 @@ impl ProcessTxBlockingTask {
             StarknetError::internal(&e.to_string())
         })?;
 
-        self.stateful_tx_validator
-            .run_transaction_validations(
-                &executable_tx,
-                nonce,
-                self.mempool_client,
-                validator,
-                self.runtime,
-            )
-            .map_err(|e| StarknetError {
-                code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
-                message: e.to_string(),
-            })?;
+        Ok(PreparedTx { internal_tx, executable_tx, nonce, validator })
+    }
+}
 
-        // TODO(Arni): Add the Sierra and the Casm to the mempool input.
-        Ok(AddTransactionArgs { tx: internal_tx, account_state: AccountState { address, nonce } })
+// A transaction that passed the stateless validations, ready for the stateful validations.
+struct PreparedTx {
+    internal_tx: InternalRpcTransaction,
+    executable_tx: ExecutableTransaction,
+    nonce: Nonce,
+    validator: BlockifierStatefulValidator,
+}
+
+fn simulated_tx_resources(receipt: TransactionReceipt) -> SimulatedTxResources {
+    SimulatedTxResources {
+        fee: receipt.fee,
+        gas: receipt.gas,
+        da_gas: receipt.da_gas,
+        n_steps: receipt.resources.computation.total_vm_resources().n_steps,
+        sierra_gas: receipt.resources.computation.sierra_gas,
     }
 }
 
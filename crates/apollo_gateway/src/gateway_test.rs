@@ -12,6 +12,7 @@ use apollo_gateway_types::gateway_types::{
     DeployAccountGatewayOutput,
     GatewayOutput,
     InvokeGatewayOutput,
+    SimulateTxOutput,
 };
 use apollo_mempool_types::communication::{
     AddTransactionArgsWrapper,
@@ -261,26 +262,35 @@ fn p2p_message_metadata() -> Option<BroadcastedMessageMetadata> {
     P2P_MESSAGE_METADATA.clone()
 }
 
-async fn setup_mock_state(
+/// Sets up the class manager and the state for the validations of the transaction.
+fn setup_mock_state_for_validations(
     mock_dependencies: &mut MockDependencies,
     tx_args: &impl TestingTxArgs,
-    expected_mempool_result: Result<(), MempoolClientError>,
 ) {
-    let input_tx = tx_args.get_rpc_tx();
-    let expected_internal_tx = tx_args.get_internal_tx();
-
     setup_class_manager_client_mock(
         &mut mock_dependencies.mock_class_manager_client,
-        input_tx.clone(),
+        tx_args.get_rpc_tx(),
     );
 
-    let address = expected_internal_tx.contract_address();
     fund_account(
         &mock_dependencies.config.chain_info,
-        address,
+        tx_args.get_internal_tx().contract_address(),
         VALID_ACCOUNT_BALANCE,
         &mut mock_dependencies.state_reader_factory.state_reader.blockifier_state_reader,
     );
+}
+
+async fn setup_mock_state(
+    mock_dependencies: &mut MockDependencies,
+    tx_args: &impl TestingTxArgs,
+    expected_mempool_result: Result<(), MempoolClientError>,
+) {
+    let input_tx = tx_args.get_rpc_tx();
+    let expected_internal_tx = tx_args.get_internal_tx();
+
+    setup_mock_state_for_validations(mock_dependencies, tx_args);
+
+    let address = expected_internal_tx.contract_address();
 
     let mempool_add_tx_args = AddTransactionArgs {
         tx: expected_internal_tx.clone(),
@@ -336,6 +346,22 @@ async fn run_add_tx_and_extract_metrics(
     Err(MempoolClientError::MempoolError(MempoolError::NonceTooLarge(Nonce::default()))),
     StarknetErrorCode::UnknownErrorCode("StarknetErrorCode.NONCE_TOO_LARGE".to_string())
 )]
+#[case::tx_with_nonce_too_far_ahead(
+    Err(MempoolClientError::MempoolError(MempoolError::NonceTooFarAhead { address: ContractAddress::default(), tx_nonce: nonce!(1000), account_nonce: Nonce::default() })),
+    StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::InvalidTransactionNonce)
+)]
+#[case::account_tx_limit_exceeded(
+    Err(MempoolClientError::MempoolError(MempoolError::AccountTxLimitExceeded { address: ContractAddress::default() })),
+    StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::TransactionLimitExceeded)
+)]
+#[case::account_size_limit_exceeded(
+    Err(MempoolClientError::MempoolError(MempoolError::AccountSizeLimitExceeded { address: ContractAddress::default() })),
+    StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::TransactionLimitExceeded)
+)]
+#[case::suspended_pool_full(
+    Err(MempoolClientError::MempoolError(MempoolError::SuspendedPoolFull { address: ContractAddress::default() })),
+    StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::TransactionLimitExceeded)
+)]
 #[tokio::test]
 async fn test_add_tx_negative(
     mut mock_dependencies: MockDependencies,
@@ -429,6 +455,84 @@ async fn test_block_declare_config(
     assert_eq!(result.unwrap_err().code, expected_code);
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_simulate_tx_positive(
+    mut mock_dependencies: MockDependencies,
+    #[values(invoke_args(), deploy_account_args())] tx_args: impl TestingTxArgs,
+) {
+    setup_mock_state_for_validations(&mut mock_dependencies, &tx_args);
+    mock_dependencies.mock_mempool_client.expect_add_tx().never();
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let _recorder_guard = metrics::set_default_local_recorder(&recorder);
+
+    let gateway = mock_dependencies.gateway();
+    let result = gateway.simulate_tx(tx_args.get_rpc_tx()).await.unwrap();
+
+    let expected_tx_hash = tx_args.get_internal_tx().tx_hash();
+    assert_matches!(
+        result,
+        SimulateTxOutput::Accepted { transaction_hash, resources: Some(_) }
+            if transaction_hash == expected_tx_hash
+    );
+    let metric_handle_for_queries = GatewayMetricHandle::new_for_simulation(&tx_args.get_rpc_tx());
+    let metrics = recorder.handle().render();
+    assert_eq!(
+        metric_handle_for_queries.get_metric_value(GATEWAY_TRANSACTIONS_RECEIVED, &metrics),
+        1
+    );
+    assert_eq!(
+        metric_handle_for_queries.get_metric_value(GATEWAY_TRANSACTIONS_FAILED, &metrics),
+        0
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_simulate_tx_rejects_declare(mut mock_dependencies: MockDependencies) {
+    // The class must not be added to the class manager.
+    mock_dependencies.mock_class_manager_client.expect_add_class().never();
+    mock_dependencies.mock_mempool_client.expect_add_tx().never();
+
+    let gateway = mock_dependencies.gateway();
+    let result = gateway.simulate_tx(declare_args().get_rpc_tx()).await.unwrap();
+
+    let expected_code = StarknetErrorCode::UnknownErrorCode(
+        "StarknetErrorCode.BLOCKED_TRANSACTION_TYPE".to_string(),
+    );
+    assert_matches!(
+        result,
+        SimulateTxOutput::Rejected { error } if error.code == expected_code
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_simulate_tx_rejected(
+    mut config: GatewayConfig,
+    state_reader_factory: TestStateReaderFactory,
+) {
+    config.block_declare = true;
+    let gateway = Gateway::new(
+        config,
+        Arc::new(state_reader_factory),
+        Arc::new(MockMempoolClient::new()),
+        TransactionConverter::new(
+            Arc::new(EmptyClassManagerClient),
+            ChainInfo::create_for_testing().chain_id,
+        ),
+    );
+
+    let result = gateway.simulate_tx(declare_tx()).await.unwrap();
+    let expected_code = StarknetErrorCode::UnknownErrorCode(
+        "StarknetErrorCode.BLOCKED_TRANSACTION_TYPE".to_string(),
+    );
+    assert_matches!(
+        result,
+        SimulateTxOutput::Rejected { error } if error.code == expected_code
+    );
+}
+
 #[test]
 fn test_register_metrics() {
     let recorder = PrometheusBuilder::new().build_recorder();
@@ -22,6 +22,7 @@ define_metrics!(
         LabeledMetricCounter { GATEWAY_TRANSACTIONS_SENT_TO_MEMPOOL, "gateway_transactions_sent_to_mempool", "Counter of transactions sent to the mempool", init = 0 , labels = TRANSACTION_TYPE_AND_SOURCE_LABELS},
         MetricHistogram { GATEWAY_ADD_TX_LATENCY, "gateway_add_tx_latency", "Latency of gateway add_tx function in secs" },
         MetricHistogram { GATEWAY_VALIDATE_TX_LATENCY, "gateway_validate_tx_latency", "Latency of gateway validate function in secs" },
+        MetricHistogram { GATEWAY_SIMULATE_TX_LATENCY, "gateway_simulate_tx_latency", "Latency of gateway simulate_tx function in secs" },
     },
 );
 
@@ -30,10 +31,13 @@ define_metrics!(
 pub enum SourceLabelValue {
     Http,
     P2p,
+    // Transactions that are simulated, rather than added.
+    Simulation,
 }
 
 enum TransactionStatus {
     SentToMempool,
+    Simulated,
     Failed,
 }
 
@@ -56,6 +60,11 @@ impl GatewayMetricHandle {
         Self { tx_type, source, tx_status: TransactionStatus::Failed }
     }
 
+    pub fn new_for_simulation(tx: &RpcTransaction) -> Self {
+        let tx_type = RpcTransactionLabelValue::from(tx);
+        Self { tx_type, source: SourceLabelValue::Simulation, tx_status: TransactionStatus::Failed }
+    }
+
     fn label(&self) -> Vec<(&'static str, &'static str)> {
         vec![(LABEL_NAME_TX_TYPE, self.tx_type.into()), (LABEL_NAME_SOURCE, self.source.into())]
     }
@@ -68,6 +77,10 @@ impl GatewayMetricHandle {
         self.tx_status = TransactionStatus::SentToMempool;
     }
 
+    pub fn transaction_simulated(&mut self) {
+        self.tx_status = TransactionStatus::Simulated;
+    }
+
     #[cfg(test)]
     pub fn get_metric_value(&self, metric_counter: LabeledMetricCounter, metrics: &str) -> u64 {
         metric_counter.parse_numeric_metric::<u64>(metrics, &self.label()).unwrap()
@@ -80,6 +93,7 @@ impl Drop for GatewayMetricHandle {
             TransactionStatus::SentToMempool => {
                 GATEWAY_TRANSACTIONS_SENT_TO_MEMPOOL.increment(1, &self.label())
             }
+            TransactionStatus::Simulated => {}
             TransactionStatus::Failed => GATEWAY_TRANSACTIONS_FAILED.increment(1, &self.label()),
         }
     }
@@ -91,4 +105,5 @@ pub(crate) fn register_metrics() {
     GATEWAY_TRANSACTIONS_SENT_TO_MEMPOOL.register();
     GATEWAY_ADD_TX_LATENCY.register();
     GATEWAY_VALIDATE_TX_LATENCY.register();
+    GATEWAY_SIMULATE_TX_LATENCY.register();
 }
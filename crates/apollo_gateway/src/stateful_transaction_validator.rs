@@ -8,11 +8,13 @@ use apollo_mempool_types::communication::SharedMempoolClient;
 use apollo_proc_macros::sequencer_latency_histogram;
 use blockifier::blockifier::stateful_validator::{
     StatefulValidator,
+    StatefulValidatorError,
     StatefulValidatorTrait as BlockifierStatefulValidatorTrait,
 };
 use blockifier::blockifier_versioned_constants::VersionedConstants;
 use blockifier::bouncer::BouncerConfig;
 use blockifier::context::{BlockContext, ChainInfo};
+use blockifier::fee::receipt::TransactionReceipt;
 use blockifier::state::cached_state::CachedState;
 use blockifier::transaction::account_transaction::{AccountTransaction, ExecutionFlags};
 use blockifier::transaction::transactions::enforce_fee;
@@ -40,7 +42,7 @@ pub struct StatefulTransactionValidator {
     pub config: StatefulTransactionValidatorConfig,
 }
 
-type BlockifierStatefulValidator = StatefulValidator<Box<dyn MempoolStateReader>>;
+pub type BlockifierStatefulValidator = StatefulValidator<Box<dyn MempoolStateReader>>;
 
 impl StatefulTransactionValidator {
     pub fn run_transaction_validations<V: BlockifierStatefulValidatorTrait>(
@@ -61,6 +63,22 @@ impl StatefulTransactionValidator {
         )
     }
 
+    /// Same as `run_transaction_validations`, and returns the receipt of the validation; [None] if
+    /// `__validate__` was skipped.
+    pub fn simulate_transaction_validations<V: BlockifierStatefulValidatorTrait>(
+        &self,
+        executable_tx: &ExecutableTransaction,
+        account_nonce: Nonce,
+        mempool_client: SharedMempoolClient,
+        mut validator: V,
+        runtime: tokio::runtime::Handle,
+    ) -> StatefulTransactionValidatorResult<Option<TransactionReceipt>> {
+        self.validate_state_preconditions(executable_tx, account_nonce, &validator)?;
+        let account_tx =
+            account_tx_for_validation(executable_tx, account_nonce, mempool_client, runtime)?;
+        validator.validate_with_receipt(account_tx).map_err(validate_failure)
+    }
+
     fn validate_state_preconditions<V: BlockifierStatefulValidatorTrait>(
         &self,
         executable_tx: &ExecutableTransaction,
@@ -125,19 +143,9 @@ impl StatefulTransactionValidator {
         mut validator: V,
         runtime: tokio::runtime::Handle,
     ) -> StatefulTransactionValidatorResult<()> {
-        let skip_validate =
-            skip_stateful_validations(executable_tx, account_nonce, mempool_client, runtime)?;
-        let only_query = false;
-        let charge_fee = enforce_fee(executable_tx, only_query);
-        let strict_nonce_check = false;
-        let execution_flags =
-            ExecutionFlags { only_query, charge_fee, validate: !skip_validate, strict_nonce_check };
-
-        let account_tx = AccountTransaction { tx: executable_tx.clone(), execution_flags };
-        validator.validate(account_tx).map_err(|e| StarknetError {
-            code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
-            message: e.to_string(),
-        })?;
+        let account_tx =
+            account_tx_for_validation(executable_tx, account_nonce, mempool_client, runtime)?;
+        validator.validate(account_tx).map_err(validate_failure)?;
         Ok(())
     }
 
@@ -219,6 +227,30 @@ impl StatefulTransactionValidator {
     }
 }
 
+fn account_tx_for_validation(
+    executable_tx: &ExecutableTransaction,
+    account_nonce: Nonce,
+    mempool_client: SharedMempoolClient,
+    runtime: tokio::runtime::Handle,
+) -> StatefulTransactionValidatorResult<AccountTransaction> {
+    let skip_validate =
+        skip_stateful_validations(executable_tx, account_nonce, mempool_client, runtime)?;
+    let only_query = false;
+    let charge_fee = enforce_fee(executable_tx, only_query);
+    let strict_nonce_check = false;
+    let execution_flags =
+        ExecutionFlags { only_query, charge_fee, validate: !skip_validate, strict_nonce_check };
+
+    Ok(AccountTransaction { tx: executable_tx.clone(), execution_flags })
+}
+
+fn validate_failure(error: StatefulValidatorError) -> StarknetError {
+    StarknetError {
+        code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+        message: error.to_string(),
+    }
+}
+
 /// Check if validation of an invoke transaction should be skipped due to deploy_account not being
 /// processed yet. This feature is used to improve UX for users sending deploy_account + invoke at
 /// once.
@@ -8,11 +8,12 @@ use async_trait::async_trait;
 #[cfg(any(feature = "testing", test))]
 use mockall::automock;
 use serde::{Deserialize, Serialize};
+use starknet_api::rpc_transaction::RpcTransaction;
 use strum_macros::AsRefStr;
 use thiserror::Error;
 
 use crate::errors::GatewayError;
-use crate::gateway_types::{GatewayInput, GatewayOutput, GatewayResult};
+use crate::gateway_types::{GatewayInput, GatewayOutput, GatewayResult, SimulateTxOutput};
 
 pub type LocalGatewayClient = LocalComponentClient<GatewayRequest, GatewayResponse>;
 pub type RemoteGatewayClient = RemoteComponentClient<GatewayRequest, GatewayResponse>;
@@ -28,11 +29,14 @@ use tracing::{error, instrument};
 #[async_trait]
 pub trait GatewayClient: Send + Sync {
     async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<GatewayOutput>;
+    /// Runs the validations of `add_tx` on the transaction, without adding it to the mempool.
+    async fn simulate_tx(&self, rpc_tx: RpcTransaction) -> GatewayClientResult<SimulateTxOutput>;
 }
 
 #[derive(Clone, Serialize, Deserialize, AsRefStr)]
 pub enum GatewayRequest {
     AddTransaction(GatewayInput),
+    SimulateTransaction(RpcTransaction),
 }
 
 impl_debug_for_infra_requests_and_responses!(GatewayRequest);
@@ -40,6 +44,7 @@ impl_debug_for_infra_requests_and_responses!(GatewayRequest);
 #[derive(Clone, Serialize, Deserialize, AsRefStr)]
 pub enum GatewayResponse {
     AddTransaction(GatewayResult<GatewayOutput>),
+    SimulateTransaction(GatewayResult<SimulateTxOutput>),
 }
 impl_debug_for_infra_requests_and_responses!(GatewayResponse);
 
@@ -67,4 +72,16 @@ where
             Direct
         )
     }
+
+    #[instrument(skip(self))]
+    async fn simulate_tx(&self, rpc_tx: RpcTransaction) -> GatewayClientResult<SimulateTxOutput> {
+        let request = GatewayRequest::SimulateTransaction(rpc_tx);
+        handle_all_response_variants!(
+            GatewayResponse,
+            SimulateTransaction,
+            GatewayClientError,
+            GatewayError,
+            Direct
+        )
+    }
 }
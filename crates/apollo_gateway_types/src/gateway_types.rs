@@ -1,9 +1,12 @@
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
 use serde::{Deserialize, Serialize};
 use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::execution_resources::{GasAmount, GasVector};
 use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::TransactionHash;
 
+use crate::deprecated_gateway_error::StarknetError;
 use crate::errors::GatewayError;
 
 const TRANSACTION_RECEIVED: &str = "TRANSACTION_RECEIVED";
@@ -73,4 +76,31 @@ impl InvokeGatewayOutput {
     }
 }
 
+/// The verdict of a transaction simulation: whether the gateway would accept the transaction.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "verdict")]
+pub enum SimulateTxOutput {
+    Accepted {
+        transaction_hash: TransactionHash,
+        // Missing if `__validate__` was skipped, i.e., for an invoke transaction that follows a
+        // deploy account transaction that is not yet in a block.
+        resources: Option<SimulatedTxResources>,
+    },
+    // The error the gateway would return when adding the transaction.
+    Rejected {
+        error: StarknetError,
+    },
+}
+
+/// The resources consumed by the validation of a transaction; the full execution is included for
+/// deploy account transactions.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedTxResources {
+    pub fee: Fee,
+    pub gas: GasVector,
+    pub da_gas: GasVector,
+    pub n_steps: usize,
+    pub sierra_gas: GasAmount,
+}
+
 pub type GatewayResult<T> = Result<T, GatewayError>;
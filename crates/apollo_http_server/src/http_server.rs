@@ -12,6 +12,7 @@ use apollo_gateway_types::errors::GatewayError;
 use apollo_gateway_types::gateway_types::{
     GatewayInput,
     GatewayOutput,
+    SimulateTxOutput,
     SUPPORTED_TRANSACTION_VERSIONS,
 };
 use apollo_infra::component_definitions::ComponentStarter;
@@ -87,6 +88,9 @@ impl HttpServer {
             // Rest api endpoint
             .route("/gateway/add_transaction", post(add_tx))
             .with_state(self.app_state.clone())
+            // Validates a transaction without submitting it.
+            .route("/gateway/simulate_rpc_transaction", post(simulate_rpc_tx))
+            .with_state(self.app_state.clone())
             // TODO(shahak): Remove this once we fix the centralized simulator to not use is_alive
             // and is_ready.
             .route(
@@ -137,6 +141,30 @@ async fn add_tx(
     add_tx_inner(app_state, headers, rpc_tx).await
 }
 
+#[instrument(skip(app_state))]
+async fn simulate_rpc_tx(
+    State(app_state): State<AppState>,
+    tx: String,
+) -> HttpServerResult<Json<SimulateTxOutput>> {
+    debug!("Http server received a transaction to simulate.");
+    validate_supported_tx_version(&tx).inspect_err(|e| {
+        debug!("Error while validating transaction version: {}", e);
+    })?;
+    let tx: RpcTransaction = serde_json::from_str(&tx).inspect_err(|e| {
+        debug!("Error while parsing transaction: {}", e);
+    })?;
+    // Wrap the gateway client interaction with a tokio::spawn as it is NOT cancel-safe.
+    let simulate_tx_result =
+        tokio::spawn(async move { app_state.gateway_client.simulate_tx(tx).await })
+            .await
+            .expect("Should be able to get simulate_tx result")
+            .map_err(|e| {
+                debug!("Error while simulating transaction: {}", e);
+                HttpServerError::from(Box::new(e))
+            })?;
+    Ok(Json(simulate_tx_result))
+}
+
 fn validate_supported_tx_version(tx: &str) -> HttpServerResult<()> {
     let tx_json_value: serde_json::Value = serde_json::from_str(tx)?;
     let tx_version_json = tx_json_value
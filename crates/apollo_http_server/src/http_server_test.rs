@@ -10,6 +10,7 @@ use apollo_gateway_types::gateway_types::{
     DeployAccountGatewayOutput,
     GatewayOutput,
     InvokeGatewayOutput,
+    SimulateTxOutput,
 };
 use apollo_infra::component_client::ClientError;
 use axum::body::{Bytes, HttpBody};
@@ -219,6 +220,52 @@ async fn test_response(#[case] index: u16, #[case] tx: impl GatewayTransaction)
     assert_eq!(error_str, expected_gateway_client_err_str);
 }
 
+#[rstest]
+#[case::accepted(
+    0,
+    SimulateTxOutput::Accepted { transaction_hash: EXPECTED_TX_HASH, resources: None }
+)]
+#[case::rejected(1, SimulateTxOutput::Rejected {
+    error: StarknetError {
+        code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+        message: "Arbitrary".to_string(),
+    },
+})]
+#[tokio::test]
+async fn simulate_tx_response(#[case] index: u16, #[case] expected_output: SimulateTxOutput) {
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client.expect_add_tx().never();
+    mock_gateway_client
+        .expect_simulate_tx()
+        .times(1)
+        .withf(|rpc_tx| *rpc_tx == rpc_invoke_tx())
+        .return_const(Ok(expected_output.clone()));
+    let http_client = add_tx_http_client(mock_gateway_client, 17 + index).await;
+
+    // A rejected transaction is a successful simulation.
+    let response = http_client.simulate_tx(rpc_invoke_tx()).await;
+    assert!(response.status().is_success(), "{:?}", response.status());
+    let output: SimulateTxOutput = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(output, expected_output);
+}
+
+#[tokio::test]
+async fn simulate_tx_with_unsupported_version() {
+    let mut tx_json = serde_json::to_value(rpc_invoke_tx()).unwrap();
+    tx_json.as_object_mut().unwrap().insert("version".to_string(), Value::String("0x1".into()));
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client.expect_simulate_tx().never();
+    let http_client = add_tx_http_client(mock_gateway_client, 19).await;
+
+    let response = http_client.simulate_tx(tx_json).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let starknet_error = serde_json::from_str::<StarknetError>(&response.text().await.unwrap());
+    assert_eq!(
+        starknet_error.unwrap().code,
+        StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::InvalidTransactionVersion)
+    );
+}
+
 #[rstest]
 #[case::missing_version(
     0,
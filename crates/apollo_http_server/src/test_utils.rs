@@ -81,6 +81,16 @@ impl HttpTestClient {
             .await
             .unwrap()
     }
+
+    pub async fn simulate_tx(&self, tx: impl Serialize) -> Response {
+        self.client
+            .post(format!("http://{}/gateway/simulate_rpc_transaction", self.socket))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&tx).unwrap()))
+            .send()
+            .await
+            .unwrap()
+    }
 }
 
 pub fn create_http_server_config(socket: SocketAddr) -> HttpServerConfig {
@@ -4,6 +4,7 @@ pub mod command;
 pub mod dumping;
 pub mod global_allocator;
 pub mod path;
+pub mod record_file;
 pub mod run_until;
 pub mod tasks;
 pub mod template;
@@ -0,0 +1,106 @@
+//! An append-only file of records, used to persist state that must survive a restart.
+//!
+//! Each record is the JSON serialization of a value, prefixed by its length as a big-endian `u32`.
+//! Records are synced to disk as they are appended, and a record torn by a crash mid-write is
+//! detected by its length, and truncated on the next open.
+
+#[cfg(test)]
+#[path = "record_file_test.rs"]
+mod record_file_test;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::warn;
+
+const LENGTH_PREFIX_SIZE: usize = size_of::<u32>();
+
+/// An append-only file of length-prefixed records.
+pub struct RecordFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl RecordFile {
+    /// Opens the record file at the given path, creating it if needed, and returns the records it
+    /// holds. A trailing record that cannot be read (e.g., a partial write before a crash) ends the
+    /// replay, and is truncated so that it doesn't corrupt the next append.
+    pub fn open<T: DeserializeOwned>(path: PathBuf) -> io::Result<(Self, Vec<T>)> {
+        let (records, valid_len) =
+            if path.exists() { read_records(&path)? } else { (Vec::new(), 0) };
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        if file.metadata()?.len() > valid_len {
+            file.set_len(valid_len)?;
+            file.sync_all()?;
+        }
+
+        Ok((RecordFile { path, file }, records))
+    }
+
+    /// Writes the record to the end of the file. Returns once the record is durably stored.
+    pub fn append<T: Serialize>(&mut self, record: &T) -> io::Result<()> {
+        write_record(&mut self.file, record)?;
+        self.file.sync_data()
+    }
+
+    /// Atomically replaces the content of the file with the given records.
+    pub fn rewrite<T: Serialize>(
+        &mut self,
+        records: impl IntoIterator<Item = T>,
+    ) -> io::Result<()> {
+        // Write to a temporary file first, so that a crash mid-write leaves the old file intact.
+        let tmp_path = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        for record in records {
+            write_record(&mut writer, &record)?;
+        }
+        writer.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+        Ok(())
+    }
+}
+
+// Returns the records of the file, along with the length of the prefix of the file they occupy.
+fn read_records<T: DeserializeOwned>(path: &Path) -> io::Result<(Vec<T>, u64)> {
+    let content = fs::read(path)?;
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while offset < content.len() {
+        match read_record(&content[offset..]) {
+            Ok((record, record_size)) => {
+                records.push(record);
+                offset += record_size;
+            }
+            Err(err) => {
+                warn!("Stopping replay of {path:?} at corrupted record {}: {err}", records.len());
+                break;
+            }
+        }
+    }
+
+    Ok((records, u64::try_from(offset).expect("The file length should fit in u64.")))
+}
+
+// Returns the record at the start of `bytes` and the number of bytes it occupies.
+fn read_record<T: DeserializeOwned>(bytes: &[u8]) -> Result<(T, usize), String> {
+    let length_prefix = bytes.get(..LENGTH_PREFIX_SIZE).ok_or("truncated length prefix")?;
+    let length = u32::from_be_bytes(length_prefix.try_into().expect("The prefix has 4 bytes."));
+    let record_size =
+        LENGTH_PREFIX_SIZE + usize::try_from(length).expect("u32 should fit in usize.");
+    let record_bytes = bytes.get(LENGTH_PREFIX_SIZE..record_size).ok_or("truncated record")?;
+    let record = serde_json::from_slice(record_bytes).map_err(|err| err.to_string())?;
+    Ok((record, record_size))
+}
+
+fn write_record<T: Serialize>(writer: &mut impl Write, record: &T) -> io::Result<()> {
+    let bytes = serde_json::to_vec(record)?;
+    let length = u32::try_from(bytes.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "record is too large"))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(&bytes)
+}
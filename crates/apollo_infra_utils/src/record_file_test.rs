@@ -0,0 +1,55 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use crate::record_file::RecordFile;
+
+#[test]
+fn records_are_restored_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("records");
+
+    let (mut file, records) = RecordFile::open::<String>(path.clone()).unwrap();
+    assert!(records.is_empty());
+    file.append(&"first").unwrap();
+    file.append(&"second").unwrap();
+    drop(file);
+
+    let (_file, records) = RecordFile::open::<String>(path).unwrap();
+    assert_eq!(records, vec!["first", "second"]);
+}
+
+#[test]
+fn rewrite_replaces_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("records");
+
+    let (mut file, _) = RecordFile::open::<u64>(path.clone()).unwrap();
+    file.append(&1_u64).unwrap();
+    file.rewrite([2_u64, 3]).unwrap();
+    file.append(&4_u64).unwrap();
+    drop(file);
+
+    let (_file, records) = RecordFile::open::<u64>(path).unwrap();
+    assert_eq!(records, vec![2, 3, 4]);
+}
+
+#[test]
+fn torn_record_is_truncated() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("records");
+    let (mut file, _) = RecordFile::open::<String>(path.clone()).unwrap();
+    file.append(&"first").unwrap();
+    drop(file);
+    let valid_len = fs::metadata(&path).unwrap().len();
+    // Simulate a crash in the middle of writing a record: a length prefix without its record.
+    OpenOptions::new().append(true).open(&path).unwrap().write_all(&[0, 0, 0, 9, b'"']).unwrap();
+
+    let (mut file, records) = RecordFile::open::<String>(path.clone()).unwrap();
+    assert_eq!(records, vec!["first"]);
+    assert_eq!(fs::metadata(&path).unwrap().len(), valid_len);
+    file.append(&"second").unwrap();
+    drop(file);
+
+    let (_file, records) = RecordFile::open::<String>(path).unwrap();
+    assert_eq!(records, vec!["first", "second"]);
+}
@@ -234,8 +234,10 @@ impl FlowSequencerSetup {
         allow_bootstrap_txs: bool,
     ) -> Self {
         let path = None;
+        let num_validators =
+            usize::try_from(consensus_manager_config.context_config.num_validators).unwrap();
         let StorageTestSetup { storage_config, storage_handles } =
-            StorageTestSetup::new(accounts, &chain_info, path);
+            StorageTestSetup::new(accounts, num_validators, &chain_info, path);
 
         let (recorder_url, _join_handle) =
             spawn_local_success_recorder(available_ports.get_next_port());
@@ -897,6 +897,7 @@ pub async fn get_sequencer_setup_configs(
             class_manager_index,
             custom_paths.clone(),
             accounts.to_vec(),
+            component_configs_len,
             &chain_info,
         );
 
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
+use std::iter::successors;
 
 use apollo_class_manager::class_storage::{ClassStorage, FsClassStorage};
 use apollo_class_manager::config::FsClassStorageConfig;
 use apollo_class_manager::test_utils::FsClassStorageBuilderForTesting;
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
+use apollo_signature_manager::SignatureManager;
 use apollo_storage::body::BodyStorageWriter;
 use apollo_storage::class::ClassStorageWriter;
 use apollo_storage::compiled_class::CasmStorageWriter;
@@ -22,7 +25,7 @@ use mempool_test_utils::starknet_api_test_utils::{
     Contract,
     VALID_ACCOUNT_BALANCE,
 };
-use starknet_api::abi::abi_utils::get_fee_token_var_address;
+use starknet_api::abi::abi_utils::{get_fee_token_var_address, get_storage_var_address};
 use starknet_api::block::{
     BlockBody,
     BlockHeader,
@@ -60,6 +63,9 @@ pub(crate) const CLASS_MANAGER_DB_PATH_SUFFIX: &str = "class_manager";
 pub(crate) const CLASS_HASH_STORAGE_DB_PATH_SUFFIX: &str = "class_hash_storage";
 pub(crate) const CLASSES_STORAGE_DB_PATH_SUFFIX: &str = "classes";
 pub(crate) const STATE_SYNC_DB_PATH_SUFFIX: &str = "state_sync";
+/// The staking contract from which the consensus committee is fetched.
+pub(crate) const STAKING_CONTRACT: FeatureContract =
+    FeatureContract::MockStakingContract(RunnableCairo1::Casm);
 
 #[derive(Debug, Clone)]
 pub struct StorageTestConfig {
@@ -104,6 +110,7 @@ pub struct StorageTestSetup {
 impl StorageTestSetup {
     pub fn new(
         test_defined_accounts: Vec<AccountTransactionGenerator>,
+        num_validators: usize,
         chain_info: &ChainInfo,
         storage_exec_paths: Option<StorageExecutablePaths>,
     ) -> Self {
@@ -122,6 +129,7 @@ impl StorageTestSetup {
             &mut batcher_storage_writer,
             chain_info,
             &test_defined_accounts,
+            num_validators,
             preset_test_contracts.clone(),
             &classes,
         );
@@ -140,6 +148,7 @@ impl StorageTestSetup {
             &mut state_sync_storage_writer,
             chain_info,
             &test_defined_accounts,
+            num_validators,
             preset_test_contracts,
             &classes,
         );
@@ -183,6 +192,7 @@ impl StorageTestSetup {
 struct PresetTestContracts {
     pub default_test_contracts: Vec<Contract>,
     pub erc20_contract: Contract,
+    pub staking_contract: Contract,
 }
 
 impl PresetTestContracts {
@@ -201,8 +211,9 @@ impl PresetTestContracts {
 
         let erc20_contract = FeatureContract::ERC20(CairoVersion::Cairo0);
         let erc20_contract = into_contract(erc20_contract);
+        let staking_contract = into_contract(STAKING_CONTRACT);
 
-        Self { default_test_contracts, erc20_contract }
+        Self { default_test_contracts, erc20_contract, staking_contract }
     }
 }
 
@@ -216,12 +227,13 @@ impl TestClasses {
         test_defined_accounts: &[AccountTransactionGenerator],
         preset_test_contracts: PresetTestContracts,
     ) -> TestClasses {
-        let PresetTestContracts { default_test_contracts, erc20_contract } = preset_test_contracts;
+        let PresetTestContracts { default_test_contracts, erc20_contract, staking_contract } =
+            preset_test_contracts;
         let contract_classes_to_retrieve = test_defined_accounts
             .iter()
             .map(|acc| acc.account)
             .chain(default_test_contracts)
-            .chain([erc20_contract]);
+            .chain([erc20_contract, staking_contract]);
         let (cairo0_contract_classes, cairo1_contract_classes) =
             prepare_contract_classes(contract_classes_to_retrieve);
 
@@ -260,10 +272,16 @@ fn initialize_papyrus_test_state(
     storage_writer: &mut StorageWriter,
     chain_info: &ChainInfo,
     test_defined_accounts: &[AccountTransactionGenerator],
+    num_validators: usize,
     preset_test_contracts: PresetTestContracts,
     classes: &TestClasses,
 ) {
-    let state_diff = prepare_state_diff(chain_info, test_defined_accounts, &preset_test_contracts);
+    let state_diff = prepare_state_diff(
+        chain_info,
+        test_defined_accounts,
+        num_validators,
+        &preset_test_contracts,
+    );
 
     write_state_to_apollo_storage(storage_writer, state_diff, classes)
 }
@@ -271,10 +289,12 @@ fn initialize_papyrus_test_state(
 fn prepare_state_diff(
     chain_info: &ChainInfo,
     test_defined_accounts: &[AccountTransactionGenerator],
+    num_validators: usize,
     preset_test_contracts: &PresetTestContracts,
 ) -> ThinStateDiff {
     let mut state_diff_builder = ThinStateDiffBuilder::new(chain_info);
-    let PresetTestContracts { default_test_contracts, erc20_contract } = preset_test_contracts;
+    let PresetTestContracts { default_test_contracts, erc20_contract, staking_contract } =
+        preset_test_contracts;
 
     // Setup the common test contracts that are used by default in all test invokes.
     // TODO(batcher): this does nothing until we actually start excuting stuff in the batcher.
@@ -283,6 +303,14 @@ fn prepare_state_diff(
     // Declare and deploy and the ERC20 contract, so that transfers from it can be made.
     state_diff_builder.set_contracts(std::slice::from_ref(erc20_contract)).declare().deploy();
 
+    // Declare and deploy the staking contract, with the validators as its stakers, so that the
+    // consensus committee can be fetched from it.
+    state_diff_builder
+        .set_contracts(std::slice::from_ref(staking_contract))
+        .declare()
+        .deploy()
+        .stake_validators(num_validators);
+
     // TODO(deploy_account_support): once we have batcher with execution, replace with:
     // ```
     // state_diff_builder.set_contracts(accounts_defined_in_the_test).declare().fund();
@@ -470,6 +498,36 @@ impl<'a> ThinStateDiffBuilder<'a> {
         self
     }
 
+    /// Only applies for the staking contract, in which a staker of equal weight is set for each
+    /// validator.
+    fn stake_validators(&mut self, num_validators: usize) -> &mut Self {
+        // All the validators sign with the signature manager's local key store.
+        let public_key = SignatureManager::new().keystore.public_key.0;
+        for staking_contract in self.contracts {
+            assert_matches!(
+                staking_contract.contract,
+                FeatureContract::MockStakingContract(_),
+                "Only the staking contract can have stakers, {staking_contract:?} is not it",
+            );
+
+            // The stakers are stored in a storage `Vec`, whose length is at the address of the
+            // variable, and whose elements are at the hash of that address with their index.
+            let storage = self.storage_diffs.entry(staking_contract.sender_address).or_default();
+            storage.insert(get_storage_var_address("stakers", &[]), Felt::from(num_validators));
+            for index in 0..num_validators {
+                let validator_id = DEFAULT_VALIDATOR_ID + u64::try_from(index).unwrap();
+                let staker_fields = [Felt::from(validator_id), Felt::ONE, public_key];
+                let staker_field_keys = successors(
+                    Some(get_storage_var_address("stakers", &[Felt::from(index)])),
+                    |key| Some(key.next_storage_key().unwrap()),
+                );
+                storage.extend(staker_field_keys.zip(staker_fields));
+            }
+        }
+
+        self
+    }
+
     fn inject_deployed_accounts_into_state(
         &mut self,
         deployed_accounts_defined_in_the_test: &'a [Contract],
@@ -101,6 +101,7 @@ pub fn get_integration_test_storage(
     class_manager_index: usize,
     custom_paths: Option<CustomPaths>,
     accounts: Vec<AccountTransactionGenerator>,
+    num_validators: usize,
     chain_info: &ChainInfo,
 ) -> StorageTestSetup {
     let storage_exec_paths = custom_paths.as_ref().and_then(|paths| {
@@ -116,7 +117,7 @@ pub fn get_integration_test_storage(
     });
 
     let StorageTestSetup { mut storage_config, storage_handles } =
-        StorageTestSetup::new(accounts, chain_info, storage_exec_paths);
+        StorageTestSetup::new(accounts, num_validators, chain_info, storage_exec_paths);
 
     // Allow overriding the path with a custom prefix for Docker mode in system tests.
     if let Some(paths) = custom_paths {
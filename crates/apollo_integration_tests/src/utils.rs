@@ -39,6 +39,7 @@ use apollo_node::config::component_config::ComponentConfig;
 use apollo_node::config::definitions::ConfigPointersMap;
 use apollo_node::config::node_config::{SequencerNodeConfig, CONFIG_POINTERS};
 use apollo_rpc::RpcConfig;
+use apollo_staking::staking_manager::StakingManagerConfig;
 use apollo_state_sync::config::StateSyncConfig;
 use apollo_storage::StorageConfig;
 use axum::extract::Query;
@@ -68,7 +69,7 @@ use tracing::{debug, info, Instrument};
 use url::Url;
 
 use crate::anvil_base_layer::AnvilBaseLayer;
-use crate::state_reader::StorageTestConfig;
+use crate::state_reader::{StorageTestConfig, STAKING_CONTRACT};
 
 pub const ACCOUNT_ID_0: AccountId = 0;
 pub const ACCOUNT_ID_1: AccountId = 1;
@@ -312,6 +313,10 @@ pub(crate) fn create_consensus_manager_configs_from_network_configs(
                 ]),
                 ..Default::default()
             },
+            staking_manager_config: StakingManagerConfig {
+                staking_contract_address: STAKING_CONTRACT.get_instance_address(0),
+                ..Default::default()
+            },
             assume_no_malicious_validators: true,
             ..Default::default()
         })
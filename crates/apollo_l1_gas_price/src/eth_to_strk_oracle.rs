@@ -9,11 +9,13 @@ use apollo_config::converters::{
     serialize_optional_list_with_url_and_headers,
     UrlAndHeaders,
 };
-use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_l1_gas_price_types::errors::EthToStrkOracleClientError;
 use apollo_l1_gas_price_types::EthToStrkOracleClientTrait;
+use apollo_metrics::metrics::LossyIntoF64;
 use async_trait::async_trait;
+use futures::future::join_all;
 use futures::FutureExt;
 use lru::LruCache;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
@@ -23,11 +25,16 @@ use serde_json;
 use tokio_util::task::AbortOnDropHandle;
 use tracing::{debug, info, instrument, warn};
 use url::Url;
+use validator::{Validate, ValidationError};
 
 use crate::metrics::{
+    eth_to_strk_source_labels,
     register_eth_to_strk_metrics,
     ETH_TO_STRK_ERROR_COUNT,
     ETH_TO_STRK_RATE,
+    ETH_TO_STRK_SOURCE_DEVIATION_BPS,
+    ETH_TO_STRK_SOURCE_ERROR_COUNT,
+    ETH_TO_STRK_SOURCE_OUTLIER_COUNT,
     ETH_TO_STRK_SUCCESS_COUNT,
 };
 
@@ -37,6 +44,8 @@ pub mod eth_to_strk_oracle_test;
 
 pub const ETH_TO_STRK_QUANTIZATION: u64 = 18;
 
+const BASIS_POINTS: u128 = 10_000;
+
 fn btreemap_to_headermap(hash_map: BTreeMap<String, String>) -> HeaderMap {
     let mut header_map = HeaderMap::new();
     for (key, value) in hash_map {
@@ -48,18 +57,58 @@ fn btreemap_to_headermap(hash_map: BTreeMap<String, String>) -> HeaderMap {
     header_map
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+/// How the rates of the oracles in `url_header_list` are combined into a single rate.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub enum EthToStrkOracleAggregationMode {
+    /// Query the oracles in order, and take the rate of the first one that answers.
+    #[default]
+    FirstSuccessful,
+    /// Query all oracles concurrently, and take the median of the rates that aren't outliers.
+    Median,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_eth_to_strk_oracle_config"))]
 pub struct EthToStrkOracleConfig {
     #[serde(deserialize_with = "deserialize_optional_list_with_url_and_headers")]
     pub url_header_list: Option<Vec<UrlAndHeaders>>,
     pub lag_interval_seconds: u64,
     pub max_cache_size: usize,
     pub query_timeout_sec: u64,
+    pub aggregation_mode: EthToStrkOracleAggregationMode,
+    pub max_deviation_bps: u64,
+    // If not set, a majority of the oracles in `url_header_list`.
+    pub min_quorum: Option<usize>,
+}
+
+impl EthToStrkOracleConfig {
+    fn min_quorum(&self) -> usize {
+        let num_oracles = self.url_header_list.as_ref().map_or(0, Vec::len);
+        self.min_quorum.unwrap_or(num_oracles / 2 + 1)
+    }
+}
+
+fn validate_eth_to_strk_oracle_config(
+    config: &EthToStrkOracleConfig,
+) -> Result<(), ValidationError> {
+    let Some(min_quorum) = config.min_quorum else {
+        return Ok(());
+    };
+    if min_quorum == 0 {
+        return Err(ValidationError::new("min_quorum must be positive"));
+    }
+    let num_oracles = config.url_header_list.as_ref().map_or(0, Vec::len);
+    if min_quorum > num_oracles {
+        return Err(ValidationError::new(
+            "min_quorum must not exceed the number of oracles in url_header_list",
+        ));
+    }
+    Ok(())
 }
 
 impl SerializeConfig for EthToStrkOracleConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut config = BTreeMap::from_iter([
             ser_param(
                 "url_header_list",
                 &serialize_optional_list_with_url_and_headers(&self.url_header_list),
@@ -92,7 +141,32 @@ impl SerializeConfig for EthToStrkOracleConfig {
                 "The timeout (seconds) for the query to the eth to strk oracle.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+            ser_param(
+                "aggregation_mode",
+                &self.aggregation_mode,
+                "How the rates of the oracles in `url_header_list` are combined: \
+                 `FirstSuccessful` takes the first oracle that answers, in order, and `Median` \
+                 queries all oracles concurrently and takes the median of the non-outlier rates.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_deviation_bps",
+                &self.max_deviation_bps,
+                "In `Median` aggregation mode, the maximal deviation (basis points) of an oracle \
+                 rate from the median of all rates, above which the rate is rejected as an \
+                 outlier.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        config.extend(ser_optional_param(
+            &self.min_quorum,
+            1,
+            "min_quorum",
+            "In `Median` aggregation mode, the minimal number of oracles that must answer with a \
+             non-outlier rate. If not set, a majority of the oracles in `url_header_list`.",
+            ParamPrivacyInput::Public,
+        ));
+        config
     }
 }
 
@@ -106,6 +180,9 @@ impl Default for EthToStrkOracleConfig {
             lag_interval_seconds: 1,
             max_cache_size: 100,
             query_timeout_sec: 3,
+            aggregation_mode: EthToStrkOracleAggregationMode::default(),
+            max_deviation_bps: 500,
+            min_quorum: None,
         }
     }
 }
@@ -172,48 +249,192 @@ impl EthToStrkOracleClient {
         let adjusted_timestamp = quantized_timestamp * self.config.lag_interval_seconds;
         let query_timeout_sec = self.config.query_timeout_sec;
         let client = self.client.clone();
-        let index_clone = self.index.clone();
         let url_header_list = self.url_header_list.clone();
-        let list_len = url_header_list.len();
-        let future = async move {
-            let initial_index = index_clone.load(Ordering::SeqCst);
-            for (i, url_and_headers) in
-                url_header_list.iter().cycle().skip(initial_index).take(list_len).enumerate()
-            {
-                let UrlAndHeaderMap { url, headers } = url_and_headers;
-                let mut url = url.clone();
-                url.query_pairs_mut().append_pair("timestamp", &adjusted_timestamp.to_string());
-                let result = tokio::time::timeout(Duration::from_secs(query_timeout_sec), async {
-                    let response = client.get(url.clone()).headers(headers.clone()).send().await?;
-                    let body = response.text().await?;
-                    let rate = resolve_query(body)?;
-                    Ok::<_, EthToStrkOracleClientError>(rate)
-                })
-                .await;
-
-                match result {
-                    Ok(Ok(rate)) => {
-                        let idx = (i + initial_index) % list_len;
-                        index_clone.store(idx, Ordering::SeqCst);
-                        debug!("Resolved query to {url} with rate {rate}");
-                        return Ok(rate);
-                    }
-                    Ok(Err(e)) => {
-                        warn!("Failed to resolve query to {url}: {e:?}");
-                    }
-                    Err(_) => {
-                        warn!("Timeout when resolving query to {url}");
-                    }
-                };
-                ETH_TO_STRK_ERROR_COUNT.increment(1);
-            }
-            warn!("All {list_len} URLs in the list failed for timestamp {adjusted_timestamp}");
-            Err(EthToStrkOracleClientError::AllUrlsFailedError(adjusted_timestamp, initial_index))
+        let future = match self.config.aggregation_mode {
+            EthToStrkOracleAggregationMode::FirstSuccessful => query_first_successful(
+                client,
+                url_header_list,
+                self.index.clone(),
+                adjusted_timestamp,
+                query_timeout_sec,
+            )
+            .boxed(),
+            EthToStrkOracleAggregationMode::Median => query_median(
+                client,
+                url_header_list,
+                adjusted_timestamp,
+                query_timeout_sec,
+                self.config.max_deviation_bps.into(),
+                self.config.min_quorum(),
+            )
+            .boxed(),
         };
         AbortOnDropHandle::new(tokio::spawn(future))
     }
 }
 
+/// Queries a single oracle, returning `None` if it failed or timed out.
+async fn query_source(
+    client: &reqwest::Client,
+    url_and_headers: &UrlAndHeaderMap,
+    adjusted_timestamp: u64,
+    query_timeout_sec: u64,
+) -> Option<u128> {
+    let UrlAndHeaderMap { url, headers } = url_and_headers;
+    let mut url = url.clone();
+    url.query_pairs_mut().append_pair("timestamp", &adjusted_timestamp.to_string());
+    let result = tokio::time::timeout(Duration::from_secs(query_timeout_sec), async {
+        let response = client.get(url.clone()).headers(headers.clone()).send().await?;
+        let body = response.text().await?;
+        let rate = resolve_query(body)?;
+        Ok::<_, EthToStrkOracleClientError>(rate)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(rate)) => {
+            debug!("Resolved query to {url} with rate {rate}");
+            return Some(rate);
+        }
+        Ok(Err(e)) => {
+            warn!("Failed to resolve query to {url}: {e:?}");
+        }
+        Err(_) => {
+            warn!("Timeout when resolving query to {url}");
+        }
+    };
+    ETH_TO_STRK_ERROR_COUNT.increment(1);
+    None
+}
+
+/// Tries the oracles in order, starting from the last one that answered, and returns the first
+/// rate resolved.
+async fn query_first_successful(
+    client: reqwest::Client,
+    url_header_list: Arc<Vec<UrlAndHeaderMap>>,
+    index: Arc<AtomicUsize>,
+    adjusted_timestamp: u64,
+    query_timeout_sec: u64,
+) -> Result<u128, EthToStrkOracleClientError> {
+    let list_len = url_header_list.len();
+    let initial_index = index.load(Ordering::SeqCst);
+    for (i, url_and_headers) in
+        url_header_list.iter().cycle().skip(initial_index).take(list_len).enumerate()
+    {
+        if let Some(rate) =
+            query_source(&client, url_and_headers, adjusted_timestamp, query_timeout_sec).await
+        {
+            let idx = (i + initial_index) % list_len;
+            index.store(idx, Ordering::SeqCst);
+            ETH_TO_STRK_RATE.set_lossy(rate);
+            return Ok(rate);
+        }
+    }
+    warn!("All {list_len} URLs in the list failed for timestamp {adjusted_timestamp}");
+    Err(EthToStrkOracleClientError::AllUrlsFailedError(adjusted_timestamp, initial_index))
+}
+
+/// Queries all oracles concurrently, rejects the rates that deviate from their median by more than
+/// `max_deviation_bps`, and returns the median of the rest, as long as at least `min_quorum` rates
+/// remain.
+async fn query_median(
+    client: reqwest::Client,
+    url_header_list: Arc<Vec<UrlAndHeaderMap>>,
+    adjusted_timestamp: u64,
+    query_timeout_sec: u64,
+    max_deviation_bps: u128,
+    min_quorum: usize,
+) -> Result<u128, EthToStrkOracleClientError> {
+    let results = join_all(url_header_list.iter().map(|url_and_headers| {
+        query_source(&client, url_and_headers, adjusted_timestamp, query_timeout_sec)
+    }))
+    .await;
+
+    let mut rates = Vec::with_capacity(results.len());
+    for (index, result) in results.into_iter().enumerate() {
+        match result {
+            Some(rate) => rates.push((index, rate)),
+            None => {
+                if let Some(labels) = eth_to_strk_source_labels(index) {
+                    ETH_TO_STRK_SOURCE_ERROR_COUNT.increment(1, &labels);
+                }
+            }
+        }
+    }
+    if rates.len() < min_quorum {
+        warn!(
+            "Only {} of {} oracles answered for timestamp {adjusted_timestamp}",
+            rates.len(),
+            url_header_list.len()
+        );
+        return Err(EthToStrkOracleClientError::QuorumNotReachedError(
+            adjusted_timestamp,
+            rates.len(),
+            min_quorum,
+        ));
+    }
+
+    let median_of_all = median(rates.iter().map(|(_, rate)| *rate).collect());
+    let mut accepted_rates = Vec::with_capacity(rates.len());
+    for (index, rate) in rates {
+        let deviation = deviation_bps(rate, median_of_all);
+        let labels = eth_to_strk_source_labels(index);
+        if let Some(labels) = labels {
+            ETH_TO_STRK_SOURCE_DEVIATION_BPS.set(deviation.into_f64(), &labels);
+        }
+        if deviation > max_deviation_bps {
+            warn!(
+                "Rejecting rate {rate} of oracle {index} for timestamp {adjusted_timestamp}: \
+                 deviates by {deviation} bps from the median {median_of_all}"
+            );
+            if let Some(labels) = labels {
+                ETH_TO_STRK_SOURCE_OUTLIER_COUNT.increment(1, &labels);
+            }
+            continue;
+        }
+        accepted_rates.push(rate);
+    }
+    if accepted_rates.len() < min_quorum {
+        warn!(
+            "Only {} oracles agreed on the rate for timestamp {adjusted_timestamp}",
+            accepted_rates.len()
+        );
+        return Err(EthToStrkOracleClientError::QuorumNotReachedError(
+            adjusted_timestamp,
+            accepted_rates.len(),
+            min_quorum,
+        ));
+    }
+
+    let rate = median(accepted_rates);
+    debug!("Aggregated the rates of the oracles for timestamp {adjusted_timestamp} to {rate}");
+    ETH_TO_STRK_RATE.set_lossy(rate);
+    Ok(rate)
+}
+
+/// The median of a non-empty list of rates. For an even number of rates, this is the average of the
+/// two middle ones.
+fn median(mut rates: Vec<u128>) -> u128 {
+    rates.sort_unstable();
+    let middle = rates.len() / 2;
+    if rates.len() % 2 == 1 {
+        rates[middle]
+    } else {
+        let (lower, upper) = (rates[middle - 1], rates[middle]);
+        lower + (upper - lower) / 2
+    }
+}
+
+/// The deviation of `rate` from `median`, in basis points of the median.
+fn deviation_bps(rate: u128, median: u128) -> u128 {
+    let difference = rate.abs_diff(median);
+    match median {
+        0 if difference == 0 => 0,
+        0 => u128::MAX,
+        _ => difference.saturating_mul(BASIS_POINTS) / median,
+    }
+}
+
 fn resolve_query(body: String) -> Result<u128, EthToStrkOracleClientError> {
     let Ok(json): Result<serde_json::Value, _> = serde_json::from_str(&body) else {
         return Err(EthToStrkOracleClientError::ParseError(serde_json::Error::custom(format!(
@@ -243,7 +464,6 @@ fn resolve_query(body: String) -> Result<u128, EthToStrkOracleClientError> {
         ));
     }
     ETH_TO_STRK_SUCCESS_COUNT.increment(1);
-    ETH_TO_STRK_RATE.set_lossy(rate);
     Ok(rate)
 }
 
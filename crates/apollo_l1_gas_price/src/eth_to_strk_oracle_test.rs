@@ -2,12 +2,19 @@ use std::collections::BTreeMap;
 
 use apollo_l1_gas_price_types::errors::EthToStrkOracleClientError;
 use apollo_l1_gas_price_types::EthToStrkOracleClientTrait;
+use assert_matches::assert_matches;
 use mockito::{Mock, ServerGuard};
 use serde_json::json;
 use tokio::{self};
 use url::Url;
+use validator::Validate;
 
-use crate::eth_to_strk_oracle::{EthToStrkOracleClient, EthToStrkOracleConfig, UrlAndHeaders};
+use crate::eth_to_strk_oracle::{
+    EthToStrkOracleAggregationMode,
+    EthToStrkOracleClient,
+    EthToStrkOracleConfig,
+    UrlAndHeaders,
+};
 
 async fn make_server(server: &mut ServerGuard, body: serde_json::Value) -> Mock {
     server
@@ -17,6 +24,39 @@ async fn make_server(server: &mut ServerGuard, body: serde_json::Value) -> Mock
         .create()
 }
 
+fn url_and_headers(server: &ServerGuard) -> UrlAndHeaders {
+    UrlAndHeaders {
+        url: Url::parse(&server.url()).unwrap(),
+        headers: BTreeMap::new(), // No additional headers needed for these tests.
+    }
+}
+
+fn median_client(servers: &[ServerGuard], min_quorum: Option<usize>) -> EthToStrkOracleClient {
+    let config = EthToStrkOracleConfig {
+        url_header_list: Some(servers.iter().map(url_and_headers).collect()),
+        lag_interval_seconds: 60,
+        aggregation_mode: EthToStrkOracleAggregationMode::Median,
+        max_deviation_bps: 500,
+        min_quorum,
+        ..Default::default()
+    };
+    EthToStrkOracleClient::new(config)
+}
+
+// Polls the client until the query for the timestamp resolves.
+async fn resolved_rate(
+    client: &EthToStrkOracleClient,
+    timestamp: u64,
+) -> Result<u128, EthToStrkOracleClientError> {
+    loop {
+        match client.eth_to_fri_rate(timestamp).await {
+            Err(EthToStrkOracleClientError::QueryNotReadyError(_)) => {}
+            result => return result,
+        }
+        tokio::task::yield_now().await; // Don't block the executor.
+    }
+}
+
 #[tokio::test]
 async fn eth_to_fri_rate_uses_cache_on_quantized_hit() {
     let expected_rate = 123456;
@@ -121,3 +161,50 @@ async fn eth_to_fri_rate_two_urls() {
         tokio::task::yield_now().await; // Don't block the executor.
     }
 }
+
+#[tokio::test]
+async fn eth_to_fri_rate_median_rejects_outliers() {
+    let rates: [u128; 3] = [100_000, 101_000, 200_000];
+    let mut servers = Vec::new();
+    // The mocks must be kept alive for the servers to answer.
+    let mut mocks = Vec::new();
+    for rate in rates {
+        let mut server = mockito::Server::new_async().await;
+        mocks.push(
+            make_server(&mut server, json!({"price": format!("0x{rate:x}"), "decimals": 18})).await,
+        );
+        servers.push(server);
+    }
+    let client = median_client(&servers, Some(2));
+
+    // The last rate deviates from the median of all rates by far more than 5%, so the result is
+    // the median of the first two.
+    assert_eq!(resolved_rate(&client, 1234567890).await.unwrap(), 100_500);
+}
+
+#[tokio::test]
+async fn eth_to_fri_rate_median_requires_quorum() {
+    let mut good_server = mockito::Server::new_async().await;
+    let mut bad_server = mockito::Server::new_async().await;
+    let _m1 = make_server(&mut good_server, json!({"price": "0x1e240", "decimals": 18})).await;
+    let _m2 = make_server(&mut bad_server, json!({"foo": "0x0", "bar": 18})).await;
+    // By default, the quorum is a majority of the oracles.
+    let client = median_client(&[good_server, bad_server], None);
+
+    assert_matches!(
+        resolved_rate(&client, 1234567890).await,
+        Err(EthToStrkOracleClientError::QuorumNotReachedError(_, 1, 2))
+    );
+}
+
+#[test]
+fn min_quorum_must_not_exceed_the_number_of_oracles() {
+    let config = EthToStrkOracleConfig { min_quorum: Some(1), ..Default::default() };
+    config.validate().unwrap();
+
+    let config = EthToStrkOracleConfig { min_quorum: Some(2), ..Default::default() };
+    config.validate().unwrap_err();
+
+    let config = EthToStrkOracleConfig { min_quorum: Some(0), ..Default::default() };
+    config.validate().unwrap_err();
+}
@@ -1,4 +1,6 @@
-use apollo_metrics::define_metrics;
+use apollo_metrics::{define_metrics, generate_permutation_labels};
+use strum::{EnumVariantNames, IntoEnumIterator, VariantNames};
+use strum_macros::{EnumIter, IntoStaticStr};
 
 define_metrics!(
     L1GasPrice => {
@@ -8,13 +10,52 @@ define_metrics!(
         MetricCounter { L1_GAS_PRICE_SCRAPER_REORG_DETECTED, "l1_gas_price_scraper_reorg_detected", "Number of times the L1 gas price scraper detected a reorganization in the base layer", init=0 },
         MetricCounter { ETH_TO_STRK_ERROR_COUNT, "eth_to_strk_error_count", "Number of times the query to the Eth to Strk oracle failed due to an error or timeout", init=0 },
         MetricCounter { ETH_TO_STRK_SUCCESS_COUNT, "eth_to_strk_success_count", "Number of times the query to the Eth to Strk oracle succeeded", init=0 },
+        LabeledMetricCounter { ETH_TO_STRK_SOURCE_ERROR_COUNT, "eth_to_strk_source_error_count", "Number of times the query to an Eth to Strk oracle source failed due to an error or timeout, in median aggregation", init=0, labels = ETH_TO_STRK_SOURCE_LABELS },
+        LabeledMetricCounter { ETH_TO_STRK_SOURCE_OUTLIER_COUNT, "eth_to_strk_source_outlier_count", "Number of times the rate of an Eth to Strk oracle source was rejected as an outlier", init=0, labels = ETH_TO_STRK_SOURCE_LABELS },
         MetricGauge { L1_GAS_PRICE_SCRAPER_LATEST_SCRAPED_BLOCK, "l1_gas_price_scraper_latest_scraped_block", "The latest block number that the L1 gas price scraper has scraped" },
         MetricGauge { ETH_TO_STRK_RATE, "eth_to_strk_rate", "The current rate of ETH to STRK conversion" },
+        LabeledMetricGauge { ETH_TO_STRK_SOURCE_DEVIATION_BPS, "eth_to_strk_source_deviation_bps", "The deviation of the rate of an Eth to Strk oracle source from the median of all sources, in basis points", labels = ETH_TO_STRK_SOURCE_LABELS },
         MetricGauge { L1_GAS_PRICE_LATEST_MEAN_VALUE, "l1_gas_price_latest_mean_value", "The latest L1 gas price, calculated as an average by the provider client" },
         MetricGauge { L1_DATA_GAS_PRICE_LATEST_MEAN_VALUE, "l1_data_gas_price_latest_mean_value", "The latest L1 data gas price, calculated as an average by the provider client" }
     }
 );
 
+pub const LABEL_NAME_ETH_TO_STRK_SOURCE: &str = "source";
+
+/// The index of an oracle in `url_header_list`. The URLs themselves are private, so they can't be
+/// used as labels. Sources past the last index aren't tracked per source.
+#[derive(IntoStaticStr, EnumIter, EnumVariantNames)]
+pub(crate) enum EthToStrkSource {
+    #[strum(serialize = "0")]
+    Source0,
+    #[strum(serialize = "1")]
+    Source1,
+    #[strum(serialize = "2")]
+    Source2,
+    #[strum(serialize = "3")]
+    Source3,
+    #[strum(serialize = "4")]
+    Source4,
+    #[strum(serialize = "5")]
+    Source5,
+    #[strum(serialize = "6")]
+    Source6,
+    #[strum(serialize = "7")]
+    Source7,
+}
+
+generate_permutation_labels! {
+    ETH_TO_STRK_SOURCE_LABELS,
+    (LABEL_NAME_ETH_TO_STRK_SOURCE, EthToStrkSource),
+}
+
+/// Returns the labels of the oracle at the given index of `url_header_list`, if it's tracked.
+pub(crate) fn eth_to_strk_source_labels(index: usize) -> Option<[(&'static str, &'static str); 1]> {
+    EthToStrkSource::iter()
+        .nth(index)
+        .map(|source| [(LABEL_NAME_ETH_TO_STRK_SOURCE, source.into())])
+}
+
 pub(crate) fn register_provider_metrics() {
     L1_GAS_PRICE_PROVIDER_INSUFFICIENT_HISTORY.register();
     L1_GAS_PRICE_LATEST_MEAN_VALUE.register();
@@ -32,4 +73,7 @@ pub(crate) fn register_eth_to_strk_metrics() {
     ETH_TO_STRK_ERROR_COUNT.register();
     ETH_TO_STRK_SUCCESS_COUNT.register();
     ETH_TO_STRK_RATE.register();
+    ETH_TO_STRK_SOURCE_ERROR_COUNT.register();
+    ETH_TO_STRK_SOURCE_OUTLIER_COUNT.register();
+    ETH_TO_STRK_SOURCE_DEVIATION_BPS.register();
 }
@@ -43,4 +43,6 @@ pub enum EthToStrkOracleClientError {
     QueryNotReadyError(u64),
     #[error("All URLs in the list failed for timestamp {0}, starting with index {1}")]
     AllUrlsFailedError(u64, usize),
+    #[error("Only {1} oracles agreed on the rate for timestamp {0}, below the quorum of {2}")]
+    QuorumNotReachedError(u64, usize, usize),
 }
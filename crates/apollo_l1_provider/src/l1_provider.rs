@@ -204,6 +204,51 @@ impl L1Provider {
         Ok(())
     }
 
+    /// Rolls back events that were scraped from L1 blocks which were since reorged out of L1.
+    /// Fails without applying any of the events if one of the transactions is already committed,
+    /// since that can't be rolled back by the provider.
+    #[instrument(skip_all, err)]
+    pub fn revert_events(&mut self, events: Vec<Event>) -> L1ProviderResult<()> {
+        if self.state.uninitialized() {
+            return Err(L1ProviderError::Uninitialized);
+        }
+
+        info!("Reverting {} l1 events", events.len());
+        trace!("Reverting events: {events:?}");
+
+        for event in &events {
+            match event {
+                Event::L1HandlerTransaction { l1_handler_tx, .. } => {
+                    let tx_hash = l1_handler_tx.tx_hash;
+                    if self.tx_manager.is_committed(tx_hash) {
+                        return Err(L1ProviderError::RevertedCommittedTransaction(tx_hash));
+                    }
+                }
+                Event::TransactionCancellationStarted { .. } => {}
+                _ => return Err(L1ProviderError::unsupported_l1_event(event.clone())),
+            }
+        }
+
+        for event in events {
+            match event {
+                Event::L1HandlerTransaction { l1_handler_tx, .. } => {
+                    let tx_hash = l1_handler_tx.tx_hash;
+                    if !self.tx_manager.revert_tx(tx_hash) {
+                        debug!("Reverted L1 Handler transaction with hash: {tx_hash} is unknown.");
+                    }
+                }
+                Event::TransactionCancellationStarted { tx_hash, .. } => {
+                    // The transaction may have been reverted by a preceding event.
+                    if self.tx_manager.exists(tx_hash) {
+                        self.tx_manager.revert_cancellation_request(tx_hash);
+                    }
+                }
+                _ => unreachable!("Unsupported events are rejected above."),
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_l1_provider_snapshot(&self) -> L1ProviderResult<L1ProviderSnapshot> {
         let txs_snapshot = self.tx_manager.snapshot();
         Ok(L1ProviderSnapshot {
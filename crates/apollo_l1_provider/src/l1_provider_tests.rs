@@ -947,6 +947,61 @@ fn add_events_double_cancellation_only_first_counted() {
     expected.assert_eq(&l1_provider);
 }
 
+#[test]
+fn revert_events_removes_uncommitted_txs_and_cancellations() {
+    // Setup.
+    let [tx1, tx2] = [l1_handler(1), l1_handler(2)];
+    let arbitrary_cancellation_timestamp = 1;
+    let mut l1_provider =
+        L1ProviderContentBuilder::new().with_state(ProviderState::Propose).build_into_l1_provider();
+    l1_provider
+        .add_events(vec![
+            l1_handler_event(tx1.tx_hash),
+            l1_handler_event(tx2.tx_hash),
+            cancellation_event(tx2.tx_hash, arbitrary_cancellation_timestamp.into()),
+        ])
+        .unwrap();
+
+    // Test.
+    l1_provider
+        .revert_events(vec![
+            l1_handler_event(tx1.tx_hash),
+            cancellation_event(tx2.tx_hash, arbitrary_cancellation_timestamp.into()),
+        ])
+        .unwrap();
+
+    let expected = L1ProviderContentBuilder::new()
+        .with_txs([tx2.clone()])
+        .with_timed_cancel_requested_txs([])
+        .build();
+    expected.assert_eq(&l1_provider);
+    // The reverted transaction is no longer proposable, and the cancelled one is again.
+    assert_eq!(l1_provider.get_txs(2, l1_provider.current_height).unwrap(), [tx2]);
+}
+
+#[test]
+fn revert_events_committed_tx_fails_without_reverting() {
+    // Setup.
+    let [committed_tx, uncommitted_tx] = [l1_handler(1), l1_handler(2)];
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs([uncommitted_tx.clone()])
+        .with_committed([committed_tx.clone()])
+        .build_into_l1_provider();
+
+    // Test.
+    let result = l1_provider.revert_events(vec![
+        l1_handler_event(uncommitted_tx.tx_hash),
+        l1_handler_event(committed_tx.tx_hash),
+    ]);
+
+    assert_eq!(result, Err(L1ProviderError::RevertedCommittedTransaction(committed_tx.tx_hash)));
+    let expected = L1ProviderContentBuilder::new()
+        .with_txs([uncommitted_tx])
+        .with_committed([committed_tx])
+        .build();
+    expected.assert_eq(&l1_provider);
+}
+
 #[test]
 fn validate_tx_unknown_returns_invalid_consumed_or_unknown() {
     let mut l1_provider = L1ProviderContentBuilder::new()
@@ -1,5 +1,5 @@
 use std::any::type_name;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::time::Duration;
 
 use apollo_config::converters::deserialize_float_seconds_to_duration;
@@ -27,6 +27,7 @@ use crate::metrics::{
     register_scraper_metrics,
     L1_MESSAGE_SCRAPER_BASELAYER_ERROR_COUNT,
     L1_MESSAGE_SCRAPER_REORG_DETECTED,
+    L1_MESSAGE_SCRAPER_REORG_RECOVERED,
     L1_MESSAGE_SCRAPER_SUCCESS_COUNT,
 };
 
@@ -45,6 +46,16 @@ pub struct L1Scraper<B: BaseLayerContract> {
     pub last_l1_block_processed: L1BlockReference,
     pub l1_provider_client: SharedL1ProviderClient,
     tracked_event_identifiers: Vec<EventIdentifier>,
+    /// The recently scraped L1 block ranges, oldest first, used to find the last common ancestor
+    /// with L1 after a reorg.
+    scraped_ranges: VecDeque<ScrapedRange>,
+}
+
+/// The events scraped from a range of L1 blocks, which ends at `last_l1_block`.
+#[derive(Clone, Debug)]
+struct ScrapedRange {
+    last_l1_block: L1BlockReference,
+    events: Vec<Event>,
 }
 
 impl<B: BaseLayerContract + Send + Sync> L1Scraper<B> {
@@ -61,6 +72,10 @@ impl<B: BaseLayerContract + Send + Sync> L1Scraper<B> {
             last_l1_block_processed: l1_start_block,
             config,
             tracked_event_identifiers: events_identifiers_to_track.to_vec(),
+            scraped_ranges: VecDeque::from([ScrapedRange {
+                last_l1_block: l1_start_block,
+                events: vec![],
+            }]),
         })
     }
 
@@ -69,16 +84,19 @@ impl<B: BaseLayerContract + Send + Sync> L1Scraper<B> {
         let (latest_l1_block, events) = self.fetch_events().await?;
 
         // If this gets too high, send in batches.
-        let initialize_result = self.l1_provider_client.initialize(events).await;
+        let initialize_result = self.l1_provider_client.initialize(events.clone()).await;
         handle_client_error(initialize_result)?;
 
-        self.last_l1_block_processed = latest_l1_block;
+        self.record_scraped_range(latest_l1_block, events);
 
         Ok(())
     }
 
     pub async fn send_events_to_l1_provider(&mut self) -> L1ScraperResult<(), B> {
-        self.assert_no_l1_reorgs().await?;
+        if let Some(reorg_reason) = self.detect_l1_reorg().await? {
+            // Recovery re-scrapes the new fork up to the latest L1 block.
+            return self.recover_from_l1_reorg(reorg_reason).await;
+        }
 
         let (latest_l1_block, events) = self.fetch_events().await?;
         trace!("scraped up to {latest_l1_block:?}");
@@ -87,14 +105,31 @@ impl<B: BaseLayerContract + Send + Sync> L1Scraper<B> {
         // Sending even if there are no events, to keep the flow as simple/debuggable as possible.
         // Perf hit is minimal, since the scraper is on the same machine as the provider (no net).
         // If this gets spammy, short-circuit on events.empty().
-        let add_events_result = self.l1_provider_client.add_events(events).await;
+        let add_events_result = self.l1_provider_client.add_events(events.clone()).await;
         handle_client_error(add_events_result)?;
 
-        self.last_l1_block_processed = latest_l1_block;
+        self.record_scraped_range(latest_l1_block, events);
 
         Ok(())
     }
 
+    fn record_scraped_range(&mut self, latest_l1_block: L1BlockReference, events: Vec<Event>) {
+        self.last_l1_block_processed = latest_l1_block;
+        self.scraped_ranges.push_back(ScrapedRange { last_l1_block: latest_l1_block, events });
+
+        // Keep the newest range that is at least `max_l1_reorg_depth` blocks deep, so that a common
+        // ancestor can be found for any reorg up to that depth.
+        let max_reorg_depth_start =
+            latest_l1_block.number.saturating_sub(self.config.max_l1_reorg_depth);
+        while self
+            .scraped_ranges
+            .get(1)
+            .is_some_and(|range| range.last_l1_block.number <= max_reorg_depth_start)
+        {
+            self.scraped_ranges.pop_front();
+        }
+    }
+
     async fn fetch_events(&self) -> L1ScraperResult<(L1BlockReference, Vec<Event>), B> {
         let latest_l1_block = self
             .base_layer
@@ -185,7 +220,8 @@ impl<B: BaseLayerContract + Send + Sync> L1Scraper<B> {
         }
     }
 
-    async fn assert_no_l1_reorgs(&self) -> L1ScraperResult<(), B> {
+    /// Returns the reason the last processed L1 block is no longer part of L1, if it isn't.
+    async fn detect_l1_reorg(&self) -> L1ScraperResult<Option<String>, B> {
         let last_processed_l1_block_number = self.last_l1_block_processed.number;
         let last_block_processed_fresh = self
             .base_layer
@@ -194,28 +230,72 @@ impl<B: BaseLayerContract + Send + Sync> L1Scraper<B> {
             .map_err(L1ScraperError::BaseLayerError)?;
 
         let Some(last_block_processed_fresh) = last_block_processed_fresh else {
-            L1_MESSAGE_SCRAPER_REORG_DETECTED.increment(1);
-            return Err(L1ScraperError::L1ReorgDetected {
-                reason: format!(
-                    "Last processed L1 block with number {last_processed_l1_block_number} no \
-                     longer exists."
-                ),
-            });
+            return Ok(Some(format!(
+                "Last processed L1 block with number {last_processed_l1_block_number} no longer \
+                 exists"
+            )));
         };
 
         if last_block_processed_fresh.hash != self.last_l1_block_processed.hash {
-            L1_MESSAGE_SCRAPER_REORG_DETECTED.increment(1);
-            return Err(L1ScraperError::L1ReorgDetected {
-                reason: format!(
-                    "Last processed L1 block hash, {}, for block number {}, is different from the \
-                     hash stored, {}",
-                    hex::encode(last_block_processed_fresh.hash),
-                    last_processed_l1_block_number,
-                    hex::encode(self.last_l1_block_processed.hash),
-                ),
-            });
+            return Ok(Some(format!(
+                "Last processed L1 block hash, {}, for block number {}, is different from the \
+                 hash stored, {}",
+                hex::encode(last_block_processed_fresh.hash),
+                last_processed_l1_block_number,
+                hex::encode(self.last_l1_block_processed.hash),
+            )));
         }
 
+        Ok(None)
+    }
+
+    /// Rewinds to the last common ancestor with L1, and rolls back the events of the orphaned
+    /// blocks in the provider, except for those that were also included in the new fork.
+    async fn recover_from_l1_reorg(&mut self, reason: String) -> L1ScraperResult<(), B> {
+        L1_MESSAGE_SCRAPER_REORG_DETECTED.increment(1);
+        warn!("L1 reorg detected: {reason}. Rewinding to the last common ancestor.");
+
+        let mut orphaned_events = Vec::new();
+        let common_ancestor = loop {
+            let Some(range) = self.scraped_ranges.pop_back() else {
+                return Err(L1ScraperError::L1ReorgDetected {
+                    reason: format!(
+                        "{reason}; no common ancestor was found within the last {} L1 blocks",
+                        self.config.max_l1_reorg_depth
+                    ),
+                });
+            };
+
+            let fresh_block = self
+                .base_layer
+                .l1_block_at(range.last_l1_block.number)
+                .await
+                .map_err(L1ScraperError::BaseLayerError)?;
+            if fresh_block.is_some_and(|block| block.hash == range.last_l1_block.hash) {
+                let common_ancestor = range.last_l1_block;
+                self.scraped_ranges.push_back(range);
+                break common_ancestor;
+            }
+            orphaned_events.splice(0..0, range.events);
+        };
+        info!("Rewinding to the last common ancestor with L1: {common_ancestor:?}.");
+        self.last_l1_block_processed = common_ancestor;
+
+        // Events that are also in the new fork don't need to be rolled back, since they would only
+        // be added right back.
+        let (latest_l1_block, events) = self.fetch_events().await?;
+        orphaned_events.retain(|event| !events.contains(event));
+
+        debug!("Reverting orphaned events: {orphaned_events:?}");
+        let revert_events_result = self.l1_provider_client.revert_events(orphaned_events).await;
+        handle_client_error(revert_events_result)?;
+
+        let add_events_result = self.l1_provider_client.add_events(events.clone()).await;
+        handle_client_error(add_events_result)?;
+
+        self.record_scraped_range(latest_l1_block, events);
+        L1_MESSAGE_SCRAPER_REORG_RECOVERED.increment(1);
+
         Ok(())
     }
 }
@@ -271,6 +351,7 @@ pub struct L1ScraperConfig {
     pub finality: u64,
     #[serde(deserialize_with = "deserialize_float_seconds_to_duration")]
     pub polling_interval_seconds: Duration,
+    pub max_l1_reorg_depth: u64,
 }
 
 impl Default for L1ScraperConfig {
@@ -280,6 +361,7 @@ impl Default for L1ScraperConfig {
             chain_id: ChainId::Mainnet,
             finality: 0,
             polling_interval_seconds: Duration::from_secs(120),
+            max_l1_reorg_depth: 64,
         }
     }
 }
@@ -305,6 +387,12 @@ impl SerializeConfig for L1ScraperConfig {
                 "Interval in Seconds between each scraping attempt of L1.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_l1_reorg_depth",
+                &self.max_l1_reorg_depth,
+                "Maximal L1 reorg depth, in blocks, that the scraper recovers from by itself.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "chain_id",
                 &self.chain_id,
@@ -326,6 +414,7 @@ pub enum L1ScraperError<T: BaseLayerContract + Send + Sync> {
     // Leaky abstraction, these errors should not propagate here.
     #[error(transparent)]
     NetworkError(ClientError),
+    // Raised only when the scraper can't recover from the reorg by itself.
     #[error("L1 reorg detected: {reason}. Restart both the L1 provider and the scraper.")]
     L1ReorgDetected { reason: String },
     // This is likely due to a provider crash, which is now waiting for the restart sequence from
@@ -379,6 +468,9 @@ fn handle_client_error<B: BaseLayerContract + Send + Sync>(
         L1ProviderClientError::L1ProviderError(L1ProviderError::Uninitialized) => {
             Err(L1ScraperError::NeedsRestart)
         }
+        L1ProviderClientError::L1ProviderError(
+            error @ L1ProviderError::RevertedCommittedTransaction(_),
+        ) => Err(L1ScraperError::L1ReorgDetected { reason: error.to_string() }),
         L1ProviderClientError::L1ProviderError(L1ProviderError::UnsupportedL1Event(event)) => {
             panic!(
                 "Scraper-->Provider consistency error: the event {event} is not supported by the \
@@ -5,18 +5,27 @@ use std::time::Duration;
 use apollo_batcher_types::batcher_types::GetHeightResponse;
 use apollo_batcher_types::communication::MockBatcherClient;
 use apollo_infra::trace_util::configure_tracing;
-use apollo_l1_provider_types::errors::L1ProviderError;
-use apollo_l1_provider_types::{L1ProviderClient, MockL1ProviderClient};
+use apollo_l1_provider_types::errors::{L1ProviderClientError, L1ProviderError};
+use apollo_l1_provider_types::{Event, L1ProviderClient, MockL1ProviderClient};
 use apollo_state_sync_types::communication::MockStateSyncClient;
 use apollo_state_sync_types::errors::StateSyncError;
 use apollo_state_sync_types::state_sync_types::SyncBlock;
 use assert_matches::assert_matches;
 use indexmap::IndexSet;
 use itertools::Itertools;
-use papyrus_base_layer::{L1BlockReference, MockBaseLayerContract};
+use mockall::Sequence;
+use papyrus_base_layer::{
+    L1BlockHash,
+    L1BlockNumber,
+    L1BlockReference,
+    L1Event,
+    MockBaseLayerContract,
+};
 use rstest::{fixture, rstest};
-use starknet_api::block::BlockNumber;
-use starknet_api::transaction::TransactionHash;
+use starknet_api::block::{BlockNumber, BlockTimestamp};
+use starknet_api::nonce;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{L1HandlerTransaction, TransactionHash};
 
 use crate::bootstrapper::Bootstrapper;
 use crate::l1_provider::{L1Provider, L1ProviderBuilder};
@@ -490,6 +499,148 @@ async fn l1_reorg_block_number(mut dummy_base_layer: MockBaseLayerContract) {
     );
 }
 
+/// An L1 chain for the mocked base layer, which can be forked by the test.
+struct FakeL1Chain {
+    block_hashes: HashMap<L1BlockNumber, L1BlockHash>,
+    latest: L1BlockNumber,
+    // The events returned by the next scrape.
+    events: Vec<L1Event>,
+}
+
+fn base_layer_following(chain: Arc<Mutex<FakeL1Chain>>) -> MockBaseLayerContract {
+    let mut base_layer = MockBaseLayerContract::new();
+    let chain_clone = chain.clone();
+    base_layer.expect_l1_block_at().returning(move |number| {
+        let chain = chain_clone.lock().unwrap();
+        Ok(chain.block_hashes.get(&number).map(|&hash| L1BlockReference { number, hash }))
+    });
+    let chain_clone = chain.clone();
+    base_layer.expect_latest_l1_block().returning(move |_| {
+        let chain = chain_clone.lock().unwrap();
+        Ok(Some(L1BlockReference { number: chain.latest, hash: chain.block_hashes[&chain.latest] }))
+    });
+    base_layer.expect_events().returning(move |_, _| Ok(chain.lock().unwrap().events.clone()));
+    base_layer
+}
+
+fn message_to_l2(nonce: u8) -> L1Event {
+    L1Event::LogMessageToL2 {
+        tx: L1HandlerTransaction { nonce: nonce!(nonce), ..Default::default() },
+        fee: Fee(1),
+        l1_tx_hash: None,
+        timestamp: BlockTimestamp(0),
+    }
+}
+
+fn provider_events(l1_events: &[L1Event]) -> Vec<Event> {
+    let chain_id = L1ScraperConfig::default().chain_id;
+    l1_events.iter().map(|event| Event::from_l1_event(&chain_id, event.clone()).unwrap()).collect()
+}
+
+/// Scrapes blocks 1 and 2 in a single range, and then forks L1 from block 2.
+async fn scrape_and_fork_l1(
+    l1_provider_client: MockL1ProviderClient,
+    chain: Arc<Mutex<FakeL1Chain>>,
+    new_fork_events: Vec<L1Event>,
+) -> L1Scraper<MockBaseLayerContract> {
+    let mut scraper = L1Scraper::new(
+        L1ScraperConfig::default(),
+        Arc::new(l1_provider_client),
+        base_layer_following(chain.clone()),
+        event_identifiers_to_track(),
+        L1BlockReference { number: 0, hash: [0; 32] },
+    )
+    .await
+    .unwrap();
+    assert_eq!(scraper.send_events_to_l1_provider().await, Ok(()));
+
+    let mut chain = chain.lock().unwrap();
+    chain.block_hashes.extend([(2, [22; 32]), (3, [3; 32])]);
+    chain.latest = 3;
+    chain.events = new_fork_events;
+    scraper
+}
+
+#[tokio::test]
+async fn l1_reorg_recovers_from_common_ancestor() {
+    // Setup.
+    let [message_in_both_forks, orphaned_message, new_fork_message] = [1, 2, 3].map(message_to_l2);
+    let chain = Arc::new(Mutex::new(FakeL1Chain {
+        block_hashes: HashMap::from([(0, [0; 32]), (1, [1; 32]), (2, [2; 32])]),
+        latest: 2,
+        events: vec![message_in_both_forks.clone(), orphaned_message.clone()],
+    }));
+
+    let mut l1_provider_client = MockL1ProviderClient::default();
+    let mut sequence = Sequence::new();
+    let scraped_events =
+        provider_events(&[message_in_both_forks.clone(), orphaned_message.clone()]);
+    l1_provider_client
+        .expect_add_events()
+        .once()
+        .in_sequence(&mut sequence)
+        .withf(move |events| events == &scraped_events)
+        .returning(|_| Ok(()));
+    // Only the events that aren't in the new fork are reverted.
+    let orphaned_events = provider_events(&[orphaned_message]);
+    l1_provider_client
+        .expect_revert_events()
+        .once()
+        .in_sequence(&mut sequence)
+        .withf(move |events| events == &orphaned_events)
+        .returning(|_| Ok(()));
+    let new_fork_events =
+        provider_events(&[message_in_both_forks.clone(), new_fork_message.clone()]);
+    l1_provider_client
+        .expect_add_events()
+        .once()
+        .in_sequence(&mut sequence)
+        .withf(move |events| events == &new_fork_events)
+        .returning(|_| Ok(()));
+
+    let mut scraper = scrape_and_fork_l1(
+        l1_provider_client,
+        chain,
+        vec![message_in_both_forks, new_fork_message],
+    )
+    .await;
+
+    // Test.
+    assert_eq!(scraper.send_events_to_l1_provider().await, Ok(()));
+    assert_eq!(scraper.last_l1_block_processed, L1BlockReference { number: 3, hash: [3; 32] });
+}
+
+#[tokio::test]
+async fn l1_reorg_of_committed_transaction_escalates() {
+    // Setup.
+    let committed_message = message_to_l2(1);
+    let chain = Arc::new(Mutex::new(FakeL1Chain {
+        block_hashes: HashMap::from([(0, [0; 32]), (1, [1; 32]), (2, [2; 32])]),
+        latest: 2,
+        events: vec![committed_message.clone()],
+    }));
+
+    let mut l1_provider_client = MockL1ProviderClient::default();
+    l1_provider_client.expect_add_events().once().returning(|_| Ok(()));
+    let committed_tx_hash = match provider_events(&[committed_message]).remove(0) {
+        Event::L1HandlerTransaction { l1_handler_tx, .. } => l1_handler_tx.tx_hash,
+        event => panic!("Unexpected event: {event}"),
+    };
+    l1_provider_client.expect_revert_events().once().returning(move |_| {
+        Err(L1ProviderClientError::L1ProviderError(L1ProviderError::RevertedCommittedTransaction(
+            committed_tx_hash,
+        )))
+    });
+
+    let mut scraper = scrape_and_fork_l1(l1_provider_client, chain, vec![]).await;
+
+    // Test.
+    assert_matches!(
+        scraper.send_events_to_l1_provider().await,
+        Err(L1ScraperError::L1ReorgDetected { .. })
+    );
+}
+
 #[test]
 #[ignore = "similar to backlog_happy_flow, only shorter, and sprinkle some start_block/get_txs \
             attempts while its bootstrapping (and assert failure on height), then assert that they \
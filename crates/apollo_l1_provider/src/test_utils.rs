@@ -533,6 +533,10 @@ impl L1ProviderClient for FakeL1ProviderClient {
         todo!()
     }
 
+    async fn revert_events(&self, _events: Vec<Event>) -> L1ProviderClientResult<()> {
+        todo!()
+    }
+
     async fn get_l1_provider_snapshot(&self) -> L1ProviderClientResult<L1ProviderSnapshot> {
         todo!()
     }
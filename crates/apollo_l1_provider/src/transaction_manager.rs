@@ -166,6 +166,32 @@ impl TransactionManager {
         )
     }
 
+    /// Removes an uncommitted transaction, for example if the L1 block it was scraped from was
+    /// reorged out. Returns false if the transaction is unknown.
+    pub fn revert_tx(&mut self, tx_hash: TransactionHash) -> bool {
+        assert!(
+            !self.is_committed(tx_hash),
+            "Attempted to revert the committed L1 handler transaction {tx_hash}."
+        );
+        let Some(record) = self.records.remove(tx_hash) else {
+            return false;
+        };
+
+        if let TransactionPayload::Full { created_at_block_timestamp, .. } = record.tx {
+            self.remove_from_index(created_at_block_timestamp, tx_hash);
+        }
+        true
+    }
+
+    pub fn revert_cancellation_request(&mut self, tx_hash: TransactionHash) {
+        self.with_record(tx_hash, |r| r.revert_cancellation_request()).unwrap_or_else(|| {
+            panic!(
+                "Should not be possible to revert cancellation for non-existent transaction \
+                 {tx_hash}"
+            )
+        })
+    }
+
     pub fn is_committed(&self, tx_hash: TransactionHash) -> bool {
         self.records.get(&tx_hash).is_some_and(|record| record.is_committed())
     }
@@ -247,20 +273,24 @@ impl TransactionManager {
                     tx_hashes.push(tx_hash);
                 }
             } else {
-                // Remove from the vec for this timestamp, and drop the entry if it becomes empty.
-                match self.proposable_index.entry(created_at) {
-                    Entry::Occupied(mut entry) => {
-                        let tx_hashes = entry.get_mut();
-                        if let Some(index_in_vec) = tx_hashes.iter().position(|&h| h == tx_hash) {
-                            tx_hashes.remove(index_in_vec);
-                            if tx_hashes.is_empty() {
-                                entry.remove();
-                            }
-                        }
+                self.remove_from_index(created_at, tx_hash);
+            }
+        }
+    }
+
+    // Remove from the vec for this timestamp, and drop the entry if it becomes empty.
+    fn remove_from_index(&mut self, created_at: BlockTimestamp, tx_hash: TransactionHash) {
+        match self.proposable_index.entry(created_at) {
+            Entry::Occupied(mut entry) => {
+                let tx_hashes = entry.get_mut();
+                if let Some(index_in_vec) = tx_hashes.iter().position(|&h| h == tx_hash) {
+                    tx_hashes.remove(index_in_vec);
+                    if tx_hashes.is_empty() {
+                        entry.remove();
                     }
-                    Entry::Vacant(_) => {}
                 }
             }
+            Entry::Vacant(_) => {}
         }
     }
 
@@ -99,6 +99,20 @@ impl TransactionRecord {
         }
     }
 
+    /// Drop the cancellation request of this transaction, for example if the L1 block it was
+    /// requested in was reorged out.
+    pub fn revert_cancellation_request(&mut self) {
+        if self.cancellation_requested_at.take().is_none() {
+            return;
+        }
+
+        if !self.is_committed() {
+            info!("Reverting cancellation of L1 handler transaction {}.", self.tx.tx_hash());
+            self.state =
+                if self.rejected { TransactionState::Rejected } else { TransactionState::Pending };
+        }
+    }
+
     /// Try to stage an l1 handler transaction, which means that we allow to include it in the
     /// current proposed or validated block. If already included in a block, this test will return
     /// false, thus preventing double-inclusion in the block. Staging is reset at the start of every
@@ -218,6 +232,10 @@ impl Records {
         self.0.get_mut(&hash)
     }
 
+    pub fn remove(&mut self, hash: TransactionHash) -> Option<TransactionRecord> {
+        self.0.shift_remove(&hash)
+    }
+
     pub fn insert(&mut self, hash: TransactionHash, record: TransactionRecord) -> bool {
         match self.0.entry(hash) {
             Entry::Occupied(_) => false,
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use apollo_infra::component_client::ClientError;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
+use starknet_api::transaction::TransactionHash;
 use thiserror::Error;
 
 use crate::Event;
@@ -24,6 +25,10 @@ pub enum L1ProviderError {
     Uninitialized,
     #[error("Unexpected height: expected {expected_height}, got {got}")]
     UnexpectedHeight { expected_height: BlockNumber, got: BlockNumber },
+    // Committed L2 blocks can't be reverted by the provider, so this requires manual
+    // intervention.
+    #[error("L1 reorg orphaned the L1 handler transaction {0}, which is already committed on L2")]
+    RevertedCommittedTransaction(TransactionHash),
     #[error("Cannot transition from {from} to {to}")]
     UnexpectedProviderStateTransition { from: String, to: String },
     #[error("L1 event not supported: {0}")]
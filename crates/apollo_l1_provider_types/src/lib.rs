@@ -63,6 +63,7 @@ pub enum L1ProviderRequest {
         height: BlockNumber,
     },
     Initialize(Vec<Event>),
+    RevertEvents(Vec<Event>),
     StartBlock {
         state: SessionState,
         height: BlockNumber,
@@ -81,6 +82,7 @@ pub enum L1ProviderResponse {
     CommitBlock(L1ProviderResult<()>),
     GetTransactions(L1ProviderResult<Vec<L1HandlerTransaction>>),
     Initialize(L1ProviderResult<()>),
+    RevertEvents(L1ProviderResult<()>),
     StartBlock(L1ProviderResult<()>),
     Validate(L1ProviderResult<ValidationStatus>),
     GetL1ProviderSnapshot(L1ProviderResult<L1ProviderSnapshot>),
@@ -119,6 +121,8 @@ pub trait L1ProviderClient: Send + Sync {
 
     async fn add_events(&self, events: Vec<Event>) -> L1ProviderClientResult<()>;
     async fn initialize(&self, events: Vec<Event>) -> L1ProviderClientResult<()>;
+    /// Rolls back events that were scraped from L1 blocks which were since reorged out of L1.
+    async fn revert_events(&self, events: Vec<Event>) -> L1ProviderClientResult<()>;
     async fn get_l1_provider_snapshot(&self) -> L1ProviderClientResult<L1ProviderSnapshot>;
 }
 
@@ -214,6 +218,18 @@ where
         )
     }
 
+    #[instrument(skip(self))]
+    async fn revert_events(&self, events: Vec<Event>) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::RevertEvents(events);
+        handle_all_response_variants!(
+            L1ProviderResponse,
+            RevertEvents,
+            L1ProviderClientError,
+            L1ProviderError,
+            Direct
+        )
+    }
+
     async fn get_l1_provider_snapshot(&self) -> L1ProviderClientResult<L1ProviderSnapshot> {
         let request = L1ProviderRequest::GetL1ProviderSnapshot;
         handle_all_response_variants!(
@@ -9,13 +9,19 @@ use apollo_mempool_types::communication::{
     MempoolResponse,
 };
 use apollo_mempool_types::errors::MempoolError;
-use apollo_mempool_types::mempool_types::{CommitBlockArgs, MempoolResult, MempoolSnapshot};
+use apollo_mempool_types::mempool_types::{
+    CommitBlockArgs,
+    MempoolEvents,
+    MempoolResult,
+    MempoolSnapshot,
+};
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
 use apollo_time::time::DefaultClock;
 use async_trait::async_trait;
 use starknet_api::block::GasPrice;
 use starknet_api::core::ContractAddress;
 use starknet_api::rpc_transaction::InternalRpcTransaction;
+use starknet_api::transaction::TransactionHash;
 use tracing::warn;
 
 use crate::config::MempoolConfig;
@@ -110,6 +116,28 @@ impl MempoolCommunicationWrapper {
     fn mempool_snapshot(&self) -> MempoolResult<MempoolSnapshot> {
         self.mempool.mempool_snapshot()
     }
+
+    fn get_tx(&self, tx_hash: TransactionHash) -> MempoolResult<InternalRpcTransaction> {
+        self.mempool.get_tx(tx_hash)
+    }
+
+    fn get_account_txs(
+        &self,
+        account_address: ContractAddress,
+    ) -> MempoolResult<Vec<InternalRpcTransaction>> {
+        Ok(self.mempool.get_account_txs(account_address))
+    }
+
+    fn remove_txs(
+        &mut self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> MempoolResult<Vec<TransactionHash>> {
+        Ok(self.mempool.remove_txs(&tx_hashes))
+    }
+
+    fn get_events(&self, sequence_number: u64) -> MempoolResult<MempoolEvents> {
+        Ok(self.mempool.events_since(sequence_number))
+    }
 }
 
 #[async_trait]
@@ -136,6 +164,18 @@ impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommuni
             MempoolRequest::GetMempoolSnapshot() => {
                 MempoolResponse::GetMempoolSnapshot(self.mempool_snapshot())
             }
+            MempoolRequest::GetTransaction(tx_hash) => {
+                MempoolResponse::GetTransaction(self.get_tx(tx_hash))
+            }
+            MempoolRequest::GetAccountTransactions(account_address) => {
+                MempoolResponse::GetAccountTransactions(self.get_account_txs(account_address))
+            }
+            MempoolRequest::RemoveTransactions(tx_hashes) => {
+                MempoolResponse::RemoveTransactions(self.remove_txs(tx_hashes))
+            }
+            MempoolRequest::GetEvents(sequence_number) => {
+                MempoolResponse::GetEvents(self.get_events(sequence_number))
+            }
         }
     }
 }
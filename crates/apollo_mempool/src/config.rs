@@ -1,12 +1,15 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use apollo_config::converters::deserialize_seconds_to_duration;
-use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::ordering_policy::TransactionOrderingPolicyType;
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
 pub struct MempoolConfig {
     pub enable_fee_escalation: bool,
@@ -17,6 +20,8 @@ pub struct MempoolConfig {
     // are inserted into the priority queue. If false, all transactions are inserted into the
     // priority queue.
     pub validate_resource_bounds: bool,
+    // Determines the order in which transactions eligible for sequencing are handed out.
+    pub transaction_ordering_policy: TransactionOrderingPolicyType,
     // Time-to-live for transactions in the mempool, in seconds.
     // Transactions older than this value will be lazily removed.
     #[serde(deserialize_with = "deserialize_seconds_to_duration")]
@@ -29,6 +34,27 @@ pub struct MempoolConfig {
     pub committed_nonce_retention_block_count: usize,
     // The maximum size of the mempool, in bytes.
     pub capacity_in_bytes: u64,
+    // The maximum number of transactions held in the mempool per account.
+    pub max_txs_per_account: usize,
+    // The maximum total size of the transactions held in the mempool per account, in bytes.
+    pub max_bytes_per_account: u64,
+    // The maximum distance of a transaction nonce ahead of the account nonce.
+    pub max_nonce_distance: u64,
+    // The maximum total size of the transactions suspended due to a nonce gap, in bytes. Defaults
+    // to the mempool capacity, i.e., no limit beyond that of the mempool.
+    pub suspended_pool_capacity_in_bytes: u64,
+    // The maximum number of suspended transactions per account. Defaults to the maximum number of
+    // transactions per account, i.e., no limit beyond that of the account.
+    pub max_suspended_txs_per_account: usize,
+    // Time-to-live for suspended transactions, in seconds. Defaults to the transaction TTL.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub suspended_transaction_ttl: Duration,
+    // The number of recent mempool events retained for observers.
+    pub event_feed_capacity: usize,
+    // If set, accepted transactions are journaled to this file and restored on startup.
+    pub journal_path: Option<PathBuf>,
+    // The number of obsolete journal records above which the journal is rewritten.
+    pub journal_compaction_threshold: usize,
 }
 
 impl Default for MempoolConfig {
@@ -36,18 +62,28 @@ impl Default for MempoolConfig {
         MempoolConfig {
             enable_fee_escalation: true,
             validate_resource_bounds: true,
+            transaction_ordering_policy: TransactionOrderingPolicyType::default(),
             fee_escalation_percentage: 10,
             transaction_ttl: Duration::from_secs(60), // 1 minute.
             declare_delay: Duration::from_secs(1),
             committed_nonce_retention_block_count: 100,
             capacity_in_bytes: 1 << 30, // 1GB.
+            max_txs_per_account: 1000,
+            max_bytes_per_account: 1 << 24,
+            max_nonce_distance: 1000,
+            suspended_pool_capacity_in_bytes: 1 << 30, // 1GB.
+            max_suspended_txs_per_account: 1000,
+            suspended_transaction_ttl: Duration::from_secs(60),
+            event_feed_capacity: 10000,
+            journal_path: None,
+            journal_compaction_threshold: 10000,
         }
     }
 }
 
 impl SerializeConfig for MempoolConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut dump = BTreeMap::from_iter([
             ser_param(
                 "enable_fee_escalation",
                 &self.enable_fee_escalation,
@@ -62,6 +98,13 @@ impl SerializeConfig for MempoolConfig {
                  are inserted into the priority queue.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "transaction_ordering_policy",
+                &self.transaction_ordering_policy,
+                "The order in which transactions eligible for sequencing are handed out. One of: \
+                 Tip, TipThenFifo, EffectiveFeePerGas, FairFifo.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "fee_escalation_percentage",
                 &self.fee_escalation_percentage,
@@ -93,6 +136,64 @@ impl SerializeConfig for MempoolConfig {
                 "Maximum size of the mempool, in bytes.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+            ser_param(
+                "max_txs_per_account",
+                &self.max_txs_per_account,
+                "Maximum number of transactions held in the mempool per account.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_bytes_per_account",
+                &self.max_bytes_per_account,
+                "Maximum total size of the transactions held in the mempool per account, in bytes.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_nonce_distance",
+                &self.max_nonce_distance,
+                "Maximum distance of a transaction nonce ahead of the account nonce.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "suspended_pool_capacity_in_bytes",
+                &self.suspended_pool_capacity_in_bytes,
+                "Maximum total size of the transactions suspended due to a nonce gap, in bytes.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_suspended_txs_per_account",
+                &self.max_suspended_txs_per_account,
+                "Maximum number of transactions suspended due to a nonce gap, per account.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "suspended_transaction_ttl",
+                &self.suspended_transaction_ttl.as_secs(),
+                "Time-to-live for transactions suspended due to a nonce gap, in seconds.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "event_feed_capacity",
+                &self.event_feed_capacity,
+                "Number of recent mempool events retained for observers.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "journal_compaction_threshold",
+                &self.journal_compaction_threshold,
+                "Number of obsolete records (of transactions that left the mempool) above which \
+                 the journal is rewritten.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.extend(ser_optional_param(
+            &self.journal_path,
+            PathBuf::new(),
+            "journal_path",
+            "Path of the file in which accepted transactions are journaled, to be restored after \
+             a restart. If not set, the mempool is not persisted.",
+            ParamPrivacyInput::Public,
+        ));
+        dump
     }
 }
@@ -0,0 +1,54 @@
+use std::collections::VecDeque;
+
+use apollo_mempool_types::mempool_types::{MempoolEvent, MempoolEventReason, MempoolEvents};
+use rand::random;
+use starknet_api::transaction::TransactionHash;
+
+#[cfg(test)]
+#[path = "event_feed_test.rs"]
+pub mod event_feed_test;
+
+/// Publishes the changes to the mempool content as events.
+/// The most recent events are retained, so that observers can poll for them by sequence number.
+pub struct MempoolEventFeed {
+    // Distinguishes this feed from those of previous runs, whose sequence numbers overlap.
+    id: u64,
+    // The most recent events, sorted by ascending sequence number.
+    recent_events: VecDeque<MempoolEvent>,
+    capacity: usize,
+    next_sequence_number: u64,
+}
+
+impl MempoolEventFeed {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Mempool event feed capacity must be positive.");
+        MempoolEventFeed {
+            id: random(),
+            recent_events: VecDeque::with_capacity(capacity),
+            capacity,
+            next_sequence_number: 0,
+        }
+    }
+
+    pub fn emit(&mut self, tx_hash: TransactionHash, reason: MempoolEventReason) {
+        let event = MempoolEvent { sequence_number: self.next_sequence_number, tx_hash, reason };
+        self.next_sequence_number += 1;
+
+        if self.recent_events.len() == self.capacity {
+            self.recent_events.pop_front();
+        }
+        self.recent_events.push_back(event);
+    }
+
+    /// Returns the retained events whose sequence number is at least the given one.
+    pub fn events_since(&self, sequence_number: u64) -> MempoolEvents {
+        let events = self
+            .recent_events
+            .iter()
+            .skip_while(|event| event.sequence_number < sequence_number)
+            .cloned()
+            .collect();
+
+        MempoolEvents { feed_id: self.id, events, next_sequence_number: self.next_sequence_number }
+    }
+}
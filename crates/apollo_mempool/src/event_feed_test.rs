@@ -0,0 +1,43 @@
+use apollo_mempool_types::mempool_types::{MempoolEvent, MempoolEventReason};
+use pretty_assertions::assert_eq;
+use starknet_api::tx_hash;
+
+use crate::event_feed::MempoolEventFeed;
+
+#[test]
+fn events_since_returns_retained_events() {
+    let mut event_feed = MempoolEventFeed::new(2);
+    for i in 0..3 {
+        event_feed.emit(tx_hash!(i), MempoolEventReason::Added);
+    }
+
+    // The oldest event was dropped due to the feed capacity.
+    let events = event_feed.events_since(0);
+    assert_eq!(events.next_sequence_number, 3);
+    assert_eq!(
+        events.events,
+        vec![
+            MempoolEvent {
+                sequence_number: 1,
+                tx_hash: tx_hash!(1),
+                reason: MempoolEventReason::Added
+            },
+            MempoolEvent {
+                sequence_number: 2,
+                tx_hash: tx_hash!(2),
+                reason: MempoolEventReason::Added
+            },
+        ]
+    );
+
+    assert_eq!(event_feed.events_since(2).events.len(), 1);
+    assert_eq!(event_feed.events_since(3).events, vec![]);
+}
+
+#[test]
+fn feeds_of_different_runs_are_distinguished() {
+    let first_event_feed = MempoolEventFeed::new(1);
+    let second_event_feed = MempoolEventFeed::new(1);
+
+    assert_ne!(first_event_feed.events_since(0).feed_id, second_event_feed.events_since(0).feed_id);
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+
+use apollo_infra_utils::record_file::RecordFile;
+use apollo_mempool_types::mempool_types::AddTransactionArgs;
+use apollo_time::time::DateTime;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::TransactionHash;
+
+/// A transaction accepted by the mempool, along with the time it was submitted.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournaledTransaction {
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub submission_time: DateTime,
+    pub args: AddTransactionArgs,
+}
+
+/// A single record of the journal file.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum JournalRecord {
+    /// Committed account nonces, either all those known to the mempool when the journal was last
+    /// compacted, or those of a single committed block.
+    CommittedNonces(HashMap<ContractAddress, Nonce>),
+    AddTransaction(JournaledTransaction),
+    /// Transactions that left the mempool since they were recorded.
+    RemoveTransactions(Vec<TransactionHash>),
+}
+
+// Borrowed counterpart of `JournalRecord`, used for writing without cloning transactions.
+#[derive(Serialize)]
+enum JournalRecordRef<'a> {
+    CommittedNonces(&'a HashMap<ContractAddress, Nonce>),
+    AddTransaction(&'a JournaledTransaction),
+    RemoveTransactions(&'a [TransactionHash]),
+}
+
+/// An append-only, on-disk log of the transactions accepted by the mempool, used to restore its
+/// content after a restart.
+/// Accepted transactions, committed nonces and removals are appended as they occur; once enough
+/// of the file is obsolete, it is atomically rewritten to hold only the transactions that are
+/// still in the mempool.
+pub struct MempoolJournal {
+    file: RecordFile,
+    // Transactions currently recorded in the journal, in submission order.
+    txs: IndexMap<TransactionHash, JournaledTransaction>,
+    // Number of records in the file that a compaction would drop.
+    n_obsolete_records: usize,
+    // Number of obsolete records above which the journal is compacted.
+    compaction_threshold: usize,
+}
+
+impl MempoolJournal {
+    /// Opens the journal at the given path, creating it if needed, and returns the records it
+    /// holds. A trailing record that cannot be parsed (e.g., a partial write before a crash) ends
+    /// the replay.
+    pub fn open(
+        path: PathBuf,
+        compaction_threshold: usize,
+    ) -> io::Result<(Self, Vec<JournalRecord>)> {
+        let (file, records) = RecordFile::open(path)?;
+
+        let journal = MempoolJournal {
+            file,
+            txs: IndexMap::new(),
+            n_obsolete_records: 0,
+            compaction_threshold,
+        };
+        Ok((journal, records))
+    }
+
+    /// Tracks the transaction in memory, without writing it to disk. Used for transactions that
+    /// are already present in the file (i.e., during replay).
+    pub fn insert(&mut self, submission_time: DateTime, args: AddTransactionArgs) {
+        self.txs.insert(args.tx.tx_hash, JournaledTransaction { submission_time, args });
+    }
+
+    /// Writes the transaction to the journal. Returns once the record is durably stored.
+    pub fn append(
+        &mut self,
+        submission_time: DateTime,
+        args: AddTransactionArgs,
+    ) -> io::Result<()> {
+        let journaled_tx = JournaledTransaction { submission_time, args };
+        self.write_durably(JournalRecordRef::AddTransaction(&journaled_tx))?;
+        self.txs.insert(journaled_tx.args.tx.tx_hash, journaled_tx);
+
+        Ok(())
+    }
+
+    /// Records the committed nonces of a block, and drops the recorded transactions for which
+    /// `retain` returns false. The journal is compacted if enough of it became obsolete.
+    pub fn commit(
+        &mut self,
+        block_committed_nonces: &HashMap<ContractAddress, Nonce>,
+        all_committed_nonces: &HashMap<ContractAddress, Nonce>,
+        retain: impl Fn(TransactionHash) -> bool,
+    ) -> io::Result<()> {
+        if !block_committed_nonces.is_empty() {
+            self.write_durably(JournalRecordRef::CommittedNonces(block_committed_nonces))?;
+            self.n_obsolete_records += 1;
+        }
+        self.remove(all_committed_nonces, retain)
+    }
+
+    /// Drops the recorded transactions for which `retain` returns false. The journal is compacted
+    /// if enough of it became obsolete.
+    fn remove(
+        &mut self,
+        all_committed_nonces: &HashMap<ContractAddress, Nonce>,
+        retain: impl Fn(TransactionHash) -> bool,
+    ) -> io::Result<()> {
+        let removed_tx_hashes: Vec<TransactionHash> =
+            self.txs.keys().copied().filter(|&tx_hash| !retain(tx_hash)).collect();
+        if removed_tx_hashes.is_empty() {
+            return Ok(());
+        }
+        for tx_hash in &removed_tx_hashes {
+            self.txs.shift_remove(tx_hash);
+        }
+
+        // Each removed transaction leaves behind both its addition and its removal.
+        self.n_obsolete_records += 2 * removed_tx_hashes.len();
+        if self.n_obsolete_records > self.compaction_threshold {
+            return self.compact(all_committed_nonces);
+        }
+        self.write_durably(JournalRecordRef::RemoveTransactions(&removed_tx_hashes))
+    }
+
+    /// Rewrites the journal with the given committed nonces and the recorded transactions.
+    pub fn compact(
+        &mut self,
+        committed_nonces: &HashMap<ContractAddress, Nonce>,
+    ) -> io::Result<()> {
+        self.file.rewrite(
+            std::iter::once(JournalRecordRef::CommittedNonces(committed_nonces))
+                .chain(self.txs.values().map(JournalRecordRef::AddTransaction)),
+        )?;
+        self.n_obsolete_records = 0;
+        Ok(())
+    }
+
+    fn write_durably(&mut self, record: JournalRecordRef<'_>) -> io::Result<()> {
+        self.file.append(&record)
+    }
+}
@@ -1,7 +1,10 @@
 pub mod communication;
 pub mod config;
+pub(crate) mod event_feed;
+pub(crate) mod journal;
 pub mod mempool;
 pub mod metrics;
+pub mod ordering_policy;
 pub(crate) mod suspended_transaction_pool;
 pub(crate) mod transaction_pool;
 pub(crate) mod transaction_queue;
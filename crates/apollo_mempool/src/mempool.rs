@@ -6,21 +6,25 @@ use apollo_mempool_types::mempool_types::{
     AccountState,
     AddTransactionArgs,
     CommitBlockArgs,
+    MempoolEventReason,
+    MempoolEvents,
     MempoolResult,
     MempoolSnapshot,
     MempoolStateSnapshot,
 };
 use apollo_time::time::{Clock, DateTime};
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use rand::{thread_rng, Rng};
 use starknet_api::block::GasPrice;
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::rpc_transaction::{InternalRpcTransaction, InternalRpcTransactionWithoutTxHash};
 use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
-use tracing::{debug, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::config::MempoolConfig;
+use crate::event_feed::MempoolEventFeed;
+use crate::journal::{JournalRecord, JournaledTransaction, MempoolJournal};
 use crate::metrics::{
     metric_count_committed_txs,
     metric_count_expired_txs,
@@ -32,8 +36,11 @@ use crate::metrics::{
     MEMPOOL_PENDING_QUEUE_SIZE,
     MEMPOOL_POOL_SIZE,
     MEMPOOL_PRIORITY_QUEUE_SIZE,
+    MEMPOOL_SUSPENDED_POOL_SIZE,
+    MEMPOOL_SUSPENDED_TXS_PROMOTED,
     MEMPOOL_TOTAL_SIZE_BYTES,
 };
+use crate::suspended_transaction_pool::SuspendedTransactionPool;
 use crate::transaction_pool::TransactionPool;
 use crate::transaction_queue::TransactionQueue;
 use crate::utils::try_increment_nonce;
@@ -104,6 +111,11 @@ impl MempoolState {
         self.staged.contains_key(&address) || self.committed.contains_key(&address)
     }
 
+    /// Returns true if the transaction was returned by `get_txs` in the current block creation.
+    fn is_staged(&self, tx_reference: &TransactionReference) -> bool {
+        self.staged.get(&tx_reference.address).is_some_and(|&nonce| tx_reference.nonce < nonce)
+    }
+
     fn stage(&mut self, tx_reference: &TransactionReference) -> MempoolResult<()> {
         let next_nonce = try_increment_nonce(tx_reference.nonce)?;
         if let Some(existing_nonce) = self.staged.insert(tx_reference.address, next_nonce) {
@@ -223,6 +235,34 @@ impl AddTransactionQueue {
         })
     }
 
+    fn contains_tx_hash(&self, tx_hash: TransactionHash) -> bool {
+        self.elements.iter().any(|(_, tx_args)| tx_args.tx.tx_hash == tx_hash)
+    }
+
+    fn get_by_tx_hash(&self, tx_hash: TransactionHash) -> Option<&InternalRpcTransaction> {
+        self.elements.iter().map(|(_, tx_args)| &tx_args.tx).find(|tx| tx.tx_hash == tx_hash)
+    }
+
+    fn account_txs(
+        &self,
+        contract_address: ContractAddress,
+    ) -> impl Iterator<Item = &InternalRpcTransaction> {
+        self.elements
+            .iter()
+            .map(|(_, tx_args)| &tx_args.tx)
+            .filter(move |tx| tx.contract_address() == contract_address)
+    }
+
+    fn remove(&mut self, tx_hash: TransactionHash) -> Option<AddTransactionArgs> {
+        let index = self.elements.iter().position(|(_, tx_args)| tx_args.tx.tx_hash == tx_hash)?;
+        let (_, removed_args) = self.elements.remove(index)?;
+        self.size_in_bytes = self
+            .size_in_bytes
+            .checked_sub(removed_args.tx.total_bytes())
+            .expect("Underflow when removing a transaction from AddTransactionQueue.");
+        Some(removed_args)
+    }
+
     fn len(&self) -> usize {
         self.elements.len()
     }
@@ -241,24 +281,50 @@ pub struct Mempool {
     tx_pool: TransactionPool,
     // Transactions eligible for sequencing.
     tx_queue: TransactionQueue,
+    // Transactions that follow a nonce gap, and are therefore not eligible for sequencing until
+    // the gap is filled. A subset of the transactions in the pool.
+    suspended_tx_pool: SuspendedTransactionPool,
     // Accounts whose lowest transaction nonce is greater than the account nonce, which are
     // therefore candidates for eviction.
     accounts_with_gap: AccountsWithGap,
     state: MempoolState,
+    // On-disk record of the mempool content, if persistence is enabled.
+    journal: Option<MempoolJournal>,
+    // Publishes the changes to the mempool content.
+    event_feed: MempoolEventFeed,
     clock: Arc<dyn Clock>,
 }
 
 impl Mempool {
+    /// Creates a new mempool. If a journal path is configured, the content persisted by a previous
+    /// run is restored.
     pub fn new(config: MempoolConfig, clock: Arc<dyn Clock>) -> Self {
-        Mempool {
+        let mut mempool = Mempool {
             config: config.clone(),
             delayed_declares: AddTransactionQueue::new(),
             tx_pool: TransactionPool::new(clock.clone()),
-            tx_queue: TransactionQueue::default(),
+            tx_queue: TransactionQueue::with_ordering_policy(
+                config.transaction_ordering_policy.policy(),
+            ),
+            suspended_tx_pool: SuspendedTransactionPool::default(),
             accounts_with_gap: AccountsWithGap::new(),
             state: MempoolState::new(config.committed_nonce_retention_block_count),
+            journal: None,
+            event_feed: MempoolEventFeed::new(config.event_feed_capacity),
             clock,
+        };
+
+        if let Some(journal_path) = config.journal_path {
+            let (journal, records) =
+                MempoolJournal::open(journal_path.clone(), config.journal_compaction_threshold)
+                    .unwrap_or_else(|err| {
+                        panic!("Failed to open the mempool journal at {journal_path:?}: {err}")
+                    });
+            mempool.journal = Some(journal);
+            mempool.replay_journal(records);
         }
+
+        mempool
     }
 
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
@@ -335,11 +401,12 @@ impl Mempool {
 
         // First remove old transactions from the pool.
         let mut account_nonce_updates = self.remove_expired_txs();
+        self.remove_expired_suspended_txs();
         self.add_ready_declares();
 
         let tx_reference = TransactionReference::new(&args.tx);
         self.validate_incoming_tx(tx_reference, args.account_state.nonce)?;
-        self.handle_fee_escalation(&args.tx)?;
+        self.validate_quotas_and_replace(&args)?;
 
         if self.exceeds_capacity(&args.tx) {
             self.handle_capacity_overflow(&args.tx, args.account_state.nonce)?;
@@ -354,10 +421,18 @@ impl Mempool {
             self.state.resolve_nonce(args.account_state.address, args.account_state.nonce),
         );
 
+        let submission_time = self.clock.now();
+        if let Some(journal) = &mut self.journal {
+            if let Err(err) = journal.append(submission_time, args.clone()) {
+                error!("Failed to write transaction {} to the journal: {err}", args.tx.tx_hash);
+            }
+        }
+
+        self.event_feed.emit(args.tx.tx_hash, MempoolEventReason::Added);
         if let InternalRpcTransactionWithoutTxHash::Declare(_) = &args.tx.tx {
-            self.delayed_declares.push_back(self.clock.now(), args);
+            self.delayed_declares.push_back(submission_time, args);
         } else {
-            self.add_tx_inner(args);
+            self.add_tx_inner(args, submission_time);
         }
 
         self.update_state_metrics();
@@ -369,7 +444,7 @@ impl Mempool {
         self.tx_queue.insert(tx_reference, self.config.validate_resource_bounds);
     }
 
-    fn add_tx_inner(&mut self, args: AddTransactionArgs) {
+    fn add_tx_inner(&mut self, args: AddTransactionArgs, submission_time: DateTime) {
         let AddTransactionArgs { tx, account_state } = args;
         info!("Adding transaction to mempool.");
         trace!("{tx:#?}");
@@ -377,7 +452,7 @@ impl Mempool {
         let tx_reference = TransactionReference::new(&tx);
 
         self.tx_pool
-            .insert(tx)
+            .insert_with_submission_time(tx, submission_time)
             .expect("Duplicate transactions should cause an error during the validation stage.");
 
         let AccountState { address, nonce: incoming_account_nonce } = account_state;
@@ -385,7 +460,7 @@ impl Mempool {
         if tx_reference.nonce == account_nonce {
             // Remove queued transactions the account might have. This includes old nonce
             // transactions that have become obsolete; those with an equal nonce should
-            // already have been removed in `handle_fee_escalation`.
+            // already have been removed in `validate_quotas_and_replace`.
             self.tx_queue.remove(address);
             self.insert_to_tx_queue(tx_reference);
         }
@@ -399,7 +474,7 @@ impl Mempool {
             }
             let (_submission_time, args) =
                 self.delayed_declares.pop_front().expect("Delay declare should exist.");
-            self.add_tx_inner(args);
+            self.add_tx_inner(args, now);
         }
         self.update_state_metrics();
     }
@@ -431,8 +506,11 @@ impl Mempool {
             }
 
             // Remove from pool.
-            let n_removed_txs = self.tx_pool.remove_up_to_nonce(address, next_nonce);
-            metric_count_committed_txs(n_removed_txs);
+            let removed_txs = self.tx_pool.remove_up_to_nonce(address, next_nonce);
+            metric_count_committed_txs(removed_txs.len());
+            for tx in removed_txs {
+                self.event_feed.emit(tx.tx_hash, MempoolEventReason::Included);
+            }
 
             // Maybe close nonce gap.
             if self.tx_queue.get_nonce(address).is_none() {
@@ -445,6 +523,7 @@ impl Mempool {
         }
 
         // Commit block and rewind nonces of addresses that were not included in block.
+        let block_committed_nonces = self.journal.is_some().then(|| address_to_nonce.clone());
         let addresses_to_rewind = self.state.commit(address_to_nonce);
         for address in addresses_to_rewind {
             // Account nonce is the minimal nonce of this address: it was proposed but not included.
@@ -466,6 +545,7 @@ impl Mempool {
         let mut account_nonce_updates = AddressToNonce::new();
         for tx_hash in rejected_tx_hashes {
             if let Ok(tx) = self.tx_pool.remove(tx_hash) {
+                self.event_feed.emit(tx_hash, MempoolEventReason::Rejected);
                 self.tx_queue.remove(tx.contract_address());
                 account_nonce_updates
                     .entry(tx.contract_address())
@@ -484,6 +564,186 @@ impl Mempool {
 
         self.update_state_metrics();
         self.update_accounts_with_gap(account_nonce_updates);
+        if let Some(block_committed_nonces) = block_committed_nonces {
+            self.update_journal(&block_committed_nonces);
+        }
+    }
+
+    /// Restores the mempool content from the journal records of a previous run.
+    /// Transactions are re-validated as if they were received again, except that they keep their
+    /// original submission time; expired or invalid transactions are dropped.
+    fn replay_journal(&mut self, records: Vec<JournalRecord>) {
+        let mut journaled_txs = IndexMap::new();
+        for record in records {
+            match record {
+                JournalRecord::CommittedNonces(address_to_nonce) => {
+                    self.state.commit(address_to_nonce);
+                }
+                JournalRecord::AddTransaction(journaled_tx) => {
+                    journaled_txs.insert(journaled_tx.args.tx.tx_hash, journaled_tx);
+                }
+                JournalRecord::RemoveTransactions(tx_hashes) => {
+                    for tx_hash in tx_hashes {
+                        journaled_txs.shift_remove(&tx_hash);
+                    }
+                }
+            }
+        }
+
+        let submission_cutoff_time = self.clock.now() - self.config.transaction_ttl;
+        let mut n_restored_txs: usize = 0;
+        for (tx_hash, JournaledTransaction { submission_time, args }) in journaled_txs {
+            if submission_time < submission_cutoff_time {
+                continue;
+            }
+            match self.restore_tx(submission_time, args) {
+                Ok(()) => n_restored_txs += 1,
+                Err(err) => debug!("Dropped journaled transaction {tx_hash}: {err}"),
+            }
+        }
+
+        info!("Restored {n_restored_txs} transactions from the mempool journal.");
+        self.update_state_metrics();
+        self.compact_journal();
+    }
+
+    fn restore_tx(
+        &mut self,
+        submission_time: DateTime,
+        args: AddTransactionArgs,
+    ) -> MempoolResult<()> {
+        let tx_reference = TransactionReference::new(&args.tx);
+        self.validate_incoming_tx(tx_reference, args.account_state.nonce)?;
+        self.validate_quotas_and_replace(&args)?;
+        if self.exceeds_capacity(&args.tx) {
+            return Err(MempoolError::MempoolFull);
+        }
+
+        let AccountState { address, nonce } = args.account_state;
+        let account_nonce = self.state.resolve_nonce(address, nonce);
+        if let Some(journal) = &mut self.journal {
+            journal.insert(submission_time, args.clone());
+        }
+
+        if let InternalRpcTransactionWithoutTxHash::Declare(_) = &args.tx.tx {
+            self.delayed_declares.push_back(submission_time, args);
+        } else {
+            self.add_tx_inner(args, submission_time);
+        }
+
+        self.update_accounts_with_gap(AddressToNonce::from([(address, account_nonce)]));
+        Ok(())
+    }
+
+    /// Records the given committed nonces in the journal, and drops the transactions that are no
+    /// longer in the mempool from it.
+    fn update_journal(&mut self, block_committed_nonces: &AddressToNonce) {
+        let Some(journal) = &mut self.journal else {
+            return;
+        };
+
+        let tx_pool = &self.tx_pool;
+        let delayed_declares = &self.delayed_declares;
+        if let Err(err) = journal.commit(block_committed_nonces, &self.state.committed, |tx_hash| {
+            tx_pool.get_by_tx_hash(tx_hash).is_ok() || delayed_declares.contains_tx_hash(tx_hash)
+        }) {
+            error!("Failed to update the mempool journal: {err}");
+        }
+    }
+
+    /// Rewrites the journal to hold only the current committed nonces and mempool transactions.
+    fn compact_journal(&mut self) {
+        let Some(journal) = &mut self.journal else {
+            return;
+        };
+
+        if let Err(err) = journal.compact(&self.state.committed) {
+            error!("Failed to compact the mempool journal: {err}");
+        }
+    }
+
+    /// Returns the transaction with the given hash, if it is held in the mempool.
+    pub fn get_tx(&self, tx_hash: TransactionHash) -> MempoolResult<InternalRpcTransaction> {
+        if let Some(tx) = self.delayed_declares.get_by_tx_hash(tx_hash) {
+            return Ok(tx.clone());
+        }
+        self.tx_pool.get_by_tx_hash(tx_hash).cloned()
+    }
+
+    /// Returns the transactions of the given account held in the mempool, sorted by nonce.
+    pub fn get_account_txs(&self, address: ContractAddress) -> Vec<InternalRpcTransaction> {
+        let mut account_txs: Vec<_> = self
+            .tx_pool
+            .account_txs_sorted_by_nonce(address)
+            .map(|tx_reference| {
+                self.tx_pool
+                    .get_by_tx_hash(tx_reference.tx_hash)
+                    .expect("Transaction in the account mapping must exist in the pool.")
+            })
+            .chain(self.delayed_declares.account_txs(address))
+            .cloned()
+            .collect();
+        account_txs.sort_by_key(|tx| tx.nonce());
+        account_txs
+    }
+
+    /// Removes the given transactions from the mempool, and returns the hashes of those that were
+    /// removed. Unknown transactions, and transactions already handed out for the block being
+    /// built, are skipped.
+    pub fn remove_txs(&mut self, tx_hashes: &[TransactionHash]) -> Vec<TransactionHash> {
+        let mut removed_tx_hashes = Vec::new();
+        let mut removed_nonces = AddressToNonce::new();
+        for &tx_hash in tx_hashes {
+            let removed_tx = if let Some(args) = self.delayed_declares.remove(tx_hash) {
+                args.tx
+            } else {
+                let Ok(tx) = self.tx_pool.get_by_tx_hash(tx_hash) else {
+                    debug!("Transaction {tx_hash} to remove is unknown to the mempool.");
+                    continue;
+                };
+                let tx_reference = TransactionReference::new(tx);
+                if self.state.is_staged(&tx_reference) {
+                    warn!("Transaction {tx_hash} to remove is staged for the current block.");
+                    continue;
+                }
+
+                self.tx_queue.remove_txs(&[tx_reference]);
+                self.suspended_tx_pool.remove(&tx_reference);
+                self.tx_pool.remove(tx_hash).expect("Transaction hash from pool must exist.")
+            };
+
+            self.event_feed.emit(tx_hash, MempoolEventReason::Removed);
+            removed_nonces
+                .entry(removed_tx.contract_address())
+                .and_modify(|nonce| *nonce = (*nonce).min(removed_tx.nonce()))
+                .or_insert(removed_tx.nonce());
+            removed_tx_hashes.push(tx_hash);
+        }
+        info!("Removed transactions from mempool: {removed_tx_hashes:?}");
+
+        // An account that is still queued keeps its queued nonce; otherwise, its remaining
+        // transactions cannot be sequenced, and are tracked as following a gap.
+        let account_nonce_updates = removed_nonces
+            .into_iter()
+            .map(|(address, removed_nonce)| {
+                let lowest_nonce = self
+                    .tx_pool
+                    .get_lowest_nonce(address)
+                    .map_or(removed_nonce, |lowest_nonce| lowest_nonce.min(removed_nonce));
+                let account_nonce = self.tx_queue.get_nonce(address).unwrap_or(lowest_nonce);
+                (address, self.state.resolve_nonce(address, account_nonce))
+            })
+            .collect();
+
+        self.update_state_metrics();
+        self.update_accounts_with_gap(account_nonce_updates);
+        self.update_journal(&AddressToNonce::new());
+        removed_tx_hashes
+    }
+
+    /// Returns the recent mempool events, starting at the given sequence number.
+    pub fn events_since(&self, sequence_number: u64) -> MempoolEvents {
+        self.event_feed.events_since(sequence_number)
     }
 
     pub fn account_tx_in_pool_or_recent_block(&self, account_address: ContractAddress) -> bool {
@@ -521,6 +781,123 @@ impl Mempool {
         self.state.validate_commitment(address, next_nonce);
     }
 
+    /// Validates that the given transaction fits in the account and suspended pool quotas, and
+    /// then removes the transaction it replaces by fee escalation, if any. The replaced
+    /// transaction is excluded from the quotas, and is kept if the incoming one is rejected.
+    fn validate_quotas_and_replace(&mut self, args: &AddTransactionArgs) -> MempoolResult<()> {
+        let replaced_tx = self.validate_fee_escalation(&args.tx)?;
+        self.validate_account_quotas(&args.tx, args.account_state.nonce, replaced_tx.as_ref())?;
+        self.validate_suspended_pool_capacity(
+            &args.tx,
+            args.account_state.nonce,
+            replaced_tx.as_ref(),
+        )?;
+        if let Some(replaced_tx) = replaced_tx {
+            self.remove_replaced_tx(replaced_tx);
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the given transaction does not exceed the per-account limits: the distance
+    /// of its nonce from the account nonce, and the number and total size of the account's
+    /// transactions held in the mempool (including delayed declares).
+    fn validate_account_quotas(
+        &self,
+        tx: &InternalRpcTransaction,
+        incoming_account_nonce: Nonce,
+        replaced_tx: Option<&TransactionReference>,
+    ) -> MempoolResult<()> {
+        let address = tx.contract_address();
+        let tx_nonce = tx.nonce();
+        let account_nonce = self.state.resolve_nonce(address, incoming_account_nonce);
+        if tx_nonce.0 - account_nonce.0 > self.config.max_nonce_distance.into() {
+            return Err(MempoolError::NonceTooFarAhead { address, tx_nonce, account_nonce });
+        }
+
+        let account_txs = self
+            .tx_pool
+            .account_txs_sorted_by_nonce(address)
+            .filter(|&tx_reference| Some(tx_reference) != replaced_tx)
+            .map(|tx_reference| {
+                self.tx_pool
+                    .get_by_tx_hash(tx_reference.tx_hash)
+                    .expect("Transaction in the account mapping must exist in the pool.")
+            })
+            .chain(self.delayed_declares.account_txs(address));
+        let (n_account_txs, account_size_in_bytes) = account_txs
+            .fold((0, 0), |(n_txs, size_in_bytes), tx| {
+                (n_txs + 1, size_in_bytes + tx.total_bytes())
+            });
+
+        if n_account_txs >= self.config.max_txs_per_account {
+            return Err(MempoolError::AccountTxLimitExceeded { address });
+        }
+        if account_size_in_bytes + tx.total_bytes() > self.config.max_bytes_per_account {
+            return Err(MempoolError::AccountSizeLimitExceeded { address });
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the given transaction fits in the suspended transaction pool, in case it
+    /// follows a nonce gap.
+    fn validate_suspended_pool_capacity(
+        &self,
+        tx: &InternalRpcTransaction,
+        incoming_account_nonce: Nonce,
+        replaced_tx: Option<&TransactionReference>,
+    ) -> MempoolResult<()> {
+        let address = tx.contract_address();
+        let account_nonce = self.state.resolve_nonce(address, incoming_account_nonce);
+        if !self.follows_nonce_gap(address, tx.nonce(), account_nonce) {
+            return Ok(());
+        }
+
+        // The replaced transaction, if suspended, frees its place for the incoming one.
+        let (n_freed_txs, n_freed_bytes) = match replaced_tx {
+            Some(replaced_tx) if self.suspended_tx_pool.contains(address, replaced_tx.nonce) => {
+                let replaced_tx_size = self
+                    .tx_pool
+                    .get_by_tx_hash(replaced_tx.tx_hash)
+                    .expect("Suspended transaction must appear in pool.")
+                    .total_bytes();
+                (1, replaced_tx_size)
+            }
+            _ => (0, 0),
+        };
+        let exceeds_account_limit = self.suspended_tx_pool.account_len(address) - n_freed_txs
+            >= self.config.max_suspended_txs_per_account;
+        let exceeds_capacity = self.suspended_tx_pool.size_in_bytes() - n_freed_bytes
+            + tx.total_bytes()
+            > self.config.suspended_pool_capacity_in_bytes;
+        if exceeds_account_limit || exceeds_capacity {
+            return Err(MempoolError::SuspendedPoolFull { address });
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if a transaction with the given nonce cannot be sequenced due to a missing
+    /// nonce between it and the account nonce.
+    fn follows_nonce_gap(
+        &self,
+        address: ContractAddress,
+        nonce: Nonce,
+        account_nonce: Nonce,
+    ) -> bool {
+        if nonce <= account_nonce {
+            return false;
+        }
+        let Ok(previous_nonce) = nonce.try_decrement() else {
+            return false;
+        };
+
+        let previous_tx_exists = self.delayed_declares.contains(address, previous_nonce)
+            || self.tx_pool.get_by_address_and_nonce(address, previous_nonce).is_some();
+        !previous_tx_exists || self.suspended_tx_pool.contains(address, previous_nonce)
+    }
+
     /// Updates the gas price threshold for transactions that are eligible for sequencing.
     pub fn update_gas_price(&mut self, threshold: GasPrice) {
         self.tx_queue.update_gas_price_threshold(threshold);
@@ -541,11 +918,13 @@ impl Mempool {
         Ok(())
     }
 
-    /// If this transaction is already in the pool but the fees have increased beyond the thereshold
-    /// in the config, remove the existing transaction from the queue and the pool.
-    /// Note: This method will **not** add the new incoming transaction.
+    /// Returns the transaction that the given one replaces by fee escalation, if any, or an error
+    /// if it has a duplicate nonce that does not qualify for replacement.
     #[instrument(level = "debug", skip(self, incoming_tx), err)]
-    fn handle_fee_escalation(&mut self, incoming_tx: &InternalRpcTransaction) -> MempoolResult<()> {
+    fn validate_fee_escalation(
+        &self,
+        incoming_tx: &InternalRpcTransaction,
+    ) -> MempoolResult<Option<TransactionReference>> {
         let incoming_tx_reference = TransactionReference::new(incoming_tx);
         let TransactionReference { address, nonce, .. } = incoming_tx_reference;
 
@@ -556,13 +935,13 @@ impl Mempool {
                 return Err(MempoolError::DuplicateNonce { address, nonce });
             };
 
-            return Ok(());
+            return Ok(None);
         }
 
         let Some(existing_tx_reference) = self.tx_pool.get_by_address_and_nonce(address, nonce)
         else {
             // Replacement irrelevant: no existing transaction with the same nonce for address.
-            return Ok(());
+            return Ok(None);
         };
 
         if !self.should_replace_tx(&existing_tx_reference, &incoming_tx_reference) {
@@ -575,13 +954,17 @@ impl Mempool {
         }
 
         debug!("{existing_tx_reference} will be replaced by {incoming_tx_reference}.");
+        Ok(Some(existing_tx_reference))
+    }
 
+    fn remove_replaced_tx(&mut self, existing_tx_reference: TransactionReference) {
         self.tx_queue.remove_txs(&[existing_tx_reference]);
+        self.suspended_tx_pool.remove(&existing_tx_reference);
         self.tx_pool
             .remove(existing_tx_reference.tx_hash)
             .expect("Transaction hash from pool must exist.");
-
-        Ok(())
+        self.event_feed
+            .emit(existing_tx_reference.tx_hash, MempoolEventReason::ReplacedByFeeEscalation);
     }
 
     fn should_replace_tx(
@@ -620,6 +1003,10 @@ impl Mempool {
         let removed_txs =
             self.tx_pool.remove_txs_older_than(self.config.transaction_ttl, &self.state.staged);
         let queued_txs = self.tx_queue.remove_txs(&removed_txs);
+        for tx in &removed_txs {
+            self.suspended_tx_pool.remove(tx);
+            self.event_feed.emit(tx.tx_hash, MempoolEventReason::Expired);
+        }
 
         metric_count_expired_txs(removed_txs.len());
         self.update_state_metrics();
@@ -629,6 +1016,24 @@ impl Mempool {
             .collect::<AddressToNonce>()
     }
 
+    /// Removes suspended transactions that outlived their (suspended) time-to-live.
+    fn remove_expired_suspended_txs(&mut self) {
+        let submission_cutoff_time = self.clock.now() - self.config.suspended_transaction_ttl;
+        let expired_txs = self.suspended_tx_pool.txs_submitted_before(submission_cutoff_time);
+        for tx in &expired_txs {
+            self.suspended_tx_pool.remove(tx);
+            self.tx_pool.remove(tx.tx_hash).expect("Suspended transaction must appear in pool.");
+            self.event_feed.emit(tx.tx_hash, MempoolEventReason::Expired);
+            // Clean up if account is now empty.
+            if !self.tx_pool.contains_account(tx.address) {
+                self.accounts_with_gap.swap_remove(&tx.address);
+            }
+        }
+
+        metric_count_expired_txs(expired_txs.len());
+        self.update_state_metrics();
+    }
+
     /// Given a chunk of transactions, removes from the pool those that are old, and returns the
     /// remaining valid ones.
     /// Note: This function assumes that the given transactions were already removed from the queue.
@@ -655,6 +1060,7 @@ impl Mempool {
                 self.tx_pool
                     .remove(tx.tx_hash)
                     .expect("Transaction hash from queue must appear in pool.");
+                self.event_feed.emit(tx.tx_hash, MempoolEventReason::Expired);
                 (tx.address, self.state.resolve_nonce(tx.address, tx.nonce))
             })
             .collect();
@@ -671,6 +1077,7 @@ impl Mempool {
                 .iter()
                 .map(|(_, args)| args.tx.tx_hash)
                 .collect(),
+            suspended_transactions: self.suspended_tx_pool.chronological_txs_hashes(),
             transaction_queue: self.tx_queue.queue_snapshot(),
             mempool_state: self.state.state_snapshot(),
         })
@@ -687,6 +1094,8 @@ impl Mempool {
 
     fn update_accounts_with_gap(&mut self, address_to_nonce: AddressToNonce) {
         for (address, account_nonce) in address_to_nonce {
+            self.update_suspended_txs(address, account_nonce);
+
             // Assumption: Future declares are not allowed — their nonce must match the account
             // nonce, so they fill a gap if one exists.
             if self.delayed_declares.contains(address, account_nonce) {
@@ -709,6 +1118,60 @@ impl Mempool {
         }
     }
 
+    /// Re-evaluates which of the account's transactions are suspended: those that follow the first
+    /// nonce gap after the account nonce. Suspended transactions whose gap was filled are promoted,
+    /// i.e. they become eligible for sequencing right after their preceding nonces.
+    fn update_suspended_txs(&mut self, address: ContractAddress, account_nonce: Nonce) {
+        let previously_suspended_txs = self.suspended_tx_pool.remove_account(address);
+
+        // Assumption: Future declares are not allowed, so a delayed declare can only hold the
+        // account nonce.
+        let mut next_nonce = if self.delayed_declares.contains(address, account_nonce) {
+            account_nonce.try_increment().ok()
+        } else {
+            Some(account_nonce)
+        };
+        let mut suspended_txs = Vec::new();
+        for tx in self.tx_pool.account_txs_sorted_by_nonce(address) {
+            if tx.nonce < account_nonce {
+                // Staged transaction.
+                continue;
+            }
+            if suspended_txs.is_empty() && Some(tx.nonce) == next_nonce {
+                next_nonce = tx.nonce.try_increment().ok();
+            } else {
+                suspended_txs.push(*tx);
+            }
+        }
+
+        for tx in suspended_txs {
+            let submission_time = self
+                .tx_pool
+                .get_submission_time(tx.tx_hash)
+                .expect("Transaction hash from pool must have a submission time.");
+            let tx_size = self
+                .tx_pool
+                .get_by_tx_hash(tx.tx_hash)
+                .expect("Transaction hash from pool must exist.")
+                .total_bytes();
+            self.suspended_tx_pool.insert(tx, submission_time, tx_size);
+        }
+
+        let n_promoted_txs = previously_suspended_txs
+            .iter()
+            .filter(|tx| {
+                !self.suspended_tx_pool.contains(tx.address, tx.nonce)
+                    && self.tx_pool.get_by_tx_hash(tx.tx_hash).is_ok()
+            })
+            .count();
+        if n_promoted_txs != 0 {
+            debug!("Promoted {n_promoted_txs} suspended transactions of address {address}.");
+            MEMPOOL_SUSPENDED_TXS_PROMOTED.increment(
+                n_promoted_txs.try_into().expect("The number of promoted txs should fit u64"),
+            );
+        }
+    }
+
     pub fn get_evictable_account(&self) -> Option<ContractAddress> {
         let len = self.accounts_with_gap.len();
         if len == 0 {
@@ -730,12 +1193,14 @@ impl Mempool {
 
             let txs: Vec<_> = self.tx_pool.account_txs_sorted_by_nonce(address).copied().collect();
             for tx_ref in txs.iter().rev() {
+                self.suspended_tx_pool.remove(tx_ref);
                 let tx = self
                     .tx_pool
                     .remove(tx_ref.tx_hash)
                     .expect("Transaction must exist in the pool.");
                 total_space_freed += tx.total_bytes();
                 MEMPOOL_EVICTIONS_COUNT.increment(1);
+                self.event_feed.emit(tx_ref.tx_hash, MempoolEventReason::Evicted);
                 if total_space_freed >= required_space {
                     break;
                 }
@@ -788,6 +1253,7 @@ impl Mempool {
         MEMPOOL_PRIORITY_QUEUE_SIZE.set_lossy(self.tx_queue.priority_queue_len());
         MEMPOOL_PENDING_QUEUE_SIZE.set_lossy(self.tx_queue.pending_queue_len());
         MEMPOOL_DELAYED_DECLARES_SIZE.set_lossy(self.delayed_declares.len());
+        MEMPOOL_SUSPENDED_POOL_SIZE.set_lossy(self.suspended_tx_pool.len());
         MEMPOOL_TOTAL_SIZE_BYTES.set_lossy(self.size_in_bytes());
     }
 }
@@ -9,7 +9,12 @@ use apollo_mempool_p2p_types::communication::{
 };
 use apollo_mempool_types::communication::AddTransactionArgsWrapper;
 use apollo_mempool_types::errors::MempoolError;
-use apollo_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
+use apollo_mempool_types::mempool_types::{
+    AccountState,
+    AddTransactionArgs,
+    MempoolEvent,
+    MempoolEventReason,
+};
 use apollo_metrics::metrics::HistogramValue;
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
 use apollo_test_utils::{get_rng, GetTestInstance};
@@ -29,6 +34,7 @@ use starknet_api::{contract_address, declare_tx_args, felt, invoke_tx_args, nonc
 
 use super::AddTransactionQueue;
 use crate::communication::MempoolCommunicationWrapper;
+use crate::event_feed::MempoolEventFeed;
 use crate::mempool::{
     AccountsWithGap,
     Mempool,
@@ -38,6 +44,7 @@ use crate::mempool::{
     TransactionReference,
 };
 use crate::metrics::register_metrics;
+use crate::suspended_transaction_pool::SuspendedTransactionPool;
 use crate::test_utils::{
     add_tx,
     add_tx_expect_error,
@@ -145,8 +152,11 @@ impl MempoolTestContentBuilder {
                 self.content.pending_txs.unwrap_or_default(),
                 self.gas_price_threshold,
             ),
+            suspended_tx_pool: SuspendedTransactionPool::default(),
             accounts_with_gap: AccountsWithGap::new(),
             state: MempoolState::new(self.config.committed_nonce_retention_block_count),
+            journal: None,
+            event_feed: MempoolEventFeed::new(self.config.event_feed_capacity),
             clock: Arc::new(FakeClock::default()),
         }
     }
@@ -1238,6 +1248,7 @@ fn metrics_correctness() {
         pending_queue_size: 1,
         get_txs_size: 1,
         delayed_declares_size: 1,
+        suspended_pool_size: 0,
         total_size_in_bytes: 1952,
         evictions_count: 1,
         transaction_time_spent_in_mempool: HistogramValue {
@@ -1724,3 +1735,469 @@ fn rejects_or_accepts_tx_based_on_freed_space(
     // We do not revert the eviction attempt even if adding large_tx ultimately fails.
     assert!(!mempool.tx_pool.contains_account(contract_address!("0x1")));
 }
+
+#[rstest]
+fn journal_restores_mempool_content_after_restart() {
+    let journal_dir = tempfile::tempdir().unwrap();
+    let config = MempoolConfig {
+        journal_path: Some(journal_dir.path().join("mempool_journal")),
+        ..Default::default()
+    };
+    let fake_clock = Arc::new(FakeClock::default());
+    let mut mempool = Mempool::new(config.clone(), fake_clock.clone());
+
+    let queued_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let gapped_tx = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 2, account_nonce: 0);
+    for input in [&queued_tx, &gapped_tx] {
+        add_tx(&mut mempool, input);
+    }
+    drop(mempool);
+
+    // Test.
+    let mempool = Mempool::new(config, fake_clock);
+
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([queued_tx.tx.clone(), gapped_tx.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&queued_tx.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool.content());
+    assert!(mempool.accounts_with_gap().contains(&gapped_tx.tx.contract_address()));
+}
+
+#[rstest]
+fn journal_prunes_committed_txs_and_restores_committed_nonces() {
+    let journal_dir = tempfile::tempdir().unwrap();
+    let config = MempoolConfig {
+        journal_path: Some(journal_dir.path().join("mempool_journal")),
+        ..Default::default()
+    };
+    let fake_clock = Arc::new(FakeClock::default());
+    let mut mempool = Mempool::new(config.clone(), fake_clock.clone());
+
+    let committed_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let remaining_tx = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    for input in [&committed_tx, &remaining_tx] {
+        add_tx(&mut mempool, input);
+    }
+    commit_block(&mut mempool, [("0x0", 1)], []);
+    drop(mempool);
+
+    // Test.
+    let mut mempool = Mempool::new(config, fake_clock);
+
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([remaining_tx.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&remaining_tx.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool.content());
+    add_tx_expect_error(
+        &mut mempool,
+        &committed_tx,
+        MempoolError::NonceTooOld {
+            address: contract_address!("0x0"),
+            tx_nonce: nonce!(0),
+            account_nonce: nonce!(1),
+        },
+    );
+}
+
+#[rstest]
+fn journal_drops_expired_and_replaced_txs() {
+    let journal_dir = tempfile::tempdir().unwrap();
+    let config = MempoolConfig {
+        journal_path: Some(journal_dir.path().join("mempool_journal")),
+        transaction_ttl: Duration::from_secs(60),
+        ..Default::default()
+    };
+    let fake_clock = Arc::new(FakeClock::default());
+    let mut mempool = Mempool::new(config.clone(), fake_clock.clone());
+
+    let expired_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &expired_tx);
+    fake_clock.advance(config.transaction_ttl / 2);
+
+    let replaced_tx =
+        add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, tip: 100, max_l2_gas_price: 100);
+    let replacing_tx =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, tip: 200, max_l2_gas_price: 200);
+    for input in [&replaced_tx, &replacing_tx] {
+        add_tx(&mut mempool, input);
+    }
+    drop(mempool);
+    fake_clock.advance(config.transaction_ttl / 2 + Duration::from_secs(1));
+
+    // Test.
+    let mempool = Mempool::new(config, fake_clock);
+
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([replacing_tx.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&replacing_tx.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool.content());
+}
+
+#[rstest]
+#[case::appended_removal(10)]
+#[case::compaction(0)]
+fn journal_does_not_restore_removed_txs(#[case] journal_compaction_threshold: usize) {
+    let journal_dir = tempfile::tempdir().unwrap();
+    let config = MempoolConfig {
+        journal_path: Some(journal_dir.path().join("mempool_journal")),
+        journal_compaction_threshold,
+        ..Default::default()
+    };
+    let fake_clock = Arc::new(FakeClock::default());
+    let mut mempool = Mempool::new(config.clone(), fake_clock.clone());
+
+    let removed_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let remaining_tx = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    for input in [&removed_tx, &remaining_tx] {
+        add_tx(&mut mempool, input);
+    }
+    assert_eq!(mempool.remove_txs(&[removed_tx.tx.tx_hash]), vec![removed_tx.tx.tx_hash]);
+    drop(mempool);
+
+    // Test.
+    let mempool = Mempool::new(config, fake_clock);
+
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([remaining_tx.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&remaining_tx.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool.content());
+}
+
+#[rstest]
+fn gapped_txs_are_suspended_until_gap_is_filled(mut mempool: Mempool) {
+    let address = contract_address!("0x0");
+    let tx_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let tx_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let tx_nonce_2 = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 2, account_nonce: 0);
+
+    add_tx(&mut mempool, &tx_nonce_2);
+    add_tx(&mut mempool, &tx_nonce_1);
+    assert!(mempool.suspended_tx_pool.contains(address, nonce!(1)));
+    assert!(mempool.suspended_tx_pool.contains(address, nonce!(2)));
+    let mut suspended_txs = mempool.mempool_snapshot().unwrap().suspended_transactions;
+    suspended_txs.sort();
+    assert_eq!(suspended_txs, vec![tx_nonce_1.tx.tx_hash, tx_nonce_2.tx.tx_hash]);
+
+    // Fill the gap; all transactions become eligible for sequencing.
+    add_tx(&mut mempool, &tx_nonce_0);
+    assert_eq!(mempool.suspended_tx_pool.len(), 0);
+    get_txs_and_assert_expected(&mut mempool, 3, &[tx_nonce_0.tx, tx_nonce_1.tx, tx_nonce_2.tx]);
+}
+
+#[rstest]
+fn suspended_txs_are_promoted_on_commit_block(mut mempool: Mempool) {
+    let tx_nonce_1 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let tx_nonce_2 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 2, account_nonce: 0);
+    for input in [&tx_nonce_1, &tx_nonce_2] {
+        add_tx(&mut mempool, input);
+    }
+    assert_eq!(mempool.suspended_tx_pool.len(), 2);
+
+    // Test.
+    commit_block(&mut mempool, [("0x0", 1)], []);
+
+    assert_eq!(mempool.suspended_tx_pool.len(), 0);
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_priority_queue([TransactionReference::new(&tx_nonce_1.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool.content());
+    get_txs_and_assert_expected(&mut mempool, 2, &[tx_nonce_1.tx, tx_nonce_2.tx]);
+}
+
+#[rstest]
+fn rejects_tx_exceeding_suspended_txs_per_account_limit() {
+    let mut mempool = Mempool::new(
+        MempoolConfig { max_suspended_txs_per_account: 1, ..Default::default() },
+        Arc::new(FakeClock::default()),
+    );
+
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 2, account_nonce: 0));
+    add_tx_expect_error(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 3, account_nonce: 0),
+        MempoolError::SuspendedPoolFull { address: contract_address!("0x0") },
+    );
+
+    // Transactions of other accounts, or ones that do not follow a gap, are not affected.
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 2, account_nonce: 0));
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 4, address: "0x0", tx_nonce: 0, account_nonce: 0));
+}
+
+#[rstest]
+fn replacing_suspended_tx_at_suspended_txs_per_account_limit() {
+    let mut mempool = Mempool::new(
+        MempoolConfig { max_suspended_txs_per_account: 1, ..Default::default() },
+        Arc::new(FakeClock::default()),
+    );
+    let replaced_tx =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 2, tip: 100, max_l2_gas_price: 100);
+    let replacing_tx =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 2, tip: 200, max_l2_gas_price: 200);
+
+    add_tx(&mut mempool, &replaced_tx);
+    add_tx(&mut mempool, &replacing_tx);
+
+    let expected_mempool_content =
+        MempoolTestContentBuilder::new().with_pool([replacing_tx.tx]).build();
+    expected_mempool_content.assert_eq(&mempool.content());
+}
+
+#[rstest]
+fn rejected_replacing_tx_does_not_remove_replaced_tx() {
+    let mut mempool = Mempool::new(
+        MempoolConfig { max_nonce_distance: 2, ..Default::default() },
+        Arc::new(FakeClock::default()),
+    );
+    let replaced_tx = add_tx_input!(
+        tx_hash: 1,
+        address: "0x0",
+        tx_nonce: 5,
+        account_nonce: 4,
+        tip: 100,
+        max_l2_gas_price: 100
+    );
+    // Reported with an older account nonce, so it is too far ahead.
+    let replacing_tx = add_tx_input!(
+        tx_hash: 2,
+        address: "0x0",
+        tx_nonce: 5,
+        account_nonce: 0,
+        tip: 200,
+        max_l2_gas_price: 200
+    );
+
+    add_tx(&mut mempool, &replaced_tx);
+    add_tx_expect_error(
+        &mut mempool,
+        &replacing_tx,
+        MempoolError::NonceTooFarAhead {
+            address: contract_address!("0x0"),
+            tx_nonce: nonce!(5),
+            account_nonce: nonce!(0),
+        },
+    );
+
+    let expected_mempool_content =
+        MempoolTestContentBuilder::new().with_pool([replaced_tx.tx]).build();
+    expected_mempool_content.assert_eq(&mempool.content());
+}
+
+#[rstest]
+fn suspended_txs_expire_after_suspended_ttl() {
+    let fake_clock = Arc::new(FakeClock::default());
+    let mut mempool = Mempool::new(
+        MempoolConfig {
+            transaction_ttl: Duration::from_secs(60),
+            suspended_transaction_ttl: Duration::from_secs(10),
+            ..Default::default()
+        },
+        fake_clock.clone(),
+    );
+
+    let suspended_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let queued_tx = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    for input in [&suspended_tx, &queued_tx] {
+        add_tx(&mut mempool, input);
+    }
+
+    fake_clock.advance(mempool.config.suspended_transaction_ttl + Duration::from_secs(1));
+
+    // Trigger cleanup.
+    let trigger_tx = add_tx_input!(tx_hash: 3, address: "0x2", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &trigger_tx);
+
+    let expected_mempool_content =
+        MempoolTestContentBuilder::new().with_pool([queued_tx.tx, trigger_tx.tx]).build();
+    expected_mempool_content.assert_eq(&mempool.content());
+    assert!(!mempool.accounts_with_gap().contains(&suspended_tx.tx.contract_address()));
+}
+
+#[rstest]
+fn rejects_tx_with_nonce_too_far_ahead() {
+    let mut mempool = Mempool::new(
+        MempoolConfig { max_nonce_distance: 2, ..Default::default() },
+        Arc::new(FakeClock::default()),
+    );
+
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 3, account_nonce: 1));
+    add_tx_expect_error(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 4, account_nonce: 1),
+        MempoolError::NonceTooFarAhead {
+            address: contract_address!("0x0"),
+            tx_nonce: nonce!(4),
+            account_nonce: nonce!(1),
+        },
+    );
+}
+
+#[rstest]
+fn rejects_tx_exceeding_txs_per_account_limit() {
+    let mut mempool = Mempool::new(
+        MempoolConfig { max_txs_per_account: 2, ..Default::default() },
+        Arc::new(FakeClock::default()),
+    );
+
+    for (tx_hash, tx_nonce) in [(1, 0), (2, 1)] {
+        let input = add_tx_input!(
+            tx_hash: tx_hash,
+            address: "0x0",
+            tx_nonce: tx_nonce,
+            account_nonce: 0,
+            tip: 100,
+            max_l2_gas_price: 100
+        );
+        add_tx(&mut mempool, &input);
+    }
+    add_tx_expect_error(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 2, account_nonce: 0),
+        MempoolError::AccountTxLimitExceeded { address: contract_address!("0x0") },
+    );
+
+    // Replacing an existing transaction does not count towards the limit.
+    let replacement_input = add_tx_input!(
+        tx_hash: 4,
+        address: "0x0",
+        tx_nonce: 1,
+        account_nonce: 0,
+        tip: 200,
+        max_l2_gas_price: 200
+    );
+    add_tx(&mut mempool, &replacement_input);
+
+    // Other accounts are not affected.
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 5, address: "0x1", tx_nonce: 0, account_nonce: 0));
+}
+
+#[rstest]
+fn rejects_tx_exceeding_bytes_per_account_limit() {
+    let tx1 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let tx2 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let mut mempool = Mempool::new(
+        MempoolConfig {
+            max_bytes_per_account: tx1.tx.total_bytes() + tx2.tx.total_bytes() - 1,
+            ..Default::default()
+        },
+        Arc::new(FakeClock::default()),
+    );
+
+    add_tx(&mut mempool, &tx1);
+    add_tx_expect_error(
+        &mut mempool,
+        &tx2,
+        MempoolError::AccountSizeLimitExceeded { address: contract_address!("0x0") },
+    );
+}
+
+#[rstest]
+fn get_tx_and_account_txs(mut mempool: Mempool) {
+    let tx_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let tx_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let other_account_tx = add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    for input in [&tx_nonce_1, &tx_nonce_0, &other_account_tx] {
+        add_tx(&mut mempool, input);
+    }
+
+    assert_eq!(mempool.get_tx(tx_nonce_1.tx.tx_hash), Ok(tx_nonce_1.tx.clone()));
+    assert_eq!(
+        mempool.get_tx(tx_hash!(4)),
+        Err(MempoolError::TransactionNotFound { tx_hash: tx_hash!(4) })
+    );
+    assert_eq!(
+        mempool.get_account_txs(contract_address!("0x0")),
+        vec![tx_nonce_0.tx, tx_nonce_1.tx]
+    );
+    assert_eq!(mempool.get_account_txs(contract_address!("0x2")), vec![]);
+}
+
+#[rstest]
+fn remove_txs_suspends_following_txs(mut mempool: Mempool) {
+    let address = contract_address!("0x0");
+    let tx_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let tx_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let tx_nonce_2 = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 2, account_nonce: 0);
+    for input in [&tx_nonce_0, &tx_nonce_1, &tx_nonce_2] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let removed_tx_hashes = mempool.remove_txs(&[tx_nonce_1.tx.tx_hash, tx_hash!(4)]);
+
+    // Assert: unknown transactions are ignored, and the transaction following the removed one is
+    // suspended until the gap is filled.
+    assert_eq!(removed_tx_hashes, vec![tx_nonce_1.tx.tx_hash]);
+    assert!(mempool.suspended_tx_pool.contains(address, nonce!(2)));
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([tx_nonce_0.tx.clone(), tx_nonce_2.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&tx_nonce_0.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool.content());
+
+    // Removing the queued transaction leaves the account with a gap.
+    assert_eq!(mempool.remove_txs(&[tx_nonce_0.tx.tx_hash]), vec![tx_nonce_0.tx.tx_hash]);
+    let expected_mempool_content =
+        MempoolTestContentBuilder::new().with_pool([tx_nonce_2.tx]).with_priority_queue([]).build();
+    expected_mempool_content.assert_eq(&mempool.content());
+    assert!(mempool.accounts_with_gap().contains(&address));
+}
+
+#[rstest]
+fn remove_txs_skips_staged_txs(mut mempool: Mempool) {
+    let input = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &input);
+    get_txs_and_assert_expected(&mut mempool, 1, &[input.tx.clone()]);
+
+    assert_eq!(mempool.remove_txs(&[input.tx.tx_hash]), vec![]);
+    let expected_mempool_content = MempoolTestContentBuilder::new().with_pool([input.tx]).build();
+    expected_mempool_content.assert_eq(&mempool.content());
+}
+
+#[rstest]
+fn mempool_emits_events_on_content_changes(mut mempool: Mempool) {
+    let tx = add_tx_input!(
+        tx_hash: 1,
+        address: "0x0",
+        tx_nonce: 0,
+        account_nonce: 0,
+        tip: 100,
+        max_l2_gas_price: 100
+    );
+    let replacement_tx = add_tx_input!(
+        tx_hash: 2,
+        address: "0x0",
+        tx_nonce: 0,
+        account_nonce: 0,
+        tip: 200,
+        max_l2_gas_price: 200
+    );
+    let rejected_tx = add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    for input in [&tx, &replacement_tx, &rejected_tx] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    commit_block(&mut mempool, [("0x0", 1)], [rejected_tx.tx.tx_hash]);
+
+    // Assert.
+    let expected_events: Vec<_> = [
+        (tx.tx.tx_hash, MempoolEventReason::Added),
+        (tx.tx.tx_hash, MempoolEventReason::ReplacedByFeeEscalation),
+        (replacement_tx.tx.tx_hash, MempoolEventReason::Added),
+        (rejected_tx.tx.tx_hash, MempoolEventReason::Added),
+        (replacement_tx.tx.tx_hash, MempoolEventReason::Included),
+        (rejected_tx.tx.tx_hash, MempoolEventReason::Rejected),
+    ]
+    .into_iter()
+    .zip(0..)
+    .map(|((tx_hash, reason), sequence_number)| MempoolEvent { sequence_number, tx_hash, reason })
+    .collect();
+
+    let events = mempool.events_since(0);
+    assert_eq!(events.events, expected_events);
+    assert_eq!(events.next_sequence_number, 6);
+}
@@ -0,0 +1,140 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::GasPrice;
+
+use crate::mempool::TransactionReference;
+
+#[cfg(test)]
+#[path = "ordering_policy_test.rs"]
+pub mod ordering_policy_test;
+
+/// The priority of a transaction in the priority queue; transactions with a higher priority are
+/// sequenced first. Transactions of equal priority are ordered by their hash.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct TransactionPriority {
+    pub fee: u128,
+    // Higher for transactions that entered the queue earlier.
+    pub seniority: u64,
+}
+
+impl TransactionPriority {
+    /// Returns the seniority of the transaction with the given queue insertion index, such that
+    /// earlier insertions are more senior.
+    pub fn seniority(insertion_index: u64) -> u64 {
+        u64::MAX - insertion_index
+    }
+}
+
+/// Determines the order in which the transactions eligible for sequencing are handed out.
+pub trait TransactionOrderingPolicy: Debug + Send + Sync {
+    /// Returns the priority of the given transaction.
+    /// `insertion_index` increases with every insertion to the queue; an account's next
+    /// transaction is inserted only after its current one is handed out.
+    fn priority(
+        &self,
+        tx: &TransactionReference,
+        insertion_index: u64,
+        gas_price_threshold: GasPrice,
+    ) -> TransactionPriority;
+
+    /// Returns true if priorities depend on the gas price threshold, and should therefore be
+    /// recomputed when it changes.
+    fn depends_on_gas_price_threshold(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum TransactionOrderingPolicyType {
+    /// Higher tip first.
+    #[default]
+    Tip,
+    /// Higher tip first; transactions with equal tips are ordered by arrival to the queue.
+    TipThenFifo,
+    /// Higher effective fee per L2 gas unit first: the gas price threshold plus the tip, bounded
+    /// by the max L2 gas price.
+    EffectiveFeePerGas,
+    /// Ordered by arrival to the queue, regardless of fees. Since an account has at most one
+    /// queued transaction, accounts are served in a round-robin manner.
+    FairFifo,
+}
+
+impl TransactionOrderingPolicyType {
+    pub fn policy(&self) -> Box<dyn TransactionOrderingPolicy> {
+        match self {
+            TransactionOrderingPolicyType::Tip => Box::new(TipPolicy),
+            TransactionOrderingPolicyType::TipThenFifo => Box::new(TipThenFifoPolicy),
+            TransactionOrderingPolicyType::EffectiveFeePerGas => Box::new(EffectiveFeePerGasPolicy),
+            TransactionOrderingPolicyType::FairFifo => Box::new(FairFifoPolicy),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TipPolicy;
+
+impl TransactionOrderingPolicy for TipPolicy {
+    fn priority(
+        &self,
+        tx: &TransactionReference,
+        _insertion_index: u64,
+        _gas_price_threshold: GasPrice,
+    ) -> TransactionPriority {
+        TransactionPriority { fee: tx.tip.0.into(), seniority: 0 }
+    }
+}
+
+#[derive(Debug)]
+pub struct TipThenFifoPolicy;
+
+impl TransactionOrderingPolicy for TipThenFifoPolicy {
+    fn priority(
+        &self,
+        tx: &TransactionReference,
+        insertion_index: u64,
+        _gas_price_threshold: GasPrice,
+    ) -> TransactionPriority {
+        TransactionPriority {
+            fee: tx.tip.0.into(),
+            seniority: TransactionPriority::seniority(insertion_index),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EffectiveFeePerGasPolicy;
+
+impl TransactionOrderingPolicy for EffectiveFeePerGasPolicy {
+    fn priority(
+        &self,
+        tx: &TransactionReference,
+        insertion_index: u64,
+        gas_price_threshold: GasPrice,
+    ) -> TransactionPriority {
+        let effective_fee =
+            gas_price_threshold.0.saturating_add(tx.tip.0.into()).min(tx.max_l2_gas_price.0);
+        TransactionPriority {
+            fee: effective_fee,
+            seniority: TransactionPriority::seniority(insertion_index),
+        }
+    }
+
+    fn depends_on_gas_price_threshold(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug)]
+pub struct FairFifoPolicy;
+
+impl TransactionOrderingPolicy for FairFifoPolicy {
+    fn priority(
+        &self,
+        _tx: &TransactionReference,
+        insertion_index: u64,
+        _gas_price_threshold: GasPrice,
+    ) -> TransactionPriority {
+        TransactionPriority { fee: 0, seniority: TransactionPriority::seniority(insertion_index) }
+    }
+}
@@ -0,0 +1,98 @@
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::block::GasPrice;
+use starknet_api::transaction::TransactionHash;
+use starknet_api::{contract_address, nonce, tx_hash};
+
+use crate::mempool::TransactionReference;
+use crate::ordering_policy::TransactionOrderingPolicyType;
+use crate::transaction_queue::TransactionQueue;
+use crate::tx;
+
+fn tx_reference(
+    tx_hash: u8,
+    address: &str,
+    tip: u64,
+    max_l2_gas_price: u128,
+) -> TransactionReference {
+    let tx = tx!(
+        tx_hash: tx_hash,
+        address: address,
+        tx_nonce: 0,
+        tip: tip,
+        max_l2_gas_price: max_l2_gas_price
+    );
+    TransactionReference::new(&tx)
+}
+
+fn queue_with_txs(
+    ordering_policy: TransactionOrderingPolicyType,
+    txs: &[TransactionReference],
+) -> TransactionQueue {
+    let mut tx_queue = TransactionQueue::with_ordering_policy(ordering_policy.policy());
+    for tx in txs {
+        tx_queue.insert(*tx, false);
+    }
+    tx_queue
+}
+
+fn ready_tx_hashes(tx_queue: &TransactionQueue) -> Vec<TransactionHash> {
+    tx_queue.iter_over_ready_txs().map(|tx| tx.tx_hash).collect()
+}
+
+#[rstest]
+#[case::tip(TransactionOrderingPolicyType::Tip, [3, 2, 1])]
+#[case::tip_then_fifo(TransactionOrderingPolicyType::TipThenFifo, [3, 1, 2])]
+#[case::fair_fifo(TransactionOrderingPolicyType::FairFifo, [1, 2, 3])]
+fn txs_are_ordered_by_policy(
+    #[case] ordering_policy: TransactionOrderingPolicyType,
+    #[case] expected_order: [u8; 3],
+) {
+    let txs = [
+        tx_reference(1, "0x1", 10, 100),
+        tx_reference(2, "0x2", 10, 100),
+        tx_reference(3, "0x3", 20, 100),
+    ];
+
+    let tx_queue = queue_with_txs(ordering_policy, &txs);
+
+    assert_eq!(ready_tx_hashes(&tx_queue), expected_order.map(|i| tx_hash!(i)));
+}
+
+#[rstest]
+fn fair_fifo_serves_accounts_in_turn() {
+    let mut tx_queue = queue_with_txs(
+        TransactionOrderingPolicyType::FairFifo,
+        &[tx_reference(1, "0x1", 100, 100), tx_reference(2, "0x2", 0, 100)],
+    );
+
+    // The account's next transaction is queued after the transactions of other accounts.
+    assert_eq!(tx_queue.pop_ready_chunk(1)[0].tx_hash, tx_hash!(1));
+    tx_queue.insert(tx_reference(3, "0x1", 100, 100), false);
+
+    assert_eq!(ready_tx_hashes(&tx_queue), [tx_hash!(2), tx_hash!(3)]);
+}
+
+#[rstest]
+fn effective_fee_per_gas_follows_gas_price_threshold() {
+    // Effective fee per gas: min(max_l2_gas_price, gas_price_threshold + tip).
+    let low_max_price_tx = tx_reference(1, "0x1", 50, 105);
+    let high_max_price_tx = tx_reference(2, "0x2", 10, 200);
+    let mut tx_queue = TransactionQueue::with_ordering_policy(
+        TransactionOrderingPolicyType::EffectiveFeePerGas.policy(),
+    );
+    tx_queue.update_gas_price_threshold(GasPrice(100));
+    tx_queue.insert(low_max_price_tx, true);
+    tx_queue.insert(high_max_price_tx, true);
+
+    // Effective fees: 105 and 110.
+    assert_eq!(ready_tx_hashes(&tx_queue), [tx_hash!(2), tx_hash!(1)]);
+
+    // Effective fees: 100 and 60.
+    tx_queue.update_gas_price_threshold(GasPrice(50));
+    assert_eq!(ready_tx_hashes(&tx_queue), [tx_hash!(1), tx_hash!(2)]);
+
+    // Removal relies on the updated priorities.
+    assert!(tx_queue.remove(low_max_price_tx.address));
+    assert_eq!(ready_tx_hashes(&tx_queue), [tx_hash!(2)]);
+}
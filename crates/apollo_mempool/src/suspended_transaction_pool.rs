@@ -1,30 +1,133 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use apollo_time::time::DateTime;
 use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::TransactionHash;
 
 use crate::mempool::TransactionReference;
 
-type _AddressNonceToTransaction = HashMap<(ContractAddress, Nonce), TransactionReference>;
+#[cfg(test)]
+#[path = "suspended_transaction_pool_test.rs"]
+pub mod suspended_transaction_pool_test;
 
-#[derive(Debug)]
-pub struct _SuspendedTransactionPool {
-    suspended_tx_pool: _AddressNonceToTransaction,
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct SuspendedTransaction {
+    tx_reference: TransactionReference,
+    submission_time: DateTime,
+    size_in_bytes: u64,
 }
 
-impl _SuspendedTransactionPool {
-    pub fn _contains(&self, address: ContractAddress, nonce: Nonce) -> bool {
-        self.suspended_tx_pool.contains_key(&(address, nonce))
+/// Tracks the transactions that cannot be sequenced due to a nonce gap: those whose nonce is
+/// greater than the account nonce, with at least one missing nonce in between.
+/// Suspended transactions are still held by the transaction pool; this pool only indexes them, so
+/// that they can be limited, expired and promoted independently once the gap is filled.
+#[derive(Debug, Default)]
+pub struct SuspendedTransactionPool {
+    // Suspended transactions organized by account address, sorted by ascending nonce values.
+    txs_by_account: HashMap<ContractAddress, BTreeMap<Nonce, SuspendedTransaction>>,
+    // Suspended transactions sorted by their submission time (i.e. oldest to newest).
+    txs_by_submission_time: BTreeMap<(DateTime, TransactionHash), TransactionReference>,
+    size_in_bytes: u64,
+}
+
+impl SuspendedTransactionPool {
+    pub fn len(&self) -> usize {
+        self.txs_by_submission_time.len()
+    }
+
+    pub fn size_in_bytes(&self) -> u64 {
+        self.size_in_bytes
+    }
+
+    pub fn account_len(&self, address: ContractAddress) -> usize {
+        self.txs_by_account.get(&address).map_or(0, BTreeMap::len)
+    }
+
+    pub fn contains(&self, address: ContractAddress, nonce: Nonce) -> bool {
+        self.txs_by_account
+            .get(&address)
+            .is_some_and(|account_txs| account_txs.contains_key(&nonce))
     }
 
-    pub fn _insert(&mut self, tx: TransactionReference) {
+    pub fn insert(
+        &mut self,
+        tx_reference: TransactionReference,
+        submission_time: DateTime,
+        size_in_bytes: u64,
+    ) {
+        let suspended_tx = SuspendedTransaction { tx_reference, submission_time, size_in_bytes };
         assert_eq!(
-            self.suspended_tx_pool.insert((tx.address, tx.nonce), tx),
+            self.txs_by_account
+                .entry(tx_reference.address)
+                .or_default()
+                .insert(tx_reference.nonce, suspended_tx),
             None,
             "Keys should be unique; duplicates are checked prior."
         );
+        self.txs_by_submission_time.insert((submission_time, tx_reference.tx_hash), tx_reference);
+        self.size_in_bytes = self
+            .size_in_bytes
+            .checked_add(size_in_bytes)
+            .expect("Overflow when adding to SuspendedTransactionPool size_in_bytes.");
     }
 
-    pub fn _remove(&mut self, tx: &TransactionReference) -> bool {
-        self.suspended_tx_pool.remove(&(tx.address, tx.nonce)).is_some()
+    /// Removes the given transaction, if suspended. Returns true if it was removed.
+    pub fn remove(&mut self, tx: &TransactionReference) -> bool {
+        let Some(account_txs) = self.txs_by_account.get_mut(&tx.address) else {
+            return false;
+        };
+        let Some(removed_tx) = account_txs.remove(&tx.nonce) else {
+            return false;
+        };
+        if account_txs.is_empty() {
+            self.txs_by_account.remove(&tx.address);
+        }
+
+        self.remove_from_timed_mapping(&removed_tx);
+        true
+    }
+
+    /// Removes all suspended transactions of the given account.
+    pub fn remove_account(&mut self, address: ContractAddress) -> Vec<TransactionReference> {
+        let Some(account_txs) = self.txs_by_account.remove(&address) else {
+            return Vec::new();
+        };
+
+        account_txs
+            .into_values()
+            .map(|removed_tx| {
+                self.remove_from_timed_mapping(&removed_tx);
+                removed_tx.tx_reference
+            })
+            .collect()
+    }
+
+    /// Returns the suspended transactions that were submitted before the given time.
+    pub fn txs_submitted_before(
+        &self,
+        submission_cutoff_time: DateTime,
+    ) -> Vec<TransactionReference> {
+        self.txs_by_submission_time
+            .iter()
+            .take_while(|((submission_time, _), _)| *submission_time < submission_cutoff_time)
+            .map(|(_, tx_reference)| *tx_reference)
+            .collect()
+    }
+
+    /// Returns the hashes of the suspended transactions, sorted from newest to oldest.
+    pub fn chronological_txs_hashes(&self) -> Vec<TransactionHash> {
+        self.txs_by_submission_time.keys().rev().map(|(_, tx_hash)| *tx_hash).collect()
+    }
+
+    fn remove_from_timed_mapping(&mut self, removed_tx: &SuspendedTransaction) {
+        let SuspendedTransaction { tx_reference, submission_time, size_in_bytes } = removed_tx;
+        self.txs_by_submission_time.remove(&(*submission_time, tx_reference.tx_hash)).expect(
+            "Suspended transaction pool consistency error: transaction does not appear in the \
+             timed mapping.",
+        );
+        self.size_in_bytes = self
+            .size_in_bytes
+            .checked_sub(*size_in_bytes)
+            .expect("Underflow when subtracting from SuspendedTransactionPool size_in_bytes.");
     }
 }
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use apollo_time::test_utils::FakeClock;
+use apollo_time::time::Clock;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::{contract_address, nonce, tx_hash};
+
+use crate::mempool::TransactionReference;
+use crate::suspended_transaction_pool::SuspendedTransactionPool;
+use crate::tx;
+
+#[rstest]
+fn remove_account_updates_size_and_timed_mapping() {
+    let clock = FakeClock::default();
+    let mut pool = SuspendedTransactionPool::default();
+
+    let tx_address_0_nonce_2 =
+        TransactionReference::new(&tx!(tx_hash: 1, address: "0x0", tx_nonce: 2));
+    let tx_address_0_nonce_4 =
+        TransactionReference::new(&tx!(tx_hash: 2, address: "0x0", tx_nonce: 4));
+    let tx_address_1_nonce_3 =
+        TransactionReference::new(&tx!(tx_hash: 3, address: "0x1", tx_nonce: 3));
+
+    pool.insert(tx_address_0_nonce_2, clock.now(), 10);
+    clock.advance(Duration::from_secs(1));
+    pool.insert(tx_address_1_nonce_3, clock.now(), 20);
+    clock.advance(Duration::from_secs(1));
+    pool.insert(tx_address_0_nonce_4, clock.now(), 30);
+
+    assert_eq!(pool.len(), 3);
+    assert_eq!(pool.size_in_bytes(), 60);
+    assert_eq!(pool.account_len(contract_address!("0x0")), 2);
+    assert_eq!(pool.chronological_txs_hashes(), vec![tx_hash!(2), tx_hash!(3), tx_hash!(1)]);
+
+    let removed_txs = pool.remove_account(contract_address!("0x0"));
+    assert_eq!(removed_txs, vec![tx_address_0_nonce_2, tx_address_0_nonce_4]);
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.size_in_bytes(), 20);
+    assert!(!pool.contains(contract_address!("0x0"), nonce!(2)));
+    assert!(pool.contains(contract_address!("0x1"), nonce!(3)));
+}
+
+#[rstest]
+fn txs_submitted_before_returns_oldest_txs() {
+    let clock = FakeClock::default();
+    let mut pool = SuspendedTransactionPool::default();
+
+    let old_tx = TransactionReference::new(&tx!(tx_hash: 1, address: "0x0", tx_nonce: 2));
+    let new_tx = TransactionReference::new(&tx!(tx_hash: 2, address: "0x1", tx_nonce: 2));
+
+    pool.insert(old_tx, clock.now(), 10);
+    clock.advance(Duration::from_secs(5));
+    let cutoff_time = clock.now();
+    pool.insert(new_tx, clock.now(), 10);
+
+    assert_eq!(pool.txs_submitted_before(cutoff_time), vec![old_tx]);
+
+    assert!(pool.remove(&old_tx));
+    assert!(!pool.remove(&old_tx));
+    assert!(pool.txs_submitted_before(cutoff_time).is_empty());
+}
@@ -20,6 +20,7 @@ use crate::metrics::{
     MEMPOOL_PENDING_QUEUE_SIZE,
     MEMPOOL_POOL_SIZE,
     MEMPOOL_PRIORITY_QUEUE_SIZE,
+    MEMPOOL_SUSPENDED_POOL_SIZE,
     MEMPOOL_TOTAL_SIZE_BYTES,
     MEMPOOL_TRANSACTIONS_COMMITTED,
     MEMPOOL_TRANSACTIONS_DROPPED,
@@ -294,6 +295,7 @@ pub struct MempoolMetrics {
     pub pending_queue_size: u64,
     pub get_txs_size: u64,
     pub delayed_declares_size: u64,
+    pub suspended_pool_size: u64,
     pub total_size_in_bytes: u64,
     pub evictions_count: u64,
     pub transaction_time_spent_in_mempool: HistogramValue,
@@ -339,6 +341,7 @@ impl MempoolMetrics {
         MEMPOOL_PENDING_QUEUE_SIZE.assert_eq(metrics, self.pending_queue_size);
         MEMPOOL_GET_TXS_SIZE.assert_eq(metrics, self.get_txs_size);
         MEMPOOL_DELAYED_DECLARES_SIZE.assert_eq(metrics, self.delayed_declares_size);
+        MEMPOOL_SUSPENDED_POOL_SIZE.assert_eq(metrics, self.suspended_pool_size);
         MEMPOOL_TOTAL_SIZE_BYTES.assert_eq(metrics, self.total_size_in_bytes);
         TRANSACTION_TIME_SPENT_IN_MEMPOOL
             .assert_eq(metrics, &self.transaction_time_spent_in_mempool);
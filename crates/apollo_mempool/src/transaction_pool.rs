@@ -54,6 +54,17 @@ impl TransactionPool {
     }
 
     pub fn insert(&mut self, tx: InternalRpcTransaction) -> MempoolResult<()> {
+        let submission_time = self.txs_by_submission_time.clock.now();
+        self.insert_with_submission_time(tx, submission_time)
+    }
+
+    /// Inserts a transaction that was submitted at the given time (e.g., a transaction restored
+    /// from a previous run).
+    pub fn insert_with_submission_time(
+        &mut self,
+        tx: InternalRpcTransaction,
+        submission_time: DateTime,
+    ) -> MempoolResult<()> {
         let tx_reference = TransactionReference::new(&tx);
         let tx_hash = tx_reference.tx_hash;
         let tx_size = tx.total_bytes();
@@ -76,7 +87,8 @@ impl TransactionPool {
         };
 
         // Insert to timed mapping.
-        let unexpected_existing_tx = self.txs_by_submission_time.insert(tx_reference);
+        let unexpected_existing_tx =
+            self.txs_by_submission_time.insert(tx_reference, submission_time);
         if unexpected_existing_tx.is_some() {
             panic!(
                 "Transaction pool consistency error: transaction with hash {tx_hash} does not
@@ -105,13 +117,17 @@ impl TransactionPool {
         Ok(tx)
     }
 
-    pub fn remove_up_to_nonce(&mut self, address: ContractAddress, nonce: Nonce) -> usize {
+    pub fn remove_up_to_nonce(
+        &mut self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Vec<TransactionReference> {
         let removed_txs = self.txs_by_account.remove_up_to_nonce(address, nonce);
 
         self.remove_from_main_mapping(&removed_txs);
         self.remove_from_timed_mapping(&removed_txs);
 
-        removed_txs.len()
+        removed_txs
     }
 
     pub fn remove_txs_older_than(
@@ -350,8 +366,12 @@ impl TimedTransactionMap {
 
     /// If a transaction with the same transaction hash already exists in the mapping, the previous
     /// submission ID is returned.
-    fn insert(&mut self, tx: TransactionReference) -> Option<SubmissionID> {
-        let submission_id = SubmissionID { submission_time: self.clock.now(), tx_hash: tx.tx_hash };
+    fn insert(
+        &mut self,
+        tx: TransactionReference,
+        submission_time: DateTime,
+    ) -> Option<SubmissionID> {
+        let submission_id = SubmissionID { submission_time, tx_hash: tx.tx_hash };
         self.txs_by_submission_time.insert(submission_id.clone(), tx);
         self.hash_to_submission_id.insert(tx.tx_hash, submission_id)
     }
@@ -8,6 +8,11 @@ use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
 
 use crate::mempool::TransactionReference;
+use crate::ordering_policy::{
+    TransactionOrderingPolicy,
+    TransactionOrderingPolicyType,
+    TransactionPriority,
+};
 
 #[cfg(test)]
 #[path = "transaction_queue_test_utils.rs"]
@@ -16,25 +21,49 @@ pub mod transaction_queue_test_utils;
 // A queue holding the transaction that with nonces that match account nonces.
 // Note: the derived comparison functionality considers the order guaranteed by the data structures
 // used.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct TransactionQueue {
     gas_price_threshold: GasPrice,
-    // Transactions with gas price above gas price threshold (sorted by tip).
+    // Transactions with gas price above gas price threshold (sorted by the ordering policy).
     priority_queue: BTreeSet<PriorityTransaction>,
     // Transactions with gas price below gas price threshold (sorted by price).
     pending_queue: BTreeSet<PendingTransaction>,
     // Set of account addresses for efficient existence checks.
-    address_to_tx: HashMap<ContractAddress, TransactionReference>,
+    address_to_tx: HashMap<ContractAddress, QueuedTransaction>,
+    // Determines the order of the priority queue.
+    ordering_policy: Box<dyn TransactionOrderingPolicy>,
+    // The insertion index of the next transaction inserted to the queue.
+    next_insertion_index: u64,
+}
+
+impl Default for TransactionQueue {
+    fn default() -> Self {
+        TransactionQueue::with_ordering_policy(TransactionOrderingPolicyType::default().policy())
+    }
 }
 
 impl TransactionQueue {
+    pub fn with_ordering_policy(ordering_policy: Box<dyn TransactionOrderingPolicy>) -> Self {
+        TransactionQueue {
+            gas_price_threshold: GasPrice::default(),
+            priority_queue: BTreeSet::new(),
+            pending_queue: BTreeSet::new(),
+            address_to_tx: HashMap::new(),
+            ordering_policy,
+            next_insertion_index: 0,
+        }
+    }
+
     /// Adds a transaction to the mempool, ensuring unique keys.
     /// Panics: if given a duplicate tx.
     /// If `validate_resource_bounds` is false, the transaction is added to the priority queue,
     /// regardless of it's L2 gas price bound.
     pub fn insert(&mut self, tx_reference: TransactionReference, validate_resource_bounds: bool) {
+        let queued_tx =
+            QueuedTransaction { tx_reference, insertion_index: self.next_insertion_index };
+        self.next_insertion_index += 1;
         assert_eq!(
-            self.address_to_tx.insert(tx_reference.address, tx_reference),
+            self.address_to_tx.insert(tx_reference.address, queued_tx),
             None,
             "Only a single transaction from the same contract class can be in the mempool at a \
              time."
@@ -45,7 +74,8 @@ impl TransactionQueue {
         let new_tx_successfully_inserted = if to_pending_queue {
             self.pending_queue.insert(tx_reference.into())
         } else {
-            self.priority_queue.insert(tx_reference.into())
+            let priority_tx = self.to_priority_tx(queued_tx);
+            self.priority_queue.insert(priority_tx)
         };
         assert!(
             new_tx_successfully_inserted,
@@ -64,7 +94,7 @@ impl TransactionQueue {
     // TODO(gilad): remove collect, if returning an iterator is possible.
     pub fn pop_ready_chunk(&mut self, n_txs: usize) -> Vec<TransactionReference> {
         let txs: Vec<TransactionReference> =
-            (0..n_txs).filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.0)).collect();
+            (0..n_txs).filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.tx)).collect();
         for tx in &txs {
             self.address_to_tx.remove(&tx.address);
         }
@@ -75,22 +105,23 @@ impl TransactionQueue {
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter_over_ready_txs(&self) -> impl Iterator<Item = &TransactionReference> {
-        self.priority_queue.iter().rev().map(|tx| &tx.0)
+        self.priority_queue.iter().rev().map(|tx| &tx.tx)
     }
 
     pub fn get_nonce(&self, address: ContractAddress) -> Option<Nonce> {
-        self.address_to_tx.get(&address).map(|tx| tx.nonce)
+        self.address_to_tx.get(&address).map(|queued_tx| queued_tx.tx_reference.nonce)
     }
 
     /// Removes the transaction of the given account address from the queue.
     /// This is well-defined, since there is at most one transaction per address in the queue.
     pub fn remove(&mut self, address: ContractAddress) -> bool {
-        let Some(tx_reference) = self.address_to_tx.remove(&address) else {
+        let Some(queued_tx) = self.address_to_tx.remove(&address) else {
             return false;
         };
 
-        self.priority_queue.remove(&tx_reference.into())
-            || self.pending_queue.remove(&tx_reference.into())
+        let priority_tx = self.to_priority_tx(queued_tx);
+        self.priority_queue.remove(&priority_tx)
+            || self.pending_queue.remove(&queued_tx.tx_reference.into())
     }
 
     /// Removes the given transactions from the queue.
@@ -99,7 +130,7 @@ impl TransactionQueue {
         let mut removed_txs = Vec::new();
         for tx in txs {
             let queued_tx = self.address_to_tx.get(&tx.address);
-            if queued_tx.is_some_and(|queued_tx| queued_tx.tx_hash == tx.tx_hash) {
+            if queued_tx.is_some_and(|queued_tx| queued_tx.tx_reference.tx_hash == tx.tx_hash) {
                 self.remove(tx.address);
                 removed_txs.push(*tx);
             };
@@ -115,10 +146,33 @@ impl TransactionQueue {
         match threshold.cmp(&self.gas_price_threshold) {
             Ordering::Less => self.promote_txs_to_priority(threshold),
             Ordering::Greater => self.demote_txs_to_pending(threshold),
-            Ordering::Equal => {}
+            Ordering::Equal => return,
         }
 
         self.gas_price_threshold = threshold;
+        if self.ordering_policy.depends_on_gas_price_threshold() {
+            self.reprioritize();
+        }
+    }
+
+    /// Recomputes the priorities of the transactions in the priority queue.
+    fn reprioritize(&mut self) {
+        let priority_queue = std::mem::take(&mut self.priority_queue);
+        self.priority_queue = priority_queue
+            .into_iter()
+            .map(|priority_tx| self.to_priority_tx(self.queued_tx(priority_tx.tx.address)))
+            .collect();
+    }
+
+    fn queued_tx(&self, address: ContractAddress) -> QueuedTransaction {
+        *self.address_to_tx.get(&address).expect("Queued transaction must appear in the mapping.")
+    }
+
+    fn to_priority_tx(&self, queued_tx: QueuedTransaction) -> PriorityTransaction {
+        let QueuedTransaction { tx_reference, insertion_index } = queued_tx;
+        let priority =
+            self.ordering_policy.priority(&tx_reference, insertion_index, self.gas_price_threshold);
+        PriorityTransaction { priority, tx: tx_reference }
     }
 
     fn promote_txs_to_priority(&mut self, threshold: GasPrice) {
@@ -135,9 +189,11 @@ impl TransactionQueue {
 
         // Insert all transactions from the split point into the priority queue, skip
         // `tmp_split_tx`.
-        // Note: extend will reorder transactions by `Tip` during insertion, despite them being
+        // Note: extend will reorder transactions by priority during insertion, despite them being
         // initially ordered by fee.
-        self.priority_queue.extend(txs_over_threshold.map(|tx| PriorityTransaction::from(tx.0)));
+        let txs_over_threshold: Vec<_> =
+            txs_over_threshold.map(|tx| self.to_priority_tx(self.queued_tx(tx.address))).collect();
+        self.priority_queue.extend(txs_over_threshold);
     }
 
     fn demote_txs_to_pending(&mut self, threshold: GasPrice) {
@@ -145,7 +201,7 @@ impl TransactionQueue {
 
         // Remove all transactions from the priority queue that are below the threshold.
         for priority_tx in &self.priority_queue {
-            if priority_tx.max_l2_gas_price < threshold {
+            if priority_tx.tx.max_l2_gas_price < threshold {
                 txs_to_remove.push(*priority_tx);
             }
         }
@@ -153,11 +209,11 @@ impl TransactionQueue {
         for tx in &txs_to_remove {
             self.priority_queue.remove(tx);
         }
-        self.pending_queue.extend(txs_to_remove.iter().map(|tx| PendingTransaction::from(tx.0)));
+        self.pending_queue.extend(txs_to_remove.iter().map(|tx| PendingTransaction::from(tx.tx)));
     }
 
     pub fn queue_snapshot(&self) -> TransactionQueueSnapshot {
-        let priority_queue = self.priority_queue.iter().map(|tx| tx.0.tx_hash).collect();
+        let priority_queue = self.priority_queue.iter().map(|tx| tx.tx.tx_hash).collect();
         let pending_queue = self.pending_queue.iter().map(|tx| tx.0.tx_hash).collect();
 
         TransactionQueueSnapshot {
@@ -199,14 +255,25 @@ impl PartialOrd for PendingTransaction {
     }
 }
 
+/// A transaction in the queue, along with its insertion index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct QueuedTransaction {
+    tx_reference: TransactionReference,
+    insertion_index: u64,
+}
+
 /// This struct behaves similarly to `PendingTransaction`, encapsulating a transaction reference
-/// to assess its order (i.e., tip); see its documentation for more details.
-#[derive(Clone, Copy, Debug, derive_more::Deref, derive_more::From)]
-struct PriorityTransaction(pub TransactionReference);
+/// to assess its order (i.e., its priority according to the ordering policy); see its
+/// documentation for more details.
+#[derive(Clone, Copy, Debug)]
+struct PriorityTransaction {
+    priority: TransactionPriority,
+    tx: TransactionReference,
+}
 
 impl PartialEq for PriorityTransaction {
     fn eq(&self, other: &PriorityTransaction) -> bool {
-        self.tip == other.tip && self.tx_hash == other.tx_hash
+        self.priority == other.priority && self.tx.tx_hash == other.tx.tx_hash
     }
 }
 
@@ -214,7 +281,7 @@ impl Eq for PriorityTransaction {}
 
 impl Ord for PriorityTransaction {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.tip.cmp(&other.tip).then_with(|| self.tx_hash.cmp(&other.tx_hash))
+        self.priority.cmp(&other.priority).then_with(|| self.tx.tx_hash.cmp(&other.tx.tx_hash))
     }
 }
 
@@ -1,9 +1,7 @@
-use std::collections::HashMap;
-
 use starknet_api::block::GasPrice;
 
 use crate::mempool::TransactionReference;
-use crate::transaction_queue::{PendingTransaction, PriorityTransaction, TransactionQueue};
+use crate::transaction_queue::{PendingTransaction, QueuedTransaction, TransactionQueue};
 
 impl TransactionQueue {
     pub fn new(
@@ -11,22 +9,28 @@ impl TransactionQueue {
         pending_queue: Vec<TransactionReference>,
         gas_price_threshold: GasPrice,
     ) -> Self {
+        let mut tx_queue = TransactionQueue { gas_price_threshold, ..Default::default() };
+
         // Build address to nonce mapping, check queues are mutually exclusive in addresses.
         let tx_references = pending_queue.iter().chain(priority_queue.iter());
-        let mut address_to_tx = HashMap::new();
         for tx_ref in tx_references {
             let address = tx_ref.address;
-            if address_to_tx.insert(address, *tx_ref).is_some() {
+            let queued_tx = QueuedTransaction {
+                tx_reference: *tx_ref,
+                insertion_index: tx_queue.next_insertion_index,
+            };
+            tx_queue.next_insertion_index += 1;
+            if tx_queue.address_to_tx.insert(address, queued_tx).is_some() {
                 panic!("Duplicate address: {address}; queues must be mutually exclusive.");
             }
         }
 
-        TransactionQueue {
-            priority_queue: priority_queue.into_iter().map(PriorityTransaction).collect(),
-            pending_queue: pending_queue.into_iter().map(PendingTransaction).collect(),
-            address_to_tx,
-            gas_price_threshold,
-        }
+        tx_queue.pending_queue = pending_queue.into_iter().map(PendingTransaction).collect();
+        tx_queue.priority_queue = priority_queue
+            .into_iter()
+            .map(|tx_ref| tx_queue.to_priority_tx(tx_queue.queued_tx(tx_ref.address)))
+            .collect();
+        tx_queue
     }
 
     pub fn pending_txs(&self) -> Vec<TransactionReference> {
@@ -12,11 +12,12 @@ use serde::{Deserialize, Serialize};
 use starknet_api::block::GasPrice;
 use starknet_api::core::ContractAddress;
 use starknet_api::rpc_transaction::InternalRpcTransaction;
+use starknet_api::transaction::TransactionHash;
 use strum_macros::AsRefStr;
 use thiserror::Error;
 
 use crate::errors::MempoolError;
-use crate::mempool_types::{AddTransactionArgs, CommitBlockArgs, MempoolSnapshot};
+use crate::mempool_types::{AddTransactionArgs, CommitBlockArgs, MempoolEvents, MempoolSnapshot};
 
 pub type LocalMempoolClient = LocalComponentClient<MempoolRequest, MempoolResponse>;
 pub type RemoteMempoolClient = RemoteComponentClient<MempoolRequest, MempoolResponse>;
@@ -25,6 +26,7 @@ pub type MempoolClientResult<T> = Result<T, MempoolClientError>;
 pub type MempoolRequestAndResponseSender =
     ComponentRequestAndResponseSender<MempoolRequest, MempoolResponse>;
 pub type SharedMempoolClient = Arc<dyn MempoolClient>;
+pub type SharedMempoolOperatorClient = Arc<dyn MempoolOperatorClient>;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AddTransactionArgsWrapper {
@@ -48,6 +50,27 @@ pub trait MempoolClient: Send + Sync {
     ) -> MempoolClientResult<bool>;
     async fn update_gas_price(&self, gas_price: GasPrice) -> MempoolClientResult<()>;
     async fn get_mempool_snapshot(&self) -> MempoolClientResult<MempoolSnapshot>;
+    async fn get_tx(&self, tx_hash: TransactionHash)
+        -> MempoolClientResult<InternalRpcTransaction>;
+    async fn get_account_txs(
+        &self,
+        contract_address: ContractAddress,
+    ) -> MempoolClientResult<Vec<InternalRpcTransaction>>;
+    /// Returns the recent mempool events, starting at the given sequence number.
+    async fn get_events(&self, sequence_number: u64) -> MempoolClientResult<MempoolEvents>;
+}
+
+/// Serves the mempool's operator-only requests. Kept apart from [`MempoolClient`], so that only the
+/// components given an operator client, and not every holder of a mempool client, can make them.
+#[cfg_attr(any(feature = "testing", test), automock)]
+#[async_trait]
+pub trait MempoolOperatorClient: Send + Sync {
+    /// Removes the given transactions from the mempool, and returns the hashes of the removed
+    /// ones.
+    async fn remove_txs(
+        &self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> MempoolClientResult<Vec<TransactionHash>>;
 }
 
 #[derive(Clone, Serialize, Deserialize, AsRefStr)]
@@ -59,6 +82,10 @@ pub enum MempoolRequest {
     // TODO(yair): Rename to `StartBlock` and add cleanup of staged txs.
     UpdateGasPrice(GasPrice),
     GetMempoolSnapshot(),
+    GetTransaction(TransactionHash),
+    GetAccountTransactions(ContractAddress),
+    RemoveTransactions(Vec<TransactionHash>),
+    GetEvents(u64),
 }
 impl_debug_for_infra_requests_and_responses!(MempoolRequest);
 
@@ -70,6 +97,10 @@ pub enum MempoolResponse {
     AccountTxInPoolOrRecentBlock(MempoolResult<bool>),
     UpdateGasPrice(MempoolResult<()>),
     GetMempoolSnapshot(MempoolResult<MempoolSnapshot>),
+    GetTransaction(MempoolResult<InternalRpcTransaction>),
+    GetAccountTransactions(MempoolResult<Vec<InternalRpcTransaction>>),
+    RemoveTransactions(MempoolResult<Vec<TransactionHash>>),
+    GetEvents(MempoolResult<MempoolEvents>),
 }
 impl_debug_for_infra_requests_and_responses!(MempoolResponse);
 
@@ -154,4 +185,63 @@ where
             Direct
         )
     }
+
+    async fn get_tx(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolClientResult<InternalRpcTransaction> {
+        let request = MempoolRequest::GetTransaction(tx_hash);
+        handle_all_response_variants!(
+            MempoolResponse,
+            GetTransaction,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+
+    async fn get_account_txs(
+        &self,
+        contract_address: ContractAddress,
+    ) -> MempoolClientResult<Vec<InternalRpcTransaction>> {
+        let request = MempoolRequest::GetAccountTransactions(contract_address);
+        handle_all_response_variants!(
+            MempoolResponse,
+            GetAccountTransactions,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+
+    async fn get_events(&self, sequence_number: u64) -> MempoolClientResult<MempoolEvents> {
+        let request = MempoolRequest::GetEvents(sequence_number);
+        handle_all_response_variants!(
+            MempoolResponse,
+            GetEvents,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+}
+
+#[async_trait]
+impl<ComponentClientType> MempoolOperatorClient for ComponentClientType
+where
+    ComponentClientType: Send + Sync + ComponentClient<MempoolRequest, MempoolResponse>,
+{
+    async fn remove_txs(
+        &self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> MempoolClientResult<Vec<TransactionHash>> {
+        let request = MempoolRequest::RemoveTransactions(tx_hashes);
+        handle_all_response_variants!(
+            MempoolResponse,
+            RemoveTransactions,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
 }
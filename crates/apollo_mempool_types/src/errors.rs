@@ -13,10 +13,24 @@ pub enum MempoolError {
     NonceTooLarge(Nonce),
     #[error("Invalid transaction nonce. Expected: {account_nonce}, got: {tx_nonce}.")]
     NonceTooOld { address: ContractAddress, tx_nonce: Nonce, account_nonce: Nonce },
+    #[error(
+        "Invalid transaction nonce: {tx_nonce} is too far ahead of the account nonce \
+         {account_nonce}, sender address: {address}."
+    )]
+    NonceTooFarAhead { address: ContractAddress, tx_nonce: Nonce, account_nonce: Nonce },
     #[error("Transaction with hash: {tx_hash} could not be sent using p2p client.")]
     P2pPropagatorClientError { tx_hash: TransactionHash },
     #[error("Transaction with hash: {tx_hash} not found")]
     TransactionNotFound { tx_hash: TransactionHash },
     #[error("Transaction rejected: mempool capacity exceeded.")]
     MempoolFull,
+    #[error(
+        "Transaction rejected: suspended transactions capacity exceeded, sender address: \
+         {address}."
+    )]
+    SuspendedPoolFull { address: ContractAddress },
+    #[error("Transaction rejected: too many transactions from sender address: {address}.")]
+    AccountTxLimitExceeded { address: ContractAddress },
+    #[error("Transaction rejected: transactions size limit exceeded, sender address: {address}.")]
+    AccountSizeLimitExceeded { address: ContractAddress },
 }
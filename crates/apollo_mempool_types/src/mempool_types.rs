@@ -41,6 +41,7 @@ pub type MempoolResult<T> = Result<T, MempoolError>;
 pub struct MempoolSnapshot {
     pub transactions: Vec<TransactionHash>,
     pub delayed_declares: Vec<TransactionHash>,
+    pub suspended_transactions: Vec<TransactionHash>,
     pub transaction_queue: TransactionQueueSnapshot,
     pub mempool_state: MempoolStateSnapshot,
 }
@@ -57,3 +58,33 @@ pub struct MempoolStateSnapshot {
     pub committed: HashMap<ContractAddress, Nonce>,
     pub staged: HashMap<ContractAddress, Nonce>,
 }
+
+/// The reason a transaction entered or left the mempool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MempoolEventReason {
+    Added,
+    ReplacedByFeeEscalation,
+    Evicted,
+    Expired,
+    Included,
+    Rejected,
+    Removed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MempoolEvent {
+    // Increases by one with each event emitted by the mempool.
+    pub sequence_number: u64,
+    pub tx_hash: TransactionHash,
+    pub reason: MempoolEventReason,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MempoolEvents {
+    // Identifies the event feed; it changes when the mempool restarts, and sequence numbers start
+    // over.
+    pub feed_id: u64,
+    pub events: Vec<MempoolEvent>,
+    // The sequence number of the next event to be emitted; used to request the following events.
+    pub next_sequence_number: u64,
+}
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Result};
 use std::net::{IpAddr, Ipv4Addr};
 
-use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -16,6 +16,8 @@ pub struct MonitoringEndpointConfig {
     pub port: u16,
     pub collect_metrics: bool,
     pub collect_profiling_metrics: bool,
+    /// The key that authorizes the operator-only routes. If not set, these routes are disabled.
+    pub operator_api_key: Option<String>,
 }
 
 impl MonitoringEndpointConfig {
@@ -25,6 +27,7 @@ impl MonitoringEndpointConfig {
             port: MONITORING_ENDPOINT_DEFAULT_PORT,
             collect_metrics: true,
             collect_profiling_metrics: true,
+            operator_api_key: None,
         }
     }
 }
@@ -37,7 +40,7 @@ impl Default for MonitoringEndpointConfig {
 
 impl SerializeConfig for MonitoringEndpointConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut dump = BTreeMap::from_iter([
             ser_param(
                 "ip",
                 &self.ip.to_string(),
@@ -62,7 +65,16 @@ impl SerializeConfig for MonitoringEndpointConfig {
                 "If true, collect and return profiling metrics in the monitoring endpoint.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+        ]);
+        dump.extend(ser_optional_param(
+            &self.operator_api_key,
+            String::new(),
+            "operator_api_key",
+            "The key that authorizes the operator-only routes of the monitoring endpoint, given \
+             as a bearer token. If not set, these routes are disabled.",
+            ParamPrivacyInput::Private,
+        ));
+        dump
     }
 }
 
@@ -1,17 +1,25 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
+use apollo_consensus::equivocation::{EquivocationEvidence, EquivocationEvidenceStore};
 use apollo_infra::component_definitions::ComponentStarter;
 use apollo_infra_utils::type_name::short_type_name;
 use apollo_l1_provider_types::{L1ProviderSnapshot, SharedL1ProviderClient};
-use apollo_mempool_types::communication::SharedMempoolClient;
-use apollo_mempool_types::mempool_types::MempoolSnapshot;
+use apollo_mempool_types::communication::{SharedMempoolClient, SharedMempoolOperatorClient};
+use apollo_mempool_types::mempool_types::{MempoolEvents, MempoolSnapshot};
 use apollo_metrics::metrics::COLLECT_SEQUENCER_PROFILING_METRICS;
-use axum::http::StatusCode;
+use async_stream::stream;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::{async_trait, Json, Router, Server};
+use futures::Stream;
 use hyper::Error;
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use starknet_api::transaction::TransactionHash;
+use tokio::time::interval;
 use tracing::{error, info, instrument};
 
 use crate::config::MonitoringEndpointConfig;
@@ -26,7 +34,12 @@ pub(crate) const READY: &str = "ready";
 pub(crate) const VERSION: &str = "nodeVersion";
 pub(crate) const METRICS: &str = "metrics";
 pub(crate) const MEMPOOL_SNAPSHOT: &str = "mempoolSnapshot";
+pub(crate) const MEMPOOL_EVENTS: &str = "mempoolEvents";
+pub(crate) const REMOVE_MEMPOOL_TRANSACTIONS: &str = "removeMempoolTransactions";
 pub(crate) const L1_PROVIDER_SNAPSHOT: &str = "l1ProviderSnapshot";
+pub(crate) const CONSENSUS_EQUIVOCATIONS: &str = "consensusEquivocations";
+
+const MEMPOOL_EVENTS_POLLING_INTERVAL: Duration = Duration::from_millis(500);
 
 const HISTOGRAM_BUCKETS: &[f64] =
     &[0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0];
@@ -36,7 +49,9 @@ pub struct MonitoringEndpoint {
     version: &'static str,
     prometheus_handle: Option<PrometheusHandle>,
     mempool_client: Option<SharedMempoolClient>,
+    mempool_operator_client: Option<SharedMempoolOperatorClient>,
     l1_provider_client: Option<SharedL1ProviderClient>,
+    equivocation_evidence_store: Option<EquivocationEvidenceStore>,
 }
 
 impl MonitoringEndpoint {
@@ -44,7 +59,9 @@ impl MonitoringEndpoint {
         config: MonitoringEndpointConfig,
         version: &'static str,
         mempool_client: Option<SharedMempoolClient>,
+        mempool_operator_client: Option<SharedMempoolOperatorClient>,
         l1_provider_client: Option<SharedL1ProviderClient>,
+        equivocation_evidence_store: Option<EquivocationEvidenceStore>,
     ) -> Self {
         // TODO(Tsabary): consider error handling
         let prometheus_handle = if config.collect_metrics {
@@ -69,7 +86,9 @@ impl MonitoringEndpoint {
             version,
             prometheus_handle,
             mempool_client,
+            mempool_operator_client,
             l1_provider_client,
+            equivocation_evidence_store,
         }
     }
 
@@ -94,7 +113,11 @@ impl MonitoringEndpoint {
         let version = self.version.to_string();
         let prometheus_handle = self.prometheus_handle.clone();
         let mempool_client = self.mempool_client.clone();
+        let mempool_events_client = self.mempool_client.clone();
+        let mempool_operator_client = self.mempool_operator_client.clone();
+        let operator_api_key = self.config.operator_api_key.clone();
         let l1_provider_client = self.l1_provider_client.clone();
+        let equivocation_evidence_store = self.equivocation_evidence_store.clone();
 
         Router::new()
             .route(
@@ -117,10 +140,29 @@ impl MonitoringEndpoint {
                 format!("/{MONITORING_PREFIX}/{MEMPOOL_SNAPSHOT}").as_str(),
                 get(move || mempool_snapshot(mempool_client)),
             )
+            .route(
+                format!("/{MONITORING_PREFIX}/{MEMPOOL_EVENTS}").as_str(),
+                get(move || mempool_events(mempool_events_client)),
+            )
+            .route(
+                format!("/{MONITORING_PREFIX}/{REMOVE_MEMPOOL_TRANSACTIONS}").as_str(),
+                post(move |headers: HeaderMap, Json(tx_hashes): Json<Vec<TransactionHash>>| {
+                    remove_mempool_transactions(
+                        mempool_operator_client,
+                        operator_api_key,
+                        headers,
+                        tx_hashes,
+                    )
+                }),
+            )
             .route(
                 format!("/{MONITORING_PREFIX}/{L1_PROVIDER_SNAPSHOT}").as_str(),
                 get(move || get_l1_provider_snapshot(l1_provider_client)),
             )
+            .route(
+                format!("/{MONITORING_PREFIX}/{CONSENSUS_EQUIVOCATIONS}").as_str(),
+                get(move || consensus_equivocations(equivocation_evidence_store)),
+            )
     }
 }
 
@@ -128,9 +170,18 @@ pub fn create_monitoring_endpoint(
     config: MonitoringEndpointConfig,
     version: &'static str,
     mempool_client: Option<SharedMempoolClient>,
+    mempool_operator_client: Option<SharedMempoolOperatorClient>,
     l1_provider_client: Option<SharedL1ProviderClient>,
+    equivocation_evidence_store: Option<EquivocationEvidenceStore>,
 ) -> MonitoringEndpoint {
-    MonitoringEndpoint::new(config, version, mempool_client, l1_provider_client)
+    MonitoringEndpoint::new(
+        config,
+        version,
+        mempool_client,
+        mempool_operator_client,
+        l1_provider_client,
+        equivocation_evidence_store,
+    )
 }
 
 #[async_trait]
@@ -180,6 +231,115 @@ async fn mempool_snapshot(
     }
 }
 
+// Streams the mempool events as server-sent events, starting with the ones retained by the mempool.
+// Besides the events themselves (with their sequence number as id), the stream holds a `lagged`
+// event, with the number of missed events, when events were dropped before they were sent, and a
+// `reset` event when the mempool restarted and its sequence numbers started over.
+#[instrument(level = "debug", skip(mempool_client))]
+async fn mempool_events(
+    mempool_client: Option<SharedMempoolClient>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+    let Some(client) = mempool_client else {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    };
+
+    let events = stream! {
+        let mut next_sequence_number = 0;
+        let mut current_feed_id = None;
+        let mut polling_interval = interval(MEMPOOL_EVENTS_POLLING_INTERVAL);
+        loop {
+            polling_interval.tick().await;
+            // Wrap the mempool client interaction with a tokio::spawn as it is NOT cancel-safe.
+            let client = client.clone();
+            let mempool_events_result =
+                tokio::spawn(async move { client.get_events(next_sequence_number).await })
+                    .await
+                    .expect("Should be able to get mempool events result");
+
+            match mempool_events_result {
+                Ok(MempoolEvents {
+                    feed_id,
+                    events,
+                    next_sequence_number: following_sequence_number,
+                }) => {
+                    match current_feed_id {
+                        Some(known_feed_id) if known_feed_id != feed_id => {
+                            yield Ok(Event::default().event("reset").data(feed_id.to_string()));
+                            // Start over with the events retained by the new mempool.
+                            if next_sequence_number != 0 {
+                                next_sequence_number = 0;
+                                current_feed_id = Some(feed_id);
+                                continue;
+                            }
+                        }
+                        Some(_) => {
+                            let first_sequence_number = events
+                                .first()
+                                .map_or(following_sequence_number, |event| event.sequence_number);
+                            if first_sequence_number > next_sequence_number {
+                                let n_missed_events = first_sequence_number - next_sequence_number;
+                                let lagged_event = Event::default().event("lagged");
+                                yield Ok(lagged_event.data(n_missed_events.to_string()));
+                            }
+                        }
+                        None => {}
+                    }
+                    current_feed_id = Some(feed_id);
+
+                    for event in events {
+                        let event_id = event.sequence_number.to_string();
+                        yield Event::default().id(event_id).json_data(event);
+                    }
+                    next_sequence_number = following_sequence_number;
+                }
+                Err(err) => error!("Failed to get mempool events: {:?}", err),
+            }
+        }
+    };
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+// Removes the given transactions from the mempool, and returns the hashes of the removed ones. The
+// request must carry the operator API key of the config as a bearer token; without a configured
+// key, the route is disabled.
+#[instrument(level = "debug", skip(mempool_operator_client, operator_api_key, headers))]
+async fn remove_mempool_transactions(
+    mempool_operator_client: Option<SharedMempoolOperatorClient>,
+    operator_api_key: Option<String>,
+    headers: HeaderMap,
+    tx_hashes: Vec<TransactionHash>,
+) -> Result<Json<Vec<TransactionHash>>, StatusCode> {
+    let (Some(client), Some(operator_api_key)) = (mempool_operator_client, operator_api_key) else {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    };
+    let bearer_token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if bearer_token != Some(operator_api_key.as_str()) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    // Wrap the mempool client interaction with a tokio::spawn as it is NOT cancel-safe.
+    let remove_txs_result = tokio::spawn(async move { client.remove_txs(tx_hashes).await })
+        .await
+        .expect("Should be able to get the mempool transaction removal result");
+
+    match remove_txs_result {
+        Ok(removed_tx_hashes) => {
+            info!(
+                "Removed transactions from the mempool by operator request: {removed_tx_hashes:?}"
+            );
+            Ok(removed_tx_hashes.into())
+        }
+        Err(err) => {
+            error!("Failed to remove mempool transactions: {:?}", err);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 // Returns L1 provider snapshot
 #[instrument(level = "debug", skip(l1_provider_client))]
 async fn get_l1_provider_snapshot(
@@ -206,3 +366,14 @@ async fn get_l1_provider_snapshot(
         None => Err(StatusCode::METHOD_NOT_ALLOWED),
     }
 }
+
+// Returns the evidence of equivocating validators collected by consensus.
+#[instrument(level = "debug", skip(equivocation_evidence_store))]
+async fn consensus_equivocations(
+    equivocation_evidence_store: Option<EquivocationEvidenceStore>,
+) -> Result<Json<Vec<EquivocationEvidence>>, StatusCode> {
+    match equivocation_evidence_store {
+        Some(store) => Ok(store.get_all().into()),
+        None => Err(StatusCode::METHOD_NOT_ALLOWED),
+    }
+}
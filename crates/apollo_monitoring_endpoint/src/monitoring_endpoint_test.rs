@@ -2,17 +2,23 @@ use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::Arc;
 
+use apollo_consensus::equivocation::EquivocationEvidenceStore;
 use apollo_l1_provider_types::{L1ProviderSnapshot, MockL1ProviderClient};
-use apollo_mempool_types::communication::MockMempoolClient;
+use apollo_mempool_types::communication::{MockMempoolClient, MockMempoolOperatorClient};
 use apollo_mempool_types::mempool_types::{
+    MempoolEvent,
+    MempoolEventReason,
+    MempoolEvents,
     MempoolSnapshot,
     MempoolStateSnapshot,
     TransactionQueueSnapshot,
 };
-use axum::http::StatusCode;
+use axum::body::Body;
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{Request, StatusCode};
 use axum::response::Response;
 use axum::Router;
-use hyper::body::to_bytes;
+use hyper::body::{to_bytes, HttpBody};
 use hyper::Client;
 use metrics::{counter, describe_counter};
 use pretty_assertions::assert_eq;
@@ -30,10 +36,14 @@ use crate::monitoring_endpoint::{
     create_monitoring_endpoint,
     MonitoringEndpoint,
     ALIVE,
+    CONSENSUS_EQUIVOCATIONS,
     L1_PROVIDER_SNAPSHOT,
+    MEMPOOL_EVENTS,
     MEMPOOL_SNAPSHOT,
     METRICS,
+    MONITORING_PREFIX,
     READY,
+    REMOVE_MEMPOOL_TRANSACTIONS,
     VERSION,
 };
 use crate::test_utils::build_request;
@@ -47,11 +57,13 @@ const CONFIG_WITHOUT_METRICS: MonitoringEndpointConfig = MonitoringEndpointConfi
     port: MONITORING_ENDPOINT_DEFAULT_PORT,
     collect_metrics: false,
     collect_profiling_metrics: false,
+    operator_api_key: None,
 };
+const TEST_OPERATOR_API_KEY: &str = "operator_api_key";
 
 fn setup_monitoring_endpoint(config: Option<MonitoringEndpointConfig>) -> MonitoringEndpoint {
     let config = config.unwrap_or(CONFIG_WITHOUT_METRICS);
-    create_monitoring_endpoint(config, TEST_VERSION, None, None)
+    create_monitoring_endpoint(config, TEST_VERSION, None, None, None, None)
 }
 
 async fn request_app(app: Router, method: &str) -> Response {
@@ -134,6 +146,8 @@ fn setup_monitoring_endpoint_with_mempool_client() -> MonitoringEndpoint {
         TEST_VERSION,
         Some(shared_mock_mempool_client),
         None,
+        None,
+        None,
     )
 }
 
@@ -147,6 +161,7 @@ fn create_hash_map(i: u32, j: u32) -> HashMap<ContractAddress, Nonce> {
 fn expected_mempool_snapshot() -> MempoolSnapshot {
     let expected_chronological_hashes = (1..10).map(|i| tx_hash!(i)).collect::<Vec<_>>();
     let expected_delayed_declares = (10..15).map(|i| tx_hash!(i)).collect::<Vec<_>>();
+    let expected_suspended_transactions = (15..17).map(|i| tx_hash!(i)).collect::<Vec<_>>();
     let expected_transaction_queue = TransactionQueueSnapshot {
         gas_price_threshold: GasPrice(1),
         priority_queue: (1..5).map(|i| tx_hash!(i)).collect::<Vec<_>>(),
@@ -157,6 +172,7 @@ fn expected_mempool_snapshot() -> MempoolSnapshot {
     MempoolSnapshot {
         transactions: expected_chronological_hashes,
         delayed_declares: expected_delayed_declares,
+        suspended_transactions: expected_suspended_transactions,
         transaction_queue: expected_transaction_queue,
         mempool_state,
     }
@@ -184,6 +200,145 @@ async fn mempool_not_present() {
     assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
 }
 
+#[tokio::test]
+async fn mempool_events() {
+    let expected_event = MempoolEvent {
+        sequence_number: 0,
+        tx_hash: tx_hash!(1),
+        reason: MempoolEventReason::Added,
+    };
+    let mut mock_mempool_client = MockMempoolClient::new();
+    let event = expected_event.clone();
+    mock_mempool_client.expect_get_events().returning(move |sequence_number| {
+        let events = if sequence_number == 0 { vec![event.clone()] } else { vec![] };
+        Ok(MempoolEvents { feed_id: 0, events, next_sequence_number: 1 })
+    });
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        Some(Arc::new(mock_mempool_client)),
+        None,
+        None,
+        None,
+    )
+    .app();
+
+    let response = request_app(app, MEMPOOL_EVENTS).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The stream is endless; read the first event only.
+    let first_chunk = response.into_body().data().await.unwrap().unwrap();
+    let first_chunk = String::from_utf8(first_chunk.to_vec()).unwrap();
+    let expected_data = serde_json::to_string(&expected_event).unwrap();
+    assert!(first_chunk.contains("id: 0"), "Unexpected event: {first_chunk}");
+    assert!(
+        first_chunk.contains(&format!("data: {expected_data}")),
+        "Unexpected event: {first_chunk}"
+    );
+}
+
+#[tokio::test]
+async fn mempool_events_reset_after_mempool_restart() {
+    let mut mock_mempool_client = MockMempoolClient::new();
+    // The mempool restarts after the first event was read, and its sequence numbers start over.
+    mock_mempool_client.expect_get_events().returning(|sequence_number| {
+        let feed_id = if sequence_number == 0 { 0 } else { 1 };
+        let events = vec![MempoolEvent {
+            sequence_number: 0,
+            tx_hash: tx_hash!(1),
+            reason: MempoolEventReason::Added,
+        }];
+        Ok(MempoolEvents { feed_id, events, next_sequence_number: 1 })
+    });
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        Some(Arc::new(mock_mempool_client)),
+        None,
+        None,
+        None,
+    )
+    .app();
+
+    let mut body = request_app(app, MEMPOOL_EVENTS).await.into_body();
+    let mut stream_content = String::new();
+    while !stream_content.contains("event: reset") {
+        let chunk = body.data().await.unwrap().unwrap();
+        stream_content.push_str(&String::from_utf8(chunk.to_vec()).unwrap());
+    }
+    assert!(stream_content.contains("data: 1"), "Unexpected events: {stream_content}");
+}
+
+#[tokio::test]
+async fn mempool_events_not_present() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app, MEMPOOL_EVENTS).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+fn setup_monitoring_endpoint_with_mempool_operator_client(
+    operator_api_key: Option<&str>,
+) -> MonitoringEndpoint {
+    let mut mock_mempool_operator_client = MockMempoolOperatorClient::new();
+    // Only the first transaction is in the mempool.
+    mock_mempool_operator_client
+        .expect_remove_txs()
+        .returning(|tx_hashes| Ok(tx_hashes.into_iter().take(1).collect()));
+    let config = MonitoringEndpointConfig {
+        operator_api_key: operator_api_key.map(str::to_string),
+        ..CONFIG_WITHOUT_METRICS
+    };
+
+    create_monitoring_endpoint(
+        config,
+        TEST_VERSION,
+        None,
+        Some(Arc::new(mock_mempool_operator_client)),
+        None,
+        None,
+    )
+}
+
+async fn request_remove_mempool_transactions(app: Router, bearer_token: Option<&str>) -> Response {
+    let mut request = Request::post(format!("/{MONITORING_PREFIX}/{REMOVE_MEMPOOL_TRANSACTIONS}"))
+        .header(CONTENT_TYPE, "application/json");
+    if let Some(bearer_token) = bearer_token {
+        request = request.header(AUTHORIZATION, format!("Bearer {bearer_token}"));
+    }
+    let body = serde_json::to_vec(&vec![tx_hash!(1), tx_hash!(2)]).unwrap();
+    app.oneshot(request.body(Body::from(body)).unwrap()).await.unwrap()
+}
+
+#[tokio::test]
+async fn remove_mempool_transactions() {
+    let app =
+        setup_monitoring_endpoint_with_mempool_operator_client(Some(TEST_OPERATOR_API_KEY)).app();
+
+    let response = request_remove_mempool_transactions(app, Some(TEST_OPERATOR_API_KEY)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let received_json: Value = from_slice(&body_bytes).unwrap();
+    assert_eq!(received_json, to_value(vec![tx_hash!(1)]).unwrap());
+}
+
+#[tokio::test]
+async fn remove_mempool_transactions_unauthorized() {
+    for bearer_token in [None, Some("wrong_key")] {
+        let app =
+            setup_monitoring_endpoint_with_mempool_operator_client(Some(TEST_OPERATOR_API_KEY))
+                .app();
+        let response = request_remove_mempool_transactions(app, bearer_token).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
+#[tokio::test]
+async fn remove_mempool_transactions_without_operator_api_key() {
+    let app = setup_monitoring_endpoint_with_mempool_operator_client(None).app();
+    let response = request_remove_mempool_transactions(app, Some("")).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
 fn setup_monitoring_endpoint_with_l1_provider_client() -> MonitoringEndpoint {
     let mut l1_provider_client = MockL1ProviderClient::new();
     l1_provider_client
@@ -195,7 +350,9 @@ fn setup_monitoring_endpoint_with_l1_provider_client() -> MonitoringEndpoint {
         CONFIG_WITHOUT_METRICS,
         TEST_VERSION,
         None,
+        None,
         Some(shared_mock_l1_provider_client),
+        None,
     )
 }
 
@@ -244,3 +401,29 @@ async fn l1_provider_not_present() {
     let response = request_app(app, L1_PROVIDER_SNAPSHOT).await;
     assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
 }
+
+#[tokio::test]
+async fn consensus_equivocations() {
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        None,
+        None,
+        None,
+        Some(EquivocationEvidenceStore::default()),
+    )
+    .app();
+
+    let response = request_app(app, CONSENSUS_EQUIVOCATIONS).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let received_json: Value = from_slice(&body_bytes).unwrap();
+    assert_eq!(received_json, Value::Array(vec![]));
+}
+
+#[tokio::test]
+async fn consensus_equivocations_not_present() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app, CONSENSUS_EQUIVOCATIONS).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
@@ -1 +1,3 @@
 pub mod negotiator;
+pub(crate) mod security_upgrade;
+pub mod staker_negotiator;
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use futures::{Sink, Stream};
 use libp2p::PeerId;
 
+#[derive(Debug)]
 pub enum NegotiatorOutput {
     None,
     /// Returned when the handshake concluded that the currently connecting peer is a duplicate of
@@ -0,0 +1,199 @@
+use std::io::Error as IoError;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc::UnboundedSender;
+use futures::future::BoxFuture;
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, FutureExt, Sink, Stream};
+use libp2p::core::upgrade::{InboundConnectionUpgrade, OutboundConnectionUpgrade, UpgradeInfo};
+use libp2p::identity::Keypair;
+use libp2p::{noise, PeerId};
+use tracing::{debug, warn};
+
+use crate::authentication::negotiator::{Negotiator, NegotiatorError, NegotiatorOutput};
+use crate::sqmr::messages::{read_message, write_message};
+use crate::Bytes;
+
+#[derive(Debug, thiserror::Error)]
+pub enum NegotiatedNoiseError {
+    #[error(transparent)]
+    Noise(#[from] noise::Error),
+    #[error(transparent)]
+    Negotiator(#[from] NegotiatorError),
+}
+
+/// A noise security upgrade that runs the given negotiator over the secured connection before
+/// accepting it. Without a negotiator, this is a plain noise upgrade.
+/// The peers that the negotiator finds to be duplicates of the connecting peers are sent to
+/// `duplicate_peers_sender`, so that their connections are closed.
+#[derive(Clone)]
+pub(crate) struct NegotiatedNoiseConfig<N: Negotiator> {
+    noise: noise::Config,
+    my_peer_id: PeerId,
+    negotiator: Option<N>,
+    duplicate_peers_sender: UnboundedSender<PeerId>,
+}
+
+impl<N: Negotiator> NegotiatedNoiseConfig<N> {
+    pub fn new(
+        keypair: &Keypair,
+        negotiator: Option<N>,
+        duplicate_peers_sender: UnboundedSender<PeerId>,
+    ) -> Result<Self, noise::Error> {
+        Ok(Self {
+            noise: noise::Config::new(keypair)?,
+            my_peer_id: keypair.public().to_peer_id(),
+            negotiator,
+            duplicate_peers_sender,
+        })
+    }
+}
+
+impl<N: Negotiator> UpgradeInfo for NegotiatedNoiseConfig<N> {
+    type Info = <noise::Config as UpgradeInfo>::Info;
+    type InfoIter = <noise::Config as UpgradeInfo>::InfoIter;
+
+    fn protocol_info(&self) -> Self::InfoIter {
+        self.noise.protocol_info()
+    }
+}
+
+impl<N, Connection> InboundConnectionUpgrade<Connection> for NegotiatedNoiseConfig<N>
+where
+    N: Negotiator + 'static,
+    Connection: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    type Output = (PeerId, noise::Output<Connection>);
+    type Error = NegotiatedNoiseError;
+    type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
+
+    fn upgrade_inbound(self, connection: Connection, info: Self::Info) -> Self::Future {
+        async move {
+            let (other_peer_id, mut output) = self.noise.upgrade_inbound(connection, info).await?;
+            if let Some(mut negotiator) = self.negotiator {
+                let (read_half, write_half) = (&mut output).split();
+                let negotiator_output = negotiator
+                    .negotiate_incoming_connection(
+                        self.my_peer_id,
+                        other_peer_id,
+                        &mut NegotiationChannel::new(read_half, write_half),
+                    )
+                    .await?;
+                handle_negotiator_output(
+                    negotiator_output,
+                    other_peer_id,
+                    &self.duplicate_peers_sender,
+                );
+            }
+            Ok((other_peer_id, output))
+        }
+        .boxed()
+    }
+}
+
+impl<N, Connection> OutboundConnectionUpgrade<Connection> for NegotiatedNoiseConfig<N>
+where
+    N: Negotiator + 'static,
+    Connection: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    type Output = (PeerId, noise::Output<Connection>);
+    type Error = NegotiatedNoiseError;
+    type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
+
+    fn upgrade_outbound(self, connection: Connection, info: Self::Info) -> Self::Future {
+        async move {
+            let (other_peer_id, mut output) = self.noise.upgrade_outbound(connection, info).await?;
+            if let Some(mut negotiator) = self.negotiator {
+                let (read_half, write_half) = (&mut output).split();
+                let negotiator_output = negotiator
+                    .negotiate_outgoing_connection(
+                        self.my_peer_id,
+                        other_peer_id,
+                        &mut NegotiationChannel::new(read_half, write_half),
+                    )
+                    .await?;
+                handle_negotiator_output(
+                    negotiator_output,
+                    other_peer_id,
+                    &self.duplicate_peers_sender,
+                );
+            }
+            Ok((other_peer_id, output))
+        }
+        .boxed()
+    }
+}
+
+// The connecting peer proved it controls the key of the duplicate peer, so it replaces it and the
+// connection to the duplicate peer is closed.
+fn handle_negotiator_output(
+    negotiator_output: NegotiatorOutput,
+    other_peer_id: PeerId,
+    duplicate_peers_sender: &UnboundedSender<PeerId>,
+) {
+    if let NegotiatorOutput::DuplicatePeer(duplicate_peer_id) = negotiator_output {
+        debug!("Peer {other_peer_id} replaces the duplicate peer {duplicate_peer_id}.");
+        if duplicate_peers_sender.unbounded_send(duplicate_peer_id).is_err() {
+            warn!("Failed closing the connection to the duplicate peer {duplicate_peer_id}.");
+        }
+    }
+}
+
+type BoxSink<'a> = Pin<Box<dyn Sink<Bytes, Error = IoError> + Send + 'a>>;
+type BoxStream<'a> = Pin<Box<dyn Stream<Item = Result<Bytes, IoError>> + Send + 'a>>;
+
+/// A channel of length-prefixed messages over a connection.
+pub(crate) struct NegotiationChannel<'a> {
+    sink: BoxSink<'a>,
+    stream: BoxStream<'a>,
+}
+
+impl<'a> NegotiationChannel<'a> {
+    pub fn new<Reader, Writer>(reader: Reader, writer: Writer) -> Self
+    where
+        Reader: AsyncRead + Unpin + Send + 'a,
+        Writer: AsyncWrite + Unpin + Send + 'a,
+    {
+        let sink = futures::sink::unfold(writer, |mut writer, message: Bytes| async move {
+            write_message(&message, &mut writer).await?;
+            writer.flush().await?;
+            Ok::<_, IoError>(writer)
+        });
+        let stream = futures::stream::unfold(reader, |mut reader| async move {
+            match read_message(&mut reader).await {
+                Ok(Some(message)) => Some((Ok(message), reader)),
+                Ok(None) => None,
+                Err(error) => Some((Err(error), reader)),
+            }
+        });
+        Self { sink: Box::pin(sink), stream: Box::pin(stream) }
+    }
+}
+
+impl Sink<Bytes> for NegotiationChannel<'_> {
+    type Error = IoError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().sink.as_mut().poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Bytes) -> Result<(), Self::Error> {
+        self.get_mut().sink.as_mut().start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().sink.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().sink.as_mut().poll_close(cx)
+    }
+}
+
+impl Stream for NegotiationChannel<'_> {
+    type Item = Result<Bytes, IoError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().stream.as_mut().poll_next(cx)
+    }
+}
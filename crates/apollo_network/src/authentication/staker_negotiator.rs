@@ -0,0 +1,213 @@
+#[cfg(test)]
+#[path = "staker_negotiator_test.rs"]
+mod staker_negotiator_test;
+
+use std::collections::HashMap;
+use std::io::Error as IoError;
+use std::sync::{Arc, RwLock};
+
+use apollo_signature_manager::signature_manager::verify_identity;
+use apollo_signature_manager_types::SharedSignatureManagerClient;
+use async_trait::async_trait;
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use libp2p::PeerId;
+use starknet_api::core::Nonce;
+use starknet_api::crypto::utils::{PublicKey, RawSignature};
+use starknet_api::hash::StarkHash;
+use tracing::{debug, warn};
+
+use crate::authentication::negotiator::{Negotiator, NegotiatorError, NegotiatorOutput};
+use crate::Bytes;
+
+const FELT_SIZE: usize = 32;
+
+/// Provides the public keys of the stakers that are allowed to connect as validators.
+pub trait StakersProvider: Send + Sync {
+    /// Returns whether the given public key belongs to a staker of the current committee.
+    fn is_staker(&self, public_key: &PublicKey) -> bool;
+}
+
+pub type SharedStakersProvider = Arc<dyn StakersProvider>;
+
+/// The peers that proved they control a staker key, mapped to that key.
+#[derive(Clone, Debug, Default)]
+pub struct AuthenticatedPeers(Arc<RwLock<HashMap<PeerId, PublicKey>>>);
+
+impl AuthenticatedPeers {
+    pub fn contains(&self, peer_id: &PeerId) -> bool {
+        self.0.read().expect("Authenticated peers lock is poisoned").contains_key(peer_id)
+    }
+
+    pub fn get_public_key(&self, peer_id: &PeerId) -> Option<PublicKey> {
+        self.0.read().expect("Authenticated peers lock is poisoned").get(peer_id).copied()
+    }
+
+    /// Marks the peer as authenticated with the given key. If another peer is authenticated with
+    /// the same key, it is removed and returned.
+    pub(crate) fn insert(&self, peer_id: PeerId, public_key: PublicKey) -> Option<PeerId> {
+        let mut authenticated_peers = self.0.write().expect("Authenticated peers lock is poisoned");
+        let duplicate_peer_id = authenticated_peers
+            .iter()
+            .find(|(other_peer_id, other_public_key)| {
+                **other_peer_id != peer_id && **other_public_key == public_key
+            })
+            .map(|(other_peer_id, _)| *other_peer_id);
+        if let Some(duplicate_peer_id) = duplicate_peer_id {
+            authenticated_peers.remove(&duplicate_peer_id);
+        }
+        authenticated_peers.insert(peer_id, public_key);
+        duplicate_peer_id
+    }
+
+    pub(crate) fn remove(&self, peer_id: &PeerId) {
+        self.0.write().expect("Authenticated peers lock is poisoned").remove(peer_id);
+    }
+}
+
+/// A negotiator that authenticates peers as stakers with a signed challenge-response.
+///
+/// Each side sends a random nonce, and answers the nonce of the other side with its staker public
+/// key and a signature over its peer id and that nonce. A peer is accepted only if its signature
+/// is valid and its key belongs to a staker of the current committee.
+#[derive(Clone)]
+pub struct StakerNegotiator {
+    signature_manager_client: SharedSignatureManagerClient,
+    public_key: PublicKey,
+    stakers_provider: SharedStakersProvider,
+    authenticated_peers: AuthenticatedPeers,
+}
+
+impl StakerNegotiator {
+    /// `public_key` is the staker key of this node, which the signature manager signs with.
+    pub fn new(
+        signature_manager_client: SharedSignatureManagerClient,
+        public_key: PublicKey,
+        stakers_provider: SharedStakersProvider,
+    ) -> Self {
+        Self {
+            signature_manager_client,
+            public_key,
+            stakers_provider,
+            authenticated_peers: AuthenticatedPeers::default(),
+        }
+    }
+
+    pub fn authenticated_peers(&self) -> AuthenticatedPeers {
+        self.authenticated_peers.clone()
+    }
+
+    async fn negotiate<NegotiatorChannel>(
+        &mut self,
+        my_peer_id: PeerId,
+        other_peer_id: PeerId,
+        connection: &mut NegotiatorChannel,
+    ) -> Result<NegotiatorOutput, NegotiatorError>
+    where
+        NegotiatorChannel:
+            Sink<Vec<u8>, Error = IoError> + Stream<Item = Result<Vec<u8>, IoError>> + Unpin + Send,
+    {
+        let my_nonce = Nonce(StarkHash::from_bytes_be(&rand::random()));
+        connection.send(my_nonce.to_bytes_be().to_vec()).await?;
+        let other_nonce = decode_nonce(&receive_message(connection).await?)?;
+
+        let signature = match self.signature_manager_client.identify(my_peer_id, other_nonce).await
+        {
+            Ok(signature) => signature,
+            Err(error) => {
+                warn!("Failed signing the identity challenge of {other_peer_id}: {error:?}");
+                return Err(NegotiatorError::AuthenticationFailed);
+            }
+        };
+        connection.send(encode_identity(self.public_key, &signature)).await?;
+        let (other_public_key, other_signature) =
+            decode_identity(&receive_message(connection).await?)?;
+
+        if !self.stakers_provider.is_staker(&other_public_key) {
+            debug!("Peer {other_peer_id} authenticated with a key that isn't of a staker.");
+            return Err(NegotiatorError::AuthenticationFailed);
+        }
+        if !verify_identity(other_peer_id, my_nonce, other_signature, other_public_key)
+            .unwrap_or(false)
+        {
+            debug!("Peer {other_peer_id} failed proving it controls its staker key.");
+            return Err(NegotiatorError::AuthenticationFailed);
+        }
+
+        Ok(match self.authenticated_peers.insert(other_peer_id, other_public_key) {
+            Some(duplicate_peer_id) => NegotiatorOutput::DuplicatePeer(duplicate_peer_id),
+            None => NegotiatorOutput::None,
+        })
+    }
+}
+
+#[async_trait]
+impl Negotiator for StakerNegotiator {
+    async fn negotiate_incoming_connection<NegotiatorChannel>(
+        &mut self,
+        my_peer_id: PeerId,
+        other_peer_id: PeerId,
+        connection: &mut NegotiatorChannel,
+    ) -> Result<NegotiatorOutput, NegotiatorError>
+    where
+        NegotiatorChannel:
+            Sink<Vec<u8>, Error = IoError> + Stream<Item = Result<Vec<u8>, IoError>> + Unpin + Send,
+    {
+        self.negotiate(my_peer_id, other_peer_id, connection).await
+    }
+
+    async fn negotiate_outgoing_connection<NegotiatorChannel>(
+        &mut self,
+        my_peer_id: PeerId,
+        other_peer_id: PeerId,
+        connection: &mut NegotiatorChannel,
+    ) -> Result<NegotiatorOutput, NegotiatorError>
+    where
+        NegotiatorChannel:
+            Sink<Vec<u8>, Error = IoError> + Stream<Item = Result<Vec<u8>, IoError>> + Unpin + Send,
+    {
+        self.negotiate(my_peer_id, other_peer_id, connection).await
+    }
+
+    fn protocol_name(&self) -> &'static str {
+        "strk_id"
+    }
+}
+
+async fn receive_message<NegotiatorChannel>(
+    connection: &mut NegotiatorChannel,
+) -> Result<Bytes, NegotiatorError>
+where
+    NegotiatorChannel: Stream<Item = Result<Vec<u8>, IoError>> + Unpin,
+{
+    connection.next().await.ok_or(NegotiatorError::AuthenticationFailed)?.map_err(Into::into)
+}
+
+fn decode_felt(bytes: &[u8]) -> Result<StarkHash, NegotiatorError> {
+    let bytes: &[u8; FELT_SIZE] =
+        bytes.try_into().map_err(|_| NegotiatorError::AuthenticationFailed)?;
+    Ok(StarkHash::from_bytes_be(bytes))
+}
+
+fn decode_nonce(message: &[u8]) -> Result<Nonce, NegotiatorError> {
+    Ok(Nonce(decode_felt(message)?))
+}
+
+// The identity message is the public key followed by the felts of the signature.
+fn encode_identity(public_key: PublicKey, signature: &RawSignature) -> Bytes {
+    let mut message = Vec::with_capacity(FELT_SIZE * (1 + signature.len()));
+    message.extend_from_slice(&public_key.to_bytes_be());
+    for felt in signature.iter() {
+        message.extend_from_slice(&felt.to_bytes_be());
+    }
+    message
+}
+
+fn decode_identity(message: &[u8]) -> Result<(PublicKey, RawSignature), NegotiatorError> {
+    if message.len() < 2 * FELT_SIZE || message.len() % FELT_SIZE != 0 {
+        return Err(NegotiatorError::AuthenticationFailed);
+    }
+    let mut felts =
+        message.chunks_exact(FELT_SIZE).map(decode_felt).collect::<Result<Vec<_>, _>>()?;
+    let signature = felts.split_off(1);
+    Ok((PublicKey(felts[0]), RawSignature(signature)))
+}
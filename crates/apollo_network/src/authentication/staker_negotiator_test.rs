@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+use std::io::Error as IoError;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use apollo_network_types::network_types::PeerId as NetworkPeerId;
+use apollo_signature_manager::signature_manager::SignatureManager;
+use apollo_signature_manager_types::{
+    ConsensusVote,
+    KeyStore,
+    KeyStoreResult,
+    SignatureManagerClient,
+    SignatureManagerClientResult,
+};
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::{Sink, Stream};
+use libp2p::PeerId;
+use starknet_api::core::Nonce;
+use starknet_api::crypto::utils::{PrivateKey, PublicKey, RawSignature};
+use starknet_api::hash::StarkHash;
+use starknet_crypto::get_public_key;
+
+use super::{StakerNegotiator, StakersProvider};
+use crate::authentication::negotiator::{Negotiator, NegotiatorError, NegotiatorOutput};
+use crate::Bytes;
+
+#[derive(Clone)]
+struct TestKeyStore(PrivateKey);
+
+#[async_trait]
+impl KeyStore for TestKeyStore {
+    async fn get_key(&self) -> KeyStoreResult<PrivateKey> {
+        Ok(self.0)
+    }
+}
+
+struct TestSignatureManagerClient(SignatureManager<TestKeyStore>);
+
+#[async_trait]
+impl SignatureManagerClient for TestSignatureManagerClient {
+    async fn identify(
+        &self,
+        peer_id: NetworkPeerId,
+        nonce: Nonce,
+    ) -> SignatureManagerClientResult<RawSignature> {
+        Ok(self.0.identify(peer_id, nonce).await?)
+    }
+
+    async fn sign_vote(&self, _vote: ConsensusVote) -> SignatureManagerClientResult<RawSignature> {
+        unimplemented!("Negotiation doesn't sign votes.")
+    }
+}
+
+impl StakersProvider for HashSet<PublicKey> {
+    fn is_staker(&self, public_key: &PublicKey) -> bool {
+        self.contains(public_key)
+    }
+}
+
+/// One side of an in-memory connection between two negotiators.
+struct TestChannel {
+    sender: UnboundedSender<Bytes>,
+    receiver: UnboundedReceiver<Bytes>,
+}
+
+impl Sink<Bytes> for TestChannel {
+    type Error = IoError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().sender).poll_ready(cx).map_err(IoError::other)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Bytes) -> Result<(), Self::Error> {
+        Pin::new(&mut self.get_mut().sender).start_send(item).map_err(IoError::other)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().sender).poll_flush(cx).map_err(IoError::other)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().sender).poll_close(cx).map_err(IoError::other)
+    }
+}
+
+impl Stream for TestChannel {
+    type Item = Result<Bytes, IoError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().receiver).poll_next(cx).map(|message| message.map(Ok))
+    }
+}
+
+fn connected_channels() -> (TestChannel, TestChannel) {
+    let (first_sender, second_receiver) = unbounded();
+    let (second_sender, first_receiver) = unbounded();
+    (
+        TestChannel { sender: first_sender, receiver: first_receiver },
+        TestChannel { sender: second_sender, receiver: second_receiver },
+    )
+}
+
+fn private_key(seed: u8) -> PrivateKey {
+    PrivateKey(StarkHash::from(seed))
+}
+
+fn public_key(private_key: PrivateKey) -> PublicKey {
+    PublicKey(get_public_key(&private_key.0))
+}
+
+// Returns a negotiator that signs with `private_key` and presents itself with `public_key`.
+fn negotiator(
+    private_key: PrivateKey,
+    public_key: PublicKey,
+    stakers: &HashSet<PublicKey>,
+) -> StakerNegotiator {
+    StakerNegotiator::new(
+        Arc::new(TestSignatureManagerClient(SignatureManager::new(TestKeyStore(private_key)))),
+        public_key,
+        Arc::new(stakers.clone()),
+    )
+}
+
+async fn negotiate(
+    outgoing_negotiator: &mut StakerNegotiator,
+    outgoing_peer_id: PeerId,
+    incoming_negotiator: &mut StakerNegotiator,
+    incoming_peer_id: PeerId,
+) -> (Result<NegotiatorOutput, NegotiatorError>, Result<NegotiatorOutput, NegotiatorError>) {
+    let (mut outgoing_channel, mut incoming_channel) = connected_channels();
+    tokio::join!(
+        outgoing_negotiator.negotiate_outgoing_connection(
+            outgoing_peer_id,
+            incoming_peer_id,
+            &mut outgoing_channel,
+        ),
+        incoming_negotiator.negotiate_incoming_connection(
+            incoming_peer_id,
+            outgoing_peer_id,
+            &mut incoming_channel,
+        ),
+    )
+}
+
+#[tokio::test]
+async fn stakers_authenticate_each_other() {
+    let (first_private_key, second_private_key) = (private_key(1), private_key(2));
+    let stakers = HashSet::from([public_key(first_private_key), public_key(second_private_key)]);
+    let mut first_negotiator =
+        negotiator(first_private_key, public_key(first_private_key), &stakers);
+    let mut second_negotiator =
+        negotiator(second_private_key, public_key(second_private_key), &stakers);
+    let (first_peer_id, second_peer_id) = (PeerId::random(), PeerId::random());
+
+    let (first_result, second_result) =
+        negotiate(&mut first_negotiator, first_peer_id, &mut second_negotiator, second_peer_id)
+            .await;
+
+    assert_matches!(first_result, Ok(NegotiatorOutput::None));
+    assert_matches!(second_result, Ok(NegotiatorOutput::None));
+    assert_eq!(
+        first_negotiator.authenticated_peers().get_public_key(&second_peer_id),
+        Some(public_key(second_private_key))
+    );
+    assert_eq!(
+        second_negotiator.authenticated_peers().get_public_key(&first_peer_id),
+        Some(public_key(first_private_key))
+    );
+}
+
+#[tokio::test]
+async fn non_staker_is_rejected() {
+    let (staker_private_key, non_staker_private_key) = (private_key(1), private_key(2));
+    let stakers = HashSet::from([public_key(staker_private_key)]);
+    let mut staker_negotiator =
+        negotiator(staker_private_key, public_key(staker_private_key), &stakers);
+    let mut non_staker_negotiator =
+        negotiator(non_staker_private_key, public_key(non_staker_private_key), &stakers);
+    let non_staker_peer_id = PeerId::random();
+
+    let (_, staker_result) = negotiate(
+        &mut non_staker_negotiator,
+        non_staker_peer_id,
+        &mut staker_negotiator,
+        PeerId::random(),
+    )
+    .await;
+
+    assert_matches!(staker_result, Err(NegotiatorError::AuthenticationFailed));
+    assert!(!staker_negotiator.authenticated_peers().contains(&non_staker_peer_id));
+}
+
+#[tokio::test]
+async fn impersonating_a_staker_is_rejected() {
+    let (staker_private_key, impersonator_private_key) = (private_key(1), private_key(2));
+    let stakers = HashSet::from([public_key(staker_private_key)]);
+    let mut staker_negotiator =
+        negotiator(staker_private_key, public_key(staker_private_key), &stakers);
+    // The impersonator presents the key of the staker but can't sign with it.
+    let mut impersonator_negotiator =
+        negotiator(impersonator_private_key, public_key(staker_private_key), &stakers);
+    let impersonator_peer_id = PeerId::random();
+
+    let (_, staker_result) = negotiate(
+        &mut impersonator_negotiator,
+        impersonator_peer_id,
+        &mut staker_negotiator,
+        PeerId::random(),
+    )
+    .await;
+
+    assert_matches!(staker_result, Err(NegotiatorError::AuthenticationFailed));
+    assert!(!staker_negotiator.authenticated_peers().contains(&impersonator_peer_id));
+}
+
+#[tokio::test]
+async fn staker_reconnecting_with_new_peer_id_replaces_duplicate() {
+    let (first_private_key, second_private_key) = (private_key(1), private_key(2));
+    let stakers = HashSet::from([public_key(first_private_key), public_key(second_private_key)]);
+    let mut first_negotiator =
+        negotiator(first_private_key, public_key(first_private_key), &stakers);
+    let mut second_negotiator =
+        negotiator(second_private_key, public_key(second_private_key), &stakers);
+    let (first_peer_id, old_second_peer_id, new_second_peer_id) =
+        (PeerId::random(), PeerId::random(), PeerId::random());
+
+    let (_, first_result) =
+        negotiate(&mut second_negotiator, old_second_peer_id, &mut first_negotiator, first_peer_id)
+            .await;
+    assert_matches!(first_result, Ok(NegotiatorOutput::None));
+
+    let (_, first_result) =
+        negotiate(&mut second_negotiator, new_second_peer_id, &mut first_negotiator, first_peer_id)
+            .await;
+    assert_matches!(
+        first_result,
+        Ok(NegotiatorOutput::DuplicatePeer(peer_id)) if peer_id == old_second_peer_id
+    );
+    let authenticated_peers = first_negotiator.authenticated_peers();
+    assert!(authenticated_peers.contains(&new_second_peer_id));
+    assert!(!authenticated_peers.contains(&old_second_peer_id));
+}
@@ -4,6 +4,7 @@ mod config_test;
 /// to the [`Starknet p2p specs`]
 ///
 /// [`Starknet p2p specs`]: https://github.com/starknet-io/starknet-p2p-specs/
+pub mod authentication;
 mod discovery;
 #[cfg(test)]
 mod e2e_broadcast_test;
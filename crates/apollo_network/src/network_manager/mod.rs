@@ -5,14 +5,14 @@ mod test;
 #[cfg(any(test, feature = "testing"))]
 pub mod test_utils;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::net::Ipv4Addr;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use apollo_network_types::network_types::{BroadcastedMessageMetadata, OpaquePeerId};
 use async_trait::async_trait;
-use futures::channel::mpsc::{Receiver, SendError, Sender};
+use futures::channel::mpsc::{Receiver, SendError, Sender, UnboundedReceiver};
 use futures::channel::oneshot;
 use futures::future::{ready, BoxFuture, Ready};
 use futures::sink::With;
@@ -21,11 +21,13 @@ use futures::{pin_mut, FutureExt, Sink, SinkExt, StreamExt};
 use libp2p::gossipsub::{SubscriptionError, TopicHash};
 use libp2p::identity::Keypair;
 use libp2p::swarm::SwarmEvent;
-use libp2p::{noise, yamux, Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder};
+use libp2p::{yamux, Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder};
 use metrics::NetworkMetrics;
 use tracing::{debug, error, trace, warn};
 
 use self::swarm_trait::SwarmTrait;
+use crate::authentication::security_upgrade::NegotiatedNoiseConfig;
+use crate::authentication::staker_negotiator::{AuthenticatedPeers, StakerNegotiator};
 use crate::gossipsub_impl::Topic;
 use crate::misconduct_score::MisconductScore;
 use crate::mixed_behaviour::{self, BridgedBehaviour};
@@ -61,6 +63,12 @@ pub struct GenericNetworkManager<SwarmT: SwarmTrait> {
     continue_propagation_sender: Sender<BroadcastedMessageMetadata>,
     continue_propagation_receiver: Receiver<BroadcastedMessageMetadata>,
     metrics: Option<NetworkMetrics>,
+    // The peers that authenticated as stakers, if the network authenticates its peers.
+    authenticated_peers: Option<AuthenticatedPeers>,
+    // Topics whose messages are accepted only from authenticated peers.
+    authenticated_topic_hashes: HashSet<TopicHash>,
+    // Peers that were replaced by a newly authenticated peer with the same staker key.
+    duplicate_peers_receiver: UnboundedReceiver<PeerId>,
 }
 
 impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
@@ -89,6 +97,7 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
                 Some(broadcasted_message_metadata) = self.continue_propagation_receiver.next() => {
                     self.swarm.continue_propagation(broadcasted_message_metadata);
                 }
+                Some(peer_id) = self.duplicate_peers_receiver.next() => self.swarm.disconnect_peer(peer_id),
             }
         }
     }
@@ -111,6 +120,8 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
             futures::channel::mpsc::channel(reported_peer_ids_buffer_size);
         let (continue_propagation_sender, continue_propagation_receiver) =
             futures::channel::mpsc::channel(broadcasted_message_metadata_buffer_size);
+        // Without peer authentication there are no duplicate peers, so the sender is dropped.
+        let (_, duplicate_peers_receiver) = futures::channel::mpsc::unbounded();
         Self {
             swarm,
             inbound_protocol_to_buffer_size: HashMap::new(),
@@ -128,6 +139,9 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
             continue_propagation_sender,
             continue_propagation_receiver,
             metrics,
+            authenticated_peers: None,
+            authenticated_topic_hashes: HashSet::new(),
+            duplicate_peers_receiver,
         }
     }
 
@@ -263,6 +277,28 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
         })
     }
 
+    /// Register a new subscriber for a broadcast topic whose messages are accepted only from peers
+    /// that authenticated as stakers. Messages from other peers are dropped.
+    /// Panics if this topic is already subscribed or if the network doesn't authenticate its peers.
+    pub fn register_authenticated_broadcast_topic<T>(
+        &mut self,
+        topic: Topic,
+        buffer_size: usize,
+    ) -> Result<BroadcastTopicChannels<T>, SubscriptionError>
+    where
+        T: TryFrom<Bytes> + 'static,
+        Bytes: From<T>,
+    {
+        assert!(
+            self.authenticated_peers.is_some(),
+            "Topic '{topic}' requires authentication but the network doesn't authenticate peers."
+        );
+        let topic_hash = topic.hash();
+        let broadcast_topic_channels = self.register_broadcast_topic(topic, buffer_size)?;
+        self.authenticated_topic_hashes.insert(topic_hash);
+        Ok(broadcast_topic_channels)
+    }
+
     fn handle_swarm_event(
         &mut self,
         event: SwarmEvent<mixed_behaviour::Event>,
@@ -297,6 +333,11 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
                         metrics.num_connected_peers.decrement(1);
                     }
                 }
+                if num_remaining_connections == 0 {
+                    if let Some(authenticated_peers) = self.authenticated_peers.as_ref() {
+                        authenticated_peers.remove(&peer_id);
+                    }
+                }
             }
             SwarmEvent::Behaviour(event) => {
                 self.handle_behaviour_event(event)?;
@@ -544,6 +585,17 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
         let gossipsub_impl::ExternalEvent::Received { originated_peer_id, message, topic_hash } =
             event;
         trace!("Received broadcast message with topic hash: {topic_hash:?}");
+        if self.authenticated_topic_hashes.contains(&topic_hash)
+            && !self.authenticated_peers.as_ref().is_some_and(|authenticated_peers| {
+                authenticated_peers.contains(&originated_peer_id)
+            })
+        {
+            debug!(
+                "Dropping a message with topic hash {topic_hash:?} from the unauthenticated peer \
+                 {originated_peer_id:?}."
+            );
+            return Ok(());
+        }
         let broadcasted_message_metadata = BroadcastedMessageMetadata {
             originator_id: OpaquePeerId::private_new(originated_peer_id),
             encoded_message_length: message.len(),
@@ -687,6 +739,34 @@ impl NetworkManager {
         config: NetworkConfig,
         node_version: Option<String>,
         metrics: Option<NetworkMetrics>,
+    ) -> Self {
+        Self::new_with_optional_negotiator(config, node_version, metrics, None)
+    }
+
+    /// Creates a network manager that accepts only connections with peers that authenticate as
+    /// stakers through the given negotiator.
+    pub fn new_with_staker_authentication(
+        config: NetworkConfig,
+        node_version: Option<String>,
+        metrics: Option<NetworkMetrics>,
+        staker_negotiator: StakerNegotiator,
+    ) -> Self {
+        let authenticated_peers = staker_negotiator.authenticated_peers();
+        let mut network_manager = Self::new_with_optional_negotiator(
+            config,
+            node_version,
+            metrics,
+            Some(staker_negotiator),
+        );
+        network_manager.authenticated_peers = Some(authenticated_peers);
+        network_manager
+    }
+
+    fn new_with_optional_negotiator(
+        config: NetworkConfig,
+        node_version: Option<String>,
+        metrics: Option<NetworkMetrics>,
+        staker_negotiator: Option<StakerNegotiator>,
     ) -> Self {
         let NetworkConfig {
             port,
@@ -711,10 +791,18 @@ impl NetworkManager {
             }
             None => Keypair::generate_ed25519(),
         };
+        let (duplicate_peers_sender, duplicate_peers_receiver) =
+            futures::channel::mpsc::unbounded();
         let mut swarm = SwarmBuilder::with_existing_identity(key_pair)
             .with_tokio()
             // TODO(AndrewL): .with_quic()
-            .with_tcp(Default::default(), noise::Config::new, yamux::Config::default)
+            .with_tcp(
+                Default::default(),
+                |key: &Keypair| {
+                    NegotiatedNoiseConfig::new(key, staker_negotiator, duplicate_peers_sender)
+                },
+                yamux::Config::default,
+            )
             .expect("Error building TCP transport")
             .with_dns()
             .expect("Error building DNS transport")
@@ -742,13 +830,15 @@ impl NetworkManager {
                 .with_p2p(*swarm.local_peer_id())
                 .expect("advertised_multiaddr has a peer id different than the local peer id")
         });
-        Self::generic_new(
+        let mut network_manager = Self::generic_new(
             swarm,
             advertised_multiaddr,
             metrics,
             broadcasted_message_metadata_buffer_size,
             reported_peer_ids_buffer_size,
-        )
+        );
+        network_manager.duplicate_peers_receiver = duplicate_peers_receiver;
+        network_manager
     }
 
     pub fn get_local_peer_id(&self) -> String {
@@ -18,12 +18,14 @@ use libp2p::core::ConnectedPoint;
 use libp2p::gossipsub::{SubscriptionError, TopicHash};
 use libp2p::swarm::ConnectionId;
 use libp2p::{Multiaddr, PeerId, StreamProtocol};
+use starknet_api::crypto::utils::PublicKey;
 use tokio::select;
 use tokio::sync::Mutex;
 use tokio::time::sleep;
 
 use super::swarm_trait::{Event, SwarmTrait};
 use super::{BroadcastTopicChannels, GenericNetworkManager};
+use crate::authentication::staker_negotiator::AuthenticatedPeers;
 use crate::gossipsub_impl::{self, Topic};
 use crate::misconduct_score::MisconductScore;
 use crate::network_manager::{BroadcastTopicClientTrait, ServerQueryManager};
@@ -45,6 +47,7 @@ struct MockSwarm {
     pub subscribed_topics: HashSet<TopicHash>,
     broadcasted_messages_senders: Vec<UnboundedSender<(Bytes, TopicHash)>>,
     reported_peer_senders: Vec<UnboundedSender<PeerId>>,
+    disconnected_peer_senders: Vec<UnboundedSender<PeerId>>,
     supported_inbound_protocols_senders: Vec<UnboundedSender<StreamProtocol>>,
     inbound_session_id_to_response_sender: HashMap<InboundSessionId, UnboundedSender<Bytes>>,
     next_outbound_session_id: usize,
@@ -98,6 +101,12 @@ impl MockSwarm {
         receiver
     }
 
+    pub fn get_disconnected_peers_stream(&mut self) -> impl Stream<Item = PeerId> {
+        let (sender, receiver) = unbounded();
+        self.disconnected_peer_senders.push(sender);
+        receiver
+    }
+
     pub fn get_supported_inbound_protocol(&mut self) -> impl Stream<Item = StreamProtocol> {
         let (sender, receiver) = unbounded();
         self.supported_inbound_protocols_senders.push(sender);
@@ -203,6 +212,12 @@ impl SwarmTrait for MockSwarm {
     fn continue_propagation(&mut self, _message_metadata: super::BroadcastedMessageMetadata) {
         unimplemented!()
     }
+
+    fn disconnect_peer(&mut self, peer_id: PeerId) {
+        for sender in &self.disconnected_peer_senders {
+            sender.unbounded_send(peer_id).unwrap();
+        }
+    }
 }
 
 const BUFFER_SIZE: usize = 100;
@@ -401,6 +416,79 @@ async fn receive_broadcasted_message_and_report_it() {
     }
 }
 
+#[tokio::test]
+async fn authenticated_topic_drops_messages_from_unauthenticated_peers() {
+    let topic = Topic::new("TOPIC");
+    let unauthenticated_peer_id = PeerId::random();
+    let authenticated_peer_id = PeerId::random();
+
+    let mut mock_swarm = MockSwarm::default();
+    for (originated_peer_id, message) in
+        [(unauthenticated_peer_id, VEC1.clone()), (authenticated_peer_id, VEC2.clone())]
+    {
+        mock_swarm.pending_events.push(Event::Behaviour(mixed_behaviour::Event::ExternalEvent(
+            mixed_behaviour::ExternalEvent::GossipSub(gossipsub_impl::ExternalEvent::Received {
+                originated_peer_id,
+                message,
+                topic_hash: topic.hash(),
+            }),
+        )));
+    }
+
+    let mut network_manager = GenericNetworkManager::generic_new(
+        mock_swarm,
+        None,
+        None,
+        MESSAGE_METADATA_BUFFER_SIZE,
+        MESSAGE_METADATA_BUFFER_SIZE,
+    );
+    let authenticated_peers = AuthenticatedPeers::default();
+    authenticated_peers.insert(authenticated_peer_id, PublicKey::default());
+    network_manager.authenticated_peers = Some(authenticated_peers);
+
+    let BroadcastTopicChannels { mut broadcasted_messages_receiver, .. } = network_manager
+        .register_authenticated_broadcast_topic::<Bytes>(topic.clone(), BUFFER_SIZE)
+        .unwrap();
+
+    tokio::select! {
+        _ = network_manager.run() => panic!("network manager ended"),
+        result = tokio::time::timeout(TIMEOUT, broadcasted_messages_receiver.next()) => {
+            let (message_result, broadcasted_message_metadata) = result.unwrap().unwrap();
+            assert_eq!(*VEC2, message_result.unwrap());
+            assert_eq!(
+                authenticated_peer_id,
+                broadcasted_message_metadata.originator_id.private_get_peer_id()
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn disconnect_duplicate_peers() {
+    let duplicate_peer_id = PeerId::random();
+
+    let mut mock_swarm = MockSwarm::default();
+    let mut disconnected_peers_stream = mock_swarm.get_disconnected_peers_stream();
+
+    let mut network_manager = GenericNetworkManager::generic_new(
+        mock_swarm,
+        None,
+        None,
+        MESSAGE_METADATA_BUFFER_SIZE,
+        MESSAGE_METADATA_BUFFER_SIZE,
+    );
+    let (duplicate_peers_sender, duplicate_peers_receiver) = unbounded();
+    network_manager.duplicate_peers_receiver = duplicate_peers_receiver;
+    duplicate_peers_sender.unbounded_send(duplicate_peer_id).unwrap();
+
+    tokio::select! {
+        _ = network_manager.run() => panic!("network manager ended"),
+        result = tokio::time::timeout(TIMEOUT, disconnected_peers_stream.next()) => {
+            assert_eq!(duplicate_peer_id, result.unwrap().unwrap());
+        }
+    }
+}
+
 fn get_test_connection_established_event(mock_peer_id: PeerId) -> Event {
     Event::ConnectionEstablished {
         peer_id: mock_peer_id,
@@ -69,6 +69,7 @@ use apollo_mempool_types::communication::{
     MempoolResponse,
     RemoteMempoolClient,
     SharedMempoolClient,
+    SharedMempoolOperatorClient,
 };
 use apollo_signature_manager_types::{
     LocalSignatureManagerClient,
@@ -218,6 +219,10 @@ impl SequencerNodeClients {
         get_shared_client!(self, mempool_client)
     }
 
+    pub fn get_mempool_operator_shared_client(&self) -> Option<SharedMempoolOperatorClient> {
+        get_shared_client!(self, mempool_client)
+    }
+
     pub fn get_mempool_p2p_propagator_local_client(
         &self,
     ) -> Option<LocalComponentClient<MempoolP2pPropagatorRequest, MempoolP2pPropagatorResponse>>
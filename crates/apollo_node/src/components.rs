@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use apollo_batcher::batcher::{create_batcher, Batcher};
 use apollo_batcher::pre_confirmed_cende_client::PreconfirmedCendeClient;
 use apollo_class_manager::class_manager::create_class_manager;
@@ -20,6 +22,7 @@ use apollo_monitoring_endpoint::monitoring_endpoint::{
     create_monitoring_endpoint,
     MonitoringEndpoint,
 };
+use apollo_rpc::SharedL1MessagesReader;
 use apollo_signature_manager::{create_signature_manager, SignatureManager};
 use apollo_state_sync::runner::StateSyncRunner;
 use apollo_state_sync::{create_state_sync_and_runner, StateSync};
@@ -244,16 +247,24 @@ pub async fn create_node_components(
                 .as_ref()
                 .expect("Monitoring Endpoint config should be set");
 
-            let mempool_client = match config.components.mempool.execution_mode {
-                ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
-                | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => Some(
-                    clients
-                        .get_mempool_shared_client()
-                        .expect("Mempool Client should be available"),
-                ),
-                ReactiveComponentExecutionMode::Disabled
-                | ReactiveComponentExecutionMode::Remote => None,
-            };
+            let (mempool_client, mempool_operator_client) =
+                match config.components.mempool.execution_mode {
+                    ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
+                    | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => (
+                        Some(
+                            clients
+                                .get_mempool_shared_client()
+                                .expect("Mempool Client should be available"),
+                        ),
+                        Some(
+                            clients
+                                .get_mempool_operator_shared_client()
+                                .expect("Mempool Client should be available"),
+                        ),
+                    ),
+                    ReactiveComponentExecutionMode::Disabled
+                    | ReactiveComponentExecutionMode::Remote => (None, None),
+                };
 
             let l1_provider_client = match config.components.l1_provider.execution_mode {
                 ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
@@ -266,11 +277,17 @@ pub async fn create_node_components(
                 | ReactiveComponentExecutionMode::Remote => None,
             };
 
+            // Evidence is held in memory, so it is only available when consensus runs locally.
+            let equivocation_evidence_store =
+                consensus_manager.as_ref().map(ConsensusManager::equivocation_evidence_store);
+
             Some(create_monitoring_endpoint(
                 monitoring_endpoint_config.clone(),
                 VERSION_FULL,
                 mempool_client,
+                mempool_operator_client,
                 l1_provider_client,
+                equivocation_evidence_store,
             ))
         }
         ActiveComponentExecutionMode::Disabled => {
@@ -287,8 +304,17 @@ pub async fn create_node_components(
             let class_manager_client = clients
                 .get_class_manager_shared_client()
                 .expect("Class Manager Client should be available");
-            let (state_sync, state_sync_runner) =
-                create_state_sync_and_runner(state_sync_config.clone(), class_manager_client);
+            // The statuses of the messages to L2 are served from the receipts of the L1
+            // transactions that sent them.
+            let l1_messages_reader = config.base_layer_config.as_ref().map(|base_layer_config| {
+                Arc::new(EthereumBaseLayerContract::new(base_layer_config.clone()))
+                    as SharedL1MessagesReader
+            });
+            let (state_sync, state_sync_runner) = create_state_sync_and_runner(
+                state_sync_config.clone(),
+                class_manager_client,
+                l1_messages_reader,
+            );
             (Some(state_sync), Some(state_sync_runner))
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
@@ -15,11 +15,11 @@ use futures::channel::mpsc::Receiver;
 use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use futures::{FutureExt, StreamExt};
-use starknet_api::block::{BlockNumber, BlockSignature};
-use starknet_api::core::ClassHash;
+use starknet_api::block::{BlockHash, BlockNumber, BlockSignature};
+use starknet_api::core::{ClassHash, ContractAddress};
 use tracing::{debug, info, trace, warn};
 
-use super::{P2pSyncClientError, STEP};
+use super::{P2pSyncClientError, ValidatorPublicKeys, STEP};
 
 pub type BlockDataResult = Result<Box<dyn BlockData>, P2pSyncClientError>;
 
@@ -57,6 +57,7 @@ where
         client_response_manager: &'a mut ClientResponsesManager<DataOrFin<InputFromNetwork>>,
         block_number: BlockNumber,
         storage_reader: &'a StorageReader,
+        validator_public_keys: &'a ValidatorPublicKeys,
     ) -> BoxFuture<'a, Result<Option<Self::Output>, ParseDataError>>;
 
     /// Get the starting block number for this stream.
@@ -101,6 +102,7 @@ where
     fn create_stream<TQuery>(
         mut sqmr_sender: SqmrClientSender<TQuery, DataOrFin<InputFromNetwork>>,
         storage_reader: StorageReader,
+        validator_public_keys: ValidatorPublicKeys,
         mut internal_block_receiver: Option<Receiver<SyncBlock>>,
         wait_period_for_new_data: Duration,
         wait_period_for_other_protocol: Duration,
@@ -161,7 +163,10 @@ where
                 while current_block_number.0 < end_block_number {
                     tokio::select! {
                         res = Self::parse_data_for_block(
-                            &mut client_response_manager, current_block_number, &storage_reader
+                            &mut client_response_manager,
+                            current_block_number,
+                            &storage_reader,
+                            &validator_public_keys,
                         ) => {
                             match res {
                                 Ok(Some(output)) => {
@@ -271,6 +276,25 @@ pub(crate) enum BadPeerError {
     ClassNotInStateDiff { class_hash: ClassHash },
     #[error("Received two classes with the same hash: {class_hash}.")]
     DuplicateClass { class_hash: ClassHash },
+    #[error(
+        "The commit certificate is for the proposal commitment {certificate_block_hash}, but the \
+         header's state diff commitment is {state_diff_commitment}."
+    )]
+    CommitCertificateForWrongBlock {
+        certificate_block_hash: BlockHash,
+        state_diff_commitment: BlockHash,
+    },
+    #[error("The commit certificate contains a precommit of {voter}, which isn't a validator.")]
+    CommitCertificateWithUnknownVoter { voter: ContractAddress },
+    #[error("The commit certificate contains more than one precommit of {voter}.")]
+    CommitCertificateWithDuplicateVoter { voter: ContractAddress },
+    #[error("The commit certificate contains a precommit of {voter} with an invalid signature.")]
+    InvalidCommitCertificateSignature { voter: ContractAddress },
+    #[error(
+        "The commit certificate contains precommits of {num_voters} out of {num_validators} \
+         validators, which isn't a quorum."
+    )]
+    CommitCertificateWithoutQuorum { num_voters: usize, num_validators: usize },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -23,7 +23,7 @@ use super::block_data_stream_builder::{
     BlockNumberLimit,
     ParseDataError,
 };
-use super::P2pSyncClientError;
+use super::{P2pSyncClientError, ValidatorPublicKeys};
 
 impl BlockData for (DeclaredClasses, DeprecatedDeclaredClasses, BlockNumber) {
     fn write_to_storage<'a>(
@@ -90,6 +90,7 @@ impl BlockDataStreamBuilder<(ApiContractClass, ClassHash)> for ClassStreamBuilde
         >,
         block_number: BlockNumber,
         storage_reader: &'a StorageReader,
+        _validator_public_keys: &'a ValidatorPublicKeys,
     ) -> BoxFuture<'a, Result<Option<Self::Output>, ParseDataError>> {
         async move {
             let (target_class_len, declared_classes, deprecated_declared_classes) = {
@@ -1,6 +1,10 @@
+use std::collections::HashSet;
+
 use apollo_class_manager_types::SharedClassManagerClient;
 use apollo_network::network_manager::ClientResponsesManager;
 use apollo_protobuf::sync::{DataOrFin, SignedBlockHeader};
+use apollo_signature_manager::signature_manager::verify_vote_signature;
+use apollo_signature_manager_types::{ConsensusVote, ConsensusVoteType};
 use apollo_state_sync_metrics::metrics::{STATE_SYNC_HEADER_LATENCY_SEC, STATE_SYNC_HEADER_MARKER};
 use apollo_state_sync_types::state_sync_types::SyncBlock;
 use apollo_storage::header::{HeaderStorageReader, HeaderStorageWriter};
@@ -8,7 +12,14 @@ use apollo_storage::{StorageError, StorageReader, StorageWriter};
 use chrono::{TimeZone, Utc};
 use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt};
-use starknet_api::block::{BlockHash, BlockHeader, BlockNumber, BlockSignature};
+use starknet_api::block::{
+    BlockHash,
+    BlockHeader,
+    BlockNumber,
+    BlockSignature,
+    CommitCertificate,
+    SignedPrecommit,
+};
 use starknet_api::hash::StarkHash;
 use tracing::debug;
 
@@ -19,7 +30,7 @@ use super::block_data_stream_builder::{
     BlockNumberLimit,
     ParseDataError,
 };
-use super::{P2pSyncClientError, ALLOWED_SIGNATURES_LENGTH};
+use super::{P2pSyncClientError, ValidatorPublicKeys, ALLOWED_SIGNATURES_LENGTH};
 
 impl BlockData for SignedBlockHeader {
     #[allow(clippy::as_conversions)] // FIXME: use int metrics so `as f64` may be removed.
@@ -29,7 +40,7 @@ impl BlockData for SignedBlockHeader {
         _class_manager_client: &'a mut SharedClassManagerClient,
     ) -> BoxFuture<'a, Result<(), P2pSyncClientError>> {
         async move {
-            storage_writer
+            let mut txn = storage_writer
                 .begin_rw_txn()?
                 .append_header(
                     self.block_header.block_header_without_hash.block_number,
@@ -44,8 +55,14 @@ impl BlockData for SignedBlockHeader {
                     // The verification that the size of the vector is 1 is done in the data
                     // verification.
                     .expect("Vec::first should return a value on a vector of size 1"),
-                )?
-                .commit()?;
+                )?;
+            if let Some(commit_certificate) = &self.commit_certificate {
+                txn = txn.append_commit_certificate(
+                    self.block_header.block_header_without_hash.block_number,
+                    commit_certificate,
+                )?;
+            }
+            txn.commit()?;
             STATE_SYNC_HEADER_MARKER.set_lossy(
                 self.block_header.block_header_without_hash.block_number.unchecked_next().0,
             );
@@ -69,6 +86,52 @@ impl BlockData for SignedBlockHeader {
     }
 }
 
+/// Verifies that the certificate holds validly signed precommits of a quorum of the validators for
+/// the given block.
+fn verify_commit_certificate(
+    block_header: &BlockHeader,
+    commit_certificate: &CommitCertificate,
+    validator_public_keys: &ValidatorPublicKeys,
+) -> Result<(), BadPeerError> {
+    // Consensus votes on the state diff commitment of the block.
+    if let Some(state_diff_commitment) = block_header.state_diff_commitment {
+        let state_diff_commitment = BlockHash(state_diff_commitment.0 .0);
+        if commit_certificate.block_hash != state_diff_commitment {
+            return Err(BadPeerError::CommitCertificateForWrongBlock {
+                certificate_block_hash: commit_certificate.block_hash,
+                state_diff_commitment,
+            });
+        }
+    }
+    let mut voters = HashSet::new();
+    for SignedPrecommit { voter, signature } in &commit_certificate.precommits {
+        let public_key = validator_public_keys
+            .get(voter)
+            .ok_or(BadPeerError::CommitCertificateWithUnknownVoter { voter: *voter })?;
+        if !voters.insert(*voter) {
+            return Err(BadPeerError::CommitCertificateWithDuplicateVoter { voter: *voter });
+        }
+        let vote = ConsensusVote {
+            vote_type: ConsensusVoteType::Precommit,
+            height: block_header.block_header_without_hash.block_number,
+            round: commit_certificate.round,
+            block_hash: Some(commit_certificate.block_hash),
+            voter: *voter,
+        };
+        if !verify_vote_signature(vote, (*signature).into(), *public_key).unwrap_or(false) {
+            return Err(BadPeerError::InvalidCommitCertificateSignature { voter: *voter });
+        }
+    }
+    // Consensus decides on a block once more than two thirds of the validators precommit to it.
+    if 3 * voters.len() <= 2 * validator_public_keys.len() {
+        return Err(BadPeerError::CommitCertificateWithoutQuorum {
+            num_voters: voters.len(),
+            num_validators: validator_public_keys.len(),
+        });
+    }
+    Ok(())
+}
+
 pub(crate) struct HeaderStreamBuilder;
 
 impl BlockDataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
@@ -83,6 +146,7 @@ impl BlockDataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
         >,
         block_number: BlockNumber,
         _storage_reader: &'a StorageReader,
+        validator_public_keys: &'a ValidatorPublicKeys,
     ) -> BoxFuture<'a, Result<Option<Self::Output>, ParseDataError>> {
         async move {
             let maybe_signed_header = signed_headers_response_manager.next().await.ok_or(
@@ -90,7 +154,7 @@ impl BlockDataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
                     type_description: Self::TYPE_DESCRIPTION,
                 }),
             )?;
-            let Some(signed_block_header) = maybe_signed_header?.0 else {
+            let Some(mut signed_block_header) = maybe_signed_header?.0 else {
                 return Ok(None);
             };
             // TODO(shahak): Check that parent_hash is the same as the previous block's hash
@@ -111,6 +175,22 @@ impl BlockDataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
                     signatures: signed_block_header.signatures,
                 }));
             }
+            if let Some(commit_certificate) = &signed_block_header.commit_certificate {
+                if validator_public_keys.is_empty() {
+                    debug!(
+                        "No validators are configured. Not storing the commit certificate of \
+                         block {block_number}."
+                    );
+                    signed_block_header.commit_certificate = None;
+                } else {
+                    verify_commit_certificate(
+                        &signed_block_header.block_header,
+                        commit_certificate,
+                        validator_public_keys,
+                    )
+                    .map_err(ParseDataError::BadPeer)?;
+                }
+            }
             Ok(Some(signed_block_header))
         }
         .boxed()
@@ -135,6 +215,7 @@ impl BlockDataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
                 ..Default::default()
             },
             signatures: vec![BlockSignature::default()],
+            commit_certificate: sync_block.commit_certificate,
         }
     }
 }
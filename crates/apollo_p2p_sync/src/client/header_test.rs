@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
 use apollo_protobuf::sync::{
     BlockHashOrNumber,
     DataOrFin,
@@ -11,7 +12,16 @@ use apollo_protobuf::sync::{
 use apollo_storage::header::HeaderStorageReader;
 use apollo_test_utils::get_rng;
 use futures::{FutureExt, StreamExt};
-use starknet_api::block::{BlockHeader, BlockHeaderWithoutHash, BlockNumber};
+use starknet_api::block::{
+    BlockHash,
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockNumber,
+    CommitCertificate,
+};
+use starknet_api::core::{ContractAddress, StateDiffCommitment};
+use starknet_api::hash::PoseidonHash;
+use starknet_types_core::felt::Felt;
 use tokio::time::timeout;
 
 use super::test_utils::{
@@ -19,6 +29,7 @@ use super::test_utils::{
     random_header,
     run_test,
     setup,
+    signed_commit_certificate,
     wait_for_marker,
     Action,
     DataType,
@@ -72,6 +83,8 @@ async fn signed_headers_basic_flow() {
                 .take(end_block_number.try_into().expect("Failed converting u64 to usize"))
                 .skip(start_block_number.try_into().expect("Failed converting u64 to usize"))
             {
+                let commit_certificate =
+                    signed_commit_certificate(BlockNumber(i.try_into().unwrap()), 0, *block_hash);
                 // Send responses
                 mock_header_responses_manager
                     .send_response(DataOrFin(Some(SignedBlockHeader {
@@ -85,6 +98,7 @@ async fn signed_headers_basic_flow() {
                             ..Default::default()
                         },
                         signatures: vec![*block_signature],
+                        commit_certificate: Some(commit_certificate.clone()),
                     })))
                     .await
                     .unwrap();
@@ -109,6 +123,10 @@ async fn signed_headers_basic_flow() {
                 let actual_block_signature =
                     txn.get_block_signature(block_number).unwrap().unwrap();
                 assert_eq!(*block_signature, actual_block_signature);
+                assert_eq!(
+                    txn.get_commit_certificate(block_number).unwrap(),
+                    Some(commit_certificate)
+                );
             }
             mock_header_responses_manager.send_response(DataOrFin(None)).await.unwrap();
         }
@@ -156,6 +174,7 @@ async fn sync_sends_new_header_query_if_it_got_partial_responses() {
                         ..Default::default()
                     },
                     signatures: vec![signature],
+                    commit_certificate: None,
                 })))
                 .await
                 .unwrap();
@@ -225,4 +244,69 @@ async fn wrong_block_number() {
     .await;
 }
 
+async fn validate_commit_certificate_fails(
+    commit_certificate: CommitCertificate,
+    state_diff_commitment: Option<StateDiffCommitment>,
+) {
+    let mut signed_header = random_header(&mut get_rng(), BlockNumber(0), None, None);
+    signed_header.block_header.state_diff_commitment = state_diff_commitment;
+    signed_header.commit_certificate = Some(commit_certificate);
+    run_test(
+        HashMap::from([(DataType::Header, 1)]),
+        None,
+        vec![
+            Action::RunP2pSync,
+            // We already validate the query content in other tests.
+            Action::ReceiveQuery(Box::new(|_query| ()), DataType::Header),
+            Action::SendHeader(DataOrFin(Some(signed_header))),
+            Action::ValidateReportSent(DataType::Header),
+            Action::CheckStorage(Box::new(|reader| {
+                async move {
+                    assert_eq!(0, reader.begin_ro_txn().unwrap().get_header_marker().unwrap().0);
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn commit_certificate_with_invalid_signature() {
+    // The precommit is signed for another height.
+    let commit_certificate = signed_commit_certificate(BlockNumber(1), 0, BlockHash(Felt::ONE));
+    validate_commit_certificate_fails(commit_certificate, None).await;
+}
+
+#[tokio::test]
+async fn commit_certificate_with_unknown_voter() {
+    let mut commit_certificate = signed_commit_certificate(BlockNumber(0), 0, BlockHash(Felt::ONE));
+    commit_certificate.precommits[0].voter = ContractAddress::from(DEFAULT_VALIDATOR_ID + 1);
+    validate_commit_certificate_fails(commit_certificate, None).await;
+}
+
+#[tokio::test]
+async fn commit_certificate_with_duplicate_voter() {
+    let mut commit_certificate = signed_commit_certificate(BlockNumber(0), 0, BlockHash(Felt::ONE));
+    commit_certificate.precommits.push(commit_certificate.precommits[0]);
+    validate_commit_certificate_fails(commit_certificate, None).await;
+}
+
+#[tokio::test]
+async fn commit_certificate_without_quorum() {
+    let commit_certificate =
+        CommitCertificate { round: 0, block_hash: BlockHash(Felt::ONE), precommits: vec![] };
+    validate_commit_certificate_fails(commit_certificate, None).await;
+}
+
+#[tokio::test]
+async fn commit_certificate_for_wrong_block() {
+    let commit_certificate = signed_commit_certificate(BlockNumber(0), 0, BlockHash(Felt::ONE));
+    validate_commit_certificate_fails(
+        commit_certificate,
+        Some(StateDiffCommitment(PoseidonHash(Felt::TWO))),
+    )
+    .await;
+}
+
 // TODO(shahak): Add more negative tests.
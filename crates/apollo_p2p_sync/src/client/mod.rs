@@ -16,14 +16,15 @@ mod transaction;
 #[cfg(test)]
 mod transaction_test;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 
 use apollo_class_manager_types::SharedClassManagerClient;
-use apollo_config::converters::deserialize_milliseconds_to_duration;
+use apollo_config::converters::{deserialize_milliseconds_to_duration, deserialize_vec};
 use apollo_config::dumping::{ser_param, SerializeConfig};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_network::network_manager::SqmrClientSender;
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
 use apollo_protobuf::sync::{
     ClassQuery,
     DataOrFin,
@@ -45,8 +46,10 @@ use header::HeaderStreamBuilder;
 use papyrus_common::pending_classes::ApiContractClass;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
-use starknet_api::core::ClassHash;
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::transaction::FullTransaction;
+use starknet_types_core::felt::Felt;
 use state_diff::StateDiffStreamBuilder;
 use tokio_stream::StreamExt;
 use tracing::{info, instrument};
@@ -56,7 +59,7 @@ use validator::Validate;
 const STEP: u64 = 1;
 const ALLOWED_SIGNATURES_LENGTH: usize = 1;
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Validate)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Validate)]
 pub struct P2pSyncClientConfig {
     pub num_headers_per_query: u64,
     pub num_block_state_diffs_per_query: u64,
@@ -67,6 +70,10 @@ pub struct P2pSyncClientConfig {
     #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
     pub wait_period_for_other_protocol: Duration,
     pub buffer_size: usize,
+    /// The public keys of the validators, in the order of their IDs. The commit certificates of
+    /// synced blocks are verified against them, and aren't stored if there are none.
+    #[serde(deserialize_with = "deserialize_vec")]
+    pub validator_public_keys: Vec<Felt>,
 }
 
 impl SerializeConfig for P2pSyncClientConfig {
@@ -117,6 +124,19 @@ impl SerializeConfig for P2pSyncClientConfig {
                 "Size of the buffer for read from the storage and for incoming responses.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "validator_public_keys",
+                &self
+                    .validator_public_keys
+                    .iter()
+                    .map(|public_key| public_key.to_hex_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                "Space separated public keys of the validators, in the order of their IDs. The \
+                 commit certificates of synced blocks are verified against them, and aren't \
+                 stored if there are none.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
@@ -134,10 +154,24 @@ impl Default for P2pSyncClientConfig {
             wait_period_for_other_protocol: Duration::from_millis(50),
             // TODO(eitan): split this by protocol
             buffer_size: 100000,
+            validator_public_keys: Vec::new(),
         }
     }
 }
 
+impl P2pSyncClientConfig {
+    // TODO(Matan): Fetch the validators from the staking contract.
+    fn validator_public_keys(&self) -> ValidatorPublicKeys {
+        (DEFAULT_VALIDATOR_ID..)
+            .zip(&self.validator_public_keys)
+            .map(|(id, public_key)| (ContractAddress::from(id), PublicKey(*public_key)))
+            .collect()
+    }
+}
+
+/// The public keys of the validators, by their address.
+pub(crate) type ValidatorPublicKeys = HashMap<ContractAddress, PublicKey>;
+
 #[derive(thiserror::Error, Debug)]
 pub enum P2pSyncClientError {
     // TODO(shahak): Remove this and report to network on invalid data once that's possible.
@@ -182,9 +216,11 @@ impl P2pSyncClientChannels {
         config: P2pSyncClientConfig,
         internal_blocks_receivers: InternalBlocksReceivers,
     ) -> impl Stream<Item = BlockDataResult> + Send + 'static {
+        let validator_public_keys = config.validator_public_keys();
         let header_stream = HeaderStreamBuilder::create_stream(
             self.header_sender,
             storage_reader.clone(),
+            validator_public_keys.clone(),
             Some(internal_blocks_receivers.header_receiver),
             config.wait_period_for_new_data,
             config.wait_period_for_other_protocol,
@@ -194,6 +230,7 @@ impl P2pSyncClientChannels {
         let state_diff_stream = StateDiffStreamBuilder::create_stream(
             self.state_diff_sender,
             storage_reader.clone(),
+            validator_public_keys.clone(),
             Some(internal_blocks_receivers.state_diff_receiver),
             config.wait_period_for_new_data,
             config.wait_period_for_other_protocol,
@@ -203,6 +240,7 @@ impl P2pSyncClientChannels {
         let transaction_stream = TransactionStreamFactory::create_stream(
             self.transaction_sender,
             storage_reader.clone(),
+            validator_public_keys.clone(),
             Some(internal_blocks_receivers.transaction_receiver),
             config.wait_period_for_new_data,
             config.wait_period_for_other_protocol,
@@ -212,6 +250,7 @@ impl P2pSyncClientChannels {
         let class_stream = ClassStreamBuilder::create_stream(
             self.class_sender,
             storage_reader.clone(),
+            validator_public_keys.clone(),
             Some(internal_blocks_receivers.class_receiver),
             config.wait_period_for_new_data,
             config.wait_period_for_other_protocol,
@@ -21,7 +21,7 @@ use crate::client::block_data_stream_builder::{
     BlockNumberLimit,
     ParseDataError,
 };
-use crate::client::P2pSyncClientError;
+use crate::client::{P2pSyncClientError, ValidatorPublicKeys};
 
 impl BlockData for (ThinStateDiff, BlockNumber) {
     #[latency_histogram("p2p_sync_state_diff_write_to_storage_latency_seconds", true)]
@@ -54,6 +54,7 @@ impl BlockDataStreamBuilder<StateDiffChunk> for StateDiffStreamBuilder {
         >,
         block_number: BlockNumber,
         storage_reader: &'a StorageReader,
+        _validator_public_keys: &'a ValidatorPublicKeys,
     ) -> BoxFuture<'a, Result<Option<Self::Output>, ParseDataError>> {
         async move {
             let mut result = ThinStateDiff::default();
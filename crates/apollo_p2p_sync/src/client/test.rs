@@ -11,7 +11,7 @@ use futures::FutureExt;
 use indexmap::IndexMap;
 use rand::Rng;
 use rand_chacha::ChaCha8Rng;
-use starknet_api::block::{BlockHeaderWithoutHash, BlockNumber};
+use starknet_api::block::{BlockHeaderWithoutHash, BlockNumber, CommitCertificate};
 use starknet_api::core::{ClassHash, ContractAddress};
 use starknet_api::state::ThinStateDiff;
 use starknet_api::transaction::TransactionHash;
@@ -39,6 +39,7 @@ async fn receive_block_internally() {
     let block_header_without_hash = sync_block.block_header_without_hash.clone();
     let transaction_hashes = sync_block.get_all_transaction_hashes();
     let state_diff = sync_block.state_diff.clone();
+    let commit_certificate = sync_block.commit_certificate.clone();
 
     run_test(
         HashMap::new(),
@@ -84,6 +85,10 @@ async fn receive_block_internally() {
                             .block_header_without_hash,
                         block_header_without_hash
                     );
+                    assert_eq!(
+                        txn.get_commit_certificate(BlockNumber(0)).unwrap(),
+                        commit_certificate
+                    );
                     assert_eq!(txn.get_state_diff(BlockNumber(0)).unwrap().unwrap(), state_diff);
                     assert_eq!(
                         txn.get_block_transaction_hashes(BlockNumber(0))
@@ -316,5 +321,6 @@ fn create_random_sync_block(
         account_transaction_hashes,
         l1_transaction_hashes,
         block_header_without_hash,
+        commit_certificate: Some(CommitCertificate::get_test_instance(&mut rng)),
     }
 }
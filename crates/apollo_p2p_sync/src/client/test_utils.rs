@@ -10,6 +10,7 @@ use apollo_network::network_manager::test_utils::{
     MockClientResponsesManager,
 };
 use apollo_network::network_manager::GenericReceiver;
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
 use apollo_protobuf::sync::{
     ClassQuery,
     DataOrFin,
@@ -20,6 +21,8 @@ use apollo_protobuf::sync::{
     StateDiffQuery,
     TransactionQuery,
 };
+use apollo_signature_manager::SignatureManager;
+use apollo_signature_manager_types::{ConsensusVote, ConsensusVoteType};
 use apollo_state_sync_types::state_sync_types::SyncBlock;
 use apollo_storage::body::BodyStorageReader;
 use apollo_storage::class_manager::ClassManagerStorageReader;
@@ -41,8 +44,10 @@ use starknet_api::block::{
     BlockHeaderWithoutHash,
     BlockNumber,
     BlockSignature,
+    CommitCertificate,
+    SignedPrecommit,
 };
-use starknet_api::core::ClassHash;
+use starknet_api::core::{ClassHash, ContractAddress};
 use starknet_api::crypto::utils::Signature;
 use starknet_api::hash::StarkHash;
 use starknet_api::transaction::FullTransaction;
@@ -72,6 +77,7 @@ lazy_static! {
         wait_period_for_new_data: WAIT_PERIOD_FOR_NEW_DATA,
         wait_period_for_other_protocol: WAIT_PERIOD_FOR_OTHER_PROTOCOL,
         buffer_size: BUFFER_SIZE,
+        validator_public_keys: vec![test_validator_public_key()],
     };
 }
 pub(crate) type HeaderTestPayload =
@@ -97,7 +103,7 @@ pub struct TestArgs {
 }
 
 pub fn setup() -> TestArgs {
-    let p2p_sync_config = *TEST_CONFIG;
+    let p2p_sync_config = TEST_CONFIG.clone();
     let buffer_size = p2p_sync_config.buffer_size;
     let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
     let (header_sender, mock_header_response_manager) =
@@ -194,6 +200,7 @@ pub async fn run_test(
         wait_period_for_new_data: WAIT_PERIOD_FOR_NEW_DATA,
         wait_period_for_other_protocol: WAIT_PERIOD_FOR_OTHER_PROTOCOL,
         buffer_size: BUFFER_SIZE,
+        validator_public_keys: vec![test_validator_public_key()],
     };
     let class_manager_client = class_manager_client.unwrap_or_default();
     let class_manager_client = Arc::new(class_manager_client);
@@ -367,6 +374,7 @@ pub fn random_header(
             r: rng.next_u64().into(),
             s: rng.next_u64().into(),
         })],
+        commit_certificate: None,
     }
 }
 
@@ -386,6 +394,34 @@ pub fn create_block_hashes_and_signatures(n_blocks: u8) -> Vec<(BlockHash, Block
         .collect()
 }
 
+pub(crate) fn test_validator_public_key() -> Felt {
+    SignatureManager::new().keystore.public_key.0
+}
+
+/// Returns a commit certificate with the precommit of the single validator of the tests.
+pub(crate) fn signed_commit_certificate(
+    block_number: BlockNumber,
+    round: u32,
+    block_hash: BlockHash,
+) -> CommitCertificate {
+    let voter = ContractAddress::from(DEFAULT_VALIDATOR_ID);
+    let vote = ConsensusVote {
+        vote_type: ConsensusVoteType::Precommit,
+        height: block_number,
+        round,
+        block_hash: Some(block_hash),
+        voter,
+    };
+    let signature = SignatureManager::new()
+        .sign_vote(vote)
+        .now_or_never()
+        .expect("The testing key store should sign immediately")
+        .expect("Failed to sign precommit")
+        .try_into()
+        .expect("Failed to convert signature");
+    CommitCertificate { round, block_hash, precommits: vec![SignedPrecommit { voter, signature }] }
+}
+
 // TODO(Shahak): Consider moving this to storage and to use poll wakeup instead of sleep
 pub(crate) async fn wait_for_marker(
     data_type: DataType,
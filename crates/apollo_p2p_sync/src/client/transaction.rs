@@ -22,7 +22,7 @@ use super::block_data_stream_builder::{
     BlockNumberLimit,
     ParseDataError,
 };
-use super::P2pSyncClientError;
+use super::{P2pSyncClientError, ValidatorPublicKeys};
 
 impl BlockData for (BlockBody, BlockNumber) {
     fn write_to_storage<'a>(
@@ -55,6 +55,7 @@ impl BlockDataStreamBuilder<FullTransaction> for TransactionStreamFactory {
         transactions_response_manager: &'a mut ClientResponsesManager<DataOrFin<FullTransaction>>,
         block_number: BlockNumber,
         storage_reader: &'a StorageReader,
+        _validator_public_keys: &'a ValidatorPublicKeys,
     ) -> BoxFuture<'a, Result<Option<Self::Output>, ParseDataError>> {
         async move {
             let mut block_body = BlockBody::default();
@@ -49,6 +49,9 @@ use syn::{
 ///     fn block_number(&self) -> Result<BlockNumber, Error>;
 /// }
 /// ```
+///
+/// The names of subscriptions and their unsubscribe methods are prefixed in the same way, while
+/// their notification names are kept as is.
 #[proc_macro_attribute]
 pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
     let version = parse_macro_input!(attr as syn::LitStr);
@@ -84,6 +87,36 @@ pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
                                     }
                                     Ok(())
                                 });
+                            } else if attr.path().is_ident("subscription") {
+                                let mut args = Vec::new();
+                                let _ = attr.parse_nested_meta(|meta| {
+                                    let key = &meta.path;
+                                    let value = meta.value()?;
+                                    if key.is_ident("name") || key.is_ident("unsubscribe") {
+                                        let method_name: LitStr = value.parse()?;
+                                        let versioned_method_name = LitStr::new(
+                                            &format!("{}_{}", version.value(), method_name.value()),
+                                            method_name.span(),
+                                        );
+                                        // The notification name isn't routed, so it isn't
+                                        // versioned.
+                                        if value.peek(Token![=>]) {
+                                            let arrow: Token![=>] = value.parse()?;
+                                            let notification_name: LitStr = value.parse()?;
+                                            args.push(quote!(
+                                                #key = #versioned_method_name #arrow
+                                                    #notification_name
+                                            ));
+                                        } else {
+                                            args.push(quote!(#key = #versioned_method_name));
+                                        }
+                                    } else {
+                                        let item: syn::Type = value.parse()?;
+                                        args.push(quote!(#key = #item));
+                                    }
+                                    Ok(())
+                                });
+                                new_attr.meta = syn::parse_quote!(subscription(#(#args),*));
                             }
                             new_attr
                         })
@@ -5,7 +5,7 @@ mod consensus_test;
 use std::convert::{TryFrom, TryInto};
 
 use prost::Message;
-use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
+use starknet_api::block::{BlockHash, BlockNumber, BlockSignature, GasPrice};
 use starknet_api::consensus_transaction::ConsensusTransaction;
 use starknet_api::hash::StarkHash;
 
@@ -60,8 +60,10 @@ impl TryFrom<protobuf::Vote> for Vote {
         let block_hash: Option<BlockHash> =
             value.block_hash.map(|block_hash| block_hash.try_into()).transpose()?.map(BlockHash);
         let voter = value.voter.ok_or(missing("voter"))?.try_into()?;
+        let signature =
+            value.signature.map(BlockSignature::try_from).transpose()?.map(|signature| signature.0);
 
-        Ok(Vote { vote_type, height, round, block_hash, voter })
+        Ok(Vote { vote_type, height, round, block_hash, voter, signature })
     }
 }
 
@@ -78,6 +80,7 @@ impl From<Vote> for protobuf::Vote {
             round: value.round,
             block_hash: value.block_hash.map(|hash| hash.0.into()),
             voter: Some(value.voter.into()),
+            signature: value.signature.map(|signature| BlockSignature(signature).into()),
         }
     }
 }
@@ -9,7 +9,9 @@ use starknet_api::block::{
     BlockHeaderWithoutHash,
     BlockNumber,
     BlockSignature,
+    CommitCertificate,
     GasPricePerToken,
+    SignedPrecommit,
     StarknetVersion,
 };
 use starknet_api::core::{
@@ -220,6 +222,10 @@ impl TryFrom<protobuf::SignedBlockHeader> for SignedBlockHeader {
                 .into_iter()
                 .map(starknet_api::block::BlockSignature::try_from)
                 .collect::<Result<Vec<_>, _>>()?,
+            commit_certificate: value
+                .commit_certificate
+                .map(CommitCertificate::try_from)
+                .transpose()?,
         })
     }
 }
@@ -230,8 +236,16 @@ impl From<DataOrFin<SignedBlockHeader>> for protobuf::BlockHeadersResponse {
     }
 }
 
-impl From<(BlockHeader, Vec<BlockSignature>)> for protobuf::SignedBlockHeader {
-    fn from((header, signatures): (BlockHeader, Vec<BlockSignature>)) -> Self {
+impl From<(BlockHeader, Vec<BlockSignature>, Option<CommitCertificate>)>
+    for protobuf::SignedBlockHeader
+{
+    fn from(
+        (header, signatures, commit_certificate): (
+            BlockHeader,
+            Vec<BlockSignature>,
+            Option<CommitCertificate>,
+        ),
+    ) -> Self {
         let state_diff_commitment =
             header.state_diff_length.map(|state_diff_length| protobuf::StateDiffCommitment {
                 state_diff_length: state_diff_length
@@ -287,6 +301,7 @@ impl From<(BlockHeader, Vec<BlockSignature>)> for protobuf::SignedBlockHeader {
             l2_gas_consumed: header.block_header_without_hash.l2_gas_consumed.0,
             next_l2_gas_price: Some(header.block_header_without_hash.next_l2_gas_price.0.into()),
             signatures: signatures.iter().map(|signature| (*signature).into()).collect(),
+            commit_certificate: commit_certificate.map(Into::into),
         }
     }
 }
@@ -307,13 +322,61 @@ impl From<starknet_api::block::BlockSignature> for protobuf::ConsensusSignature
     }
 }
 
+impl TryFrom<protobuf::CommitCertificate> for CommitCertificate {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::CommitCertificate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            round: value.round,
+            block_hash: value
+                .block_hash
+                .ok_or(missing("CommitCertificate::block_hash"))?
+                .try_into()
+                .map(BlockHash)?,
+            precommits: value
+                .precommits
+                .into_iter()
+                .map(SignedPrecommit::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<CommitCertificate> for protobuf::CommitCertificate {
+    fn from(value: CommitCertificate) -> Self {
+        Self {
+            round: value.round,
+            precommits: value.precommits.into_iter().map(Into::into).collect(),
+            block_hash: Some(value.block_hash.into()),
+        }
+    }
+}
+
+impl TryFrom<protobuf::SignedPrecommit> for SignedPrecommit {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::SignedPrecommit) -> Result<Self, Self::Error> {
+        let voter = value.voter.ok_or(missing("SignedPrecommit::voter"))?.try_into()?;
+        let BlockSignature(signature) =
+            value.signature.ok_or(missing("SignedPrecommit::signature"))?.try_into()?;
+        Ok(Self { voter, signature })
+    }
+}
+
+impl From<SignedPrecommit> for protobuf::SignedPrecommit {
+    fn from(value: SignedPrecommit) -> Self {
+        Self {
+            voter: Some(value.voter.into()),
+            signature: Some(BlockSignature(value.signature).into()),
+        }
+    }
+}
+
 impl From<Option<SignedBlockHeader>> for protobuf::BlockHeadersResponse {
     fn from(data: Option<SignedBlockHeader>) -> Self {
         match data {
-            Some(SignedBlockHeader { block_header, signatures }) => {
+            Some(SignedBlockHeader { block_header, signatures, commit_certificate }) => {
                 protobuf::BlockHeadersResponse {
                     header_message: Some(protobuf::block_headers_response::HeaderMessage::Header(
-                        (block_header, signatures).into(),
+                        (block_header, signatures, commit_certificate).into(),
                     )),
                 }
             }
@@ -371,6 +371,9 @@ pub struct Vote {
     pub block_hash: ::core::option::Option<Hash>,
     #[prost(message, optional, tag = "6")]
     pub voter: ::core::option::Option<Address>,
+    /// Signed by the voter. Votes without a valid signature are rejected.
+    #[prost(message, optional, tag = "7")]
+    pub signature: ::core::option::Option<ConsensusSignature>,
 }
 /// Nested message and enum types in `Vote`.
 pub mod vote {
@@ -1079,6 +1082,29 @@ pub struct SignedBlockHeader {
     /// can be more explicit here about the signature structure as this is not part of account abstraction
     #[prost(message, repeated, tag = "21")]
     pub signatures: ::prost::alloc::vec::Vec<ConsensusSignature>,
+    /// The precommits with which consensus decided on the block. Not set for blocks which weren't decided by consensus.
+    #[prost(message, optional, tag = "22")]
+    pub commit_certificate: ::core::option::Option<CommitCertificate>,
+}
+/// A validator's precommit for the block, signed on the block hash.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedPrecommit {
+    #[prost(message, optional, tag = "1")]
+    pub voter: ::core::option::Option<Address>,
+    #[prost(message, optional, tag = "2")]
+    pub signature: ::core::option::Option<ConsensusSignature>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommitCertificate {
+    #[prost(uint32, tag = "1")]
+    pub round: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub precommits: ::prost::alloc::vec::Vec<SignedPrecommit>,
+    /// The proposal commitment the precommits voted for.
+    #[prost(message, optional, tag = "3")]
+    pub block_hash: ::core::option::Option<Hash>,
 }
 /// sent to all peers (except the ones this was received from, if any).
 /// for a fraction of peers, also send the GetBlockHeaders response (as if they asked for it for this block)
@@ -3,7 +3,7 @@ use std::fmt::Debug;
 #[cfg(any(feature = "testing", test))]
 use apollo_test_utils::{auto_impl_get_test_instance, get_number_of_variants, GetTestInstance};
 use indexmap::IndexMap;
-use starknet_api::block::{BlockHash, BlockHeader, BlockNumber, BlockSignature};
+use starknet_api::block::{BlockHash, BlockHeader, BlockNumber, BlockSignature, CommitCertificate};
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::state::StorageKey;
 use starknet_types_core::felt::Felt;
@@ -113,6 +113,8 @@ impl From<Query> for EventQuery {
 pub struct SignedBlockHeader {
     pub block_header: BlockHeader,
     pub signatures: Vec<BlockSignature>,
+    /// The precommits with which consensus decided on the block, if it was decided by consensus.
+    pub commit_certificate: Option<CommitCertificate>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -210,5 +212,6 @@ auto_impl_get_test_instance! {
     pub struct SignedBlockHeader {
         pub block_header: BlockHeader,
         pub signatures: Vec<BlockSignature>,
+        pub commit_certificate: Option<CommitCertificate>,
     }
 }
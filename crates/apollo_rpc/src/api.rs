@@ -13,7 +13,10 @@ use starknet_api::core::{ChainId, ContractAddress, EntryPointSelector};
 use starknet_api::transaction::fields::Calldata;
 use tokio::sync::RwLock;
 
+use crate::l1_messages::SharedL1MessagesReader;
+use crate::state_tries::SharedStateTriesReader;
 use crate::v0_8::api::api_impl::JsonRpcServerImpl as JsonRpcServerV0_8Impl;
+use crate::v0_9::api::api_impl::JsonRpcServerImpl as JsonRpcServerV0_9Impl;
 use crate::version_config;
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -58,12 +61,15 @@ pub fn get_methods_from_supported_apis(
     storage_reader: StorageReader,
     max_events_chunk_size: usize,
     max_events_keys: usize,
+    max_storage_proof_keys: usize,
     starting_block: BlockHashAndNumber,
     shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pending_data: Arc<RwLock<PendingData>>,
     pending_classes: Arc<RwLock<PendingClasses>>,
     starknet_writer: Arc<dyn StarknetWriter>,
     class_manager_client: Option<SharedClassManagerClient>,
+    state_tries_reader: Option<SharedStateTriesReader>,
+    l1_messages_reader: Option<SharedL1MessagesReader>,
 ) -> Methods {
     let mut methods: Methods = Methods::new();
     let server_gen = JsonRpcServerImplGenerator {
@@ -72,12 +78,15 @@ pub fn get_methods_from_supported_apis(
         storage_reader,
         max_events_chunk_size,
         max_events_keys,
+        max_storage_proof_keys,
         starting_block,
         shared_highest_block,
         pending_data,
         pending_classes,
         starknet_writer,
         class_manager_client,
+        state_tries_reader,
+        l1_messages_reader,
     };
     version_config::VERSION_CONFIG
         .iter()
@@ -90,6 +99,9 @@ pub fn get_methods_from_supported_apis(
                         version_config::VERSION_0_8 => {
                             server_gen.clone().generator::<JsonRpcServerV0_8Impl>()
                         }
+                        version_config::VERSION_0_9 => {
+                            server_gen.clone().generator::<JsonRpcServerV0_9Impl>()
+                        }
                         // TODO(yair): remove this once the version is an enum instead of a string.
                         _ => unreachable!("Unrecognized RPC spec version: {}", version),
                     };
@@ -104,6 +116,26 @@ pub fn get_methods_from_supported_apis(
     methods
 }
 
+/// Registers the methods of the last supported version under their unversioned names as well.
+/// Requests over HTTP are versioned by the proxy middleware, but messages over WebSocket aren't.
+pub fn with_unversioned_aliases(methods: Methods) -> Methods {
+    let versioned_prefix = format!("starknet_{}_", version_config::LATEST_VERSION.name);
+    let mut module = RpcModule::new(());
+    module.merge(methods).expect("An empty module shouldn't have conflicting methods.");
+    let method_names = module.method_names().collect::<Vec<_>>();
+    for method_name in method_names {
+        let Some(unversioned_method_name) = method_name.strip_prefix(&versioned_prefix) else {
+            continue;
+        };
+        // The method names are registered for the lifetime of the server.
+        let alias = Box::leak(format!("starknet_{unversioned_method_name}").into_boxed_str());
+        module
+            .register_alias(alias, method_name)
+            .expect("Unversioned method names shouldn't be registered.");
+    }
+    module.into()
+}
+
 pub trait JsonRpcServerTrait: Sized {
     #[allow(clippy::too_many_arguments)]
     fn new(
@@ -112,12 +144,15 @@ pub trait JsonRpcServerTrait: Sized {
         storage_reader: StorageReader,
         max_events_chunk_size: usize,
         max_events_keys: usize,
+        max_storage_proof_keys: usize,
         starting_block: BlockHashAndNumber,
         shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
         pending_data: Arc<RwLock<PendingData>>,
         pending_classes: Arc<RwLock<PendingClasses>>,
         starknet_writer: Arc<dyn StarknetWriter>,
         class_manager_client: Option<SharedClassManagerClient>,
+        state_tries_reader: Option<SharedStateTriesReader>,
+        l1_messages_reader: Option<SharedL1MessagesReader>,
     ) -> Self;
 
     fn into_rpc_module(self) -> RpcModule<Self>;
@@ -130,6 +165,7 @@ struct JsonRpcServerImplGenerator {
     storage_reader: StorageReader,
     max_events_chunk_size: usize,
     max_events_keys: usize,
+    max_storage_proof_keys: usize,
     starting_block: BlockHashAndNumber,
     shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pending_data: Arc<RwLock<PendingData>>,
@@ -137,6 +173,8 @@ struct JsonRpcServerImplGenerator {
     // TODO(shahak): Change this struct to be with a generic type of StarknetWriter.
     starknet_writer: Arc<dyn StarknetWriter>,
     class_manager_client: Option<SharedClassManagerClient>,
+    state_tries_reader: Option<SharedStateTriesReader>,
+    l1_messages_reader: Option<SharedL1MessagesReader>,
 }
 
 type JsonRpcServerImplParams = (
@@ -145,12 +183,15 @@ type JsonRpcServerImplParams = (
     StorageReader,
     usize,
     usize,
+    usize,
     BlockHashAndNumber,
     Arc<RwLock<Option<BlockHashAndNumber>>>,
     Arc<RwLock<PendingData>>,
     Arc<RwLock<PendingClasses>>,
     Arc<dyn StarknetWriter>,
     Option<SharedClassManagerClient>,
+    Option<SharedStateTriesReader>,
+    Option<SharedL1MessagesReader>,
 );
 
 impl JsonRpcServerImplGenerator {
@@ -161,12 +202,15 @@ impl JsonRpcServerImplGenerator {
             self.storage_reader,
             self.max_events_chunk_size,
             self.max_events_keys,
+            self.max_storage_proof_keys,
             self.starting_block,
             self.shared_highest_block,
             self.pending_data,
             self.pending_classes,
             self.starknet_writer,
             self.class_manager_client,
+            self.state_tries_reader,
+            self.l1_messages_reader,
         )
     }
 
@@ -180,12 +224,15 @@ impl JsonRpcServerImplGenerator {
             storage_reader,
             max_events_chunk_size,
             max_events_keys,
+            max_storage_proof_keys,
             starting_block,
             shared_highest_block,
             pending_data,
             pending_classes,
             starknet_writer,
             class_manager_client,
+            state_tries_reader,
+            l1_messages_reader,
         ) = self.get_params();
         Into::<Methods>::into(
             T::new(
@@ -194,12 +241,15 @@ impl JsonRpcServerImplGenerator {
                 storage_reader,
                 max_events_chunk_size,
                 max_events_keys,
+                max_storage_proof_keys,
                 starting_block,
                 shared_highest_block,
                 pending_data,
                 pending_classes,
                 starknet_writer,
                 class_manager_client,
+                state_tries_reader,
+                l1_messages_reader,
             )
             .into_rpc_module(),
         )
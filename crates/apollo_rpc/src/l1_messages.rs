@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::types::H256;
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerContract;
+use starknet_api::transaction::L1HandlerTransaction;
+
+/// The hash of an L1 transaction.
+pub type L1TransactionHash = H256;
+
+/// Reads the messages that L1 transactions sent to L2, in order to serve the status of the
+/// messages.
+#[async_trait]
+pub trait L1MessagesReader: Send + Sync {
+    /// Returns the L1 handler transactions of the messages that the given L1 transaction sent, in
+    /// the order they were sent, or None if the L1 transaction is unknown.
+    async fn get_messages_to_l2(
+        &self,
+        l1_transaction_hash: L1TransactionHash,
+    ) -> anyhow::Result<Option<Vec<L1HandlerTransaction>>>;
+}
+
+pub type SharedL1MessagesReader = Arc<dyn L1MessagesReader>;
+
+#[async_trait]
+impl L1MessagesReader for EthereumBaseLayerContract {
+    async fn get_messages_to_l2(
+        &self,
+        l1_transaction_hash: L1TransactionHash,
+    ) -> anyhow::Result<Option<Vec<L1HandlerTransaction>>> {
+        Ok(EthereumBaseLayerContract::get_messages_to_l2(self, l1_transaction_hash.0.into())
+            .await?)
+    }
+}
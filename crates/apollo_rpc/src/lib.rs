@@ -3,23 +3,32 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
 mod api;
+mod l1_messages;
 mod middleware;
 mod pending;
 mod rpc_metrics;
 #[cfg(test)]
 mod rpc_test;
+mod state_tries;
 mod syncing_state;
 #[cfg(test)]
 mod test_utils;
 mod v0_8;
+mod v0_9;
 mod version_config;
 
 use std::collections::BTreeMap;
 use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use apollo_class_manager_types::SharedClassManagerClient;
-use apollo_config::dumping::{prepend_sub_config_name, ser_param, SerializeConfig};
+use apollo_config::dumping::{
+    prepend_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use apollo_config::validators::validate_ascii;
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_rpc_execution::ExecutionConfig;
@@ -42,6 +51,7 @@ use rpc_metrics::MetricLogger;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHashAndNumber, BlockNumber, BlockStatus};
 use starknet_api::core::ChainId;
+use starknet_committer::forest::garbage_collection::ForestRetentionConfig;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument};
 // Aliasing the latest version of the RPC.
@@ -49,8 +59,11 @@ use v0_8 as latest;
 pub use v0_8::api::CompiledContractClass;
 use validator::Validate;
 
-use crate::api::get_methods_from_supported_apis;
+use crate::api::{get_methods_from_supported_apis, with_unversioned_aliases};
+pub use crate::l1_messages::{L1MessagesReader, L1TransactionHash, SharedL1MessagesReader};
 use crate::middleware::proxy_rpc_request;
+use crate::state_tries::spawn_state_tries_writer;
+pub use crate::state_tries::{SharedStateTriesReader, StateTriesReader};
 use crate::syncing_state::get_last_synced_block;
 pub use crate::v0_8::transaction::{
     InvokeTransaction as InvokeTransactionRPC0_8,
@@ -75,6 +88,9 @@ pub struct RpcConfig {
     pub port: u16,
     pub max_events_chunk_size: usize,
     pub max_events_keys: usize,
+    pub max_storage_proof_keys: usize,
+    pub state_tries_path: Option<PathBuf>,
+    pub state_tries_retention_config: ForestRetentionConfig,
     // TODO(lev,shahak): remove once we remove papyrus.
     pub collect_metrics: bool,
     pub starknet_url: String,
@@ -90,6 +106,9 @@ impl Default for RpcConfig {
             port: 8090,
             max_events_chunk_size: 1000,
             max_events_keys: 100,
+            max_storage_proof_keys: 100,
+            state_tries_path: None,
+            state_tries_retention_config: ForestRetentionConfig::default(),
             collect_metrics: false,
             starknet_url: String::from("https://alpha-mainnet.starknet.io/"),
             apollo_gateway_retry_config: RetryConfig {
@@ -134,6 +153,13 @@ impl SerializeConfig for RpcConfig {
                 "Maximum number of keys supported by the node in get_events requests.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_storage_proof_keys",
+                &self.max_storage_proof_keys,
+                "Maximum number of classes, contracts and storage keys supported by the node in \
+                 get_storage_proof requests.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "collect_metrics",
                 &self.collect_metrics,
@@ -148,6 +174,18 @@ impl SerializeConfig for RpcConfig {
             ),
         ]);
 
+        self_params_dump.extend(ser_optional_param(
+            &self.state_tries_path,
+            PathBuf::from("./state_tries"),
+            "state_tries_path",
+            "The directory of the state tries, which are committed to as blocks are synced in \
+             order to serve storage proofs. If not set, storage proofs aren't supported.",
+            ParamPrivacyInput::Public,
+        ));
+        self_params_dump.append(&mut prepend_sub_config_name(
+            self.state_tries_retention_config.dump(),
+            "state_tries_retention_config",
+        ));
         self_params_dump
             .append(&mut prepend_sub_config_name(self.execution_config.dump(), "execution_config"));
         let mut retry_config_dump = prepend_sub_config_name(
@@ -209,7 +247,8 @@ fn get_block_status<Mode: TransactionKind>(
 #[derive(Clone, Debug, PartialEq)]
 struct ContinuationTokenAsStruct(EventIndex);
 
-#[instrument(skip(storage_reader, class_manager_client), level = "debug", err)]
+#[instrument(skip(storage_reader, class_manager_client, l1_messages_reader), level = "debug", err)]
+#[allow(clippy::too_many_arguments)]
 pub async fn run_server(
     config: &RpcConfig,
     shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
@@ -218,8 +257,20 @@ pub async fn run_server(
     storage_reader: StorageReader,
     node_version: &'static str,
     class_manager_client: Option<SharedClassManagerClient>,
+    l1_messages_reader: Option<SharedL1MessagesReader>,
 ) -> anyhow::Result<(SocketAddr, ServerHandle)> {
     let starting_block = get_last_synced_block(storage_reader.clone())?;
+    let state_tries_reader = config
+        .state_tries_path
+        .as_ref()
+        .map(|path| {
+            spawn_state_tries_writer(
+                path,
+                config.state_tries_retention_config,
+                storage_reader.clone(),
+            )
+        })
+        .transpose()?;
     debug!("Starting JSON-RPC.");
     let methods = get_methods_from_supported_apis(
         &config.chain_id,
@@ -227,6 +278,7 @@ pub async fn run_server(
         storage_reader,
         config.max_events_chunk_size,
         config.max_events_keys,
+        config.max_storage_proof_keys,
         starting_block,
         shared_highest_block,
         pending_data,
@@ -237,6 +289,8 @@ pub async fn run_server(
             config.apollo_gateway_retry_config,
         )?),
         class_manager_client,
+        state_tries_reader,
+        l1_messages_reader,
     );
     let addr;
     let handle;
@@ -247,14 +301,16 @@ pub async fn run_server(
     let server_address = SocketAddr::new(config.ip, config.port);
 
     if config.collect_metrics {
+        // The metrics are of the versioned methods, so the logger is created before adding the
+        // unversioned aliases.
         let server =
             server_builder.set_logger(MetricLogger::new(&methods)).build(&server_address).await?;
         addr = server.local_addr()?;
-        handle = server.start(methods);
+        handle = server.start(with_unversioned_aliases(methods));
     } else {
         let server = server_builder.build(&server_address).await?;
         addr = server.local_addr()?;
-        handle = server.start(methods);
+        handle = server.start(with_unversioned_aliases(methods));
     }
     info!(local_address = %addr, "JSON-RPC is running.");
     Ok((addr, handle))
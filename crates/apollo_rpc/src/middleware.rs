@@ -1,16 +1,17 @@
+use hyper::header::UPGRADE;
 use hyper::{Body, Request};
 use jsonrpsee::core::http_helpers::read_body;
 use regex::Regex;
 use tower::BoxError;
 use tracing::debug;
 
-use crate::version_config::{VERSION_0_8, VERSION_PATTERN};
+use crate::version_config::{LATEST_VERSION, VERSION_PATTERN};
 use crate::SERVER_MAX_BODY_SIZE;
 
-/// [`Tower`] middleware intended to proxy method requests to the last supported version of the API,
-/// which is V0_8. The middleware reads the JsonRPC request body and request path then prefixes the
-/// method name with the appropriate version identifier. It returns a new [`hyper::Request`] object
-/// with the new method name.
+/// [`Tower`] middleware intended to proxy method requests to the version of the API in the request
+/// path, or to the last supported version if the path has no version. The middleware reads the
+/// JsonRPC request body and request path then prefixes the method name with the appropriate version
+/// identifier. It returns a new [`hyper::Request`] object with the new method name.
 ///
 /// # Arguments
 /// * req - [`hyper::Request`] object passed by the server.
@@ -24,7 +25,14 @@ pub(crate) async fn proxy_rpc_request(req: Request<Body>) -> Result<Request<Body
         return Err(BoxError::from("Unsupported path for request"));
     }
 
-    let prefix = VERSION_0_8.name;
+    // A WebSocket handshake has no body to proxy. The messages of the connection use the
+    // unversioned method names, which are registered for the last supported version.
+    if is_websocket_upgrade(&req) {
+        return Ok(req);
+    }
+
+    let prefix = get_version_name_from_path(req.uri().path());
+    let prefix = prefix.as_str();
     let (parts, body) = req.into_parts();
     let (body_bytes, is_single) =
         read_body(&parts.headers, body, SERVER_MAX_BODY_SIZE).await.map_err(BoxError::from)?;
@@ -74,6 +82,21 @@ fn strip_starknet_from_method(method: &str) -> Option<&str> {
     split_method_name.get(1).copied()
 }
 
+fn is_websocket_upgrade(req: &Request<Body>) -> bool {
+    req.headers()
+        .get(UPGRADE)
+        .is_some_and(|upgrade| upgrade.as_bytes().eq_ignore_ascii_case(b"websocket"))
+}
+
+/// Returns the name of the version in the given path, e.g. V0_8 for /rpc/v0_8_1, or the name of the
+/// last supported version if the path has no version. Assumes the path is supported.
+fn get_version_name_from_path(path: &str) -> String {
+    match path.strip_prefix("/rpc/") {
+        Some(version) => version.to_uppercase().split('_').take(2).collect::<Vec<_>>().join("_"),
+        None => LATEST_VERSION.name.to_string(),
+    }
+}
+
 fn is_supported_path(path: &str) -> bool {
     let re = Regex::new((r"^\/rpc(\/".to_string() + VERSION_PATTERN + ")?$").as_str())
         .expect("should be a valid regex");
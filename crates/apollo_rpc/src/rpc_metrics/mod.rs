@@ -10,6 +10,8 @@ use jsonrpsee::types::Params;
 use jsonrpsee::Methods;
 use metrics::{counter, histogram};
 
+use crate::version_config::LATEST_VERSION;
+
 // Name of the metrics.
 const INCOMING_REQUEST: &str = "rpc_incoming_requests";
 const FAILED_REQUESTS: &str = "rpc_failed_requests";
@@ -58,9 +60,17 @@ impl Logger for MetricLogger {
         started_at: Self::Instant,
         _transport: TransportProtocol,
     ) {
+        // Messages over WebSocket may call the methods of the last supported version by their
+        // unversioned aliases.
+        let method_name = match method_name.strip_prefix("starknet_") {
+            Some(unversioned_method_name) if !self.methods_set.contains(method_name) => {
+                format!("starknet_{}_{unversioned_method_name}", LATEST_VERSION.name)
+            }
+            _ => method_name.to_owned(),
+        };
         // To prevent creating metrics for illegal methods.
-        if self.methods_set.contains(method_name) {
-            let (method, version) = get_method_and_version(method_name);
+        if self.methods_set.contains(&method_name) {
+            let (method, version) = get_method_and_version(&method_name);
             if let jsonrpsee::helpers::MethodResponseResult::Failed(_) = success_or_error {
                 counter!(FAILED_REQUESTS, METHOD_LABEL=> method.clone(), VERSION_LABEL=> version.clone()).increment(1);
             }
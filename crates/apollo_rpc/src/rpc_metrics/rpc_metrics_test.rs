@@ -169,6 +169,7 @@ async fn server_metrics() {
         storage_reader,
         "NODE VERSION",
         None,
+        None,
     )
     .await
     .unwrap();
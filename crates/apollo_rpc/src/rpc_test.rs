@@ -40,6 +40,7 @@ async fn run_server_no_blocks() {
         storage_reader,
         "NODE VERSION",
         None,
+        None,
     )
     .await
     .unwrap();
@@ -0,0 +1,203 @@
+#[cfg(test)]
+#[path = "state_tries_test.rs"]
+mod state_tries_test;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use apollo_storage::header::HeaderStorageReader;
+use apollo_storage::state::StateStorageReader;
+use apollo_storage::StorageReader;
+use starknet_api::block::{BlockHeader, BlockNumber};
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::state::ThinStateDiff;
+use starknet_committer::block_committer::commit::commit_block;
+use starknet_committer::block_committer::input::{ConfigImpl, Input, StarknetStorageKey};
+use starknet_committer::forest::forest_roots::{
+    read_block_forest_roots,
+    read_forest_roots_marker,
+    ForestRoots,
+};
+use starknet_committer::forest::garbage_collection::{
+    prune_forest,
+    write_block_forest,
+    ForestRetentionConfig,
+};
+use starknet_committer::forest::storage_proof::{fetch_storage_proof, StorageProof};
+use starknet_patricia_storage::map_storage::MapStorage;
+use starknet_patricia_storage::mdbx_storage::{MdbxStorage, MdbxStorageConfig};
+use starknet_patricia_storage::storage_trait::{DbKey, DbValue, Storage};
+use tracing::{error, info};
+
+// The interval between checks for newly synced blocks, once the tries are up to date.
+const WRITER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Reads the Patricia-Merkle tries of the state, which are kept next to the node's storage, in
+/// order to serve storage proofs.
+pub trait StateTriesReader: Send + Sync {
+    /// Returns the roots of the tries after the given block, or None if the tries of the block are
+    /// not kept.
+    fn get_forest_roots(&self, block_number: BlockNumber) -> anyhow::Result<Option<ForestRoots>>;
+
+    /// Returns the proofs of the given classes, contracts and storage keys against the given
+    /// roots.
+    fn get_storage_proof(
+        &self,
+        forest_roots: &ForestRoots,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[ContractAddress],
+        contracts_storage_keys: &HashMap<ContractAddress, Vec<StarknetStorageKey>>,
+    ) -> anyhow::Result<StorageProof>;
+}
+
+pub type SharedStateTriesReader = Arc<dyn StateTriesReader>;
+
+impl<S: Storage + Send + Sync> StateTriesReader for RwLock<S> {
+    fn get_forest_roots(&self, block_number: BlockNumber) -> anyhow::Result<Option<ForestRoots>> {
+        let storage = self.read().map_err(to_anyhow_error)?;
+        read_block_forest_roots(&*storage, block_number).map_err(to_anyhow_error)
+    }
+
+    fn get_storage_proof(
+        &self,
+        forest_roots: &ForestRoots,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[ContractAddress],
+        contracts_storage_keys: &HashMap<ContractAddress, Vec<StarknetStorageKey>>,
+    ) -> anyhow::Result<StorageProof> {
+        let storage = self.read().map_err(to_anyhow_error)?;
+        fetch_storage_proof(
+            &*storage,
+            forest_roots,
+            class_hashes,
+            contract_addresses,
+            contracts_storage_keys,
+        )
+        .map_err(to_anyhow_error)
+    }
+}
+
+// The errors of the tries aren't thread safe, so they are converted into their messages.
+fn to_anyhow_error(err: impl ToString) -> anyhow::Error {
+    anyhow::anyhow!(err.to_string())
+}
+
+/// Opens the state tries at the given path, and spawns a task that commits the state diffs of the
+/// synced blocks into them, keeping the tries of the blocks in the retention window. Returns a
+/// reader of the tries.
+pub(crate) fn spawn_state_tries_writer(
+    path: &Path,
+    retention_config: ForestRetentionConfig,
+    storage_reader: StorageReader,
+) -> anyhow::Result<SharedStateTriesReader> {
+    let config = MdbxStorageConfig { path: path.to_path_buf(), ..Default::default() };
+    let state_tries = Arc::new(RwLock::new(MdbxStorage::open(&config)?));
+    let writer_state_tries = state_tries.clone();
+    tokio::spawn(async move {
+        if let Err(err) =
+            run_state_tries_writer(storage_reader, &writer_state_tries, &retention_config).await
+        {
+            error!("Stopped writing the state tries: {err}");
+        }
+    });
+    Ok(state_tries)
+}
+
+// Commits the state diff of each synced block on top of the tries of its parent, and writes the
+// tries of the block once their roots match its state root. Then, prunes the tries of the blocks
+// that left the retention window. Returns only on failure.
+pub(crate) async fn run_state_tries_writer<S: Storage + Send + Sync>(
+    storage_reader: StorageReader,
+    state_tries: &RwLock<S>,
+    retention_config: &ForestRetentionConfig,
+) -> anyhow::Result<()> {
+    let mut storage = LockedStorage(state_tries);
+    let mut block_number = read_forest_roots_marker(&storage).map_err(to_anyhow_error)?;
+    info!("Writing the state tries from block {block_number}.");
+    loop {
+        let Some((header, state_diff)) = read_synced_block(&storage_reader, block_number)? else {
+            tokio::time::sleep(WRITER_POLL_INTERVAL).await;
+            continue;
+        };
+        let parent_roots = match block_number.prev() {
+            None => ForestRoots::default(),
+            Some(parent_block_number) => read_block_forest_roots(&storage, parent_block_number)
+                .map_err(to_anyhow_error)?
+                .ok_or_else(|| anyhow!("The tries of block {parent_block_number} are missing."))?,
+        };
+        let input = Input {
+            state_diff: state_diff.into(),
+            contracts_trie_root_hash: parent_roots.contracts_trie_root_hash,
+            classes_trie_root_hash: parent_roots.classes_trie_root_hash,
+            config: ConfigImpl::default(),
+        };
+        let filled_forest = commit_block(input, &storage).await.map_err(to_anyhow_error)?;
+        let forest_roots = ForestRoots {
+            contracts_trie_root_hash: filled_forest.get_contract_root_hash(),
+            classes_trie_root_hash: filled_forest.get_compiled_class_root_hash(),
+        };
+        if forest_roots.global_root() != header.block_header_without_hash.state_root {
+            bail!("The committed state of block {block_number} doesn't match its state root.");
+        }
+        write_block_forest(&mut storage, &filled_forest, block_number).map_err(to_anyhow_error)?;
+        prune_forest(&mut storage, block_number, retention_config).map_err(to_anyhow_error)?;
+        block_number = block_number.unchecked_next();
+    }
+}
+
+// Returns the header and the state diff of the given block, or None if its state wasn't synced yet.
+fn read_synced_block(
+    storage_reader: &StorageReader,
+    block_number: BlockNumber,
+) -> anyhow::Result<Option<(BlockHeader, ThinStateDiff)>> {
+    let txn = storage_reader.begin_ro_txn()?;
+    if txn.get_state_marker()? <= block_number {
+        return Ok(None);
+    }
+    let header = txn
+        .get_block_header(block_number)?
+        .ok_or_else(|| anyhow!("The header of block {block_number} is missing."))?;
+    let state_diff = txn
+        .get_state_diff(block_number)?
+        .ok_or_else(|| anyhow!("The state diff of block {block_number} is missing."))?;
+    Ok(Some((header, state_diff)))
+}
+
+// Accesses the tries that are shared with their readers, locking them for each access rather than
+// for the whole commitment of a block.
+struct LockedStorage<'a, S>(&'a RwLock<S>);
+
+impl<S: Storage> LockedStorage<'_, S> {
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, S> {
+        self.0.read().expect("The state tries lock is poisoned.")
+    }
+
+    fn write(&mut self) -> std::sync::RwLockWriteGuard<'_, S> {
+        self.0.write().expect("The state tries lock is poisoned.")
+    }
+}
+
+impl<S: Storage> Storage for LockedStorage<'_, S> {
+    fn get(&self, key: &DbKey) -> Option<DbValue> {
+        self.read().get(key)
+    }
+
+    fn set(&mut self, key: DbKey, value: DbValue) -> Option<DbValue> {
+        self.write().set(key, value)
+    }
+
+    fn mget(&self, keys: &[DbKey]) -> Vec<Option<DbValue>> {
+        self.read().mget(keys)
+    }
+
+    fn mset(&mut self, key_to_value: MapStorage) {
+        self.write().mset(key_to_value);
+    }
+
+    fn delete(&mut self, key: &DbKey) -> Option<DbValue> {
+        self.write().delete(key)
+    }
+}
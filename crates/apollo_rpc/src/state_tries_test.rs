@@ -0,0 +1,160 @@
+use std::sync::RwLock;
+use std::time::Duration;
+
+use apollo_storage::header::HeaderStorageWriter;
+use apollo_storage::state::StateStorageWriter;
+use apollo_storage::test_utils::get_test_storage;
+use apollo_storage::StorageWriter;
+use starknet_api::block::{BlockHeader, BlockHeaderWithoutHash, BlockNumber};
+use starknet_api::core::{GlobalRoot, Nonce};
+use starknet_api::state::ThinStateDiff;
+use starknet_api::{class_hash, compiled_class_hash, contract_address, felt, storage_key};
+use starknet_committer::block_committer::commit::commit_block;
+use starknet_committer::block_committer::input::{ConfigImpl, Input};
+use starknet_committer::forest::forest_roots::{
+    read_block_forest_roots,
+    read_forest_roots_marker,
+    ForestRoots,
+};
+use starknet_committer::forest::garbage_collection::ForestRetentionConfig;
+use starknet_patricia_storage::map_storage::MapStorage;
+
+use super::run_state_tries_writer;
+
+fn state_diffs() -> Vec<ThinStateDiff> {
+    let address = contract_address!("0x11");
+    vec![
+        ThinStateDiff {
+            deployed_contracts: [(address, class_hash!("0x1"))].into_iter().collect(),
+            storage_diffs: [(address, [(storage_key!("0x5"), felt!("0x7"))].into_iter().collect())]
+                .into_iter()
+                .collect(),
+            declared_classes: [(class_hash!("0x1"), compiled_class_hash!(0x31_u8))]
+                .into_iter()
+                .collect(),
+            nonces: [(address, Nonce(felt!("0x1")))].into_iter().collect(),
+            ..Default::default()
+        },
+        ThinStateDiff {
+            storage_diffs: [(address, [(storage_key!("0x5"), felt!("0x9"))].into_iter().collect())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        },
+    ]
+}
+
+// Returns the roots of the forest after each of the given state diffs.
+async fn expected_forest_roots(state_diffs: &[ThinStateDiff]) -> Vec<ForestRoots> {
+    let mut tries = MapStorage::new();
+    let mut forest_roots = ForestRoots::default();
+    let mut blocks_forest_roots = vec![];
+    for state_diff in state_diffs {
+        let input = Input {
+            state_diff: state_diff.clone().into(),
+            contracts_trie_root_hash: forest_roots.contracts_trie_root_hash,
+            classes_trie_root_hash: forest_roots.classes_trie_root_hash,
+            config: ConfigImpl::default(),
+        };
+        let filled_forest = commit_block(input, &tries).await.unwrap();
+        filled_forest.write_to_storage(&mut tries);
+        forest_roots = ForestRoots {
+            contracts_trie_root_hash: filled_forest.get_contract_root_hash(),
+            classes_trie_root_hash: filled_forest.get_compiled_class_root_hash(),
+        };
+        blocks_forest_roots.push(forest_roots);
+    }
+    blocks_forest_roots
+}
+
+fn append_block(
+    storage_writer: &mut StorageWriter,
+    block_number: BlockNumber,
+    state_root: GlobalRoot,
+    state_diff: ThinStateDiff,
+) {
+    let header = BlockHeader {
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number,
+            state_root,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(block_number, &header)
+        .unwrap()
+        .append_state_diff(block_number, state_diff)
+        .unwrap()
+        .commit()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn writes_the_tries_of_synced_blocks() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let state_diffs = state_diffs();
+    let forest_roots = expected_forest_roots(&state_diffs).await;
+    for (block_number, (state_diff, block_forest_roots)) in
+        state_diffs.into_iter().zip(&forest_roots).enumerate()
+    {
+        append_block(
+            &mut storage_writer,
+            BlockNumber(block_number.try_into().unwrap()),
+            block_forest_roots.global_root(),
+            state_diff,
+        );
+    }
+
+    // The writer returns only on failure, so it runs until the tries of all the blocks are written.
+    let state_tries = RwLock::new(MapStorage::new());
+    let all_blocks_written = async {
+        loop {
+            let marker = read_forest_roots_marker(&*state_tries.read().unwrap()).unwrap();
+            if marker == BlockNumber(2) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::select! {
+        result = run_state_tries_writer(
+            storage_reader,
+            &state_tries,
+            &ForestRetentionConfig::default(),
+        ) => {
+            panic!("The writer stopped: {result:?}")
+        }
+        _ = all_blocks_written => {}
+    }
+
+    let state_tries = state_tries.read().unwrap();
+    for (block_number, block_forest_roots) in forest_roots.into_iter().enumerate() {
+        let block_number = BlockNumber(block_number.try_into().unwrap());
+        assert_eq!(
+            read_block_forest_roots(&*state_tries, block_number).unwrap(),
+            Some(block_forest_roots)
+        );
+    }
+}
+
+#[tokio::test]
+async fn stops_on_state_root_mismatch() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let state_diff = state_diffs().remove(0);
+    append_block(&mut storage_writer, BlockNumber(0), GlobalRoot(felt!("0x1")), state_diff);
+
+    let state_tries = RwLock::new(MapStorage::new());
+    assert!(run_state_tries_writer(
+        storage_reader,
+        &state_tries,
+        &ForestRetentionConfig::default()
+    )
+    .await
+    .is_err());
+    let state_tries = state_tries.read().unwrap();
+    assert_eq!(read_forest_roots_marker(&*state_tries).unwrap(), BlockNumber(0));
+    assert_eq!(read_block_forest_roots(&*state_tries, BlockNumber(0)).unwrap(), None);
+}
@@ -23,6 +23,8 @@ use strum_macros::EnumIter;
 use tokio::sync::RwLock;
 
 use crate::api::JsonRpcServerTrait;
+use crate::l1_messages::SharedL1MessagesReader;
+use crate::state_tries::SharedStateTriesReader;
 use crate::version_config::{VersionId, VERSION_PATTERN};
 use crate::RpcConfig;
 
@@ -38,6 +40,7 @@ pub fn get_test_rpc_config() -> RpcConfig {
         port: 0,
         max_events_chunk_size: 10,
         max_events_keys: 10,
+        max_storage_proof_keys: 10,
         collect_metrics: false,
         ..Default::default()
     }
@@ -66,6 +69,54 @@ pub(crate) fn get_test_rpc_server_and_storage_writer_from_params<T: JsonRpcServe
     pending_data: Option<Arc<RwLock<PendingData>>>,
     pending_classes: Option<Arc<RwLock<PendingClasses>>>,
     storage_scope: Option<StorageScope>,
+) -> (RpcModule<T>, StorageWriter) {
+    get_test_rpc_server_and_storage_writer_from_params_and_readers(
+        mock_client,
+        shared_highest_block,
+        pending_data,
+        pending_classes,
+        storage_scope,
+        None,
+        None,
+    )
+}
+
+pub(crate) fn get_test_rpc_server_and_storage_writer_with_state_tries<T: JsonRpcServerTrait>(
+    state_tries_reader: SharedStateTriesReader,
+) -> (RpcModule<T>, StorageWriter) {
+    get_test_rpc_server_and_storage_writer_from_params_and_readers(
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(state_tries_reader),
+        None,
+    )
+}
+
+pub(crate) fn get_test_rpc_server_and_storage_writer_with_l1_messages<T: JsonRpcServerTrait>(
+    l1_messages_reader: SharedL1MessagesReader,
+) -> (RpcModule<T>, StorageWriter) {
+    get_test_rpc_server_and_storage_writer_from_params_and_readers(
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(l1_messages_reader),
+    )
+}
+
+fn get_test_rpc_server_and_storage_writer_from_params_and_readers<T: JsonRpcServerTrait>(
+    mock_client: Option<MockStarknetWriter>,
+    shared_highest_block: Option<Arc<RwLock<Option<BlockHashAndNumber>>>>,
+    pending_data: Option<Arc<RwLock<PendingData>>>,
+    pending_classes: Option<Arc<RwLock<PendingClasses>>>,
+    storage_scope: Option<StorageScope>,
+    state_tries_reader: Option<SharedStateTriesReader>,
+    l1_messages_reader: Option<SharedL1MessagesReader>,
 ) -> (RpcModule<T>, StorageWriter) {
     let mock_client = mock_client.unwrap_or_default();
     let shared_highest_block = shared_highest_block.unwrap_or(get_test_highest_block());
@@ -83,12 +134,15 @@ pub(crate) fn get_test_rpc_server_and_storage_writer_from_params<T: JsonRpcServe
             storage_reader,
             config.max_events_chunk_size,
             config.max_events_keys,
+            config.max_storage_proof_keys,
             BlockHashAndNumber::default(),
             shared_highest_block,
             pending_data,
             pending_classes,
             mock_client_arc,
             None,
+            state_tries_reader,
+            l1_messages_reader,
         )
         .into_rpc_module(),
         storage_writer,
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use apollo_class_manager_types::SharedClassManagerClient;
@@ -29,9 +30,9 @@ use apollo_storage::db::{TransactionKind, RO};
 use apollo_storage::state::StateStorageReader;
 use apollo_storage::{StorageError, StorageReader, StorageTxn};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::core::{RpcResult, SubscriptionResult};
 use jsonrpsee::types::ErrorObjectOwned;
-use jsonrpsee::RpcModule;
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
 use papyrus_common::pending_classes::{PendingClasses, PendingClassesTrait};
 use starknet_api::block::{
     BlockHash,
@@ -56,11 +57,14 @@ use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::{
     EventContent,
     EventIndexInTransactionOutput,
+    EventKey,
     Transaction as StarknetApiTransaction,
     TransactionHash,
     TransactionOffsetInBlock,
     TransactionVersion,
 };
+use starknet_committer::block_committer::input::StarknetStorageKey;
+use starknet_committer::forest::storage_proof::{node_hash_to_node_mapping, ContractsProof};
 use starknet_types_core::felt::Felt;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
@@ -69,6 +73,7 @@ use tracing::{instrument, trace, warn};
 use super::super::block::{
     get_accepted_block_number,
     get_block_header_by_number,
+    verify_state_not_pruned,
     Block,
     BlockHeader,
     BlockNotRevertedValidator,
@@ -90,11 +95,25 @@ use super::super::error::{
     INVALID_TRANSACTION_INDEX,
     NO_BLOCKS,
     PAGE_SIZE_TOO_BIG,
+    STORAGE_PROOF_NOT_SUPPORTED,
+    TOO_MANY_ADDRESSES_IN_FILTER,
+    TOO_MANY_BLOCKS_BACK,
     TOO_MANY_KEYS_IN_FILTER,
+    TOO_MANY_KEYS_IN_STORAGE_PROOF,
     TRANSACTION_HASH_NOT_FOUND,
 };
 use super::super::execution::TransactionTrace;
 use super::super::state::{AcceptedStateUpdate, PendingStateUpdate, StateUpdate};
+use super::super::storage_proof::{BlockGlobalRoots, ContractStorageKeys, StorageProof};
+use super::super::subscription::{
+    send_notifications_of_accepted_blocks,
+    wait_for_changes,
+    PendingTransaction,
+    SubscriptionsNotifier,
+    TransactionStatusNotification,
+    MAX_BLOCKS_BACK,
+    MAX_SENDER_ADDRESSES_IN_FILTER,
+};
 use super::super::transaction::{
     get_block_tx_hashes_by_number,
     get_block_txs_by_number,
@@ -107,6 +126,7 @@ use super::super::transaction::{
     PendingTransactionOutput,
     PendingTransactionReceipt,
     Transaction,
+    TransactionFinalityStatus,
     TransactionOutput,
     TransactionReceipt,
     TransactionStatus,
@@ -143,7 +163,9 @@ use super::{
     TransactionTraceWithHash,
 };
 use crate::api::{BlockHashOrNumber, JsonRpcServerTrait, Tag};
+use crate::l1_messages::SharedL1MessagesReader;
 use crate::pending::client_pending_data_to_execution_pending_data;
+use crate::state_tries::SharedStateTriesReader;
 use crate::syncing_state::{get_last_synced_block, SyncStatus, SyncingState};
 use crate::v0_8::state::ThinStateDiff;
 use crate::version_config::VERSION_0_8 as VERSION;
@@ -166,12 +188,15 @@ pub struct JsonRpcServerImpl {
     pub storage_reader: StorageReader,
     pub max_events_chunk_size: usize,
     pub max_events_keys: usize,
+    pub max_storage_proof_keys: usize,
     pub starting_block: BlockHashAndNumber,
     pub shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pub pending_data: Arc<RwLock<PendingData>>,
     pub pending_classes: Arc<RwLock<PendingClasses>>,
     pub writer_client: Arc<dyn StarknetWriter>,
     pub class_manager_client: Option<SharedClassManagerClient>,
+    pub state_tries_reader: Option<SharedStateTriesReader>,
+    pub(crate) subscriptions_notifier: Arc<SubscriptionsNotifier>,
 }
 
 async fn create_class_manager_client(
@@ -362,6 +387,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         // Check that the block is valid and get the state number.
         let block_number = get_accepted_block_number(&txn, block_id)?;
         let state_number = StateNumber::unchecked_right_after_block(block_number);
+        verify_state_not_pruned(&txn, state_number)?;
         let res = execution_utils::get_storage_at(
             &txn,
             state_number,
@@ -697,6 +723,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         // Check that the block is valid and get the state number.
         let block_number = get_accepted_block_number(&txn, block_id)?;
         let state_number = StateNumber::unchecked_right_after_block(block_number);
+        verify_state_not_pruned(&txn, state_number)?;
         execution_utils::get_nonce_at(
             &txn,
             state_number,
@@ -707,6 +734,88 @@ impl JsonRpcServer for JsonRpcServerImpl {
         .ok_or_else(|| ErrorObjectOwned::from(CONTRACT_NOT_FOUND))
     }
 
+    #[instrument(skip(self), level = "debug", err)]
+    async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: Option<Vec<ClassHash>>,
+        contract_addresses: Option<Vec<ContractAddress>>,
+        contracts_storage_keys: Option<Vec<ContractStorageKeys>>,
+    ) -> RpcResult<StorageProof> {
+        // The tries of the pending block aren't computed.
+        if matches!(block_id, BlockId::Tag(Tag::Pending)) {
+            return Err(ErrorObjectOwned::from(STORAGE_PROOF_NOT_SUPPORTED));
+        }
+        let Some(state_tries_reader) = &self.state_tries_reader else {
+            return Err(ErrorObjectOwned::from(STORAGE_PROOF_NOT_SUPPORTED));
+        };
+        let n_keys = class_hashes.as_ref().map_or(0, Vec::len)
+            + contract_addresses.as_ref().map_or(0, Vec::len)
+            + contracts_storage_keys
+                .iter()
+                .flatten()
+                .map(|keys| keys.storage_keys.len())
+                .sum::<usize>();
+        if n_keys > self.max_storage_proof_keys {
+            return Err(ErrorObjectOwned::from(TOO_MANY_KEYS_IN_STORAGE_PROOF));
+        }
+
+        let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
+        let block_number = get_accepted_block_number(&txn, block_id)?;
+        let header = get_block_header_by_number(&txn, block_number)?;
+        let forest_roots = state_tries_reader
+            .get_forest_roots(block_number)
+            .map_err(internal_server_error)?
+            .ok_or_else(|| ErrorObjectOwned::from(STORAGE_PROOF_NOT_SUPPORTED))?;
+        if forest_roots.global_root() != header.block_header_without_hash.state_root {
+            return Err(internal_server_error(format!(
+                "The tries of block {block_number} don't match its state root."
+            )));
+        }
+
+        let class_hashes = class_hashes.unwrap_or_default();
+        let contract_addresses = contract_addresses.unwrap_or_default();
+        let contracts_storage_keys = contracts_storage_keys.unwrap_or_default();
+        let mut storage_keys_by_contract = HashMap::<_, Vec<_>>::new();
+        for ContractStorageKeys { contract_address, storage_keys } in &contracts_storage_keys {
+            storage_keys_by_contract
+                .entry(*contract_address)
+                .or_default()
+                .extend(storage_keys.iter().copied().map(StarknetStorageKey));
+        }
+        let storage_proof = state_tries_reader
+            .get_storage_proof(
+                &forest_roots,
+                &class_hashes,
+                &contract_addresses,
+                &storage_keys_by_contract,
+            )
+            .map_err(internal_server_error)?;
+
+        Ok(StorageProof {
+            classes_proof: node_hash_to_node_mapping(storage_proof.classes_trie_proof),
+            contracts_proof: ContractsProof {
+                nodes: node_hash_to_node_mapping(storage_proof.contracts_trie_proof),
+                contract_leaves_data: contract_addresses
+                    .iter()
+                    .map(|address| storage_proof.contracts_trie_leaves[address].clone().into())
+                    .collect(),
+            },
+            contracts_storage_proofs: contracts_storage_keys
+                .iter()
+                .map(|ContractStorageKeys { contract_address, .. }| {
+                    node_hash_to_node_mapping(
+                        storage_proof.contracts_storage_proofs[contract_address].clone(),
+                    )
+                })
+                .collect(),
+            global_roots: BlockGlobalRoots {
+                global_roots: (&forest_roots).into(),
+                block_hash: header.block_hash,
+            },
+        })
+    }
+
     #[instrument(skip(self), level = "debug", err, ret)]
     fn chain_id(&self) -> RpcResult<String> {
         Ok(self.chain_id.as_hex())
@@ -774,10 +883,25 @@ impl JsonRpcServer for JsonRpcServerImpl {
         // pointing to the next relevant event. Otherwise, we return a continuation token None.
         let mut filtered_events = vec![];
         if start_event_index.0.0 <= latest_block_number {
-            for ((from_address, event_index), content) in txn
-                .iter_events(filter.address, start_event_index, to_block_number)
-                .map_err(internal_server_error)?
-            {
+            // Without an address, use the event keys index if the events are filtered by a single
+            // first key and the index covers the requested blocks.
+            let events_by_first_key = match (filter.address, filter.keys.first()) {
+                (None, Some(first_keys)) if first_keys.len() == 1 => txn
+                    .iter_events_by_first_key(
+                        first_keys.iter().next().expect("Checked the length of the keys").clone(),
+                        start_event_index,
+                        to_block_number,
+                    )
+                    .map_err(internal_server_error)?,
+                _ => None,
+            };
+            let events = match events_by_first_key {
+                Some(events) => events,
+                None => txn
+                    .iter_events(filter.address, start_event_index, to_block_number)
+                    .map_err(internal_server_error)?,
+            };
+            for ((from_address, event_index), content) in events {
                 let block_number = (event_index.0).0;
                 if block_number > to_block_number {
                     break;
@@ -871,6 +995,168 @@ impl JsonRpcServer for JsonRpcServerImpl {
         Ok(EventsChunk { events: filtered_events, continuation_token: None })
     }
 
+    #[instrument(skip(self, pending), level = "debug")]
+    async fn subscribe_new_heads(
+        &self,
+        pending: PendingSubscriptionSink,
+        block_id: Option<BlockId>,
+    ) -> SubscriptionResult {
+        let first_block_number =
+            match get_subscription_first_block_number(&self.storage_reader, block_id) {
+                Ok(first_block_number) => first_block_number,
+                Err(err) => {
+                    pending.reject(err).await;
+                    return Ok(());
+                }
+            };
+        let sink = pending.accept().await?;
+        send_notifications_of_accepted_blocks(
+            &self.storage_reader,
+            &self.subscriptions_notifier,
+            sink,
+            first_block_number,
+            |txn, block_number| {
+                Ok(vec![BlockHeader::from(get_block_header_by_number(txn, block_number)?)])
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip(self, pending), level = "debug")]
+    async fn subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+        from_address: Option<ContractAddress>,
+        keys: Option<Vec<HashSet<EventKey>>>,
+        block_id: Option<BlockId>,
+    ) -> SubscriptionResult {
+        let filter = EventFilter {
+            address: from_address,
+            keys: keys.unwrap_or_default(),
+            ..Default::default()
+        };
+        let first_block_number = verify_storage_scope(&self.storage_reader).and_then(|()| {
+            if filter.keys.len() > self.max_events_keys {
+                return Err(ErrorObjectOwned::from(TOO_MANY_KEYS_IN_FILTER));
+            }
+            get_subscription_first_block_number(&self.storage_reader, block_id)
+        });
+        let first_block_number = match first_block_number {
+            Ok(first_block_number) => first_block_number,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(());
+            }
+        };
+        let sink = pending.accept().await?;
+        send_notifications_of_accepted_blocks(
+            &self.storage_reader,
+            &self.subscriptions_notifier,
+            sink,
+            first_block_number,
+            |txn, block_number| get_block_events(txn, block_number, &filter),
+        )
+        .await
+    }
+
+    #[instrument(skip(self, pending), level = "debug")]
+    async fn subscribe_transaction_status(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_hash: TransactionHash,
+    ) -> SubscriptionResult {
+        if let Err(err) = verify_storage_scope(&self.storage_reader) {
+            pending.reject(err).await;
+            return Ok(());
+        }
+        let sink = pending.accept().await?;
+        let mut changes = self.subscriptions_notifier.subscribe();
+        let mut last_status = None;
+        loop {
+            match self.get_transaction_status(transaction_hash).await {
+                Ok(status) if last_status.as_ref() != Some(&status) => {
+                    let is_final =
+                        status.finality_status == TransactionFinalityStatus::AcceptedOnL1;
+                    let notification =
+                        TransactionStatusNotification { transaction_hash, status: status.clone() };
+                    sink.send(SubscriptionMessage::from_json(&notification)?).await?;
+                    // The status of a transaction that was accepted on L1 doesn't change anymore.
+                    if is_final {
+                        return Ok(());
+                    }
+                    last_status = Some(status);
+                }
+                Ok(_) => {}
+                // The transaction didn't reach the pending block yet.
+                Err(err) if err == TRANSACTION_HASH_NOT_FOUND.into() => {}
+                Err(err) => return Err(err.into()),
+            }
+            if !wait_for_changes(&sink, &mut changes).await {
+                return Ok(());
+            }
+        }
+    }
+
+    #[instrument(skip(self, pending), level = "debug")]
+    async fn subscribe_pending_transactions(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_details: Option<bool>,
+        sender_address: Option<Vec<ContractAddress>>,
+    ) -> SubscriptionResult {
+        let sender_addresses: HashSet<ContractAddress> =
+            sender_address.unwrap_or_default().into_iter().collect();
+        if sender_addresses.len() > MAX_SENDER_ADDRESSES_IN_FILTER {
+            pending.reject(ErrorObjectOwned::from(TOO_MANY_ADDRESSES_IN_FILTER)).await;
+            return Ok(());
+        }
+        let transaction_details = transaction_details.unwrap_or_default();
+        let sink = pending.accept().await?;
+
+        // The transactions that were already sent, and the parent of the pending block they are
+        // in.
+        let mut sent_transactions = HashSet::new();
+        let mut sent_transactions_parent_hash = None;
+        let mut changes = self.subscriptions_notifier.subscribe();
+        loop {
+            let pending_data = {
+                let txn = self.storage_reader.begin_ro_txn()?;
+                read_pending_data(&self.pending_data, &txn).await?
+            };
+            let parent_block_hash = pending_data.block.parent_block_hash();
+            if sent_transactions_parent_hash != Some(parent_block_hash) {
+                sent_transactions.clear();
+                sent_transactions_parent_hash = Some(parent_block_hash);
+            }
+            for client_transaction in pending_data.block.transactions() {
+                let transaction_hash = client_transaction.transaction_hash();
+                if !sent_transactions.insert(transaction_hash) {
+                    continue;
+                }
+                let transaction: StarknetApiTransaction =
+                    client_transaction.clone().try_into().map_err(internal_server_error)?;
+                if !sender_addresses.is_empty()
+                    && !get_transaction_sender_address(&transaction)
+                        .is_some_and(|sender_address| sender_addresses.contains(&sender_address))
+                {
+                    continue;
+                }
+                let notification = if transaction_details {
+                    PendingTransaction::Full(TransactionWithHash {
+                        transaction: transaction.try_into()?,
+                        transaction_hash,
+                    })
+                } else {
+                    PendingTransaction::Hash(transaction_hash)
+                };
+                sink.send(SubscriptionMessage::from_json(&notification)?).await?;
+            }
+            if !wait_for_changes(&sink, &mut changes).await {
+                return Ok(());
+            }
+        }
+    }
+
     #[instrument(skip(self), level = "debug", err, ret)]
     async fn syncing(&self) -> RpcResult<SyncingState> {
         let Some(highest_block) = *self.shared_highest_block.read().await else {
@@ -904,8 +1190,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         };
         let block_number = get_accepted_block_number(&txn, block_id)?;
         let block_not_reverted_validator = BlockNotRevertedValidator::new(block_number, &txn)?;
-        drop(txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
+        verify_state_not_pruned(&txn, state_number)?;
+        drop(txn);
         let execution_config = self.execution_config;
 
         let chain_id = self.chain_id.clone();
@@ -1024,8 +1311,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_number = get_accepted_block_number(&storage_txn, block_id)?;
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
-        drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
+        verify_state_not_pruned(&storage_txn, state_number)?;
+        drop(storage_txn);
         let execution_config = self.execution_config;
 
         let chain_id = self.chain_id.clone();
@@ -1091,8 +1379,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_number = get_accepted_block_number(&storage_txn, block_id)?;
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
-        drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
+        verify_state_not_pruned(&storage_txn, state_number)?;
+        drop(storage_txn);
         let execution_config = self.execution_config;
 
         let chain_id = self.chain_id.clone();
@@ -1168,6 +1457,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
             let block_number =
                 get_latest_block_number(&storage_txn)?.ok_or(INVALID_TRANSACTION_HASH)?;
             let state_number = StateNumber::unchecked_right_after_block(block_number);
+            verify_state_not_pruned(&storage_txn, state_number)?;
             let executable_transactions = pending_block
                 .transactions()
                 .iter()
@@ -1238,6 +1528,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 })?;
 
             let state_number = StateNumber::right_before_block(block_number);
+            verify_state_not_pruned(&storage_txn, state_number)?;
             let executable_transactions = block_transactions
                 .into_iter()
                 .take(tx_offset.0 + 1)
@@ -1373,6 +1664,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 ),
             };
 
+        verify_state_not_pruned(&storage_txn, state_number)?;
+
         let executable_txns = block_transactions
             .into_iter()
             .map(|tx| stored_txn_to_executable_txn(tx, &storage_txn, state_number))
@@ -1455,8 +1748,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_number = get_accepted_block_number(&storage_txn, block_id)?;
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
-        drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
+        verify_state_not_pruned(&storage_txn, state_number)?;
+        drop(storage_txn);
         let execution_config = self.execution_config;
 
         let chain_id = self.chain_id.clone();
@@ -1665,6 +1959,7 @@ impl JsonRpcServerImpl {
 
         let block_number = get_accepted_block_number(&txn, block_id)?;
         let state_number = StateNumber::unchecked_right_after_block(block_number);
+        verify_state_not_pruned(&txn, state_number)?;
         execution_utils::get_class_hash_at(
             &txn,
             state_number,
@@ -1777,6 +2072,83 @@ fn do_event_keys_match_filter(event_content: &EventContent, filter: &EventFilter
     })
 }
 
+/// Returns the block from which a subscription starts, i.e., the given block or the latest block by
+/// default.
+fn get_subscription_first_block_number(
+    storage_reader: &StorageReader,
+    block_id: Option<BlockId>,
+) -> RpcResult<BlockNumber> {
+    let txn = storage_reader.begin_ro_txn().map_err(internal_server_error)?;
+    let latest_block_number = get_latest_block_number(&txn)?;
+    let first_block_number = match block_id {
+        // Without accepted blocks, the subscription starts from the first block to be accepted.
+        None | Some(BlockId::Tag(Tag::Latest)) => latest_block_number.unwrap_or_default(),
+        Some(BlockId::Tag(Tag::Pending)) => {
+            latest_block_number.map_or(BlockNumber(0), |block_number| block_number.unchecked_next())
+        }
+        Some(block_id) => get_accepted_block_number(&txn, block_id)?,
+    };
+    if let Some(latest_block_number) = latest_block_number {
+        if latest_block_number.0.saturating_sub(first_block_number.0) > MAX_BLOCKS_BACK {
+            return Err(ErrorObjectOwned::from(TOO_MANY_BLOCKS_BACK));
+        }
+    }
+    Ok(first_block_number)
+}
+
+/// Returns the events of the given accepted block that match the address and the keys of the
+/// filter.
+fn get_block_events(
+    txn: &StorageTxn<'_, RO>,
+    block_number: BlockNumber,
+    filter: &EventFilter,
+) -> RpcResult<Vec<Event>> {
+    let block_hash = get_block_header_by_number(txn, block_number)?.block_hash;
+    let first_event_index = EventIndex(
+        TransactionIndex(block_number, TransactionOffsetInBlock(0)),
+        EventIndexInTransactionOutput(0),
+    );
+    let mut events = vec![];
+    for ((from_address, event_index), content) in txn
+        .iter_events(filter.address, first_event_index, block_number)
+        .map_err(internal_server_error)?
+    {
+        if (event_index.0).0 > block_number {
+            break;
+        }
+        // As in getEvents, the iterator outputs other addresses only after all the events of the
+        // filter's address.
+        if filter.address.is_some_and(|filter_address| from_address != filter_address) {
+            break;
+        }
+        if !do_event_keys_match_filter(&content, filter) {
+            continue;
+        }
+        let transaction_hash = txn
+            .get_transaction_hash_by_idx(&event_index.0)
+            .map_err(internal_server_error)?
+            .ok_or_else(|| internal_server_error("Unknown internal error."))?;
+        events.push(Event {
+            block_hash: Some(block_hash),
+            block_number: Some(block_number),
+            transaction_hash,
+            event: starknet_api::transaction::Event { from_address, content },
+        });
+    }
+    Ok(events)
+}
+
+/// Returns the address of the account that sent the transaction, if the transaction has one.
+fn get_transaction_sender_address(transaction: &StarknetApiTransaction) -> Option<ContractAddress> {
+    match transaction {
+        StarknetApiTransaction::Declare(tx) => Some(tx.sender_address()),
+        StarknetApiTransaction::Invoke(tx) => Some(tx.sender_address()),
+        StarknetApiTransaction::Deploy(_)
+        | StarknetApiTransaction::DeployAccount(_)
+        | StarknetApiTransaction::L1Handler(_) => None,
+    }
+}
+
 impl JsonRpcServerTrait for JsonRpcServerImpl {
     fn new(
         chain_id: ChainId,
@@ -1784,25 +2156,34 @@ impl JsonRpcServerTrait for JsonRpcServerImpl {
         storage_reader: StorageReader,
         max_events_chunk_size: usize,
         max_events_keys: usize,
+        max_storage_proof_keys: usize,
         starting_block: BlockHashAndNumber,
         shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
         pending_data: Arc<RwLock<PendingData>>,
         pending_classes: Arc<RwLock<PendingClasses>>,
         writer_client: Arc<dyn StarknetWriter>,
         class_manager_client: Option<SharedClassManagerClient>,
+        state_tries_reader: Option<SharedStateTriesReader>,
+        // The status of L1 messages is served only from V0_9.
+        _l1_messages_reader: Option<SharedL1MessagesReader>,
     ) -> Self {
+        let subscriptions_notifier =
+            Arc::new(SubscriptionsNotifier::new(storage_reader.clone(), pending_data.clone()));
         Self {
             chain_id,
             execution_config,
             storage_reader,
             max_events_chunk_size,
             max_events_keys,
+            max_storage_proof_keys,
             starting_block,
             shared_highest_block,
             pending_data,
             pending_classes,
             writer_client,
             class_manager_client,
+            state_tries_reader,
+            subscriptions_notifier,
         }
     }
 
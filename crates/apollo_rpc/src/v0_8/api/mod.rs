@@ -11,7 +11,7 @@ use apollo_storage::state::StateStorageReader;
 use apollo_storage::StorageTxn;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use flate2::bufread::GzDecoder;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::core::{RpcResult, SubscriptionResult};
 use jsonrpsee::proc_macros::rpc;
 use jsonrpsee::types::ErrorObjectOwned;
 use papyrus_common::deprecated_class_abi::calculate_deprecated_class_abi_length;
@@ -30,7 +30,7 @@ use starknet_api::transaction::{EventKey, TransactionHash, TransactionOffsetInBl
 use starknet_types_core::felt::Felt;
 use tracing::debug;
 
-use super::block::Block;
+use super::block::{Block, BlockHeader};
 use super::broadcasted_transaction::{
     BroadcastedDeclareTransaction,
     BroadcastedDeclareV1Transaction,
@@ -46,6 +46,8 @@ use super::error::{
 };
 use super::execution::TransactionTrace;
 use super::state::{ContractClass, StateUpdate};
+use super::storage_proof::{ContractStorageKeys, StorageProof};
+use super::subscription::{PendingTransaction, TransactionStatusNotification};
 use super::transaction::{
     DeployAccountTransaction,
     DeployAccountTransactionV1,
@@ -180,6 +182,17 @@ pub trait JsonRpc {
         contract_address: ContractAddress,
     ) -> RpcResult<Nonce>;
 
+    /// Gets the Merkle proofs of the given classes, contracts and contracts storage keys against
+    /// the state roots of the given block.
+    #[method(name = "getStorageProof")]
+    async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: Option<Vec<ClassHash>>,
+        contract_addresses: Option<Vec<ContractAddress>>,
+        contracts_storage_keys: Option<Vec<ContractStorageKeys>>,
+    ) -> RpcResult<StorageProof>;
+
     /// Returns the currently configured StarkNet chain id.
     #[method(name = "chainId")]
     fn chain_id(&self) -> RpcResult<String>;
@@ -188,6 +201,53 @@ pub trait JsonRpc {
     #[method(name = "getEvents")]
     async fn get_events(&self, filter: EventFilter) -> RpcResult<EventsChunk>;
 
+    /// Subscribes to the headers of new blocks, starting from the given block (the latest block by
+    /// default).
+    #[subscription(
+        name = "subscribeNewHeads" => "subscriptionNewHeads",
+        unsubscribe = "unsubscribeNewHeads",
+        item = BlockHeader
+    )]
+    async fn subscribe_new_heads(&self, block_id: Option<BlockId>) -> SubscriptionResult;
+
+    /// Subscribes to the events of new blocks that match the given filter, starting from the given
+    /// block (the latest block by default).
+    #[subscription(
+        name = "subscribeEvents" => "subscriptionEvents",
+        unsubscribe = "unsubscribeEvents",
+        item = Event
+    )]
+    async fn subscribe_events(
+        &self,
+        from_address: Option<ContractAddress>,
+        keys: Option<Vec<HashSet<EventKey>>>,
+        block_id: Option<BlockId>,
+    ) -> SubscriptionResult;
+
+    /// Subscribes to the changes in the status of the given transaction.
+    #[subscription(
+        name = "subscribeTransactionStatus" => "subscriptionTransactionStatus",
+        unsubscribe = "unsubscribeTransactionStatus",
+        item = TransactionStatusNotification
+    )]
+    async fn subscribe_transaction_status(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> SubscriptionResult;
+
+    /// Subscribes to the transactions that enter the pending block, optionally only of the given
+    /// senders.
+    #[subscription(
+        name = "subscribePendingTransactions" => "subscriptionPendingTransactions",
+        unsubscribe = "unsubscribePendingTransactions",
+        item = PendingTransaction
+    )]
+    async fn subscribe_pending_transactions(
+        &self,
+        transaction_details: Option<bool>,
+        sender_address: Option<Vec<ContractAddress>>,
+    ) -> SubscriptionResult;
+
     /// Returns the synching status of the node, or false if the node is not synching.
     #[method(name = "syncing")]
     async fn syncing(&self) -> RpcResult<SyncingState>;
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use std::iter;
 use std::net::SocketAddr;
 use std::ops::Index;
+use std::sync::Arc;
 
 use apollo_starknet_client::reader::objects::pending_data::{
     DeprecatedPendingBlock,
@@ -45,7 +46,7 @@ use apollo_storage::compiled_class::CasmStorageWriter;
 use apollo_storage::header::HeaderStorageWriter;
 use apollo_storage::state::StateStorageWriter;
 use apollo_storage::test_utils::get_test_storage;
-use apollo_storage::StorageScope;
+use apollo_storage::{StorageScope, StorageWriter};
 use apollo_test_utils::{
     auto_impl_get_test_instance,
     get_number_of_variants,
@@ -114,10 +115,27 @@ use starknet_api::transaction::{
     TransactionOutput as StarknetApiTransactionOutput,
 };
 use starknet_api::{class_hash, contract_address, felt, storage_key, tx_hash};
+use starknet_committer::block_committer::commit::commit_block;
+use starknet_committer::block_committer::input::{
+    ConfigImpl,
+    Input,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff as CommitterStateDiff,
+};
+use starknet_committer::forest::forest_roots::{write_block_forest_roots, ForestRoots};
+use starknet_committer::forest::storage_proof::ContractLeafData;
+use starknet_patricia::patricia_merkle_tree::filled_tree::tree::FilledTree;
+use starknet_patricia_storage::map_storage::MapStorage;
 use starknet_types_core::felt::Felt;
 
 use super::super::api::EventsChunk;
-use super::super::block::{Block, GeneralBlockHeader, PendingBlockHeader};
+use super::super::block::{
+    Block,
+    BlockHeader as RpcBlockHeader,
+    GeneralBlockHeader,
+    PendingBlockHeader,
+};
 use super::super::broadcasted_transaction::BroadcastedDeclareTransaction;
 use super::super::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use super::super::error::{
@@ -132,7 +150,10 @@ use super::super::error::{
     INVALID_TRANSACTION_INDEX,
     NO_BLOCKS,
     PAGE_SIZE_TOO_BIG,
+    STORAGE_PROOF_NOT_SUPPORTED,
+    TOO_MANY_ADDRESSES_IN_FILTER,
     TOO_MANY_KEYS_IN_FILTER,
+    TOO_MANY_KEYS_IN_STORAGE_PROOF,
     TRANSACTION_HASH_NOT_FOUND,
 };
 use super::super::state::{
@@ -148,6 +169,13 @@ use super::super::state::{
     StorageEntry,
     ThinStateDiff,
 };
+use super::super::storage_proof::{BlockGlobalRoots, ContractStorageKeys, StorageProof};
+use super::super::subscription::{
+    PendingTransaction,
+    ReorgData,
+    TransactionStatusNotification,
+    MAX_SENDER_ADDRESSES_IN_FILTER,
+};
 use super::super::transaction::{
     DeployAccountTransaction,
     Event,
@@ -191,9 +219,11 @@ use crate::test_utils::{
     get_test_rpc_config,
     get_test_rpc_server_and_storage_writer,
     get_test_rpc_server_and_storage_writer_from_params,
+    get_test_rpc_server_and_storage_writer_with_state_tries,
     method_name_to_spec_method_name,
     raw_call,
     validate_schema,
+    SerializeJsonValue,
     SpecFile,
 };
 use crate::v0_8::api::CompiledContractClass;
@@ -2007,6 +2037,188 @@ async fn get_nonce() {
     assert_matches!(err, Error::Call(err) if err == BLOCK_NOT_FOUND.into());
 }
 
+#[tokio::test]
+async fn get_storage_proof() {
+    let method_name = "starknet_V0_8_getStorageProof";
+    let address = contract_address!("0x11");
+    let class_hash = class_hash!("0x1");
+    let key = storage_key!("0x5");
+    let nonce = Nonce(felt!("0x1"));
+    let proof_params_without_keys = |block_id: BlockId| -> Vec<Box<dyn SerializeJsonValue>> {
+        vec![
+            Box::new(block_id),
+            Box::new(Vec::<ClassHash>::new()),
+            Box::new(Vec::<ContractAddress>::new()),
+            Box::new(Vec::<ContractStorageKeys>::new()),
+        ]
+    };
+
+    // Commit the state of the first block into the tries.
+    let mut tries_storage = MapStorage::new();
+    let input = Input {
+        state_diff: CommitterStateDiff {
+            address_to_class_hash: HashMap::from([(address, class_hash)]),
+            address_to_nonce: HashMap::from([(address, nonce)]),
+            class_hash_to_compiled_class_hash: HashMap::from([(
+                class_hash,
+                starknet_committer::patricia_merkle_tree::types::CompiledClassHash(felt!("0x31")),
+            )]),
+            storage_updates: HashMap::from([(
+                address,
+                HashMap::from([(StarknetStorageKey(key), StarknetStorageValue(felt!("0x7")))]),
+            )]),
+        },
+        contracts_trie_root_hash: Default::default(),
+        classes_trie_root_hash: Default::default(),
+        config: ConfigImpl::default(),
+    };
+    let filled_forest = commit_block(input, &tries_storage).await.unwrap();
+    filled_forest.write_to_storage(&mut tries_storage);
+    let forest_roots = ForestRoots {
+        contracts_trie_root_hash: filled_forest.get_contract_root_hash(),
+        classes_trie_root_hash: filled_forest.get_compiled_class_root_hash(),
+    };
+    write_block_forest_roots(&mut tries_storage, BlockNumber(0), &forest_roots);
+    let storage_root = filled_forest.storage_tries[&address].get_root_hash().0;
+
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_with_state_tries::<
+        JsonRpcServerImpl,
+    >(Arc::new(std::sync::RwLock::new(tries_storage)));
+    let header = BlockHeader {
+        block_hash: BlockHash(felt!("0x1")),
+        block_header_without_hash: BlockHeaderWithoutHash {
+            state_root: forest_roots.global_root(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    // The tries of the second block aren't kept.
+    let next_header = BlockHeader {
+        block_hash: BlockHash(felt!("0x2")),
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: BlockNumber(1),
+            parent_hash: header.block_hash,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &header)
+        .unwrap()
+        .append_state_diff(BlockNumber(0), starknet_api::state::ThinStateDiff::default())
+        .unwrap()
+        .append_header(BlockNumber(1), &next_header)
+        .unwrap()
+        .append_state_diff(BlockNumber(1), starknet_api::state::ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let res = call_and_validate_schema_for_result::<_, StorageProof>(
+        &module,
+        method_name,
+        vec![
+            Box::new(BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0)))),
+            Box::new(vec![class_hash]),
+            Box::new(vec![address, contract_address!("0x12")]),
+            Box::new(vec![ContractStorageKeys {
+                contract_address: address,
+                storage_keys: vec![key, storage_key!("0x6")],
+            }]),
+        ],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+    )
+    .await;
+    assert_eq!(
+        res.global_roots,
+        BlockGlobalRoots { global_roots: (&forest_roots).into(), block_hash: header.block_hash }
+    );
+    assert!(res
+        .classes_proof
+        .iter()
+        .any(|node| node.node_hash == forest_roots.classes_trie_root_hash.0));
+    assert!(res
+        .contracts_proof
+        .nodes
+        .iter()
+        .any(|node| node.node_hash == forest_roots.contracts_trie_root_hash.0));
+    assert_eq!(
+        res.contracts_proof.contract_leaves_data,
+        vec![
+            ContractLeafData { nonce, class_hash, storage_root },
+            ContractLeafData {
+                nonce: Nonce::default(),
+                class_hash: ClassHash::default(),
+                storage_root: Felt::ZERO,
+            },
+        ]
+    );
+    assert_eq!(res.contracts_storage_proofs.len(), 1);
+    assert!(res.contracts_storage_proofs[0].iter().any(|node| node.node_hash == storage_root));
+
+    // Ask for a block whose tries aren't kept.
+    call_api_then_assert_and_validate_schema_for_err::<_, StorageProof>(
+        &module,
+        method_name,
+        proof_params_without_keys(BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(1)))),
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &STORAGE_PROOF_NOT_SUPPORTED.into(),
+    )
+    .await;
+
+    // Ask for the pending block.
+    call_api_then_assert_and_validate_schema_for_err::<_, StorageProof>(
+        &module,
+        method_name,
+        proof_params_without_keys(BlockId::Tag(Tag::Pending)),
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &STORAGE_PROOF_NOT_SUPPORTED.into(),
+    )
+    .await;
+
+    // Ask for more keys than the node supports.
+    let too_many_class_hashes = vec![class_hash; get_test_rpc_config().max_storage_proof_keys + 1];
+    let (_, res) = raw_call::<_, _, StorageProof>(
+        &module,
+        method_name,
+        &(
+            BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0))),
+            too_many_class_hashes,
+            Vec::<ContractAddress>::new(),
+            Vec::<ContractStorageKeys>::new(),
+        ),
+    )
+    .await;
+    assert_eq!(res.unwrap_err(), TOO_MANY_KEYS_IN_STORAGE_PROOF.into());
+
+    // Ask a node that doesn't keep the tries.
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &header)
+        .unwrap()
+        .append_state_diff(BlockNumber(0), starknet_api::state::ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+    call_api_then_assert_and_validate_schema_for_err::<_, StorageProof>(
+        &module,
+        method_name,
+        proof_params_without_keys(BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0)))),
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &STORAGE_PROOF_NOT_SUPPORTED.into(),
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn get_storage_at() {
     let method_name = "starknet_V0_8_getStorageAt";
@@ -3490,6 +3702,296 @@ async fn get_events_invalid_ct() {
     .await;
 }
 
+fn append_block(storage_writer: &mut StorageWriter, block: &StarknetApiBlock) {
+    let block_number = block.header.block_header_without_hash.block_number;
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(block_number, &block.header)
+        .unwrap()
+        .append_body(block_number, block.body.clone())
+        .unwrap()
+        .append_state_diff(block_number, starknet_api::state::ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn subscribe_new_heads() {
+    let method_name = "starknet_V0_8_subscribeNewHeads";
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let mut rng = get_rng();
+    let mut parent_hash = BlockHash(felt!(GENESIS_HASH));
+    let blocks = (0..3)
+        .map(|i| {
+            let block =
+                BlockMetadata::default().generate_block(&mut rng, parent_hash, BlockNumber(i));
+            parent_hash = block.header.block_hash;
+            block
+        })
+        .collect::<Vec<_>>();
+    append_block(&mut storage_writer, &blocks[0]);
+    append_block(&mut storage_writer, &blocks[1]);
+
+    let mut subscription = module
+        .subscribe_unbounded(
+            method_name,
+            [BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0)))],
+        )
+        .await
+        .unwrap();
+    for block in &blocks[..2] {
+        let (header, _) = subscription.next::<RpcBlockHeader>().await.unwrap().unwrap();
+        assert_eq!(header, RpcBlockHeader::from(block.header.clone()));
+    }
+
+    // A block that is accepted after subscribing.
+    append_block(&mut storage_writer, &blocks[2]);
+    let (header, _) = subscription.next::<RpcBlockHeader>().await.unwrap().unwrap();
+    assert_eq!(header, RpcBlockHeader::from(blocks[2].header.clone()));
+
+    // Subscribe from a block that doesn't exist.
+    let err = module
+        .subscribe_unbounded(
+            method_name,
+            [BlockId::HashOrNumber(BlockHashOrNumber::Hash(BlockHash(felt!("0x1234"))))],
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, Error::Call(err) if err == BLOCK_NOT_FOUND.into());
+}
+
+#[tokio::test]
+async fn subscribe_new_heads_reorg() {
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let mut rng = get_rng();
+    let mut parent_hash = BlockHash(felt!(GENESIS_HASH));
+    let blocks = (0..3)
+        .map(|i| {
+            let block =
+                BlockMetadata::default().generate_block(&mut rng, parent_hash, BlockNumber(i));
+            parent_hash = block.header.block_hash;
+            block
+        })
+        .collect::<Vec<_>>();
+    for block in &blocks {
+        append_block(&mut storage_writer, block);
+    }
+
+    let mut subscription = module
+        .subscribe_unbounded(
+            "starknet_V0_8_subscribeNewHeads",
+            [BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0)))],
+        )
+        .await
+        .unwrap();
+    for _ in &blocks {
+        subscription.next::<RpcBlockHeader>().await.unwrap().unwrap();
+    }
+
+    // Replace the last two blocks with a different block.
+    let mut txn = storage_writer.begin_rw_txn().unwrap();
+    for block_number in [BlockNumber(2), BlockNumber(1)] {
+        (txn, _) = txn.revert_state_diff(block_number).unwrap();
+        (txn, _) = txn.revert_body(block_number).unwrap();
+        (txn, _, _) = txn.revert_header(block_number).unwrap();
+    }
+    txn.commit().unwrap();
+    let new_block = BlockMetadata::default().generate_block(
+        &mut rng,
+        blocks[0].header.block_hash,
+        BlockNumber(1),
+    );
+    append_block(&mut storage_writer, &new_block);
+
+    let (reorg, _) = subscription.next::<ReorgData>().await.unwrap().unwrap();
+    assert_eq!(
+        reorg,
+        ReorgData {
+            starting_block_hash: blocks[1].header.block_hash,
+            starting_block_number: BlockNumber(1),
+            ending_block_hash: blocks[2].header.block_hash,
+            ending_block_number: BlockNumber(2),
+        }
+    );
+    let (header, _) = subscription.next::<RpcBlockHeader>().await.unwrap().unwrap();
+    assert_eq!(header, RpcBlockHeader::from(new_block.header.clone()));
+}
+
+#[tokio::test]
+async fn subscribe_events() {
+    let method_name = "starknet_V0_8_subscribeEvents";
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let mut rng = get_rng();
+    let address = contract_address!("0x22");
+    let block_metadata = BlockMetadata(vec![
+        vec![DEFAULT_EVENT_METADATA, EventMetadata { address: Some(address), keys: None }],
+        vec![EventMetadata { address: Some(address), keys: None }, DEFAULT_EVENT_METADATA],
+    ]);
+    let first_block =
+        block_metadata.generate_block(&mut rng, BlockHash(felt!(GENESIS_HASH)), BlockNumber(0));
+    let second_block =
+        block_metadata.generate_block(&mut rng, first_block.header.block_hash, BlockNumber(1));
+    let get_expected_events = |block: &StarknetApiBlock| {
+        block
+            .body
+            .transaction_outputs
+            .iter()
+            .zip(block.body.transaction_hashes.iter())
+            .flat_map(|(output, transaction_hash)| {
+                output.events().iter().filter(|event| event.from_address == address).map(|event| {
+                    Event {
+                        block_hash: Some(block.header.block_hash),
+                        block_number: Some(block.header.block_header_without_hash.block_number),
+                        transaction_hash: *transaction_hash,
+                        event: event.clone(),
+                    }
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    append_block(&mut storage_writer, &first_block);
+
+    // Subscribe from the latest block.
+    let mut subscription = module
+        .subscribe_unbounded(
+            method_name,
+            (Some(address), Option::<Vec<HashSet<EventKey>>>::None, Option::<BlockId>::None),
+        )
+        .await
+        .unwrap();
+    for expected_event in get_expected_events(&first_block) {
+        let (event, _) = subscription.next::<Event>().await.unwrap().unwrap();
+        assert_eq!(event, expected_event);
+    }
+
+    // A block that is accepted after subscribing.
+    append_block(&mut storage_writer, &second_block);
+    for expected_event in get_expected_events(&second_block) {
+        let (event, _) = subscription.next::<Event>().await.unwrap().unwrap();
+        assert_eq!(event, expected_event);
+    }
+
+    // Subscribe with too many keys.
+    let keys = (0..get_test_rpc_config().max_events_keys + 1)
+        .map(|i| HashSet::from([EventKey(Felt::from(u128::try_from(i).unwrap()))]))
+        .collect::<Vec<_>>();
+    let err = module
+        .subscribe_unbounded(
+            method_name,
+            (Option::<ContractAddress>::None, Some(keys), Option::<BlockId>::None),
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, Error::Call(err) if err == TOO_MANY_KEYS_IN_FILTER.into());
+}
+
+#[tokio::test]
+async fn subscribe_transaction_status() {
+    let method_name = "starknet_V0_8_subscribeTransactionStatus";
+    let pending_data = get_test_pending_data();
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_from_params::<
+        JsonRpcServerImpl,
+    >(None, None, Some(pending_data.clone()), None, None);
+    let mut rng = get_rng();
+    let (client_transaction, client_transaction_receipt, _, expected_receipt) =
+        generate_client_transaction_client_receipt_rpc_transaction_and_rpc_receipt(&mut rng);
+    let transaction_hash = client_transaction_receipt.transaction_hash;
+
+    // Subscribe before the transaction is known.
+    let mut subscription =
+        module.subscribe_unbounded(method_name, [transaction_hash]).await.unwrap();
+
+    // The transaction enters the pending block.
+    {
+        let pending_block = &mut pending_data.write().await.block;
+        pending_block.transactions_mutable().push(client_transaction);
+        pending_block.transaction_receipts_mutable().push(client_transaction_receipt);
+    }
+    let (notification, _) =
+        subscription.next::<TransactionStatusNotification>().await.unwrap().unwrap();
+    assert_eq!(
+        notification,
+        TransactionStatusNotification {
+            transaction_hash,
+            status: TransactionStatus {
+                finality_status: TransactionFinalityStatus::AcceptedOnL2,
+                execution_status: expected_receipt.output.execution_status().clone(),
+            },
+        }
+    );
+
+    // The transaction is accepted in a block that is accepted on L1.
+    let mut block = get_test_block(1, None, None, None);
+    block.body.transaction_hashes[0] = transaction_hash;
+    let block_number = block.header.block_header_without_hash.block_number;
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(block_number, &block.header)
+        .unwrap()
+        .append_body(block_number, block.body)
+        .unwrap()
+        .update_base_layer_block_marker(&block_number.unchecked_next())
+        .unwrap()
+        .commit()
+        .unwrap();
+    let (notification, _) =
+        subscription.next::<TransactionStatusNotification>().await.unwrap().unwrap();
+    assert_eq!(notification.transaction_hash, transaction_hash);
+    assert_eq!(notification.status.finality_status, TransactionFinalityStatus::AcceptedOnL1);
+}
+
+#[tokio::test]
+async fn subscribe_pending_transactions() {
+    let method_name = "starknet_V0_8_subscribePendingTransactions";
+    let pending_data = get_test_pending_data();
+    let (module, _) = get_test_rpc_server_and_storage_writer_from_params::<JsonRpcServerImpl>(
+        None,
+        None,
+        Some(pending_data.clone()),
+        None,
+        None,
+    );
+    let mut rng = get_rng();
+
+    let mut hashes_subscription = module
+        .subscribe_unbounded(
+            method_name,
+            (Option::<bool>::None, Option::<Vec<ContractAddress>>::None),
+        )
+        .await
+        .unwrap();
+    let mut details_subscription = module
+        .subscribe_unbounded(method_name, (Some(true), Option::<Vec<ContractAddress>>::None))
+        .await
+        .unwrap();
+
+    let (client_transaction, rpc_transaction) =
+        generate_client_transaction_and_rpc_transaction(&mut rng);
+    pending_data.write().await.block.transactions_mutable().push(client_transaction);
+    let (notification, _) =
+        hashes_subscription.next::<PendingTransaction>().await.unwrap().unwrap();
+    assert_eq!(notification, PendingTransaction::Hash(rpc_transaction.transaction_hash));
+    let (notification, _) =
+        details_subscription.next::<PendingTransaction>().await.unwrap().unwrap();
+    assert_eq!(notification, PendingTransaction::Full(rpc_transaction));
+
+    // Subscribe with too many sender addresses.
+    let sender_addresses = (0..MAX_SENDER_ADDRESSES_IN_FILTER + 1)
+        .map(|i| ContractAddress::from(u128::try_from(i).unwrap()))
+        .collect::<Vec<_>>();
+    let err = module
+        .subscribe_unbounded(method_name, (Option::<bool>::None, Some(sender_addresses)))
+        .await
+        .unwrap_err();
+    assert_matches!(err, Error::Call(err) if err == TOO_MANY_ADDRESSES_IN_FILTER.into());
+}
+
 #[tokio::test]
 async fn serialize_returns_valid_json() {
     let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
@@ -3564,6 +4066,7 @@ async fn serialize_returns_valid_json() {
         storage_reader,
         NODE_VERSION,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -1,13 +1,15 @@
 use apollo_storage::db::TransactionKind;
 use apollo_storage::header::HeaderStorageReader;
+use apollo_storage::state::pruning::StatePruningStorageReader;
 use apollo_storage::{StorageError, StorageReader, StorageTxn};
 use jsonrpsee::types::ErrorObjectOwned;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber, BlockStatus, BlockTimestamp, GasPricePerToken};
 use starknet_api::core::{GlobalRoot, SequencerContractAddress};
 use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::state::StateNumber;
 
-use super::error::BLOCK_NOT_FOUND;
+use super::error::{BLOCK_NOT_FOUND, BLOCK_PRUNED};
 use super::transaction::Transactions;
 use crate::api::{BlockHashOrNumber, BlockId, Tag};
 use crate::{get_latest_block_number, internal_server_error};
@@ -132,6 +134,18 @@ pub(crate) fn get_accepted_block_number<Mode: TransactionKind>(
     })
 }
 
+/// Returns an error if the node pruned the state history needed for reading the given state.
+pub(crate) fn verify_state_not_pruned<Mode: TransactionKind>(
+    txn: &StorageTxn<'_, Mode>,
+    state_number: StateNumber,
+) -> Result<(), ErrorObjectOwned> {
+    let pruning_marker = txn.get_state_pruning_marker().map_err(internal_server_error)?;
+    if state_number.0 < pruning_marker {
+        return Err(ErrorObjectOwned::from(BLOCK_PRUNED));
+    }
+    Ok(())
+}
+
 /// Validates that a given block wasn't reverted. Given an instance of this class, we can call its
 /// `validate` method and it will validate that the block's hash didn't change from the validator's
 /// creation.
@@ -51,6 +51,19 @@ pub const INVALID_CONTINUATION_TOKEN: JsonRpcError<String> = JsonRpcError {
 pub const TOO_MANY_KEYS_IN_FILTER: JsonRpcError<String> =
     JsonRpcError { code: 34, message: "Too many keys provided in a filter", data: None };
 
+// Not part of the spec. Returned when the node doesn't keep the state history of the requested
+// block anymore.
+pub const BLOCK_PRUNED: JsonRpcError<String> =
+    JsonRpcError { code: 1001, message: "The state of the requested block was pruned", data: None };
+
+// Not part of the spec. Returned when a storage proof is requested for more classes, contracts and
+// storage keys than the node supports.
+pub const TOO_MANY_KEYS_IN_STORAGE_PROOF: JsonRpcError<String> = JsonRpcError {
+    code: 1002,
+    message: "Too many keys provided in a storage proof request",
+    data: None,
+};
+
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct ContractError {
     pub revert_error: String,
@@ -72,6 +85,13 @@ impl From<TransactionExecutionError> for JsonRpcError<TransactionExecutionError>
         Self { code: 41, message: "Transaction execution error", data: Some(tx_execution_error) }
     }
 }
+
+pub const STORAGE_PROOF_NOT_SUPPORTED: JsonRpcError<String> = JsonRpcError {
+    code: 42,
+    message: "the node doesn't support storage proofs for blocks that are too far in the past",
+    data: None,
+};
+
 pub const CLASS_ALREADY_DECLARED: JsonRpcError<String> =
     JsonRpcError { code: 51, message: "Class already declared", data: None };
 
@@ -125,6 +145,15 @@ pub fn unexpected_error(data: String) -> JsonRpcError<String> {
     JsonRpcError { code: 63, message: "An unexpected error occurred", data: Some(data) }
 }
 
+pub const TOO_MANY_ADDRESSES_IN_FILTER: JsonRpcError<String> = JsonRpcError {
+    code: 67,
+    message: "Too many addresses in filter sender_address filter",
+    data: None,
+};
+
+pub const TOO_MANY_BLOCKS_BACK: JsonRpcError<String> =
+    JsonRpcError { code: 68, message: "Cannot go back more than 1024 blocks", data: None };
+
 impl<T: Serialize> From<JsonRpcError<T>> for ErrorObjectOwned {
     fn from(err: JsonRpcError<T>) -> Self {
         ErrorObjectOwned::owned(err.code, err.message, err.data)
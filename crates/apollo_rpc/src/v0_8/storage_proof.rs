@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockHash;
+use starknet_api::core::ContractAddress;
+use starknet_api::state::StorageKey;
+use starknet_committer::forest::storage_proof::{ContractsProof, GlobalRoots, NodeHashToNode};
+
+/// The storage keys of a contract to prove.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ContractStorageKeys {
+    pub contract_address: ContractAddress,
+    pub storage_keys: Vec<StorageKey>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockGlobalRoots {
+    #[serde(flatten)]
+    pub global_roots: GlobalRoots,
+    /// The block to which the roots belong.
+    pub block_hash: BlockHash,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct StorageProof {
+    pub classes_proof: Vec<NodeHashToNode>,
+    pub contracts_proof: ContractsProof,
+    /// The proofs of the storage keys of each requested contract, in the order of the request.
+    pub contracts_storage_proofs: Vec<Vec<NodeHashToNode>>,
+    pub global_roots: BlockGlobalRoots,
+}
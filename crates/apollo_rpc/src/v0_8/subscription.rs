@@ -0,0 +1,268 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use apollo_starknet_client::reader::PendingData;
+use apollo_storage::base_layer::BaseLayerStorageReader;
+use apollo_storage::body::BodyStorageReader;
+use apollo_storage::db::RO;
+use apollo_storage::header::HeaderStorageReader;
+use apollo_storage::state::StateStorageReader;
+use apollo_storage::{StorageReader, StorageResult, StorageTxn};
+use jsonrpsee::core::{RpcResult, SubscriptionResult};
+use jsonrpsee::{SubscriptionMessage, SubscriptionSink};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::transaction::TransactionHash;
+use tokio::sync::{watch, RwLock};
+use tracing::warn;
+
+use super::transaction::{TransactionStatus, TransactionWithHash};
+use crate::{get_latest_block_number, internal_server_error};
+
+/// The method name of the notification sent to the subscriptions of accepted blocks when blocks
+/// they were notified about are reverted.
+pub(crate) const REORG_NOTIFICATION_METHOD: &str = "starknet_subscriptionReorg";
+
+/// The interval in which the storage and the pending data are checked for updates.
+pub(crate) const SUBSCRIPTION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The maximal number of blocks before the latest block that a subscription can start from.
+pub(crate) const MAX_BLOCKS_BACK: u64 = 1024;
+
+/// The maximal number of sender addresses in the filter of the pending transactions subscription.
+pub(crate) const MAX_SENDER_ADDRESSES_IN_FILTER: usize = 128;
+
+/// The blocks that were reverted after the subscriber was notified about them.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ReorgData {
+    pub starting_block_hash: BlockHash,
+    pub starting_block_number: BlockNumber,
+    pub ending_block_hash: BlockHash,
+    pub ending_block_number: BlockNumber,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionStatusNotification {
+    pub transaction_hash: TransactionHash,
+    pub status: TransactionStatus,
+}
+
+/// A transaction that entered the pending block. Holds only the transaction hash unless the
+/// subscriber asked for the transaction details.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum PendingTransaction {
+    Hash(TransactionHash),
+    Full(TransactionWithHash),
+}
+
+/// The parts of the storage and the pending data that the subscriptions are notified about.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub(crate) struct PolledState {
+    state_marker: BlockNumber,
+    latest_block_hash: Option<BlockHash>,
+    body_marker: BlockNumber,
+    base_layer_marker: BlockNumber,
+    pending_parent_block_hash: BlockHash,
+    n_pending_transactions: usize,
+}
+
+/// Notifies the subscriptions of the server when the storage or the pending data change. A single
+/// task polls them for all the subscriptions, and it is spawned by the first subscription.
+pub(crate) struct SubscriptionsNotifier {
+    storage_reader: StorageReader,
+    pending_data: Arc<RwLock<PendingData>>,
+    changes: OnceLock<watch::Receiver<PolledState>>,
+}
+
+impl SubscriptionsNotifier {
+    pub(crate) fn new(
+        storage_reader: StorageReader,
+        pending_data: Arc<RwLock<PendingData>>,
+    ) -> Self {
+        Self { storage_reader, pending_data, changes: OnceLock::new() }
+    }
+
+    /// Returns a receiver that is marked as changed whenever the polled state changes.
+    pub(crate) fn subscribe(&self) -> watch::Receiver<PolledState> {
+        let mut changes = self
+            .changes
+            .get_or_init(|| {
+                let (sender, changes) = watch::channel(PolledState::default());
+                tokio::spawn(poll_for_changes(
+                    self.storage_reader.clone(),
+                    self.pending_data.clone(),
+                    sender,
+                ));
+                changes
+            })
+            .clone();
+        changes.mark_unchanged();
+        changes
+    }
+}
+
+async fn poll_for_changes(
+    storage_reader: StorageReader,
+    pending_data: Arc<RwLock<PendingData>>,
+    sender: watch::Sender<PolledState>,
+) {
+    loop {
+        tokio::time::sleep(SUBSCRIPTION_POLL_INTERVAL).await;
+        // The notifier holds a receiver of its own, so there are no subscriptions to poll for.
+        if sender.receiver_count() <= 1 {
+            continue;
+        }
+        let state = match read_polled_state(&storage_reader, &pending_data).await {
+            Ok(state) => state,
+            Err(err) => {
+                warn!("Failed to poll the storage for subscription updates: {err}");
+                continue;
+            }
+        };
+        sender.send_if_modified(|current_state| {
+            if *current_state == state {
+                return false;
+            }
+            *current_state = state;
+            true
+        });
+    }
+}
+
+async fn read_polled_state(
+    storage_reader: &StorageReader,
+    pending_data: &RwLock<PendingData>,
+) -> StorageResult<PolledState> {
+    let (state_marker, latest_block_hash, body_marker, base_layer_marker) = {
+        let txn = storage_reader.begin_ro_txn()?;
+        let state_marker = txn.get_state_marker()?;
+        let latest_block_hash = match state_marker.prev() {
+            Some(latest_block_number) => {
+                txn.get_block_header(latest_block_number)?.map(|header| header.block_hash)
+            }
+            None => None,
+        };
+        (
+            state_marker,
+            latest_block_hash,
+            txn.get_body_marker()?,
+            txn.get_base_layer_block_marker()?,
+        )
+    };
+    let pending_data = pending_data.read().await;
+    Ok(PolledState {
+        state_marker,
+        latest_block_hash,
+        body_marker,
+        base_layer_marker,
+        pending_parent_block_hash: pending_data.block.parent_block_hash(),
+        n_pending_transactions: pending_data.block.transactions().len(),
+    })
+}
+
+/// Sends the notifications of each accepted block from the given block, as the state marker
+/// advances, until the subscriber disconnects. If blocks that were notified about are reverted, a
+/// reorg notification is sent and the notifications are resent from the first reverted block.
+pub(crate) async fn send_notifications_of_accepted_blocks<T: Serialize>(
+    storage_reader: &StorageReader,
+    notifier: &SubscriptionsNotifier,
+    sink: SubscriptionSink,
+    mut next_block_number: BlockNumber,
+    get_block_notifications: impl Fn(&StorageTxn<'_, RO>, BlockNumber) -> RpcResult<Vec<T>>,
+) -> SubscriptionResult {
+    let mut changes = notifier.subscribe();
+    // The hashes of the latest blocks that were notified about, to detect when they are reverted.
+    let mut notified_blocks = VecDeque::new();
+    loop {
+        // The notifications are collected before sending them in order to not hold the
+        // transaction while waiting for the subscriber.
+        let (reorg, notifications) = {
+            let txn = storage_reader.begin_ro_txn()?;
+            let latest_block_number = get_latest_block_number(&txn)?;
+            let reorg = find_reorg(&txn, latest_block_number, &mut notified_blocks)?;
+            if let Some(reorg) = &reorg {
+                next_block_number = reorg.starting_block_number;
+            }
+            let mut notifications = vec![];
+            if let Some(latest_block_number) = latest_block_number {
+                while next_block_number <= latest_block_number {
+                    notifications.extend(get_block_notifications(&txn, next_block_number)?);
+                    notified_blocks
+                        .push_back((next_block_number, get_block_hash(&txn, next_block_number)?));
+                    if notified_blocks.len() > MAX_BLOCKS_BACK as usize {
+                        notified_blocks.pop_front();
+                    }
+                    next_block_number = next_block_number.unchecked_next();
+                }
+            }
+            (reorg, notifications)
+        };
+        if let Some(reorg) = reorg {
+            sink.send(SubscriptionMessage::new(
+                REORG_NOTIFICATION_METHOD,
+                sink.subscription_id(),
+                &reorg,
+            )?)
+            .await?;
+        }
+        for notification in notifications {
+            sink.send(SubscriptionMessage::from_json(&notification)?).await?;
+        }
+        if !wait_for_changes(&sink, &mut changes).await {
+            return Ok(());
+        }
+    }
+}
+
+/// Removes the notified blocks that were reverted, and returns them as a reorg.
+fn find_reorg(
+    txn: &StorageTxn<'_, RO>,
+    latest_block_number: Option<BlockNumber>,
+    notified_blocks: &mut VecDeque<(BlockNumber, BlockHash)>,
+) -> RpcResult<Option<ReorgData>> {
+    let mut reorg: Option<ReorgData> = None;
+    while let Some(&(block_number, block_hash)) = notified_blocks.back() {
+        let is_reverted = latest_block_number.is_none_or(|latest| block_number > latest)
+            || txn
+                .get_block_header(block_number)
+                .map_err(internal_server_error)?
+                .map(|header| header.block_hash)
+                != Some(block_hash);
+        if !is_reverted {
+            break;
+        }
+        notified_blocks.pop_back();
+        let (ending_block_hash, ending_block_number) = match &reorg {
+            Some(reorg) => (reorg.ending_block_hash, reorg.ending_block_number),
+            None => (block_hash, block_number),
+        };
+        reorg = Some(ReorgData {
+            starting_block_hash: block_hash,
+            starting_block_number: block_number,
+            ending_block_hash,
+            ending_block_number,
+        });
+    }
+    Ok(reorg)
+}
+
+fn get_block_hash(txn: &StorageTxn<'_, RO>, block_number: BlockNumber) -> RpcResult<BlockHash> {
+    txn.get_block_header(block_number)
+        .map_err(internal_server_error)?
+        .map(|header| header.block_hash)
+        .ok_or_else(|| internal_server_error(format!("Missing header of block {block_number}.")))
+}
+
+/// Waits until the storage or the pending data change. Returns false if the subscriber
+/// disconnected in the meantime.
+pub(crate) async fn wait_for_changes(
+    sink: &SubscriptionSink,
+    changes: &mut watch::Receiver<PolledState>,
+) -> bool {
+    tokio::select! {
+        _ = sink.closed() => false,
+        changed = changes.changed() => changed.is_ok(),
+    }
+}
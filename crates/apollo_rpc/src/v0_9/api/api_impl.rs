@@ -0,0 +1,482 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use apollo_class_manager_types::SharedClassManagerClient;
+use apollo_rpc_execution::objects::FeeEstimation;
+use apollo_rpc_execution::ExecutionConfig;
+use apollo_starknet_client::reader::PendingData;
+use apollo_starknet_client::writer::StarknetWriter;
+use apollo_storage::StorageReader;
+use async_trait::async_trait;
+use jsonrpsee::core::{RpcResult, SubscriptionResult};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
+use papyrus_common::pending_classes::PendingClasses;
+use starknet_api::block::{BlockHashAndNumber, BlockNumber};
+use starknet_api::contract_class::SierraVersion;
+use starknet_api::core::{ChainId, ClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_api::transaction::{
+    EventKey,
+    TransactionHash,
+    TransactionHasher,
+    TransactionOffsetInBlock,
+};
+use starknet_types_core::felt::Felt;
+use tokio::sync::RwLock;
+use tracing::instrument;
+
+use super::super::block::{
+    get_pre_confirmed_block_number,
+    to_v0_8_block_id,
+    to_v0_9_block,
+    Block,
+    BlockId,
+};
+use super::super::error::TRANSACTION_HASH_NOT_FOUND;
+use super::super::subscription::TransactionStatusNotification;
+use super::super::transaction::{
+    MessageStatus,
+    TransactionFinalityStatus,
+    TransactionReceipt,
+    TransactionStatus,
+};
+use super::{EventFilter, JsonRpcV0_9Server as JsonRpcServer};
+use crate::api::{BlockId as V0_8BlockId, CallRequest, JsonRpcServerTrait};
+use crate::l1_messages::{L1TransactionHash, SharedL1MessagesReader};
+use crate::state_tries::SharedStateTriesReader;
+use crate::syncing_state::SyncingState;
+use crate::v0_8::api::api_impl::JsonRpcServerImpl as JsonRpcServerV0_8Impl;
+use crate::v0_8::api::{
+    CompiledContractClass,
+    EventsChunk,
+    GatewayContractClass,
+    JsonRpcV0_8Server,
+    SimulatedTransaction,
+    SimulationFlag,
+    TransactionTraceWithHash,
+};
+use crate::v0_8::block::Block as V0_8Block;
+use crate::v0_8::broadcasted_transaction::{BroadcastedDeclareTransaction, BroadcastedTransaction};
+use crate::v0_8::execution::TransactionTrace;
+use crate::v0_8::state::StateUpdate;
+use crate::v0_8::storage_proof::{ContractStorageKeys, StorageProof};
+use crate::v0_8::subscription::{wait_for_changes, SubscriptionsNotifier};
+use crate::v0_8::transaction::{
+    MessageFromL1,
+    TransactionWithHash,
+    TypedDeployAccountTransaction,
+    TypedInvokeTransaction,
+};
+use crate::v0_8::write_api_result::{
+    AddDeclareOkResult,
+    AddDeployAccountOkResult,
+    AddInvokeOkResult,
+};
+use crate::version_config::VERSION_0_9 as VERSION;
+use crate::{internal_server_error, verify_storage_scope};
+
+/// Rpc server. The methods that didn't change since V0_8 are served by the server of V0_8, which
+/// shares the storage reader and the pending data with this server.
+pub struct JsonRpcServerImpl {
+    pub v0_8: JsonRpcServerV0_8Impl,
+    pub l1_messages_reader: Option<SharedL1MessagesReader>,
+}
+
+impl JsonRpcServerImpl {
+    fn to_v0_8_block_id(&self, block_id: BlockId) -> RpcResult<V0_8BlockId> {
+        to_v0_8_block_id(&self.v0_8.storage_reader, block_id)
+    }
+
+    fn to_v0_9_block(&self, block: V0_8Block) -> RpcResult<Block> {
+        to_v0_9_block(&self.v0_8.storage_reader, block)
+    }
+}
+
+#[async_trait]
+impl JsonRpcServer for JsonRpcServerImpl {
+    #[instrument(skip(self), level = "debug", err, ret)]
+    fn spec_version(&self) -> RpcResult<String> {
+        Ok(format!("{VERSION}"))
+    }
+
+    fn block_number(&self) -> RpcResult<BlockNumber> {
+        self.v0_8.block_number()
+    }
+
+    fn block_hash_and_number(&self) -> RpcResult<BlockHashAndNumber> {
+        self.v0_8.block_hash_and_number()
+    }
+
+    async fn get_block_w_transaction_hashes(&self, block_id: BlockId) -> RpcResult<Block> {
+        let block =
+            self.v0_8.get_block_w_transaction_hashes(self.to_v0_8_block_id(block_id)?).await?;
+        self.to_v0_9_block(block)
+    }
+
+    async fn get_block_w_full_transactions(&self, block_id: BlockId) -> RpcResult<Block> {
+        let block =
+            self.v0_8.get_block_w_full_transactions(self.to_v0_8_block_id(block_id)?).await?;
+        self.to_v0_9_block(block)
+    }
+
+    async fn get_block_w_full_transactions_and_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Block> {
+        let block = self
+            .v0_8
+            .get_block_w_full_transactions_and_receipts(self.to_v0_8_block_id(block_id)?)
+            .await?;
+        self.to_v0_9_block(block)
+    }
+
+    async fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        block_id: BlockId,
+    ) -> RpcResult<Felt> {
+        self.v0_8.get_storage_at(contract_address, key, self.to_v0_8_block_id(block_id)?).await
+    }
+
+    async fn get_transaction_by_hash(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionWithHash> {
+        self.v0_8.get_transaction_by_hash(transaction_hash).await
+    }
+
+    async fn get_transaction_by_block_id_and_index(
+        &self,
+        block_id: BlockId,
+        index: TransactionOffsetInBlock,
+    ) -> RpcResult<TransactionWithHash> {
+        self.v0_8
+            .get_transaction_by_block_id_and_index(self.to_v0_8_block_id(block_id)?, index)
+            .await
+    }
+
+    async fn get_block_transaction_count(&self, block_id: BlockId) -> RpcResult<usize> {
+        self.v0_8.get_block_transaction_count(self.to_v0_8_block_id(block_id)?).await
+    }
+
+    async fn get_state_update(&self, block_id: BlockId) -> RpcResult<StateUpdate> {
+        self.v0_8.get_state_update(self.to_v0_8_block_id(block_id)?).await
+    }
+
+    #[instrument(skip(self), level = "debug", err, ret)]
+    async fn get_transaction_status(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionStatus> {
+        Ok(self.get_transaction_receipt(transaction_hash).await?.into())
+    }
+
+    #[instrument(skip(self), level = "debug", err, ret)]
+    async fn get_messages_status(
+        &self,
+        transaction_hash: L1TransactionHash,
+    ) -> RpcResult<Vec<MessageStatus>> {
+        let Some(l1_messages_reader) = &self.l1_messages_reader else {
+            return Err(TRANSACTION_HASH_NOT_FOUND.into());
+        };
+        let messages = l1_messages_reader
+            .get_messages_to_l2(transaction_hash)
+            .await
+            .map_err(internal_server_error)?
+            .ok_or_else(|| ErrorObjectOwned::from(TRANSACTION_HASH_NOT_FOUND))?;
+
+        let mut messages_status = Vec::with_capacity(messages.len());
+        for l1_handler_transaction in messages {
+            let l1_handler_transaction_hash = l1_handler_transaction
+                .calculate_transaction_hash(&self.v0_8.chain_id, &l1_handler_transaction.version)
+                .map_err(internal_server_error)?;
+            let status = match self.get_transaction_status(l1_handler_transaction_hash).await {
+                Ok(status) => status,
+                // The message was sent from L1 but it wasn't handled on L2 yet.
+                Err(err) if err == TRANSACTION_HASH_NOT_FOUND.into() => {
+                    TransactionStatus::received()
+                }
+                Err(err) => return Err(err),
+            };
+            messages_status
+                .push(MessageStatus { transaction_hash: l1_handler_transaction_hash, status });
+        }
+        Ok(messages_status)
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionReceipt> {
+        let receipt = self.v0_8.get_transaction_receipt(transaction_hash).await?;
+        TransactionReceipt::from_v0_8_receipt(receipt, || {
+            get_pre_confirmed_block_number(&self.v0_8.storage_reader)
+        })
+    }
+
+    async fn get_class(
+        &self,
+        block_id: BlockId,
+        class_hash: ClassHash,
+    ) -> RpcResult<GatewayContractClass> {
+        self.v0_8.get_class(self.to_v0_8_block_id(block_id)?, class_hash).await
+    }
+
+    async fn get_class_at(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+    ) -> RpcResult<GatewayContractClass> {
+        self.v0_8.get_class_at(self.to_v0_8_block_id(block_id)?, contract_address).await
+    }
+
+    async fn get_class_hash_at(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+    ) -> RpcResult<ClassHash> {
+        self.v0_8.get_class_hash_at(self.to_v0_8_block_id(block_id)?, contract_address).await
+    }
+
+    async fn get_nonce(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+    ) -> RpcResult<Nonce> {
+        self.v0_8.get_nonce(self.to_v0_8_block_id(block_id)?, contract_address).await
+    }
+
+    async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: Option<Vec<ClassHash>>,
+        contract_addresses: Option<Vec<ContractAddress>>,
+        contracts_storage_keys: Option<Vec<ContractStorageKeys>>,
+    ) -> RpcResult<StorageProof> {
+        self.v0_8
+            .get_storage_proof(
+                self.to_v0_8_block_id(block_id)?,
+                class_hashes,
+                contract_addresses,
+                contracts_storage_keys,
+            )
+            .await
+    }
+
+    fn chain_id(&self) -> RpcResult<String> {
+        self.v0_8.chain_id()
+    }
+
+    async fn get_events(&self, filter: EventFilter) -> RpcResult<EventsChunk> {
+        let from_block =
+            filter.from_block.map(|block_id| self.to_v0_8_block_id(block_id)).transpose()?;
+        let to_block =
+            filter.to_block.map(|block_id| self.to_v0_8_block_id(block_id)).transpose()?;
+        self.v0_8.get_events(filter.into_v0_8_filter(from_block, to_block)).await
+    }
+
+    async fn subscribe_new_heads(
+        &self,
+        pending: PendingSubscriptionSink,
+        block_id: Option<BlockId>,
+    ) -> SubscriptionResult {
+        let block_id = match block_id.map(|block_id| self.to_v0_8_block_id(block_id)).transpose() {
+            Ok(block_id) => block_id,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(());
+            }
+        };
+        self.v0_8.subscribe_new_heads(pending, block_id).await
+    }
+
+    async fn subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+        from_address: Option<ContractAddress>,
+        keys: Option<Vec<HashSet<EventKey>>>,
+        block_id: Option<BlockId>,
+    ) -> SubscriptionResult {
+        let block_id = match block_id.map(|block_id| self.to_v0_8_block_id(block_id)).transpose() {
+            Ok(block_id) => block_id,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(());
+            }
+        };
+        self.v0_8.subscribe_events(pending, from_address, keys, block_id).await
+    }
+
+    #[instrument(skip(self, pending), level = "debug")]
+    async fn subscribe_transaction_status(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_hash: TransactionHash,
+    ) -> SubscriptionResult {
+        if let Err(err) = verify_storage_scope(&self.v0_8.storage_reader) {
+            pending.reject(err).await;
+            return Ok(());
+        }
+        let sink = pending.accept().await?;
+        let mut changes = self.v0_8.subscriptions_notifier.subscribe();
+        let mut last_status = None;
+        loop {
+            match self.get_transaction_status(transaction_hash).await {
+                Ok(status) if last_status.as_ref() != Some(&status) => {
+                    let is_final =
+                        status.finality_status == TransactionFinalityStatus::AcceptedOnL1;
+                    let notification =
+                        TransactionStatusNotification { transaction_hash, status: status.clone() };
+                    sink.send(SubscriptionMessage::from_json(&notification)?).await?;
+                    // The status of a transaction that was accepted on L1 doesn't change anymore.
+                    if is_final {
+                        return Ok(());
+                    }
+                    last_status = Some(status);
+                }
+                Ok(_) => {}
+                // The transaction didn't reach the pre-confirmed block yet.
+                Err(err) if err == TRANSACTION_HASH_NOT_FOUND.into() => {}
+                Err(err) => return Err(err.into()),
+            }
+            if !wait_for_changes(&sink, &mut changes).await {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn subscribe_pending_transactions(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_details: Option<bool>,
+        sender_address: Option<Vec<ContractAddress>>,
+    ) -> SubscriptionResult {
+        self.v0_8.subscribe_pending_transactions(pending, transaction_details, sender_address).await
+    }
+
+    async fn syncing(&self) -> RpcResult<SyncingState> {
+        self.v0_8.syncing().await
+    }
+
+    async fn call(&self, request: CallRequest, block_id: BlockId) -> RpcResult<Vec<Felt>> {
+        self.v0_8.call(request, self.to_v0_8_block_id(block_id)?).await
+    }
+
+    async fn add_invoke_transaction(
+        &self,
+        invoke_transaction: TypedInvokeTransaction,
+    ) -> RpcResult<AddInvokeOkResult> {
+        self.v0_8.add_invoke_transaction(invoke_transaction).await
+    }
+
+    async fn add_deploy_account_transaction(
+        &self,
+        deploy_account_transaction: TypedDeployAccountTransaction,
+    ) -> RpcResult<AddDeployAccountOkResult> {
+        self.v0_8.add_deploy_account_transaction(deploy_account_transaction).await
+    }
+
+    async fn add_declare_transaction(
+        &self,
+        declare_transaction: BroadcastedDeclareTransaction,
+    ) -> RpcResult<AddDeclareOkResult> {
+        self.v0_8.add_declare_transaction(declare_transaction).await
+    }
+
+    async fn estimate_fee(
+        &self,
+        request: Vec<BroadcastedTransaction>,
+        simulation_flags: Vec<SimulationFlag>,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<FeeEstimation>> {
+        self.v0_8.estimate_fee(request, simulation_flags, self.to_v0_8_block_id(block_id)?).await
+    }
+
+    async fn estimate_message_fee(
+        &self,
+        message: MessageFromL1,
+        block_id: BlockId,
+    ) -> RpcResult<FeeEstimation> {
+        self.v0_8.estimate_message_fee(message, self.to_v0_8_block_id(block_id)?).await
+    }
+
+    async fn simulate_transactions(
+        &self,
+        block_id: BlockId,
+        transactions: Vec<BroadcastedTransaction>,
+        simulation_flags: Vec<SimulationFlag>,
+    ) -> RpcResult<Vec<SimulatedTransaction>> {
+        self.v0_8
+            .simulate_transactions(self.to_v0_8_block_id(block_id)?, transactions, simulation_flags)
+            .await
+    }
+
+    async fn trace_transaction(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionTrace> {
+        self.v0_8.trace_transaction(transaction_hash).await
+    }
+
+    async fn trace_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<TransactionTraceWithHash>> {
+        self.v0_8.trace_block_transactions(self.to_v0_8_block_id(block_id)?).await
+    }
+
+    fn get_compiled_class(
+        &self,
+        block_id: BlockId,
+        class_hash: ClassHash,
+    ) -> RpcResult<(CompiledContractClass, SierraVersion)> {
+        self.v0_8.get_compiled_class(self.to_v0_8_block_id(block_id)?, class_hash)
+    }
+}
+
+impl JsonRpcServerTrait for JsonRpcServerImpl {
+    fn new(
+        chain_id: ChainId,
+        execution_config: ExecutionConfig,
+        storage_reader: StorageReader,
+        max_events_chunk_size: usize,
+        max_events_keys: usize,
+        max_storage_proof_keys: usize,
+        starting_block: BlockHashAndNumber,
+        shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
+        pending_data: Arc<RwLock<PendingData>>,
+        pending_classes: Arc<RwLock<PendingClasses>>,
+        writer_client: Arc<dyn StarknetWriter>,
+        class_manager_client: Option<SharedClassManagerClient>,
+        state_tries_reader: Option<SharedStateTriesReader>,
+        l1_messages_reader: Option<SharedL1MessagesReader>,
+    ) -> Self {
+        let subscriptions_notifier =
+            Arc::new(SubscriptionsNotifier::new(storage_reader.clone(), pending_data.clone()));
+        Self {
+            v0_8: JsonRpcServerV0_8Impl {
+                chain_id,
+                execution_config,
+                storage_reader,
+                max_events_chunk_size,
+                max_events_keys,
+                max_storage_proof_keys,
+                starting_block,
+                shared_highest_block,
+                pending_data,
+                pending_classes,
+                writer_client,
+                class_manager_client,
+                state_tries_reader,
+                subscriptions_notifier,
+            },
+            l1_messages_reader,
+        }
+    }
+
+    fn into_rpc_module(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+}
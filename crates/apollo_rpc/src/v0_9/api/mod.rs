@@ -0,0 +1,347 @@
+use std::collections::HashSet;
+
+use apollo_proc_macros::versioned_rpc;
+use apollo_rpc_execution::objects::FeeEstimation;
+use jsonrpsee::core::{RpcResult, SubscriptionResult};
+use jsonrpsee::proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockHashAndNumber, BlockNumber};
+use starknet_api::contract_class::SierraVersion;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_api::transaction::{EventKey, TransactionHash, TransactionOffsetInBlock};
+use starknet_types_core::felt::Felt;
+
+use super::block::{Block, BlockId};
+use super::subscription::TransactionStatusNotification;
+use super::transaction::{MessageStatus, TransactionReceipt, TransactionStatus};
+use crate::api::CallRequest;
+use crate::l1_messages::L1TransactionHash;
+use crate::syncing_state::SyncingState;
+use crate::v0_8::api::{
+    CompiledContractClass,
+    ContinuationToken,
+    EventFilter as V0_8EventFilter,
+    EventsChunk,
+    GatewayContractClass,
+    SimulatedTransaction,
+    SimulationFlag,
+    TransactionTraceWithHash,
+};
+use crate::v0_8::block::BlockHeader;
+use crate::v0_8::broadcasted_transaction::{BroadcastedDeclareTransaction, BroadcastedTransaction};
+use crate::v0_8::execution::TransactionTrace;
+use crate::v0_8::state::StateUpdate;
+use crate::v0_8::storage_proof::{ContractStorageKeys, StorageProof};
+use crate::v0_8::subscription::PendingTransaction;
+use crate::v0_8::transaction::{
+    Event,
+    MessageFromL1,
+    TransactionWithHash,
+    TypedDeployAccountTransaction,
+    TypedInvokeTransaction,
+};
+use crate::v0_8::write_api_result::{
+    AddDeclareOkResult,
+    AddDeployAccountOkResult,
+    AddInvokeOkResult,
+};
+
+pub mod api_impl;
+#[cfg(test)]
+mod test;
+
+#[versioned_rpc("V0_9")]
+#[async_trait]
+pub trait JsonRpc {
+    /// Returns the version of the Starknet JSON-RPC specification being used
+    #[method(name = "specVersion")]
+    fn spec_version(&self) -> RpcResult<String>;
+
+    /// Gets the most recent accepted block number.
+    #[method(name = "blockNumber")]
+    fn block_number(&self) -> RpcResult<BlockNumber>;
+
+    /// Gets the most recent accepted block hash and number.
+    #[method(name = "blockHashAndNumber")]
+    fn block_hash_and_number(&self) -> RpcResult<BlockHashAndNumber>;
+
+    /// Gets block information with transaction hashes given a block identifier.
+    #[method(name = "getBlockWithTxHashes")]
+    async fn get_block_w_transaction_hashes(&self, block_id: BlockId) -> RpcResult<Block>;
+
+    /// Gets block information with full transactions given a block identifier.
+    #[method(name = "getBlockWithTxs")]
+    async fn get_block_w_full_transactions(&self, block_id: BlockId) -> RpcResult<Block>;
+
+    /// Gets block information with full transactions given a block identifier.
+    #[method(name = "getBlockWithReceipts")]
+    async fn get_block_w_full_transactions_and_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Block>;
+
+    /// Gets the value of the storage at the given address, key, and block.
+    #[method(name = "getStorageAt")]
+    async fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        block_id: BlockId,
+    ) -> RpcResult<Felt>;
+
+    /// Gets the details of a submitted transaction.
+    #[method(name = "getTransactionByHash")]
+    async fn get_transaction_by_hash(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionWithHash>;
+
+    /// Gets the details of a transaction by a given block id and index.
+    #[method(name = "getTransactionByBlockIdAndIndex")]
+    async fn get_transaction_by_block_id_and_index(
+        &self,
+        block_id: BlockId,
+        index: TransactionOffsetInBlock,
+    ) -> RpcResult<TransactionWithHash>;
+
+    /// Gets the number of transactions in a block given a block id.
+    #[method(name = "getBlockTransactionCount")]
+    async fn get_block_transaction_count(&self, block_id: BlockId) -> RpcResult<usize>;
+
+    /// Gets the information about the result of executing the requested block.
+    #[method(name = "getStateUpdate")]
+    async fn get_state_update(&self, block_id: BlockId) -> RpcResult<StateUpdate>;
+
+    /// Gets the transaction status.
+    #[method(name = "getTransactionStatus")]
+    async fn get_transaction_status(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionStatus>;
+
+    /// Gets the statuses of the L1 handler transactions of the messages that the given L1
+    /// transaction sent to L2.
+    #[method(name = "getMessagesStatus")]
+    async fn get_messages_status(
+        &self,
+        transaction_hash: L1TransactionHash,
+    ) -> RpcResult<Vec<MessageStatus>>;
+
+    /// Gets the transaction receipt by the transaction hash.
+    #[method(name = "getTransactionReceipt")]
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionReceipt>;
+
+    /// Gets the contract class definition associated with the given hash.
+    #[method(name = "getClass")]
+    async fn get_class(
+        &self,
+        block_id: BlockId,
+        class_hash: ClassHash,
+    ) -> RpcResult<GatewayContractClass>;
+
+    /// Gets the contract class definition in the given block at the given address.
+    #[method(name = "getClassAt")]
+    async fn get_class_at(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+    ) -> RpcResult<GatewayContractClass>;
+
+    /// Gets the contract class hash in the given block for the contract deployed at the given
+    /// address.
+    #[method(name = "getClassHashAt")]
+    async fn get_class_hash_at(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+    ) -> RpcResult<ClassHash>;
+
+    /// Gets the nonce associated with the given address in the given block.
+    #[method(name = "getNonce")]
+    async fn get_nonce(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+    ) -> RpcResult<Nonce>;
+
+    /// Gets the Merkle proofs of the given classes, contracts and contracts storage keys against
+    /// the state roots of the given block.
+    #[method(name = "getStorageProof")]
+    async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: Option<Vec<ClassHash>>,
+        contract_addresses: Option<Vec<ContractAddress>>,
+        contracts_storage_keys: Option<Vec<ContractStorageKeys>>,
+    ) -> RpcResult<StorageProof>;
+
+    /// Returns the currently configured StarkNet chain id.
+    #[method(name = "chainId")]
+    fn chain_id(&self) -> RpcResult<String>;
+
+    /// Returns all events matching the given filter.
+    #[method(name = "getEvents")]
+    async fn get_events(&self, filter: EventFilter) -> RpcResult<EventsChunk>;
+
+    /// Subscribes to the headers of new blocks, starting from the given block (the latest block by
+    /// default).
+    #[subscription(
+        name = "subscribeNewHeads" => "subscriptionNewHeads",
+        unsubscribe = "unsubscribeNewHeads",
+        item = BlockHeader
+    )]
+    async fn subscribe_new_heads(&self, block_id: Option<BlockId>) -> SubscriptionResult;
+
+    /// Subscribes to the events of new blocks that match the given filter, starting from the given
+    /// block (the latest block by default).
+    #[subscription(
+        name = "subscribeEvents" => "subscriptionEvents",
+        unsubscribe = "unsubscribeEvents",
+        item = Event
+    )]
+    async fn subscribe_events(
+        &self,
+        from_address: Option<ContractAddress>,
+        keys: Option<Vec<HashSet<EventKey>>>,
+        block_id: Option<BlockId>,
+    ) -> SubscriptionResult;
+
+    /// Subscribes to the changes in the status of the given transaction.
+    #[subscription(
+        name = "subscribeTransactionStatus" => "subscriptionTransactionStatus",
+        unsubscribe = "unsubscribeTransactionStatus",
+        item = TransactionStatusNotification
+    )]
+    async fn subscribe_transaction_status(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> SubscriptionResult;
+
+    /// Subscribes to the transactions that enter the pre-confirmed block, optionally only of the
+    /// given senders.
+    #[subscription(
+        name = "subscribePendingTransactions" => "subscriptionPendingTransactions",
+        unsubscribe = "unsubscribePendingTransactions",
+        item = PendingTransaction
+    )]
+    async fn subscribe_pending_transactions(
+        &self,
+        transaction_details: Option<bool>,
+        sender_address: Option<Vec<ContractAddress>>,
+    ) -> SubscriptionResult;
+
+    /// Returns the synching status of the node, or false if the node is not synching.
+    #[method(name = "syncing")]
+    async fn syncing(&self) -> RpcResult<SyncingState>;
+
+    /// Executes the entry point of the contract at the given address with the given calldata,
+    /// returns the result (Retdata).
+    #[method(name = "call")]
+    async fn call(&self, request: CallRequest, block_id: BlockId) -> RpcResult<Vec<Felt>>;
+
+    /// Submits a new invoke transaction to be added to the chain.
+    #[method(name = "addInvokeTransaction")]
+    async fn add_invoke_transaction(
+        &self,
+        invoke_transaction: TypedInvokeTransaction,
+    ) -> RpcResult<AddInvokeOkResult>;
+
+    /// Submits a new deploy account transaction to be added to the chain.
+    #[method(name = "addDeployAccountTransaction")]
+    async fn add_deploy_account_transaction(
+        &self,
+        deploy_account_transaction: TypedDeployAccountTransaction,
+    ) -> RpcResult<AddDeployAccountOkResult>;
+
+    /// Submits a new declare transaction to be added to the chain.
+    #[method(name = "addDeclareTransaction")]
+    async fn add_declare_transaction(
+        &self,
+        declare_transaction: BroadcastedDeclareTransaction,
+    ) -> RpcResult<AddDeclareOkResult>;
+
+    /// Estimates the fee of a series of transactions.
+    #[method(name = "estimateFee")]
+    async fn estimate_fee(
+        &self,
+        request: Vec<BroadcastedTransaction>,
+        simulation_flags: Vec<SimulationFlag>,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<FeeEstimation>>;
+
+    /// Estimates the fee of a message from L1.
+    #[method(name = "estimateMessageFee")]
+    async fn estimate_message_fee(
+        &self,
+        message: MessageFromL1,
+        block_id: BlockId,
+    ) -> RpcResult<FeeEstimation>;
+
+    /// Simulates execution of a series of transactions.
+    #[method(name = "simulateTransactions")]
+    async fn simulate_transactions(
+        &self,
+        block_id: BlockId,
+        transactions: Vec<BroadcastedTransaction>,
+        simulation_flags: Vec<SimulationFlag>,
+    ) -> RpcResult<Vec<SimulatedTransaction>>;
+
+    /// Calculates the transaction trace of a transaction that is already included in a block.
+    #[method(name = "traceTransaction")]
+    async fn trace_transaction(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionTrace>;
+
+    /// Calculates the transaction trace of all of the transactions in a block.
+    #[method(name = "traceBlockTransactions")]
+    async fn trace_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<TransactionTraceWithHash>>;
+
+    /// Returns the compiled class associated with the given class hash.
+    #[method(name = "getCompiledContractClass")]
+    fn get_compiled_class(
+        &self,
+        block_id: BlockId,
+        class_hash: ClassHash,
+    ) -> RpcResult<(CompiledContractClass, SierraVersion)>;
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EventFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation_token: Option<ContinuationToken>,
+    pub chunk_size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<ContractAddress>,
+    #[serde(default)]
+    pub keys: Vec<HashSet<EventKey>>,
+}
+
+impl EventFilter {
+    /// Converts the filter into a filter of V0_8, with the given block ids.
+    fn into_v0_8_filter(
+        self,
+        from_block: Option<crate::api::BlockId>,
+        to_block: Option<crate::api::BlockId>,
+    ) -> V0_8EventFilter {
+        V0_8EventFilter {
+            from_block,
+            to_block,
+            continuation_token: self.continuation_token,
+            chunk_size: self.chunk_size,
+            address: self.address,
+            keys: self.keys,
+        }
+    }
+}
@@ -0,0 +1,515 @@
+use std::collections::HashMap;
+use std::iter;
+use std::sync::Arc;
+
+use apollo_starknet_client::reader::objects::transaction::{
+    Transaction as ClientTransaction,
+    TransactionExecutionStatus as ClientTransactionExecutionStatus,
+    TransactionReceipt as ClientTransactionReceipt,
+};
+use apollo_storage::base_layer::BaseLayerStorageWriter;
+use apollo_storage::body::BodyStorageWriter;
+use apollo_storage::header::HeaderStorageWriter;
+use apollo_storage::state::StateStorageWriter;
+use apollo_storage::test_utils::get_test_storage;
+use apollo_storage::StorageWriter;
+use apollo_test_utils::{get_rng, get_test_block, get_test_body, send_request, GetTestInstance};
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use itertools::Itertools;
+use jsonrpsee::Methods;
+use pretty_assertions::assert_eq;
+use starknet_api::block::{
+    Block as StarknetApiBlock,
+    BlockHash,
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockNumber,
+};
+use starknet_api::core::Nonce;
+use starknet_api::transaction::{
+    L1HandlerTransaction,
+    TransactionExecutionStatus as StarknetApiTransactionExecutionStatus,
+    TransactionHasher,
+};
+use starknet_api::{felt, tx_hash};
+use starknet_types_core::felt::Felt;
+
+use super::super::block::{Block, BlockId, GeneralBlockHeader, Tag};
+use super::super::error::{BLOCK_NOT_FOUND, TRANSACTION_HASH_NOT_FOUND};
+use super::super::transaction::{
+    MessageStatus,
+    ReceiptFinalityStatus,
+    TransactionExecutionStatus,
+    TransactionFinalityStatus,
+    TransactionReceipt,
+    TransactionStatus,
+    Transactions,
+};
+use super::api_impl::JsonRpcServerImpl;
+use crate::l1_messages::{L1MessagesReader, L1TransactionHash};
+use crate::test_utils::{
+    call_and_validate_schema_for_result,
+    call_api_then_assert_and_validate_schema_for_err,
+    call_api_then_assert_and_validate_schema_for_result,
+    get_method_names_from_spec,
+    get_test_highest_block,
+    get_test_pending_classes,
+    get_test_pending_data,
+    get_test_rpc_config,
+    get_test_rpc_server_and_storage_writer,
+    get_test_rpc_server_and_storage_writer_from_params,
+    get_test_rpc_server_and_storage_writer_with_l1_messages,
+    method_name_to_spec_method_name,
+    raw_call,
+    SpecFile,
+};
+use crate::version_config::{VERSION_0_8, VERSION_0_9 as VERSION};
+use crate::{run_server, GENESIS_HASH};
+
+const NODE_VERSION: &str = "NODE VERSION";
+
+fn append_block(storage_writer: &mut StorageWriter, block: &StarknetApiBlock) {
+    let block_number = block.header.block_header_without_hash.block_number;
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(block_number, &block.header)
+        .unwrap()
+        .append_body(block_number, block.body.clone())
+        .unwrap()
+        .append_state_diff(block_number, starknet_api::state::ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+}
+
+fn update_base_layer_block_marker(storage_writer: &mut StorageWriter, block_number: BlockNumber) {
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .update_base_layer_block_marker(&block_number)
+        .unwrap()
+        .commit()
+        .unwrap();
+}
+
+// Returns the status of the first transaction of the given block, which was stored.
+fn get_first_transaction_status(
+    block: &StarknetApiBlock,
+    finality_status: TransactionFinalityStatus,
+) -> TransactionStatus {
+    let (execution_status, failure_reason) =
+        match block.body.transaction_outputs[0].execution_status() {
+            StarknetApiTransactionExecutionStatus::Succeeded => {
+                (TransactionExecutionStatus::Succeeded, None)
+            }
+            StarknetApiTransactionExecutionStatus::Reverted(reverted_status) => {
+                (TransactionExecutionStatus::Reverted, Some(reverted_status.revert_reason.clone()))
+            }
+        };
+    TransactionStatus { finality_status, execution_status: Some(execution_status), failure_reason }
+}
+
+#[tokio::test]
+async fn spec_version() {
+    let (module, _) = get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        "starknet_V0_9_specVersion",
+        vec![],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &format!("{VERSION}"),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn block_id_tags() {
+    let method_name = "starknet_V0_9_getBlockTransactionCount";
+    let pending_data = get_test_pending_data();
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_from_params::<
+        JsonRpcServerImpl,
+    >(None, None, Some(pending_data.clone()), None, None);
+
+    // No block was accepted on L1 yet.
+    call_api_then_assert_and_validate_schema_for_err::<_, usize>(
+        &module,
+        method_name,
+        vec![Box::new(BlockId::Tag(Tag::L1Accepted))],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &BLOCK_NOT_FOUND.into(),
+    )
+    .await;
+
+    let mut parent_hash = BlockHash(felt!(GENESIS_HASH));
+    let blocks = (0..2_u64)
+        .map(|i| {
+            let block = StarknetApiBlock {
+                header: BlockHeader {
+                    block_hash: BlockHash(Felt::from(i + 1)),
+                    block_header_without_hash: BlockHeaderWithoutHash {
+                        block_number: BlockNumber(i),
+                        parent_hash,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                body: get_test_body(usize::try_from(i).unwrap() + 1, None, None, None),
+            };
+            parent_hash = block.header.block_hash;
+            block
+        })
+        .collect::<Vec<_>>();
+    for block in &blocks {
+        append_block(&mut storage_writer, block);
+    }
+    // Only the first block was accepted on L1.
+    update_base_layer_block_marker(&mut storage_writer, BlockNumber(1));
+
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        method_name,
+        vec![Box::new(BlockId::Tag(Tag::L1Accepted))],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &blocks[0].body.transactions.len(),
+    )
+    .await;
+
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        method_name,
+        vec![Box::new(BlockId::Tag(Tag::Latest))],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &blocks[1].body.transactions.len(),
+    )
+    .await;
+
+    // The pre-confirmed block is built on top of the latest block.
+    let pre_confirmed_transaction_count = 3;
+    let mut rng = get_rng();
+    {
+        let pending_block = &mut pending_data.write().await.block;
+        *pending_block.parent_block_hash_mutable() = blocks[1].header.block_hash;
+        pending_block.transactions_mutable().extend(iter::repeat_n(
+            ClientTransaction::get_test_instance(&mut rng),
+            pre_confirmed_transaction_count,
+        ));
+    }
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        method_name,
+        vec![Box::new(BlockId::Tag(Tag::PreConfirmed))],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &pre_confirmed_transaction_count,
+    )
+    .await;
+
+    // The pending tag was replaced by the pre-confirmed tag.
+    let (_, res) = raw_call::<_, _, usize>(&module, method_name, &["pending"]).await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn get_transaction_status() {
+    let method_name = "starknet_V0_9_getTransactionStatus";
+    let pending_data = get_test_pending_data();
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_from_params::<
+        JsonRpcServerImpl,
+    >(None, None, Some(pending_data.clone()), None, None);
+    let block = get_test_block(1, None, None, None);
+    append_block(&mut storage_writer, &block);
+    let transaction_hash = block.body.transaction_hashes[0];
+
+    let mut expected_status =
+        get_first_transaction_status(&block, TransactionFinalityStatus::AcceptedOnL2);
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        method_name,
+        vec![Box::new(transaction_hash)],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &expected_status,
+    )
+    .await;
+
+    // Ask for a transaction in a block that was accepted on L1.
+    update_base_layer_block_marker(
+        &mut storage_writer,
+        block.header.block_header_without_hash.block_number.unchecked_next(),
+    );
+    expected_status.finality_status = TransactionFinalityStatus::AcceptedOnL1;
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        method_name,
+        vec![Box::new(transaction_hash)],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &expected_status,
+    )
+    .await;
+
+    // Add a reverted transaction to the pre-confirmed block and ask for its status.
+    let mut rng = get_rng();
+    let pre_confirmed_transaction_hash = tx_hash!(0x1234);
+    let mut client_transaction = loop {
+        let client_transaction = ClientTransaction::get_test_instance(&mut rng);
+        // A deploy transaction can't be in the pre-confirmed block.
+        if !matches!(client_transaction, ClientTransaction::Deploy(_)) {
+            break client_transaction;
+        }
+    };
+    *client_transaction.transaction_hash_mut() = pre_confirmed_transaction_hash;
+    let revert_reason = "Reverted.".to_string();
+    let client_transaction_receipt = ClientTransactionReceipt {
+        transaction_hash: pre_confirmed_transaction_hash,
+        execution_status: ClientTransactionExecutionStatus::Reverted,
+        revert_error: Some(revert_reason.clone()),
+        ..Default::default()
+    };
+    {
+        let pending_block = &mut pending_data.write().await.block;
+        *pending_block.parent_block_hash_mutable() = block.header.block_hash;
+        pending_block.transactions_mutable().push(client_transaction);
+        pending_block.transaction_receipts_mutable().push(client_transaction_receipt);
+    }
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        method_name,
+        vec![Box::new(pre_confirmed_transaction_hash)],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &TransactionStatus {
+            finality_status: TransactionFinalityStatus::PreConfirmed,
+            execution_status: Some(TransactionExecutionStatus::Reverted),
+            failure_reason: Some(revert_reason),
+        },
+    )
+    .await;
+
+    // Ask for an invalid transaction.
+    call_api_then_assert_and_validate_schema_for_err::<_, TransactionStatus>(
+        &module,
+        method_name,
+        vec![Box::new(tx_hash!(1))],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &TRANSACTION_HASH_NOT_FOUND.into(),
+    )
+    .await;
+}
+
+#[async_trait]
+impl L1MessagesReader for HashMap<L1TransactionHash, Vec<L1HandlerTransaction>> {
+    async fn get_messages_to_l2(
+        &self,
+        l1_transaction_hash: L1TransactionHash,
+    ) -> anyhow::Result<Option<Vec<L1HandlerTransaction>>> {
+        Ok(self.get(&l1_transaction_hash).cloned())
+    }
+}
+
+#[tokio::test]
+async fn get_messages_status() {
+    let method_name = "starknet_V0_9_getMessagesStatus";
+    let chain_id = get_test_rpc_config().chain_id;
+    let get_message_hash = |message: &L1HandlerTransaction| {
+        message.calculate_transaction_hash(&chain_id, &message.version).unwrap()
+    };
+    let handled_message = L1HandlerTransaction { nonce: Nonce(Felt::ONE), ..Default::default() };
+    let unhandled_message = L1HandlerTransaction { nonce: Nonce(Felt::TWO), ..Default::default() };
+    let l1_transaction_hash = L1TransactionHash::repeat_byte(1);
+    let l1_messages = HashMap::from([(
+        l1_transaction_hash,
+        vec![handled_message.clone(), unhandled_message.clone()],
+    )]);
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_with_l1_messages::<
+        JsonRpcServerImpl,
+    >(Arc::new(l1_messages));
+    let mut block = get_test_block(1, None, None, None);
+    block.body.transaction_hashes[0] = get_message_hash(&handled_message);
+    append_block(&mut storage_writer, &block);
+
+    call_api_then_assert_and_validate_schema_for_result(
+        &module,
+        method_name,
+        vec![Box::new(l1_transaction_hash)],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &vec![
+            MessageStatus {
+                transaction_hash: get_message_hash(&handled_message),
+                status: get_first_transaction_status(
+                    &block,
+                    TransactionFinalityStatus::AcceptedOnL2,
+                ),
+            },
+            // The L1 handler transaction of the message wasn't executed yet.
+            MessageStatus {
+                transaction_hash: get_message_hash(&unhandled_message),
+                status: TransactionStatus::received(),
+            },
+        ],
+    )
+    .await;
+
+    // Ask for an unknown L1 transaction.
+    call_api_then_assert_and_validate_schema_for_err::<_, Vec<MessageStatus>>(
+        &module,
+        method_name,
+        vec![Box::new(L1TransactionHash::repeat_byte(2))],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &TRANSACTION_HASH_NOT_FOUND.into(),
+    )
+    .await;
+
+    // Without a reader of the L1 messages, no L1 transaction is known.
+    let (module, _) = get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    call_api_then_assert_and_validate_schema_for_err::<_, Vec<MessageStatus>>(
+        &module,
+        method_name,
+        vec![Box::new(l1_transaction_hash)],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &TRANSACTION_HASH_NOT_FOUND.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn pre_confirmed_block_and_receipts() {
+    let pending_data = get_test_pending_data();
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_from_params::<
+        JsonRpcServerImpl,
+    >(None, None, Some(pending_data.clone()), None, None);
+    let block = get_test_block(1, None, None, None);
+    append_block(&mut storage_writer, &block);
+
+    let mut rng = get_rng();
+    let pre_confirmed_transaction_hash = tx_hash!(0x1234);
+    let mut client_transaction = loop {
+        let client_transaction = ClientTransaction::get_test_instance(&mut rng);
+        // A deploy transaction can't be in the pre-confirmed block.
+        if !matches!(client_transaction, ClientTransaction::Deploy(_)) {
+            break client_transaction;
+        }
+    };
+    *client_transaction.transaction_hash_mut() = pre_confirmed_transaction_hash;
+    let client_transaction_receipt = ClientTransactionReceipt {
+        transaction_hash: pre_confirmed_transaction_hash,
+        ..Default::default()
+    };
+    {
+        let pending_block = &mut pending_data.write().await.block;
+        *pending_block.parent_block_hash_mutable() = block.header.block_hash;
+        pending_block.transactions_mutable().push(client_transaction);
+        pending_block.transaction_receipts_mutable().push(client_transaction_receipt);
+    }
+
+    // The pre-confirmed block is identified by its number instead of its parent.
+    let pre_confirmed_block = call_and_validate_schema_for_result::<_, Block>(
+        &module,
+        "starknet_V0_9_getBlockWithReceipts",
+        vec![Box::new(BlockId::Tag(Tag::PreConfirmed))],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+    )
+    .await;
+    assert_matches!(
+        pre_confirmed_block.header,
+        GeneralBlockHeader::PreConfirmedBlockHeader(header) if header.block_number == BlockNumber(1)
+    );
+    assert_matches!(
+        pre_confirmed_block.transactions,
+        Transactions::FullWithReceipts(transactions)
+            if transactions.len() == 1
+                && transactions[0].receipt.finality_status == ReceiptFinalityStatus::PreConfirmed
+    );
+
+    let method_name = "starknet_V0_9_getTransactionReceipt";
+    let receipt = call_and_validate_schema_for_result::<_, TransactionReceipt>(
+        &module,
+        method_name,
+        vec![Box::new(pre_confirmed_transaction_hash)],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+    )
+    .await;
+    assert_eq!(receipt.finality_status, ReceiptFinalityStatus::PreConfirmed);
+    assert_eq!(receipt.block_hash, None);
+    assert_eq!(receipt.block_number, BlockNumber(1));
+
+    let receipt = call_and_validate_schema_for_result::<_, TransactionReceipt>(
+        &module,
+        method_name,
+        vec![Box::new(block.body.transaction_hashes[0])],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+    )
+    .await;
+    assert_eq!(receipt.finality_status, ReceiptFinalityStatus::AcceptedOnL2);
+    assert_eq!(receipt.block_hash, Some(block.header.block_hash));
+    assert_eq!(receipt.block_number, BlockNumber(0));
+}
+
+#[tokio::test]
+async fn serve_each_version_on_its_path() {
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let (server_address, _handle) = run_server(
+        &get_test_rpc_config(),
+        get_test_highest_block(),
+        get_test_pending_data(),
+        get_test_pending_classes(),
+        storage_reader,
+        NODE_VERSION,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    for (path_version, expected_version) in
+        [("V0_8", VERSION_0_8), ("V0_9", VERSION), ("v0_9_0", VERSION)]
+    {
+        let res = send_request(server_address, "starknet_specVersion", "", path_version).await;
+        assert_eq!(res["result"], format!("{expected_version}"));
+    }
+}
+
+#[test]
+fn spec_api_methods_coverage() {
+    let (module, _) = get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let implemented_methods: Methods = module.into();
+    let implemented_method_names = implemented_methods
+        .method_names()
+        .map(method_name_to_spec_method_name)
+        .sorted()
+        .collect::<Vec<_>>();
+    let non_implemented_apis = ["starknet_pendingTransactions".to_string()];
+    let method_names_in_spec = get_method_names_from_spec(&VERSION)
+        .iter()
+        .filter_map(|method| {
+            let stripped_method_name = method.clone().replace('\"', "");
+            if non_implemented_apis.contains(&stripped_method_name) {
+                None
+            } else {
+                Some(stripped_method_name)
+            }
+        })
+        .sorted()
+        .collect::<Vec<_>>();
+
+    // Methods in the spec are a subset of the implemented methods.
+    assert!(
+        method_names_in_spec.iter().all(|method| implemented_method_names.contains(method)),
+        "Implemented methods: {implemented_method_names:#?}, methods in spec: \
+         {method_names_in_spec:#?}"
+    );
+}
@@ -0,0 +1,144 @@
+use apollo_storage::base_layer::BaseLayerStorageReader;
+use apollo_storage::StorageReader;
+use jsonrpsee::types::ErrorObjectOwned;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp, GasPricePerToken};
+use starknet_api::core::SequencerContractAddress;
+use starknet_api::data_availability::L1DataAvailabilityMode;
+
+use super::error::BLOCK_NOT_FOUND;
+use super::transaction::Transactions;
+use crate::api::{BlockHashOrNumber, BlockId as V0_8BlockId, Tag as V0_8Tag};
+use crate::v0_8::block::{
+    Block as V0_8Block,
+    BlockHeader,
+    GeneralBlockHeader as V0_8GeneralBlockHeader,
+    PendingBlockHeader as V0_8PendingBlockHeader,
+};
+use crate::{get_latest_block_number, internal_server_error};
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Tag {
+    /// The most recent block that was accepted on L1
+    #[serde(rename = "l1_accepted")]
+    L1Accepted,
+    /// The most recent fully constructed block
+    #[serde(rename = "latest")]
+    Latest,
+    /// Currently constructed block, whose transactions were already executed
+    #[serde(rename = "pre_confirmed")]
+    PreConfirmed,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum BlockId {
+    HashOrNumber(BlockHashOrNumber),
+    Tag(Tag),
+}
+
+/// Converts the block id into a block id of V0_8, whose implementation serves the methods that
+/// didn't change. The pre-confirmed block is the pending block of V0_8, and the block that was
+/// accepted on L1 is resolved to its number.
+pub(crate) fn to_v0_8_block_id(
+    storage_reader: &StorageReader,
+    block_id: BlockId,
+) -> Result<V0_8BlockId, ErrorObjectOwned> {
+    Ok(match block_id {
+        BlockId::HashOrNumber(block_hash_or_number) => {
+            V0_8BlockId::HashOrNumber(block_hash_or_number)
+        }
+        BlockId::Tag(Tag::Latest) => V0_8BlockId::Tag(V0_8Tag::Latest),
+        BlockId::Tag(Tag::PreConfirmed) => V0_8BlockId::Tag(V0_8Tag::Pending),
+        BlockId::Tag(Tag::L1Accepted) => {
+            let txn = storage_reader.begin_ro_txn().map_err(internal_server_error)?;
+            // The blocks before the base layer marker are accepted on L1.
+            let l1_accepted_block_number = txn
+                .get_base_layer_block_marker()
+                .map_err(internal_server_error)?
+                .prev()
+                .ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?;
+            let latest_block_number = get_latest_block_number(&txn)?
+                .ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?;
+            V0_8BlockId::HashOrNumber(BlockHashOrNumber::Number(
+                l1_accepted_block_number.min(latest_block_number),
+            ))
+        }
+    })
+}
+
+/// The header of the block that is built on top of the latest block.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(deny_unknown_fields)]
+pub struct PreConfirmedBlockHeader {
+    pub block_number: BlockNumber,
+    pub sequencer_address: SequencerContractAddress,
+    pub timestamp: BlockTimestamp,
+    pub l1_gas_price: GasPricePerToken,
+    pub l1_data_gas_price: GasPricePerToken,
+    pub l2_gas_price: GasPricePerToken,
+    pub l1_da_mode: L1DataAvailabilityMode,
+    pub starknet_version: String,
+}
+
+impl PreConfirmedBlockHeader {
+    fn from_v0_8_pending_header(header: V0_8PendingBlockHeader, block_number: BlockNumber) -> Self {
+        Self {
+            block_number,
+            sequencer_address: header.sequencer_address,
+            timestamp: header.timestamp,
+            l1_gas_price: header.l1_gas_price,
+            l1_data_gas_price: header.l1_data_gas_price,
+            l2_gas_price: header.l2_gas_price,
+            l1_da_mode: header.l1_da_mode,
+            starknet_version: header.starknet_version,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(untagged)]
+pub enum GeneralBlockHeader {
+    BlockHeader(BlockHeader),
+    PreConfirmedBlockHeader(PreConfirmedBlockHeader),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct Block {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<BlockStatus>,
+    #[serde(flatten)]
+    pub header: GeneralBlockHeader,
+    pub transactions: Transactions,
+}
+
+/// Converts a block of V0_8, in which the pending block is the pre-confirmed block.
+pub(crate) fn to_v0_9_block(
+    storage_reader: &StorageReader,
+    block: V0_8Block,
+) -> Result<Block, ErrorObjectOwned> {
+    let (header, is_pre_confirmed) = match block.header {
+        V0_8GeneralBlockHeader::BlockHeader(header) => {
+            (GeneralBlockHeader::BlockHeader(header), false)
+        }
+        V0_8GeneralBlockHeader::PendingBlockHeader(header) => {
+            let block_number = get_pre_confirmed_block_number(storage_reader)?;
+            let header = PreConfirmedBlockHeader::from_v0_8_pending_header(header, block_number);
+            (GeneralBlockHeader::PreConfirmedBlockHeader(header), true)
+        }
+    };
+    Ok(Block {
+        status: block.status,
+        header,
+        transactions: Transactions::from_v0_8_transactions(block.transactions, is_pre_confirmed),
+    })
+}
+
+/// Returns the number of the pre-confirmed block, which is built on top of the latest block.
+pub(crate) fn get_pre_confirmed_block_number(
+    storage_reader: &StorageReader,
+) -> Result<BlockNumber, ErrorObjectOwned> {
+    let txn = storage_reader.begin_ro_txn().map_err(internal_server_error)?;
+    Ok(get_latest_block_number(&txn)?
+        .map_or(BlockNumber(0), |latest_block_number| latest_block_number.unchecked_next()))
+}
@@ -0,0 +1,5 @@
+pub mod api;
+pub mod block;
+pub use super::v0_8::error;
+pub mod subscription;
+pub mod transaction;
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::TransactionHash;
+
+use super::transaction::TransactionStatus;
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionStatusNotification {
+    pub transaction_hash: TransactionHash,
+    pub status: TransactionStatus,
+}
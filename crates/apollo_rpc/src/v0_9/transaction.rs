@@ -0,0 +1,219 @@
+use jsonrpsee::core::RpcResult;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::transaction::{
+    TransactionExecutionStatus as StarknetApiTransactionExecutionStatus,
+    TransactionHash,
+};
+
+use crate::v0_8::transaction::{
+    GeneralTransactionReceipt,
+    Transaction,
+    TransactionFinalityStatus as V0_8TransactionFinalityStatus,
+    TransactionOutput,
+    TransactionReceiptInBlock as V0_8TransactionReceiptInBlock,
+    TransactionWithHash,
+    Transactions as V0_8Transactions,
+};
+
+/// The status of a transaction, from the time it was received until it was accepted on L1.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct TransactionStatus {
+    pub finality_status: TransactionFinalityStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_status: Option<TransactionExecutionStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
+impl TransactionStatus {
+    /// The status of a transaction that is known but wasn't executed yet.
+    pub fn received() -> Self {
+        Self {
+            finality_status: TransactionFinalityStatus::Received,
+            execution_status: None,
+            failure_reason: None,
+        }
+    }
+}
+
+impl From<TransactionReceipt> for TransactionStatus {
+    fn from(receipt: TransactionReceipt) -> Self {
+        let (execution_status, failure_reason) = match receipt.output.execution_status() {
+            StarknetApiTransactionExecutionStatus::Succeeded => {
+                (TransactionExecutionStatus::Succeeded, None)
+            }
+            StarknetApiTransactionExecutionStatus::Reverted(reverted_status) => {
+                (TransactionExecutionStatus::Reverted, Some(reverted_status.revert_reason.clone()))
+            }
+        };
+        Self {
+            finality_status: receipt.finality_status.into(),
+            execution_status: Some(execution_status),
+            failure_reason,
+        }
+    }
+}
+
+/// The status of the L1 handler transaction of a message that was sent from L1 to L2.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct MessageStatus {
+    pub transaction_hash: TransactionHash,
+    #[serde(flatten)]
+    pub status: TransactionStatus,
+}
+
+/// Transaction finality status on starknet.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionFinalityStatus {
+    /// The transaction was received by the sequencer.
+    Received,
+    /// The transaction passed the validation and is a candidate for the pre-confirmed block.
+    Candidate,
+    /// The transaction was executed in the pre-confirmed block.
+    PreConfirmed,
+    /// The transaction entered an actual created block.
+    #[serde(rename = "ACCEPTED_ON_L2")]
+    AcceptedOnL2,
+    /// The transaction was accepted on-chain.
+    #[serde(rename = "ACCEPTED_ON_L1")]
+    AcceptedOnL1,
+}
+
+impl From<ReceiptFinalityStatus> for TransactionFinalityStatus {
+    fn from(status: ReceiptFinalityStatus) -> Self {
+        match status {
+            ReceiptFinalityStatus::PreConfirmed => TransactionFinalityStatus::PreConfirmed,
+            ReceiptFinalityStatus::AcceptedOnL2 => TransactionFinalityStatus::AcceptedOnL2,
+            ReceiptFinalityStatus::AcceptedOnL1 => TransactionFinalityStatus::AcceptedOnL1,
+        }
+    }
+}
+
+/// The finality status of a transaction that was executed, as it appears in its receipt.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub enum ReceiptFinalityStatus {
+    /// The transaction was executed in the pre-confirmed block.
+    #[serde(rename = "PRE_CONFIRMED")]
+    PreConfirmed,
+    /// The transaction entered an actual created block.
+    #[serde(rename = "ACCEPTED_ON_L2")]
+    AcceptedOnL2,
+    /// The transaction was accepted on-chain.
+    #[serde(rename = "ACCEPTED_ON_L1")]
+    AcceptedOnL1,
+}
+
+impl From<V0_8TransactionFinalityStatus> for ReceiptFinalityStatus {
+    fn from(status: V0_8TransactionFinalityStatus) -> Self {
+        match status {
+            V0_8TransactionFinalityStatus::AcceptedOnL2 => ReceiptFinalityStatus::AcceptedOnL2,
+            V0_8TransactionFinalityStatus::AcceptedOnL1 => ReceiptFinalityStatus::AcceptedOnL1,
+        }
+    }
+}
+
+/// The receipt of a transaction. The receipts of the transactions in the pre-confirmed block have
+/// no block hash.
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct TransactionReceipt {
+    pub finality_status: ReceiptFinalityStatus,
+    pub transaction_hash: TransactionHash,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<BlockHash>,
+    pub block_number: BlockNumber,
+    #[serde(flatten)]
+    pub output: TransactionOutput,
+}
+
+impl TransactionReceipt {
+    /// Converts a receipt of V0_8, in which the receipts of the pending block are the receipts of
+    /// the pre-confirmed block.
+    pub(crate) fn from_v0_8_receipt(
+        receipt: GeneralTransactionReceipt,
+        get_pre_confirmed_block_number: impl FnOnce() -> RpcResult<BlockNumber>,
+    ) -> RpcResult<Self> {
+        Ok(match receipt {
+            GeneralTransactionReceipt::TransactionReceipt(receipt) => Self {
+                finality_status: receipt.finality_status.into(),
+                transaction_hash: receipt.transaction_hash,
+                block_hash: Some(receipt.block_hash),
+                block_number: receipt.block_number,
+                output: receipt.output,
+            },
+            GeneralTransactionReceipt::PendingTransactionReceipt(_) => {
+                let receipt = V0_8TransactionReceiptInBlock::from(receipt);
+                Self {
+                    finality_status: ReceiptFinalityStatus::PreConfirmed,
+                    transaction_hash: receipt.transaction_hash,
+                    block_hash: None,
+                    block_number: get_pre_confirmed_block_number()?,
+                    output: receipt.output,
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct TransactionReceiptInBlock {
+    pub finality_status: ReceiptFinalityStatus,
+    pub transaction_hash: TransactionHash,
+    #[serde(flatten)]
+    pub output: TransactionOutput,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct TransactionWithReceipt {
+    pub receipt: TransactionReceiptInBlock,
+    pub transaction: Transaction,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(untagged)]
+pub enum Transactions {
+    Hashes(Vec<TransactionHash>),
+    Full(Vec<TransactionWithHash>),
+    FullWithReceipts(Vec<TransactionWithReceipt>),
+}
+
+impl Transactions {
+    /// Converts the transactions of a block of V0_8. The receipts of the transactions in the
+    /// pre-confirmed block have the pre-confirmed finality status.
+    pub(crate) fn from_v0_8_transactions(
+        transactions: V0_8Transactions,
+        is_pre_confirmed: bool,
+    ) -> Self {
+        match transactions {
+            V0_8Transactions::Hashes(transaction_hashes) => Self::Hashes(transaction_hashes),
+            V0_8Transactions::Full(transactions) => Self::Full(transactions),
+            V0_8Transactions::FullWithReceipts(transactions) => Self::FullWithReceipts(
+                transactions
+                    .into_iter()
+                    .map(|transaction_with_receipt| TransactionWithReceipt {
+                        receipt: TransactionReceiptInBlock {
+                            finality_status: if is_pre_confirmed {
+                                ReceiptFinalityStatus::PreConfirmed
+                            } else {
+                                transaction_with_receipt.receipt.finality_status.into()
+                            },
+                            transaction_hash: transaction_with_receipt.receipt.transaction_hash,
+                            output: transaction_with_receipt.receipt.output,
+                        },
+                        transaction: transaction_with_receipt.transaction,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Transaction execution status on starknet. The reason of a revert is the failure reason of the
+/// transaction status.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionExecutionStatus {
+    Succeeded,
+    Reverted,
+}
@@ -25,10 +25,8 @@ impl ComponentRequestHandler<SignatureManagerRequest, SignatureManagerResponse>
             SignatureManagerRequest::Identify(peer_id, nonce) => {
                 SignatureManagerResponse::Identify(self.identify(peer_id, nonce).await)
             }
-            SignatureManagerRequest::SignPrecommitVote(block_hash) => {
-                SignatureManagerResponse::SignPrecommitVote(
-                    self.sign_precommit_vote(block_hash).await,
-                )
+            SignatureManagerRequest::SignVote(vote) => {
+                SignatureManagerResponse::SignVote(self.sign_vote(vote).await)
             }
         }
     }
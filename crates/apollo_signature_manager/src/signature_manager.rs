@@ -3,6 +3,8 @@ use std::ops::Deref;
 use apollo_infra::component_definitions::ComponentStarter;
 use apollo_network_types::network_types::PeerId;
 use apollo_signature_manager_types::{
+    ConsensusVote,
+    ConsensusVoteType,
     KeyStore,
     KeyStoreResult,
     SignatureManagerError,
@@ -10,7 +12,6 @@ use apollo_signature_manager_types::{
 };
 use async_trait::async_trait;
 use blake2s::blake2s_to_felt;
-use starknet_api::block::BlockHash;
 use starknet_api::core::Nonce;
 use starknet_api::crypto::utils::{PrivateKey, PublicKey, RawSignature, SignatureConversionError};
 use starknet_core::crypto::{ecdsa_sign, ecdsa_verify, EcdsaVerifyError};
@@ -24,6 +25,7 @@ pub mod signature_manager_test;
 
 // Message domain separators.
 pub(crate) const INIT_PEER_ID: &[u8] = b"INIT_PEER_ID";
+pub(crate) const PREVOTE_VOTE: &[u8] = b"PREVOTE_VOTE";
 pub(crate) const PRECOMMIT_VOTE: &[u8] = b"PRECOMMIT_VOTE";
 
 pub type SignatureVerificationResult<T> = Result<T, SignatureVerificationError>;
@@ -59,11 +61,8 @@ impl<KS: KeyStore> SignatureManager<KS> {
         self.sign(message_digest).await
     }
 
-    pub async fn sign_precommit_vote(
-        &self,
-        block_hash: BlockHash,
-    ) -> SignatureManagerResult<RawSignature> {
-        let message_digest = build_precommit_vote_message_digest(block_hash);
+    pub async fn sign_vote(&self, vote: ConsensusVote) -> SignatureManagerResult<RawSignature> {
+        let message_digest = build_vote_message_digest(vote);
         self.sign(message_digest).await
     }
 
@@ -125,11 +124,34 @@ fn build_peer_identity_message_digest(peer_id: PeerId, nonce: Nonce) -> MessageD
     MessageDigest(blake2s_to_felt(&message))
 }
 
-fn build_precommit_vote_message_digest(block_hash: BlockHash) -> MessageDigest {
-    let block_hash = block_hash.to_bytes_be();
-    let mut message = Vec::with_capacity(PRECOMMIT_VOTE.len() + block_hash.len());
-    message.extend_from_slice(PRECOMMIT_VOTE);
-    message.extend_from_slice(&block_hash);
+// The domain separator binds the vote type, so a prevote signature can't pass for a precommit and
+// vice versa. A nil block hash is encoded as a single zero byte, and a block hash as a one byte
+// followed by the hash, so a nil vote can't pass for a vote for any block.
+fn build_vote_message_digest(vote: ConsensusVote) -> MessageDigest {
+    let ConsensusVote { vote_type, height, round, block_hash, voter } = vote;
+    let domain_separator = match vote_type {
+        ConsensusVoteType::Prevote => PREVOTE_VOTE,
+        ConsensusVoteType::Precommit => PRECOMMIT_VOTE,
+    };
+    let height = height.0.to_be_bytes();
+    let round = round.to_be_bytes();
+    let block_hash = block_hash.map(|block_hash| block_hash.to_bytes_be());
+    let voter = voter.to_bytes_be();
+    let block_hash_len = 1 + block_hash.map_or(0, |block_hash| block_hash.len());
+    let mut message = Vec::with_capacity(
+        domain_separator.len() + height.len() + round.len() + block_hash_len + voter.len(),
+    );
+    message.extend_from_slice(domain_separator);
+    message.extend_from_slice(&height);
+    message.extend_from_slice(&round);
+    match block_hash {
+        Some(block_hash) => {
+            message.push(1);
+            message.extend_from_slice(&block_hash);
+        }
+        None => message.push(0),
+    }
+    message.extend_from_slice(&voter);
 
     MessageDigest(blake2s_to_felt(&message))
 }
@@ -166,11 +188,11 @@ pub fn verify_identity(
     verify_signature(message_digest, signature, public_key)
 }
 
-pub fn verify_precommit_vote_signature(
-    block_hash: BlockHash,
+pub fn verify_vote_signature(
+    vote: ConsensusVote,
     signature: RawSignature,
     public_key: PublicKey,
 ) -> SignatureVerificationResult<bool> {
-    let message_digest = build_precommit_vote_message_digest(block_hash);
+    let message_digest = build_vote_message_digest(vote);
     verify_signature(message_digest, signature, public_key)
 }
@@ -1,16 +1,17 @@
 use apollo_network_types::network_types::PeerId;
+use apollo_signature_manager_types::{ConsensusVote, ConsensusVoteType};
 use hex::FromHex;
 use pretty_assertions::assert_eq;
 use rstest::rstest;
-use starknet_api::block::BlockHash;
+use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::Nonce;
-use starknet_api::{felt, nonce};
+use starknet_api::{contract_address, felt, nonce};
 use starknet_core::crypto::Signature;
 use starknet_core::types::Felt;
 
 use crate::signature_manager::{
     verify_identity,
-    verify_precommit_vote_signature,
+    verify_vote_signature,
     LocalKeyStore,
     SignatureManager,
 };
@@ -25,9 +26,11 @@ const ALICE_IDENTITY_SIGNATURE: Signature = Signature {
 };
 
 const ALICE_PRECOMMIT_SIGNATURE: Signature = Signature {
-    r: Felt::from_hex_unchecked("0xe16ecc38c135735e8aed7ffdb150ebb956a93ec19ac53e8295cdbd04d552b2"),
+    r: Felt::from_hex_unchecked(
+        "0x538707bb303bdc449bfdcb6384663ee1fa3891df1110ed80655ca05fab70f65",
+    ),
     s: Felt::from_hex_unchecked(
-        "0x4de081a9459b0e7defc49f7166f8869b33313020a20ffcc97506b8df6c42a7b",
+        "0x20f2fe47a3658f83f2d1cbd8b63472d588adc294d01080fa935971c8c4d3a6b",
     ),
 };
 
@@ -61,20 +64,27 @@ fn test_verify_identity(#[case] signature: Signature, #[case] expected: bool) {
     assert_eq!(verify_identity(peer_id, nonce, signature.into(), public_key).unwrap(), expected);
 }
 
+fn precommit_vote() -> ConsensusVote {
+    ConsensusVote {
+        vote_type: ConsensusVoteType::Precommit,
+        height: BlockNumber(7),
+        round: 1,
+        block_hash: Some(BlockHash(felt!("0x1234"))),
+        voter: contract_address!("0x64"),
+    }
+}
+
 #[rstest]
 #[case::valid_signature(ALICE_PRECOMMIT_SIGNATURE, true)]
 #[case::invalid_signature(
     Signature { r: felt!("0x1"), s: felt!("0x2") },
     false
 )]
-fn test_verify_precommit_vote_signature(#[case] signature: Signature, #[case] expected: bool) {
-    use starknet_api::block::BlockHash;
-
-    let block_hash = BlockHash(felt!("0x1234"));
+fn test_verify_vote_signature(#[case] signature: Signature, #[case] expected: bool) {
     let public_key = LocalKeyStore::new_for_testing().public_key;
 
     assert_eq!(
-        verify_precommit_vote_signature(block_hash, signature.into(), public_key).unwrap(),
+        verify_vote_signature(precommit_vote(), signature.into(), public_key).unwrap(),
         expected
     );
 }
@@ -97,19 +107,37 @@ async fn test_identify() {
 }
 
 #[tokio::test]
-async fn test_sign_precommit_vote() {
+async fn test_sign_vote() {
     let key_store = LocalKeyStore::new_for_testing();
     let signature_manager = SignatureManager::new(key_store);
 
-    let block_hash = BlockHash(felt!("0x1234"));
-    let signature = signature_manager.sign_precommit_vote(block_hash).await;
+    let vote = precommit_vote();
+    let signature = signature_manager.sign_vote(vote).await;
 
     assert_eq!(signature, Ok(ALICE_PRECOMMIT_SIGNATURE.into()));
 
     // Test alignment with verification function.
     assert_eq!(
-        verify_precommit_vote_signature(block_hash, signature.unwrap(), key_store.public_key)
-            .unwrap(),
+        verify_vote_signature(vote, signature.unwrap(), key_store.public_key).unwrap(),
         true
     );
 }
+
+#[rstest]
+#[case::vote_type(ConsensusVote { vote_type: ConsensusVoteType::Prevote, ..precommit_vote() })]
+#[case::height(ConsensusVote { height: BlockNumber(8), ..precommit_vote() })]
+#[case::round(ConsensusVote { round: 2, ..precommit_vote() })]
+#[case::block_hash(
+    ConsensusVote { block_hash: Some(BlockHash(felt!("0x4321"))), ..precommit_vote() }
+)]
+#[case::nil(ConsensusVote { block_hash: None, ..precommit_vote() })]
+#[case::voter(ConsensusVote { voter: contract_address!("0x65"), ..precommit_vote() })]
+#[tokio::test]
+async fn vote_signature_does_not_verify_another_vote(#[case] other_vote: ConsensusVote) {
+    let key_store = LocalKeyStore::new_for_testing();
+    let signature_manager = SignatureManager::new(key_store);
+
+    let signature = signature_manager.sign_vote(precommit_vote()).await.unwrap();
+
+    assert_eq!(verify_vote_signature(other_vote, signature, key_store.public_key).unwrap(), false);
+}
@@ -9,8 +9,8 @@ use async_trait::async_trait;
 #[cfg(any(feature = "testing", test))]
 use mockall::automock;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::BlockHash;
-use starknet_api::core::Nonce;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::crypto::utils::{PrivateKey, RawSignature, SignatureConversionError};
 use strum_macros::AsRefStr;
 use thiserror::Error;
@@ -28,6 +28,23 @@ pub type SharedSignatureManagerClient = Arc<dyn SignatureManagerClient>;
 pub type SignatureManagerRequestAndResponseSender =
     ComponentRequestAndResponseSender<SignatureManagerRequest, SignatureManagerResponse>;
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ConsensusVoteType {
+    Prevote,
+    Precommit,
+}
+
+/// The fields of a consensus vote covered by the voter's signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusVote {
+    pub vote_type: ConsensusVoteType,
+    pub height: BlockNumber,
+    pub round: u32,
+    // None for a nil vote.
+    pub block_hash: Option<BlockHash>,
+    pub voter: ContractAddress,
+}
+
 /// A read-only key store that contains exactly one key.
 #[async_trait]
 pub trait KeyStore: Clone + Send + Sync {
@@ -53,10 +70,7 @@ pub trait SignatureManagerClient: Send + Sync {
         nonce: Nonce,
     ) -> SignatureManagerClientResult<RawSignature>;
 
-    async fn sign_precommit_vote(
-        &self,
-        block_hash: BlockHash,
-    ) -> SignatureManagerClientResult<RawSignature>;
+    async fn sign_vote(&self, vote: ConsensusVote) -> SignatureManagerClientResult<RawSignature>;
 }
 
 #[derive(Clone, Debug, Error, Eq, PartialEq, Serialize, Deserialize)]
@@ -82,14 +96,14 @@ pub enum SignatureManagerClientError {
 #[derive(Clone, Serialize, Deserialize, AsRefStr)]
 pub enum SignatureManagerRequest {
     Identify(PeerId, Nonce),
-    SignPrecommitVote(BlockHash),
+    SignVote(ConsensusVote),
 }
 impl_debug_for_infra_requests_and_responses!(SignatureManagerRequest);
 
 #[derive(Clone, Serialize, Deserialize, AsRefStr)]
 pub enum SignatureManagerResponse {
     Identify(SignatureManagerResult<RawSignature>),
-    SignPrecommitVote(SignatureManagerResult<RawSignature>),
+    SignVote(SignatureManagerResult<RawSignature>),
 }
 impl_debug_for_infra_requests_and_responses!(SignatureManagerResponse);
 
@@ -114,14 +128,11 @@ where
         )
     }
 
-    async fn sign_precommit_vote(
-        &self,
-        block_hash: BlockHash,
-    ) -> SignatureManagerClientResult<RawSignature> {
-        let request = SignatureManagerRequest::SignPrecommitVote(block_hash);
+    async fn sign_vote(&self, vote: ConsensusVote) -> SignatureManagerClientResult<RawSignature> {
+        let request = SignatureManagerRequest::SignVote(vote);
         handle_all_response_variants!(
             SignatureManagerResponse,
-            SignPrecommitVote,
+            SignVote,
             SignatureManagerClientError,
             SignatureManagerError,
             Direct
@@ -1,6 +1,7 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use apollo_consensus::types::Round;
+use apollo_network::authentication::staker_negotiator::StakersProvider;
 use apollo_state_sync_types::communication::{SharedStateSyncClient, StateSyncClientError};
 use async_trait::async_trait;
 use blockifier::context::BlockContext;
@@ -8,6 +9,7 @@ use blockifier::execution::errors::EntryPointExecutionError;
 use blockifier::state::state_api::StateReader;
 use starknet_api::block::BlockNumber;
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::staking::StakingWeight;
 use starknet_types_core::felt::Felt;
 use thiserror::Error;
@@ -28,6 +30,46 @@ pub struct Staker {
     pub public_key: Felt,
 }
 
+/// The current committee, shared with the consensus network to authenticate its stakers as
+/// validators. Should be updated whenever the committee of a new epoch is fetched.
+#[derive(Clone, Debug, Default)]
+pub struct SharedCommittee(Arc<RwLock<Arc<Committee>>>);
+
+impl SharedCommittee {
+    pub fn new(committee: Committee) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(committee))))
+    }
+
+    pub fn update(&self, committee: Arc<Committee>) {
+        *self.0.write().expect("Committee lock is poisoned") = committee;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.read().expect("Committee lock is poisoned").is_empty()
+    }
+
+    /// Returns the public key of the committee member with the given address, or None if the
+    /// address is not a member of the committee.
+    pub fn public_key(&self, address: &ContractAddress) -> Option<PublicKey> {
+        self.0
+            .read()
+            .expect("Committee lock is poisoned")
+            .iter()
+            .find(|staker| staker.address == *address)
+            .map(|staker| PublicKey(staker.public_key))
+    }
+}
+
+impl StakersProvider for SharedCommittee {
+    fn is_staker(&self, public_key: &PublicKey) -> bool {
+        self.0
+            .read()
+            .expect("Committee lock is poisoned")
+            .iter()
+            .any(|staker| staker.public_key == public_key.0)
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum CommitteeProviderError {
     #[error(transparent)]
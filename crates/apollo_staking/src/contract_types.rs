@@ -1,4 +1,5 @@
 use blockifier::execution::call_info::Retdata;
+use starknet_api::block::BlockNumber;
 use starknet_api::core::ContractAddress;
 use starknet_api::staking::StakingWeight;
 use starknet_types_core::felt::Felt;
@@ -9,6 +10,11 @@ use crate::committee_provider::Staker;
 pub(crate) const GET_STAKERS_ENTRY_POINT: &str = "get_stakers";
 pub(crate) const EPOCH_LENGTH: u64 = 100; // Number of heights in an epoch.
 
+/// Returns the epoch that the given height belongs to.
+pub fn epoch_of_height(height: BlockNumber) -> u64 {
+    height.0 / EPOCH_LENGTH
+}
+
 // Represents a Cairo1 `Array` containing elements that can be deserialized to `T`.
 // `T` must implement `TryFrom<[Felt; N]>`, where `N` is the size of `T`'s Cairo equivalent.
 #[derive(Debug, PartialEq, Eq)]
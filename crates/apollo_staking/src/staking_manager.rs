@@ -1,15 +1,20 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_consensus::types::Round;
 use apollo_state_sync_types::communication::SharedStateSyncClient;
 use async_trait::async_trait;
+use blockifier::abi::constants::STORED_BLOCK_HASH_BUFFER;
 use blockifier::execution::entry_point::call_view_entry_point;
 use blockifier::state::state_api::StateReader;
+use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
 use starknet_api::transaction::fields::Calldata;
 use starknet_types_core::felt::Felt;
+use validator::{Validate, ValidationError};
 
 use crate::committee_provider::{
     Committee,
@@ -17,9 +22,10 @@ use crate::committee_provider::{
     CommitteeProviderError,
     CommitteeProviderResult,
     ExecutionContext,
+    SharedCommittee,
     Staker,
 };
-use crate::contract_types::{EPOCH_LENGTH, GET_STAKERS_ENTRY_POINT};
+use crate::contract_types::{epoch_of_height, GET_STAKERS_ENTRY_POINT};
 use crate::utils::BlockRandomGenerator;
 
 pub type StakerSet = Vec<Staker>;
@@ -28,8 +34,8 @@ pub type StakerSet = Vec<Staker>;
 #[path = "staking_manager_test.rs"]
 mod staking_manager_test;
 
-// TODO(Dafna): implement SerializeConfig and Validate for this struct. Specifically, the Validate
-// should check that proposer_prediction_window_in_heights >= STORED_BLOCK_HASH_BUFFER.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Validate)]
+#[validate(schema(function = "validate_staking_manager_config"))]
 pub struct StakingManagerConfig {
     pub staking_contract_address: ContractAddress,
     pub max_cached_epochs: usize,
@@ -44,6 +50,57 @@ pub struct StakingManagerConfig {
     pub proposer_prediction_window_in_heights: u64,
 }
 
+impl SerializeConfig for StakingManagerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "staking_contract_address",
+                &self.staking_contract_address,
+                "The address of the staking contract, from which the committee is fetched.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_cached_epochs",
+                &self.max_cached_epochs,
+                "The maximum number of epochs whose committee is cached.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "committee_size",
+                &self.committee_size,
+                "The desired number of committee members to select from the available stakers.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "proposer_prediction_window_in_heights",
+                &self.proposer_prediction_window_in_heights,
+                "Defines how many heights in advance the proposer can be predicted.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for StakingManagerConfig {
+    fn default() -> Self {
+        Self {
+            staking_contract_address: ContractAddress::default(),
+            max_cached_epochs: 10,
+            committee_size: 100,
+            proposer_prediction_window_in_heights: STORED_BLOCK_HASH_BUFFER,
+        }
+    }
+}
+
+fn validate_staking_manager_config(config: &StakingManagerConfig) -> Result<(), ValidationError> {
+    if config.proposer_prediction_window_in_heights < STORED_BLOCK_HASH_BUFFER {
+        return Err(ValidationError::new(
+            "proposer_prediction_window_in_heights must be at least STORED_BLOCK_HASH_BUFFER",
+        ));
+    }
+    Ok(())
+}
+
 struct CommitteeData {
     committee_members: Arc<Committee>,
     cumulative_weights: Vec<u128>,
@@ -65,6 +122,8 @@ pub struct StakingManager {
     committee_data_cache: CommitteeDataCache,
     random_generator: Box<dyn BlockRandomGenerator>,
     config: StakingManagerConfig,
+    // The committee of the highest fetched epoch.
+    shared_committee: SharedCommittee,
 }
 
 impl CommitteeDataCache {
@@ -93,9 +152,16 @@ impl StakingManager {
             committee_data_cache: CommitteeDataCache::new(config.max_cached_epochs),
             random_generator,
             config,
+            shared_committee: SharedCommittee::default(),
         }
     }
 
+    /// Returns the committee of the highest epoch fetched so far, which is kept up to date as new
+    /// epochs are fetched.
+    pub fn shared_committee(&self) -> SharedCommittee {
+        self.shared_committee.clone()
+    }
+
     // Returns the committee data for the given epoch.
     // If the data is not cached, it is fetched from the state and cached.
     fn committee_data_at_epoch<S: StateReader>(
@@ -112,6 +178,11 @@ impl StakingManager {
         let committee_data =
             Arc::new(self.fetch_and_build_committee_data(epoch, execution_context)?);
         self.committee_data_cache.insert(epoch, committee_data.clone());
+        if self.committee_data_cache.cache.last_key_value().map(|(last_epoch, _)| *last_epoch)
+            == Some(epoch)
+        {
+            self.shared_committee.update(committee_data.committee_members.clone());
+        }
 
         Ok(committee_data)
     }
@@ -250,7 +321,7 @@ impl CommitteeProvider for StakingManager {
             .await?;
 
         // Get the committee for the epoch this height belongs to.
-        let epoch = height.0 / EPOCH_LENGTH; // TODO(Dafna): export to a utility function.
+        let epoch = epoch_of_height(height);
         let committee_data = self.committee_data_at_epoch(epoch, execution_context)?;
 
         // Generate a pseudorandom value in the range [0, total_weight) based on the height, round,
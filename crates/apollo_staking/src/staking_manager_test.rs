@@ -1,8 +1,10 @@
 use std::convert::TryFrom;
 use std::sync::Arc;
 
+use apollo_network::authentication::staker_negotiator::StakersProvider;
 use apollo_state_sync_types::communication::MockStateSyncClient;
 use assert_matches::assert_matches;
+use blockifier::abi::constants::STORED_BLOCK_HASH_BUFFER;
 use blockifier::context::BlockContext;
 use blockifier::execution::call_info::Retdata;
 use blockifier::state::cached_state::CachedState;
@@ -21,15 +23,18 @@ use blockifier_test_utils::contracts::FeatureContract;
 use rstest::{fixture, rstest};
 use starknet_api::block::BlockNumber;
 use starknet_api::core::{ContractAddress, PatriciaKey, CONTRACT_ADDRESS_DOMAIN_SIZE};
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::staking::StakingWeight;
 use starknet_api::{contract_address, invoke_tx_args};
 use starknet_types_core::felt::Felt;
+use validator::Validate;
 
 use crate::committee_provider::{
     Committee,
     CommitteeProvider,
     CommitteeProviderError,
     ExecutionContext,
+    SharedCommittee,
     Staker,
 };
 use crate::contract_types::RetdataDeserializationError;
@@ -143,6 +148,65 @@ fn get_committee_success(
     assert_eq!(*committee, expected_committee);
 }
 
+#[rstest]
+fn shared_committee_follows_highest_epoch(
+    default_config: StakingManagerConfig,
+    mut state: State,
+    block_context: Context,
+) {
+    let mut committee_manager =
+        StakingManager::new(Box::new(MockBlockRandomGenerator::new()), default_config);
+    let shared_committee = committee_manager.shared_committee();
+    assert!(!shared_committee.is_staker(&PublicKey(STAKER_1.public_key)));
+
+    set_stakers(&mut state, &block_context, vec![STAKER_1].as_slice());
+    let context = ExecutionContext {
+        state_reader: state.clone(),
+        block_context: block_context.clone(),
+        state_sync_client: Arc::new(MockStateSyncClient::new()),
+    };
+    committee_manager.get_committee(2, context).unwrap();
+    assert!(shared_committee.is_staker(&PublicKey(STAKER_1.public_key)));
+
+    // Fetching an older epoch doesn't replace the committee of the highest epoch.
+    set_stakers(&mut state, &block_context, vec![STAKER_2].as_slice());
+    let context = ExecutionContext {
+        state_reader: state.clone(),
+        block_context: block_context.clone(),
+        state_sync_client: Arc::new(MockStateSyncClient::new()),
+    };
+    committee_manager.get_committee(1, context).unwrap();
+    assert!(shared_committee.is_staker(&PublicKey(STAKER_1.public_key)));
+    assert!(!shared_committee.is_staker(&PublicKey(STAKER_2.public_key)));
+}
+
+#[test]
+fn shared_committee_public_key() {
+    let shared_committee = SharedCommittee::new(vec![STAKER_1, STAKER_2]);
+
+    assert_eq!(shared_committee.public_key(&STAKER_2.address), Some(PublicKey(Felt::TWO)));
+    assert_eq!(shared_committee.public_key(&STAKER_3.address), None);
+}
+
+#[test]
+fn shared_committee_is_empty() {
+    assert!(SharedCommittee::default().is_empty());
+    assert!(!SharedCommittee::new(vec![STAKER_1]).is_empty());
+}
+
+#[rstest]
+#[case::below_stored_block_hash_buffer(STORED_BLOCK_HASH_BUFFER - 1, false)]
+#[case::equal_to_stored_block_hash_buffer(STORED_BLOCK_HASH_BUFFER, true)]
+fn config_validates_proposer_prediction_window(
+    default_config: StakingManagerConfig,
+    #[case] proposer_prediction_window_in_heights: u64,
+    #[case] is_valid: bool,
+) {
+    let config = StakingManagerConfig { proposer_prediction_window_in_heights, ..default_config };
+
+    assert_eq!(config.validate().is_ok(), is_valid);
+}
+
 #[rstest]
 fn get_committee_cache(
     default_config: StakingManagerConfig,
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use apollo_class_manager_types::SharedClassManagerClient;
+use apollo_state_sync_types::communication::{SharedStateSyncClient, StateSyncClientError};
+use apollo_state_sync_types::errors::StateSyncError;
+use blockifier::blockifier_versioned_constants::VersionedConstants;
+use blockifier::bouncer::BouncerConfig;
+use blockifier::context::{BlockContext, ChainInfo, FeeTokenAddresses};
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader, StateResult};
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::committee_provider::{CommitteeProviderResult, ExecutionContext};
+
+/// Reads the state of a given block from the state sync, for calling the staking contract.
+/// Must be used outside of the async runtime, since it blocks on the given runtime handle.
+pub struct SyncStateReader {
+    block_number: BlockNumber,
+    state_sync_client: SharedStateSyncClient,
+    class_manager_client: SharedClassManagerClient,
+    runtime: tokio::runtime::Handle,
+}
+
+impl SyncStateReader {
+    pub fn from_number(
+        state_sync_client: SharedStateSyncClient,
+        class_manager_client: SharedClassManagerClient,
+        block_number: BlockNumber,
+        runtime: tokio::runtime::Handle,
+    ) -> Self {
+        Self { block_number, state_sync_client, class_manager_client, runtime }
+    }
+}
+
+impl StateReader for SyncStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        let res = self.runtime.block_on(self.state_sync_client.get_storage_at(
+            self.block_number,
+            contract_address,
+            key,
+        ));
+
+        match res {
+            Ok(value) => Ok(value),
+            Err(StateSyncClientError::StateSyncError(StateSyncError::ContractNotFound(_))) => {
+                Ok(Felt::default())
+            }
+            Err(e) => Err(StateError::StateReadError(e.to_string())),
+        }
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let res = self
+            .runtime
+            .block_on(self.state_sync_client.get_nonce_at(self.block_number, contract_address));
+
+        match res {
+            Ok(value) => Ok(value),
+            Err(StateSyncClientError::StateSyncError(StateSyncError::ContractNotFound(_))) => {
+                Ok(Nonce::default())
+            }
+            Err(e) => Err(StateError::StateReadError(e.to_string())),
+        }
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        let is_class_declared = self
+            .runtime
+            .block_on(self.state_sync_client.is_class_declared_at(self.block_number, class_hash))
+            .map_err(|e| StateError::StateReadError(e.to_string()))?;
+
+        if !is_class_declared {
+            return Err(StateError::UndeclaredClassHash(class_hash));
+        }
+
+        let contract_class = self
+            .runtime
+            .block_on(self.class_manager_client.get_executable(class_hash))
+            .map_err(|e| StateError::StateReadError(e.to_string()))?
+            .ok_or(StateError::UndeclaredClassHash(class_hash))?;
+
+        match contract_class {
+            ContractClass::V1(casm_contract_class) => {
+                Ok(RunnableCompiledClass::V1(casm_contract_class.try_into()?))
+            }
+            ContractClass::V0(deprecated_contract_class) => {
+                Ok(RunnableCompiledClass::V0(deprecated_contract_class.try_into()?))
+            }
+        }
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let res = self.runtime.block_on(
+            self.state_sync_client.get_class_hash_at(self.block_number, contract_address),
+        );
+
+        match res {
+            Ok(value) => Ok(value),
+            Err(StateSyncClientError::StateSyncError(StateSyncError::ContractNotFound(_))) => {
+                Ok(ClassHash::default())
+            }
+            Err(e) => Err(StateError::StateReadError(e.to_string())),
+        }
+    }
+
+    fn get_compiled_class_hash(&self, _class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        todo!()
+    }
+}
+
+/// Returns the context for calling the staking contract in the state of the latest synced block.
+pub async fn latest_block_execution_context(
+    state_sync_client: SharedStateSyncClient,
+    class_manager_client: SharedClassManagerClient,
+    chain_id: ChainId,
+) -> CommitteeProviderResult<ExecutionContext<SyncStateReader>> {
+    let block_number = state_sync_client
+        .get_latest_block_number()
+        .await?
+        .ok_or(StateSyncClientError::StateSyncError(StateSyncError::EmptyState))?;
+    let block_header = state_sync_client.get_block(block_number).await?.block_header_without_hash;
+
+    // Calling a view entry point charges no fee, so the gas prices are left unset.
+    let block_info = BlockInfo {
+        block_number,
+        block_timestamp: block_header.timestamp,
+        sequencer_address: block_header.sequencer.0,
+        ..Default::default()
+    };
+    let chain_info =
+        ChainInfo { chain_id, fee_token_addresses: FeeTokenAddresses::default(), is_l3: false };
+    let block_context = BlockContext::new(
+        block_info,
+        chain_info,
+        VersionedConstants::latest_constants().clone(),
+        BouncerConfig::max(),
+    );
+
+    Ok(ExecutionContext {
+        state_reader: SyncStateReader::from_number(
+            Arc::clone(&state_sync_client),
+            class_manager_client,
+            block_number,
+            tokio::runtime::Handle::current(),
+        ),
+        block_context: Arc::new(block_context),
+        state_sync_client,
+    })
+}
@@ -14,7 +14,6 @@ pub trait BlockRandomGenerator: Send + Sync {
     ) -> u128;
 }
 
-#[allow(dead_code)]
 pub struct BlockPseudorandomGenerator;
 
 impl BlockRandomGenerator for BlockPseudorandomGenerator {
@@ -8,6 +8,7 @@ use std::cmp::min;
 use apollo_class_manager_types::SharedClassManagerClient;
 use apollo_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
 use apollo_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use apollo_rpc::SharedL1MessagesReader;
 use apollo_state_sync_types::communication::{StateSyncRequest, StateSyncResponse};
 use apollo_state_sync_types::errors::StateSyncError;
 use apollo_state_sync_types::state_sync_types::{StateSyncResult, SyncBlock};
@@ -33,10 +34,11 @@ const BUFFER_SIZE: usize = 100000;
 pub fn create_state_sync_and_runner(
     config: StateSyncConfig,
     class_manager_client: SharedClassManagerClient,
+    l1_messages_reader: Option<SharedL1MessagesReader>,
 ) -> (StateSync, StateSyncRunner) {
     let (new_block_sender, new_block_receiver) = channel(BUFFER_SIZE);
     let (state_sync_runner, storage_reader) =
-        StateSyncRunner::new(config, new_block_receiver, class_manager_client);
+        StateSyncRunner::new(config, new_block_receiver, class_manager_client, l1_messages_reader);
     (StateSync { storage_reader, new_block_sender }, state_sync_runner)
 }
 
@@ -100,6 +102,7 @@ impl StateSync {
             .ok_or(StateSyncError::BlockNotFound(block_number))?;
         let thin_state_diff =
             txn.get_state_diff(block_number)?.ok_or(StateSyncError::BlockNotFound(block_number))?;
+        let commit_certificate = txn.get_commit_certificate(block_number)?;
 
         let mut l1_transaction_hashes: Vec<TransactionHash> = vec![];
         let mut account_transaction_hashes: Vec<TransactionHash> = vec![];
@@ -115,6 +118,7 @@ impl StateSync {
             block_header_without_hash: block_header.block_header_without_hash,
             account_transaction_hashes,
             l1_transaction_hashes,
+            commit_certificate,
         })
     }
 
@@ -24,7 +24,7 @@ use apollo_p2p_sync::client::{
 use apollo_p2p_sync::server::{P2pSyncServer, P2pSyncServerChannels};
 use apollo_p2p_sync::{Protocol, BUFFER_SIZE};
 use apollo_reverts::{revert_block, revert_blocks_and_eternal_pending};
-use apollo_rpc::{run_server, RpcConfig};
+use apollo_rpc::{run_server, RpcConfig, SharedL1MessagesReader};
 use apollo_starknet_client::reader::objects::pending_data::{
     PendingBlock,
     PendingBlockOrDeprecated,
@@ -127,6 +127,7 @@ impl StateSyncRunner {
         config: StateSyncConfig,
         new_block_receiver: Receiver<SyncBlock>,
         class_manager_client: SharedClassManagerClient,
+        l1_messages_reader: Option<SharedL1MessagesReader>,
     ) -> (Self, StorageReader) {
         let StateSyncConfig {
             storage_config,
@@ -291,6 +292,7 @@ impl StateSyncRunner {
             pending_classes.clone(),
             storage_reader.clone(),
             Some(class_manager_client.clone()),
+            l1_messages_reader,
         );
 
         (
@@ -426,6 +428,7 @@ fn spawn_rpc_server(
     pending_classes: Arc<RwLock<PendingClasses>>,
     storage_reader: StorageReader,
     class_manager_client: Option<SharedClassManagerClient>,
+    l1_messages_reader: Option<SharedL1MessagesReader>,
 ) -> BoxFuture<'static, ()> {
     let rpc_config = rpc_config.clone();
     async move {
@@ -437,6 +440,7 @@ fn spawn_rpc_server(
             storage_reader,
             VERSION_FULL,
             class_manager_client,
+            l1_messages_reader,
         )
         .await
         .expect("Failed running JSON-RPC server");
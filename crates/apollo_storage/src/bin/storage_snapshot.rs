@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use apollo_storage::db::DbConfig;
+use apollo_storage::mmap_file::MmapFileConfig;
+use apollo_storage::snapshot::{export_snapshot, import_snapshot};
+use apollo_storage::{open_storage_for_reading, StorageConfig, StorageScope};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use starknet_api::block::BlockHash;
+use starknet_types_core::felt::Felt;
+
+// Exports a snapshot of a storage, or imports a snapshot into a new storage. The exported storage
+// is opened for reading only, so it isn't modified. The import options should match the config of
+// the node that uses the storage, since opening the storage applies them (e.g., disabling the event
+// keys index removes it).
+pub fn main() {
+    let matches = Command::new("Storage snapshot")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("export")
+                .about("Exports a snapshot of the storage to an empty directory")
+                .args(storage_args()),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Creates a storage from a snapshot, in an empty directory")
+                .args(storage_args())
+                .args(import_args()),
+        )
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("export", matches)) => {
+            let (db_config, snapshot_dir) = get_cli_params(matches);
+            let reader = open_storage_for_reading(db_config.clone(), MmapFileConfig::default())
+                .expect("Should be able to open storage");
+            let manifest = export_snapshot(&reader, &db_config, &snapshot_dir)
+                .expect("Should be able to export the snapshot");
+            println!(
+                "Exported a snapshot with the markers {:?} and the last block hash {:?}",
+                manifest.markers, manifest.last_block_hash
+            );
+        }
+        Some(("import", matches)) => {
+            let (db_config, snapshot_dir) = get_cli_params(matches);
+            let (config, block_hash) = get_import_params(matches, db_config);
+            import_snapshot(&snapshot_dir, config, block_hash)
+                .expect("Should be able to import the snapshot");
+            println!("Imported the snapshot");
+        }
+        _ => unreachable!("A subcommand is required"),
+    }
+}
+
+fn storage_args() -> Vec<Arg> {
+    vec![
+        Arg::new("db_path")
+            .short('d')
+            .long("db_path")
+            .required(true)
+            .help("The path to the database"),
+        Arg::new("chain_id")
+            .short('c')
+            .long("chain_id")
+            .required(true)
+            .help("The chain id SN_MAIN/SN_SEPOLIA for example"),
+        Arg::new("snapshot_dir")
+            .short('s')
+            .long("snapshot_dir")
+            .required(true)
+            .help("The path to the snapshot directory"),
+    ]
+}
+
+fn import_args() -> Vec<Arg> {
+    vec![
+        Arg::new("block_hash").short('b').long("block_hash").required(true).help(
+            "The hash of the last block of the snapshot, as a hex string, taken from a trusted \
+             source. The header chain of the snapshot is verified against it",
+        ),
+        Arg::new("scope")
+            .long("scope")
+            .value_parser(["FullArchive", "StateOnly"])
+            .default_value("FullArchive")
+            .help("The scope of the storage"),
+        Arg::new("index_event_keys")
+            .long("index_event_keys")
+            .action(ArgAction::SetTrue)
+            .help("Whether the storage indexes the events by their first key"),
+    ]
+}
+
+fn get_cli_params(matches: &ArgMatches) -> (DbConfig, PathBuf) {
+    let db_path = matches.get_one::<String>("db_path").expect("Missing db_path");
+    let chain_id = matches.get_one::<String>("chain_id").expect("Missing chain_id").to_string();
+    let snapshot_dir = matches.get_one::<String>("snapshot_dir").expect("Missing snapshot_dir");
+
+    let db_config =
+        DbConfig { path_prefix: db_path.into(), chain_id: chain_id.into(), ..Default::default() };
+    (db_config, snapshot_dir.into())
+}
+
+fn get_import_params(matches: &ArgMatches, db_config: DbConfig) -> (StorageConfig, BlockHash) {
+    let block_hash = matches.get_one::<String>("block_hash").expect("Missing block_hash");
+    let block_hash = BlockHash(Felt::from_hex(block_hash).expect("Invalid block_hash"));
+    let scope = match matches.get_one::<String>("scope").expect("Missing scope").as_str() {
+        "StateOnly" => StorageScope::StateOnly,
+        _ => StorageScope::FullArchive,
+    };
+
+    let config = StorageConfig {
+        db_config,
+        scope,
+        index_event_keys: matches.get_flag("index_event_keys"),
+        ..Default::default()
+    };
+    (config, block_hash)
+}
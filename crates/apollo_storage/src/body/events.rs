@@ -3,6 +3,9 @@
 //! Events are part of the transaction output. Each transaction output holds an array of events.
 //! Import [`EventsReader`] to iterate over events using a read-only [`StorageTxn`].
 //!
+//! If [`StorageConfig::index_event_keys`](crate::StorageConfig::index_event_keys) is set, the
+//! events are also indexed by their first key, see [`EventsReader::iter_events_by_first_key`].
+//!
 //! # Example
 //! ```
 //! use apollo_storage::open_storage;
@@ -57,15 +60,24 @@ use starknet_api::transaction::{
     Event,
     EventContent,
     EventIndexInTransactionOutput,
+    EventKey,
     TransactionOutput,
 };
+use tracing::debug;
 
 use super::TransactionMetadataTable;
-use crate::body::{EventsTableKey, TransactionIndex};
+use crate::body::{BodyStorageReader, EventKeysTableKey, EventsTableKey, TransactionIndex};
 use crate::db::serialization::{NoVersionValueWrapper, VersionZeroWrapper};
 use crate::db::table_types::{CommonPrefix, DbCursor, DbCursorTrait, NoValue, SimpleTable, Table};
 use crate::db::{DbTransaction, RO};
-use crate::{FileHandlers, StorageResult, StorageTxn, TransactionMetadata};
+use crate::{
+    FileHandlers,
+    MarkerKind,
+    StorageResult,
+    StorageTxn,
+    StorageWriter,
+    TransactionMetadata,
+};
 
 /// An identifier of an event.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize, PartialOrd, Ord)]
@@ -91,6 +103,28 @@ pub trait EventsReader<'txn, 'env> {
         event_index: EventIndex,
         to_block_number: BlockNumber,
     ) -> StorageResult<EventIter<'txn, 'env>>;
+
+    /// Returns an iterator over the events whose first key is the given key, by the order of the
+    /// event index. Returns `None` if the events from the given event index aren't indexed by
+    /// their keys.
+    ///
+    /// # Arguments
+    /// * key - the first key of the events to iterate over.
+    /// * event_index - event index to start iterate from it.
+    /// * to_block_number - block number to stop iterate at it.
+    ///
+    /// # Errors
+    /// Returns [`StorageError`](crate::StorageError) if there was an error.
+    fn iter_events_by_first_key(
+        &'env self,
+        key: EventKey,
+        event_index: EventIndex,
+        to_block_number: BlockNumber,
+    ) -> StorageResult<Option<EventIter<'txn, 'env>>>;
+
+    /// Returns the first block whose events are indexed by their first key, or `None` if the
+    /// event keys index is disabled.
+    fn get_event_keys_index_start(&'env self) -> StorageResult<Option<BlockNumber>>;
 }
 
 // TODO(DanB): support all read transactions (including RW).
@@ -109,20 +143,46 @@ impl<'txn, 'env> EventsReader<'txn, 'env> for StorageTxn<'env, RO> {
 
         Ok(EventIter::ByEventIndex(self.iter_events_by_event_index(event_index, to_block_number)?))
     }
+
+    fn iter_events_by_first_key(
+        &'env self,
+        key: EventKey,
+        event_index: EventIndex,
+        to_block_number: BlockNumber,
+    ) -> StorageResult<Option<EventIter<'txn, 'env>>> {
+        let Some(index_start) = self.get_event_keys_index_start()? else {
+            return Ok(None);
+        };
+        if event_index.0.0 < index_start {
+            return Ok(None);
+        }
+        Ok(Some(EventIter::ByFirstKey(self.iter_events_by_event_key(
+            key,
+            event_index,
+            to_block_number,
+        )?)))
+    }
+
+    fn get_event_keys_index_start(&'env self) -> StorageResult<Option<BlockNumber>> {
+        let markers_table = self.open_table(&self.tables.markers)?;
+        Ok(markers_table.get(&self.txn, &MarkerKind::EventKeysIndexStart)?)
+    }
 }
 
 // TODO(dvir): add transaction hash to the return value. In the RPC when returning events this is
 // with the transaction hash. We can do it efficiently here because we anyway read the relevant
 // entry in the transaction_metadata table..
 #[allow(missing_docs)]
-/// A wrapper of two iterators [`EventIterByContractAddress`] and [`EventIterByEventIndex`].
+/// A wrapper of the iterators [`EventIterByContractAddress`], [`EventIterByEventIndex`] and
+/// [`EventIterByFirstKey`].
 pub enum EventIter<'txn, 'env> {
     ByContractAddress(EventIterByContractAddress<'env, 'txn>),
     ByEventIndex(EventIterByEventIndex<'txn>),
+    ByFirstKey(EventIterByFirstKey<'env, 'txn>),
 }
 
-/// This iterator is a wrapper of two iterators [`EventIterByContractAddress`]
-/// and [`EventIterByEventIndex`].
+/// This iterator is a wrapper of the iterators [`EventIterByContractAddress`],
+/// [`EventIterByEventIndex`] and [`EventIterByFirstKey`].
 /// With this wrapper we can execute the same code, regardless the
 /// type of iteration used.
 impl Iterator for EventIter<'_, '_> {
@@ -132,6 +192,7 @@ impl Iterator for EventIter<'_, '_> {
         match self {
             EventIter::ByContractAddress(it) => it.next(),
             EventIter::ByEventIndex(it) => it.next(),
+            EventIter::ByFirstKey(it) => it.next(),
         }
         .unwrap_or(None)
     }
@@ -181,6 +242,53 @@ impl EventIterByContractAddress<'_, '_> {
     }
 }
 
+/// This iterator goes over the events whose first key is a given key, in the order of the event
+/// index, using the event keys index.
+pub struct EventIterByFirstKey<'env, 'txn> {
+    txn: &'txn DbTransaction<'env, RO>,
+    file_handles: &'txn FileHandlers<RO>,
+    key: EventKey,
+    to_block_number: BlockNumber,
+    // The next entry in the event keys table and the index of the first event to return from its
+    // transaction. If it is None there are no more events.
+    next_entry_in_event_keys_table: Option<(EventKeysTableKey, usize)>,
+    // Queue of events to return from the iterator. When this queue is empty, we need to fetch more
+    // events.
+    events_queue: VecDeque<((ContractAddress, EventIndex), EventContent)>,
+    cursor: EventKeysTableCursor<'txn>,
+    transaction_metadata_table: TransactionMetadataTable<'env>,
+}
+
+impl EventIterByFirstKey<'_, '_> {
+    /// Returns the next event. If there are no more events, returns None.
+    ///
+    /// # Errors
+    /// Returns [`StorageError`](crate::StorageError) if there was an error.
+    fn next(&mut self) -> StorageResult<Option<((ContractAddress, EventIndex), EventContent)>> {
+        while self.events_queue.is_empty() {
+            let Some(((key, tx_index), start_index)) = self.next_entry_in_event_keys_table.take()
+            else {
+                return Ok(None);
+            };
+            if key != self.key || tx_index.0 > self.to_block_number {
+                return Ok(None);
+            }
+            let tx_metadata =
+                self.transaction_metadata_table.get(self.txn, &tx_index)?.unwrap_or_else(|| {
+                    panic!("Transaction metadata not found for transaction index: {tx_index:?}")
+                });
+            let tx_output = self
+                .file_handles
+                .get_transaction_output_unchecked(tx_metadata.tx_output_location)?;
+            self.events_queue =
+                get_events_with_first_key(tx_output.events(), tx_index, &self.key, start_index);
+            self.next_entry_in_event_keys_table = self.cursor.next()?.map(|(key, _)| (key, 0));
+        }
+
+        Ok(Some(self.events_queue.pop_front().expect("events_queue should not be empty.")))
+    }
+}
+
 /// This iterator goes over the events in the order of the event index.
 /// That is, the events are iterated by the order they are emitted.
 /// First by the block number, then by the transaction offset in the block,
@@ -303,6 +411,46 @@ where
         })
     }
 
+    /// Returns an events iterator that iterates the events with the given first key from the given
+    /// event index.
+    ///
+    /// # Arguments
+    /// * key - the first key of the events to iterate over.
+    /// * event_index - event index to start from the first event with an index greater or equals
+    ///   to.
+    /// * to_block_number - block number to stop iterate at it.
+    ///
+    /// # Errors
+    /// Returns [`StorageError`](crate::StorageError) if there was an error.
+    fn iter_events_by_event_key(
+        &'env self,
+        key: EventKey,
+        event_index: EventIndex,
+        to_block_number: BlockNumber,
+    ) -> StorageResult<EventIterByFirstKey<'env, 'txn>> {
+        let transaction_metadata_table = self.open_table(&self.tables.transaction_metadata)?;
+        let event_keys_table = self.open_table(&self.tables.event_keys)?;
+        let mut cursor = event_keys_table.cursor(&self.txn)?;
+        let next_entry_in_event_keys_table =
+            cursor.lower_bound(&(key.clone(), event_index.0))?.map(|((key, tx_index), _)| {
+                // In case of we get tx_index different from the event index, it means we need to
+                // start a new transaction which means the first event.
+                let start_index = if tx_index == event_index.0 { event_index.1.0 } else { 0 };
+                ((key, tx_index), start_index)
+            });
+
+        Ok(EventIterByFirstKey {
+            txn: &self.txn,
+            file_handles: &self.file_handlers,
+            key,
+            to_block_number,
+            next_entry_in_event_keys_table,
+            events_queue: VecDeque::new(),
+            cursor,
+            transaction_metadata_table,
+        })
+    }
+
     /// Returns an events iterator that iterates events by event index from the given event index.
     ///
     /// # Arguments
@@ -357,9 +505,52 @@ fn get_events_from_tx(
     events
 }
 
+fn get_events_with_first_key(
+    events_list: &[Event],
+    tx_index: TransactionIndex,
+    key: &EventKey,
+    start_index: usize,
+) -> VecDeque<((ContractAddress, EventIndex), EventContent)> {
+    let mut events = VecDeque::new();
+    for (i, event) in events_list.iter().enumerate().skip(start_index) {
+        if event.content.keys.first() == Some(key) {
+            let event_index = EventIndex(tx_index, EventIndexInTransactionOutput(i));
+            events.push_back(((event.from_address, event_index), event.content.clone()));
+        }
+    }
+    events
+}
+
+// Marks the block from which the events are indexed by their first key when the index gets
+// enabled, and removes the mark when it gets disabled, since the index isn't maintained anymore.
+pub(crate) fn update_event_keys_index_start(
+    writer: &mut StorageWriter,
+    index_event_keys: bool,
+) -> StorageResult<()> {
+    let txn = writer.begin_rw_txn()?;
+    let markers_table = txn.open_table(&txn.tables.markers)?;
+    let index_start = markers_table.get(&txn.txn, &MarkerKind::EventKeysIndexStart)?;
+    match (index_start, index_event_keys) {
+        (None, true) => {
+            let body_marker = txn.get_body_marker()?;
+            debug!("Indexing the events by their first key from block {body_marker}.");
+            markers_table.upsert(&txn.txn, &MarkerKind::EventKeysIndexStart, &body_marker)?;
+        }
+        (Some(_), false) => {
+            debug!("Disabling the event keys index.");
+            markers_table.delete(&txn.txn, &MarkerKind::EventKeysIndexStart)?;
+        }
+        _ => return Ok(()),
+    }
+    txn.commit()
+}
+
 /// A cursor of the events table.
 type EventsTableCursor<'txn> =
     DbCursor<'txn, RO, EventsTableKey, NoVersionValueWrapper<NoValue>, CommonPrefix>;
+/// A cursor of the event keys table.
+type EventKeysTableCursor<'txn> =
+    DbCursor<'txn, RO, EventKeysTableKey, NoVersionValueWrapper<NoValue>, CommonPrefix>;
 /// A cursor of the transaction outputs table.
 type TransactionMetadataTableCursor<'txn> =
     DbCursor<'txn, RO, TransactionIndex, VersionZeroWrapper<TransactionMetadata>, SimpleTable>;
@@ -9,14 +9,16 @@ use starknet_api::transaction::{
     EventContent,
     EventData,
     EventIndexInTransactionOutput,
+    EventKey,
     TransactionOffsetInBlock,
 };
 
 use crate::body::events::{get_events_from_tx, EventIndex, EventsReader};
 use crate::body::{BodyStorageWriter, TransactionIndex};
-use crate::db::table_types::Table;
+use crate::db::table_types::{DbCursorTrait, Table};
 use crate::header::HeaderStorageWriter;
-use crate::test_utils::get_test_storage;
+use crate::test_utils::{get_test_config, get_test_storage};
+use crate::{open_storage, StorageConfig};
 
 #[test]
 fn iter_events_by_key() {
@@ -207,6 +209,85 @@ fn revert_events() {
     }
 }
 
+#[test]
+fn iter_events_by_first_key() {
+    let (config, _temp_dir) = get_test_config(None);
+    let config = StorageConfig { index_event_keys: true, ..config };
+    let (storage_reader, mut storage_writer) = open_storage(config.clone()).unwrap();
+    let key1 = EventKey(1u32.into());
+    let key2 = EventKey(2u32.into());
+    let block = get_test_block(4, Some(3), None, Some(vec![vec![key1.clone(), key2.clone()]]));
+    let block_number = block.header.block_header_without_hash.block_number;
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(block_number, &block.header)
+        .unwrap()
+        .append_body(block_number, block.body.clone())
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let mut events_key1 = vec![];
+    for (tx_i, tx_output) in block.body.transaction_outputs.iter().enumerate() {
+        for (event_i, event) in tx_output.events().iter().enumerate() {
+            let event_index = EventIndex(
+                TransactionIndex(block_number, TransactionOffsetInBlock(tx_i)),
+                EventIndexInTransactionOutput(event_i),
+            );
+            if event.content.keys.first() == Some(&key1) {
+                events_key1.push(((event.from_address, event_index), event.content.clone()));
+            }
+        }
+    }
+
+    let event_index = EventIndex(
+        TransactionIndex(block_number, TransactionOffsetInBlock(0)),
+        EventIndexInTransactionOutput(0),
+    );
+    let txn = storage_reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_event_keys_index_start().unwrap(), Some(block_number));
+    let event_iter =
+        txn.iter_events_by_first_key(key1.clone(), event_index, block_number).unwrap().unwrap();
+    assert_eq!(event_iter.collect::<Vec<_>>(), events_key1);
+
+    // Start from the middle of the events.
+    let (_, second_event_index) = events_key1[1].0;
+    let event_iter = txn
+        .iter_events_by_first_key(key1.clone(), second_event_index, block_number)
+        .unwrap()
+        .unwrap();
+    assert_eq!(event_iter.collect::<Vec<_>>(), events_key1[1..].to_vec());
+    drop(txn);
+
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .revert_header(block_number)
+        .unwrap()
+        .0
+        .revert_body(block_number)
+        .unwrap()
+        .0
+        .commit()
+        .unwrap();
+    let txn = storage_reader.begin_ro_txn().unwrap();
+    let event_iter =
+        txn.iter_events_by_first_key(key1.clone(), event_index, block_number).unwrap().unwrap();
+    assert_eq!(event_iter.last(), None);
+    let event_keys_table = txn.txn.open_table(&txn.tables.event_keys).unwrap();
+    assert!(event_keys_table.cursor(&txn.txn).unwrap().next().unwrap().is_none());
+    drop(txn);
+
+    // Disabling the index removes it from the reader.
+    drop((storage_reader, storage_writer));
+    let (storage_reader, _) =
+        open_storage(StorageConfig { index_event_keys: false, ..config }).unwrap();
+    let txn = storage_reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_event_keys_index_start().unwrap(), None);
+    assert!(txn.iter_events_by_first_key(key1, event_index, block_number).unwrap().is_none());
+}
+
 #[test]
 fn get_events_from_tx_test() {
     let tx_index = TransactionIndex(BlockNumber(0), TransactionOffsetInBlock(0));
@@ -50,6 +50,7 @@ use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockBody, BlockNumber};
 use starknet_api::core::ContractAddress;
 use starknet_api::transaction::{
+    EventKey,
     Transaction,
     TransactionHash,
     TransactionOffsetInBlock,
@@ -81,6 +82,9 @@ type TransactionHashToIdxTable<'env> =
 type EventsTableKey = (ContractAddress, TransactionIndex);
 type EventsTable<'env> =
     TableHandle<'env, EventsTableKey, NoVersionValueWrapper<NoValue>, CommonPrefix>;
+type EventKeysTableKey = (EventKey, TransactionIndex);
+type EventKeysTable<'env> =
+    TableHandle<'env, EventKeysTableKey, NoVersionValueWrapper<NoValue>, CommonPrefix>;
 
 /// The index of a transaction in a block.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, PartialOrd, Ord)]
@@ -379,10 +383,13 @@ impl BodyStorageWriter for StorageTxn<'_, RW> {
 
         if self.scope != StorageScope::StateOnly {
             let events_table = self.open_table(&self.tables.events)?;
+            let event_keys_table = self.open_table(&self.tables.event_keys)?;
             let transaction_hash_to_idx_table =
                 self.open_table(&self.tables.transaction_hash_to_idx)?;
             let transaction_metadata_table = self.open_table(&self.tables.transaction_metadata)?;
             let file_offset_table = self.txn.open_table(&self.tables.file_offsets)?;
+            let is_event_keys_indexed =
+                is_block_in_event_keys_index(&self.txn, &markers_table, block_number)?;
 
             write_transactions(
                 &block_body,
@@ -392,6 +399,7 @@ impl BodyStorageWriter for StorageTxn<'_, RW> {
                 &transaction_hash_to_idx_table,
                 &transaction_metadata_table,
                 &events_table,
+                is_event_keys_indexed.then_some(&event_keys_table),
                 block_number,
             )?;
         }
@@ -428,6 +436,9 @@ impl BodyStorageWriter for StorageTxn<'_, RW> {
             let transaction_hash_to_idx_table =
                 self.open_table(&self.tables.transaction_hash_to_idx)?;
             let events_table = self.open_table(&self.tables.events)?;
+            let event_keys_table = self.open_table(&self.tables.event_keys)?;
+            let is_event_keys_indexed =
+                is_block_in_event_keys_index(&self.txn, &markers_table, block_number)?;
 
             let transactions = self
                 .get_block_transactions(block_number)?
@@ -448,6 +459,11 @@ impl BodyStorageWriter for StorageTxn<'_, RW> {
                 for event in tx_output.events().iter() {
                     events_table.delete(&self.txn, &(event.from_address, tx_index))?;
                 }
+                if is_event_keys_indexed {
+                    for first_key in get_first_event_keys(tx_output) {
+                        event_keys_table.delete(&self.txn, &(first_key, tx_index))?;
+                    }
+                }
                 transaction_hash_to_idx_table.delete(&self.txn, tx_hash)?;
                 transaction_metadata_table.delete(&self.txn, &tx_index)?;
             }
@@ -471,6 +487,7 @@ fn write_transactions<'env>(
     transaction_hash_to_idx_table: &'env TransactionHashToIdxTable<'env>,
     transaction_metadata_table: &'env TransactionMetadataTable<'env>,
     events_table: &'env EventsTable<'env>,
+    event_keys_table: Option<&'env EventKeysTable<'env>>,
     block_number: BlockNumber,
 ) -> StorageResult<()> {
     for (index, ((tx, tx_output), tx_hash)) in block_body
@@ -485,6 +502,9 @@ fn write_transactions<'env>(
         let tx_location = file_handlers.append_transaction(tx);
         let tx_output_location = file_handlers.append_transaction_output(tx_output);
         write_events(tx_output, txn, events_table, transaction_index)?;
+        if let Some(event_keys_table) = event_keys_table {
+            write_event_keys(tx_output, txn, event_keys_table, transaction_index)?;
+        }
         transaction_hash_to_idx_table.insert(txn, tx_hash, &transaction_index)?;
         transaction_metadata_table.append(
             txn,
@@ -528,6 +548,35 @@ fn write_events<'env>(
     Ok(())
 }
 
+// This function assumes that the `transaction_index` is the last index used to call it.
+fn write_event_keys<'env>(
+    tx_output: &TransactionOutput,
+    txn: &DbTransaction<'env, RW>,
+    event_keys_table: &'env EventKeysTable<'env>,
+    transaction_index: TransactionIndex,
+) -> StorageResult<()> {
+    for first_key in get_first_event_keys(tx_output) {
+        event_keys_table.append_greater_sub_key(txn, &(first_key, transaction_index), &NoValue)?;
+    }
+    Ok(())
+}
+
+// Returns the distinct first keys of the events of the transaction.
+fn get_first_event_keys(tx_output: &TransactionOutput) -> HashSet<EventKey> {
+    tx_output.events().iter().filter_map(|event| event.content.keys.first().cloned()).collect()
+}
+
+// Returns whether the events of the block are indexed by their first key. The index exists only
+// from the block in which it was enabled.
+fn is_block_in_event_keys_index<'env>(
+    txn: &DbTransaction<'env, RW>,
+    markers_table: &'env MarkersTable<'env>,
+    block_number: BlockNumber,
+) -> StorageResult<bool> {
+    let index_start = markers_table.get(txn, &MarkerKind::EventKeysIndexStart)?;
+    Ok(index_start.is_some_and(|index_start| index_start <= block_number))
+}
+
 fn update_marker<'env>(
     txn: &DbTransaction<'env, RW>,
     markers_table: &'env MarkersTable<'env>,
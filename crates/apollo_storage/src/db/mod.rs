@@ -33,7 +33,7 @@ use apollo_config::dumping::{ser_param, SerializeConfig};
 use apollo_config::validators::validate_ascii;
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_proc_macros::latency_histogram;
-use libmdbx::{DatabaseFlags, Geometry, PageSize, WriteMap};
+use libmdbx::{DatabaseFlags, Geometry, PageSize, WriteFlags, WriteMap};
 use serde::{Deserialize, Serialize};
 use starknet_api::core::ChainId;
 use validator::Validate;
@@ -43,7 +43,7 @@ use self::table_types::{DbCursor, DbCursorTrait};
 use crate::db::table_types::TableType;
 
 // Maximum number of Sub-Databases.
-const MAX_DBS: usize = 21;
+const MAX_DBS: usize = 23;
 
 // Note that NO_TLS mode is used by default.
 type EnvironmentKind = WriteMap;
@@ -254,6 +254,12 @@ impl DbReader {
 type DbReadTransaction<'env> = DbTransaction<'env, RO>;
 
 impl DbWriter {
+    // Returns another writer of the same environment. The database serializes the write
+    // transactions of all the writers.
+    pub(crate) fn new_writer_handle(&self) -> DbWriter {
+        DbWriter { env: self.env.clone() }
+    }
+
     pub(crate) fn begin_rw_txn(&mut self) -> DbResult<DbWriteTransaction<'_>> {
         Ok(DbWriteTransaction { txn: self.env.begin_rw_txn()? })
     }
@@ -293,7 +299,34 @@ impl<Mode: TransactionKind> DbTransaction<'_, Mode> {
             _table_type: PhantomData {},
         })
     }
+
+    // Calls `f` with the serialized key and value of each entry of the table, in the order of the
+    // entries in the table. Used for copying tables regardless of their types.
+    pub(crate) fn for_each_raw_entry<E: From<DbError>>(
+        &self,
+        table_name: &str,
+        mut f: impl FnMut(&[u8], &[u8]) -> result::Result<(), E>,
+    ) -> result::Result<(), E> {
+        let database = self.txn.open_table(Some(table_name)).map_err(DbError::from)?;
+        let mut cursor = self.txn.cursor(&database).map_err(DbError::from)?;
+        while let Some((key, value)) =
+            cursor.next::<DbKeyType<'_>, DbValueType<'_>>().map_err(DbError::from)?
+        {
+            f(&key, &value)?;
+        }
+        Ok(())
+    }
 }
+
+impl DbTransaction<'_, RW> {
+    // Writes an entry that was read by `for_each_raw_entry` to a table of the same type.
+    pub(crate) fn put_raw_entry(&self, table_name: &str, key: &[u8], value: &[u8]) -> DbResult<()> {
+        let database = self.txn.open_table(Some(table_name))?;
+        self.txn.put(&database, key, value, WriteFlags::UPSERT)?;
+        Ok(())
+    }
+}
+
 pub(crate) struct TableIdentifier<K: Key + Debug, V: ValueSerde + Debug, T: TableType> {
     pub(crate) name: &'static str,
     _key_type: PhantomData<K>,
@@ -46,6 +46,7 @@ use starknet_api::block::{
     BlockNumber,
     BlockSignature,
     BlockTimestamp,
+    CommitCertificate,
     GasPrice,
     GasPricePerToken,
     StarknetVersion,
@@ -117,6 +118,13 @@ pub trait HeaderStorageReader {
         &self,
         block_number: BlockNumber,
     ) -> StorageResult<Option<BlockSignature>>;
+
+    /// Returns the commit certificate of the block with the given number, if it was decided by
+    /// consensus.
+    fn get_commit_certificate(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<CommitCertificate>>;
 }
 
 /// Interface for writing data related to the block headers.
@@ -140,7 +148,7 @@ where
     ) -> StorageResult<Self>;
 
     /// Removes a block header and its signature (if exists) from the storage and returns the
-    /// removed data.
+    /// removed data. The commit certificate of the block (if exists) is removed as well.
     fn revert_header(
         self,
         block_number: BlockNumber,
@@ -153,6 +161,13 @@ where
         block_number: BlockNumber,
         block_signature: &BlockSignature,
     ) -> StorageResult<Self>;
+
+    /// Appends the commit certificate of a block to the storage.
+    fn append_commit_certificate(
+        self,
+        block_number: BlockNumber,
+        commit_certificate: &CommitCertificate,
+    ) -> StorageResult<Self>;
 }
 
 impl<Mode: TransactionKind> HeaderStorageReader for StorageTxn<'_, Mode> {
@@ -238,6 +253,15 @@ impl<Mode: TransactionKind> HeaderStorageReader for StorageTxn<'_, Mode> {
         let block_signature = block_signatures_table.get(&self.txn, &block_number)?;
         Ok(block_signature)
     }
+
+    fn get_commit_certificate(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<CommitCertificate>> {
+        let commit_certificates_table = self.open_table(&self.tables.commit_certificates)?;
+        let commit_certificate = commit_certificates_table.get(&self.txn, &block_number)?;
+        Ok(commit_certificate)
+    }
 }
 
 impl HeaderStorageWriter for StorageTxn<'_, RW> {
@@ -317,6 +341,7 @@ impl HeaderStorageWriter for StorageTxn<'_, RW> {
         let block_hash_to_number_table = self.open_table(&self.tables.block_hash_to_number)?;
         let starknet_version_table = self.open_table(&self.tables.starknet_version)?;
         let block_signatures_table = self.open_table(&self.tables.block_signatures)?;
+        let commit_certificates_table = self.open_table(&self.tables.commit_certificates)?;
 
         // Assert that header marker equals the reverted block number + 1
         let current_header_marker = self.get_header_marker()?;
@@ -362,6 +387,11 @@ impl HeaderStorageWriter for StorageTxn<'_, RW> {
             block_signatures_table.delete(&self.txn, &block_number)?;
         }
 
+        // Revert commit certificate.
+        if commit_certificates_table.get(&self.txn, &block_number)?.is_some() {
+            commit_certificates_table.delete(&self.txn, &block_number)?;
+        }
+
         Ok((
             self,
             Some(BlockHeader {
@@ -409,6 +439,20 @@ impl HeaderStorageWriter for StorageTxn<'_, RW> {
         block_signatures_table.insert(&self.txn, &block_number, block_signature)?;
         Ok(self)
     }
+
+    fn append_commit_certificate(
+        self,
+        block_number: BlockNumber,
+        commit_certificate: &CommitCertificate,
+    ) -> StorageResult<Self> {
+        if block_number >= self.get_header_marker()? {
+            return Err(StorageError::CommitCertificateForNonExistingBlock { block_number });
+        }
+
+        let commit_certificates_table = self.open_table(&self.tables.commit_certificates)?;
+        commit_certificates_table.insert(&self.txn, &block_number, commit_certificate)?;
+        Ok(self)
+    }
 }
 
 fn update_hash_mapping<'env>(
@@ -6,6 +6,8 @@ use starknet_api::block::{
     BlockHeaderWithoutHash,
     BlockNumber,
     BlockSignature,
+    CommitCertificate,
+    SignedPrecommit,
 };
 use starknet_api::felt;
 
@@ -294,6 +296,47 @@ fn get_reverted_block_signature_returns_none() {
     assert!(reader.begin_ro_txn().unwrap().get_block_signature(BlockNumber(0)).unwrap().is_none());
 }
 
+#[test]
+fn commit_certificate() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let commit_certificate = CommitCertificate {
+        round: 1,
+        precommits: vec![SignedPrecommit::default()],
+        ..Default::default()
+    };
+    let Err(err) = writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_commit_certificate(BlockNumber(0), &commit_certificate)
+    else {
+        panic!("Unexpected Ok.");
+    };
+    assert_matches!(
+        err,
+        StorageError::CommitCertificateForNonExistingBlock { block_number }
+        if block_number == BlockNumber(0)
+    );
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &BlockHeader::default())
+        .unwrap()
+        .append_commit_certificate(BlockNumber(0), &commit_certificate)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_commit_certificate(BlockNumber(0)).unwrap(),
+        Some(commit_certificate)
+    );
+
+    writer.begin_rw_txn().unwrap().revert_header(BlockNumber(0)).unwrap().0.commit().unwrap();
+    assert!(
+        reader.begin_ro_txn().unwrap().get_commit_certificate(BlockNumber(0)).unwrap().is_none()
+    );
+}
+
 #[test]
 fn revert_overflowing_block_number() {
     let ((_, mut writer), _temp_dir) = get_test_storage();
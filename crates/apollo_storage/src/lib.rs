@@ -91,6 +91,7 @@ pub mod db;
 pub mod header;
 pub mod mmap_file;
 mod serialization;
+pub mod snapshot;
 pub mod state;
 mod version;
 
@@ -107,7 +108,12 @@ use std::fmt::Debug;
 use std::fs;
 use std::sync::Arc;
 
-use apollo_config::dumping::{prepend_sub_config_name, ser_param, SerializeConfig};
+use apollo_config::dumping::{
+    prepend_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_proc_macros::latency_histogram;
 use body::events::EventIndex;
@@ -125,16 +131,23 @@ use mmap_file::{
     Writer,
 };
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{BlockHash, BlockNumber, BlockSignature, StarknetVersion};
+use starknet_api::block::{
+    BlockHash,
+    BlockNumber,
+    BlockSignature,
+    CommitCertificate,
+    StarknetVersion,
+};
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::state::{SierraContractClass, StateNumber, StorageKey, ThinStateDiff};
-use starknet_api::transaction::{Transaction, TransactionHash, TransactionOutput};
+use starknet_api::transaction::{EventKey, Transaction, TransactionHash, TransactionOutput};
 use starknet_types_core::felt::Felt;
 use tracing::{debug, info, warn};
 use validator::Validate;
 use version::{StorageVersionError, Version};
 
+use crate::body::events::update_event_keys_index_start;
 use crate::body::TransactionIndex;
 use crate::db::table_types::SimpleTable;
 use crate::db::{
@@ -153,13 +166,14 @@ use crate::db::{
 use crate::header::StorageBlockHeader;
 use crate::mmap_file::MMapFileStats;
 use crate::state::data::IndexedDeprecatedContractClass;
+use crate::state::pruning::spawn_state_pruner;
 use crate::version::{VersionStorageReader, VersionStorageWriter};
 
 // For more details on the storage version, see the module documentation.
 /// The current version of the storage state code.
-pub const STORAGE_VERSION_STATE: Version = Version { major: 6, minor: 0 };
+pub const STORAGE_VERSION_STATE: Version = Version { major: 6, minor: 1 };
 /// The current version of the storage blocks code.
-pub const STORAGE_VERSION_BLOCKS: Version = Version { major: 6, minor: 0 };
+pub const STORAGE_VERSION_BLOCKS: Version = Version { major: 6, minor: 2 };
 
 /// Opens a storage and returns a [`StorageReader`] and a [`StorageWriter`].
 pub fn open_storage(
@@ -173,11 +187,65 @@ pub fn open_storage(
         info!("Created storage directory: {}", storage_config.db_config.path_prefix.display());
     }
 
-    let (db_reader, mut db_writer) = open_env(&storage_config.db_config)?;
+    let (reader, writer) = open_storage_handles(
+        &storage_config.db_config,
+        storage_config.mmap_file_config,
+        storage_config.scope,
+    )?;
+    // The state pruner writes with its own writer, in parallel to the main writer.
+    let state_pruner = storage_config.state_history_length.map(|state_history_length| {
+        let pruner_writer = StorageWriter {
+            db_writer: writer.db_writer.new_writer_handle(),
+            tables: writer.tables.clone(),
+            scope: writer.scope,
+            file_writers: writer.file_writers.clone(),
+        };
+        (pruner_writer, state_history_length)
+    });
+
+    let mut writer = set_version_if_needed(reader.clone(), writer)?;
+    verify_storage_version(reader.clone())?;
+    if storage_config.scope == StorageScope::FullArchive {
+        update_event_keys_index_start(&mut writer, storage_config.index_event_keys)?;
+    }
+    if let Some((pruner_writer, state_history_length)) = state_pruner {
+        info!("Pruning the state history, keeping the last {state_history_length} blocks.");
+        spawn_state_pruner(pruner_writer, state_history_length);
+    }
+    Ok((reader, writer))
+}
+
+/// Opens an existing storage for reading only. Unlike [`open_storage`], the storage isn't modified:
+/// its version isn't set or migrated, the event keys index is kept as is and the state history
+/// isn't pruned. The scope of the storage is the one it was written with.
+pub fn open_storage_for_reading(
+    db_config: DbConfig,
+    mmap_file_config: MmapFileConfig,
+) -> StorageResult<StorageReader> {
+    info!("Opening storage for reading: {}", db_config.path_prefix.display());
+    let db_config = DbConfig { enforce_file_exists: true, ..db_config };
+    let (mut reader, _) =
+        open_storage_handles(&db_config, mmap_file_config, StorageScope::default())?;
+    verify_storage_version(reader.clone())?;
+    reader.scope = match get_storage_version(reader.clone())? {
+        Some(StorageVersion::StateOnly(_)) => StorageScope::StateOnly,
+        _ => StorageScope::FullArchive,
+    };
+    Ok(reader)
+}
+
+// Opens the tables and the mmap files of the storage.
+fn open_storage_handles(
+    db_config: &DbConfig,
+    mmap_file_config: MmapFileConfig,
+    scope: StorageScope,
+) -> StorageResult<(StorageReader, StorageWriter)> {
+    let (db_reader, mut db_writer) = open_env(db_config)?;
     let tables = Arc::new(Tables {
         block_hash_to_number: db_writer.create_simple_table("block_hash_to_number")?,
         block_signatures: db_writer.create_simple_table("block_signatures")?,
         casms: db_writer.create_simple_table("casms")?,
+        commit_certificates: db_writer.create_simple_table("commit_certificates")?,
         contract_storage: db_writer.create_common_prefix_table("contract_storage")?,
         declared_classes: db_writer.create_simple_table("declared_classes")?,
         declared_classes_block: db_writer.create_simple_table("declared_classes_block")?,
@@ -187,6 +255,7 @@ pub fn open_storage(
             .create_simple_table("deprecated_declared_classes_block")?,
         deployed_contracts: db_writer.create_simple_table("deployed_contracts")?,
         events: db_writer.create_common_prefix_table("events")?,
+        event_keys: db_writer.create_common_prefix_table("event_keys")?,
         headers: db_writer.create_simple_table("headers")?,
         markers: db_writer.create_simple_table("markers")?,
         nonces: db_writer.create_common_prefix_table("nonces")?,
@@ -204,23 +273,11 @@ pub fn open_storage(
         stateless_compiled_class_hash_v2: db_writer
             .create_simple_table("stateless_compiled_class_hash_v2")?,
     });
-    let (file_writers, file_readers) = open_storage_files(
-        &storage_config.db_config,
-        storage_config.mmap_file_config,
-        db_reader.clone(),
-        &tables.file_offsets,
-    )?;
+    let (file_writers, file_readers) =
+        open_storage_files(db_config, mmap_file_config, db_reader.clone(), &tables.file_offsets)?;
 
-    let reader = StorageReader {
-        db_reader,
-        tables: tables.clone(),
-        scope: storage_config.scope,
-        file_readers,
-    };
-    let writer = StorageWriter { db_writer, tables, scope: storage_config.scope, file_writers };
-
-    let writer = set_version_if_needed(reader.clone(), writer)?;
-    verify_storage_version(reader.clone())?;
+    let reader = StorageReader { db_reader, tables: tables.clone(), scope, file_readers };
+    let writer = StorageWriter { db_writer, tables, scope, file_writers };
     Ok((reader, writer))
 }
 
@@ -506,6 +563,7 @@ impl<Mode: TransactionKind> StorageTxn<'_, Mode> {
         if self.scope == StorageScope::StateOnly {
             let unused_tables = [
                 self.tables.events.name,
+                self.tables.event_keys.name,
                 self.tables.transaction_hash_to_idx.name,
                 self.tables.transaction_metadata.name,
             ];
@@ -530,6 +588,7 @@ struct_field_names! {
         block_hash_to_number: TableIdentifier<BlockHash, NoVersionValueWrapper<BlockNumber>, SimpleTable>,
         block_signatures: TableIdentifier<BlockNumber, VersionZeroWrapper<BlockSignature>, SimpleTable>,
         casms: TableIdentifier<ClassHash, VersionZeroWrapper<LocationInFile>, SimpleTable>,
+        commit_certificates: TableIdentifier<BlockNumber, VersionZeroWrapper<CommitCertificate>, SimpleTable>,
         // Empirically, defining the common prefix as (ContractAddress, StorageKey) is better space-wise than defining the
         // common prefix only as ContractAddress.
         contract_storage: TableIdentifier<((ContractAddress, StorageKey), BlockNumber), NoVersionValueWrapper<Felt>, CommonPrefix>,
@@ -540,6 +599,8 @@ struct_field_names! {
         // TODO(dvir): consider use here also the CommonPrefix table type.
         deployed_contracts: TableIdentifier<(ContractAddress, BlockNumber), VersionZeroWrapper<ClassHash>, SimpleTable>,
         events: TableIdentifier<(ContractAddress, TransactionIndex), NoVersionValueWrapper<NoValue>, CommonPrefix>,
+        // Indexes the transactions by the first keys of their events, from the block marked by MarkerKind::EventKeysIndexStart.
+        event_keys: TableIdentifier<(EventKey, TransactionIndex), NoVersionValueWrapper<NoValue>, CommonPrefix>,
         headers: TableIdentifier<BlockNumber, VersionZeroWrapper<StorageBlockHeader>, SimpleTable>,
         markers: TableIdentifier<MarkerKind, VersionZeroWrapper<BlockNumber>, SimpleTable>,
         nonces: TableIdentifier<(ContractAddress, BlockNumber), VersionZeroWrapper<Nonce>, CommonPrefix>,
@@ -625,11 +686,21 @@ pub enum StorageError {
          {block_number}."
     )]
     BlockSignatureForNonExistingBlock { block_number: BlockNumber, block_signature: BlockSignature },
+    #[error("Attempt to write a commit certificate of non-existing block {block_number}.")]
+    CommitCertificateForNonExistingBlock { block_number: BlockNumber },
+    #[error(
+        "The state {state_number:?} was pruned. The state history is kept starting from block \
+         {pruning_marker}."
+    )]
+    StatePruned { state_number: StateNumber, pruning_marker: BlockNumber },
 }
 
 /// A type alias that maps to std::result::Result<T, StorageError>.
 pub type StorageResult<V> = std::result::Result<V, StorageError>;
 
+// The default number of recent blocks whose state history is kept, when pruning is enabled.
+const DEFAULT_STATE_HISTORY_LENGTH: u64 = 1000;
+
 /// A struct for the configuration of the storage.
 #[allow(missing_docs)]
 #[derive(Serialize, Debug, Default, Deserialize, Clone, PartialEq, Validate)]
@@ -639,19 +710,42 @@ pub struct StorageConfig {
     #[validate]
     pub mmap_file_config: MmapFileConfig,
     pub scope: StorageScope,
+    /// The number of recent blocks whose state history is kept. If set, older state history is
+    /// pruned in the background. Otherwise, the entire state history is kept.
+    pub state_history_length: Option<u64>,
+    /// Whether to index the events by their first key. The index covers the blocks written since
+    /// it was enabled.
+    pub index_event_keys: bool,
 }
 
 impl SerializeConfig for StorageConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        let mut dumped_config = BTreeMap::from_iter([ser_param(
-            "scope",
-            &self.scope,
-            "The categories of data saved in storage.",
-            ParamPrivacyInput::Public,
-        )]);
+        let mut dumped_config = BTreeMap::from_iter([
+            ser_param(
+                "scope",
+                &self.scope,
+                "The categories of data saved in storage.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "index_event_keys",
+                &self.index_event_keys,
+                "Whether to index the events by their first key, in order to filter events by key \
+                 efficiently. Ignored in StateOnly scope.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
         dumped_config
             .extend(prepend_sub_config_name(self.mmap_file_config.dump(), "mmap_file_config"));
         dumped_config.extend(prepend_sub_config_name(self.db_config.dump(), "db_config"));
+        dumped_config.extend(ser_optional_param(
+            &self.state_history_length,
+            DEFAULT_STATE_HISTORY_LENGTH,
+            "state_history_length",
+            "The number of recent blocks whose state history is kept. Older state history is \
+             pruned, keeping only the latest values. If not set, the entire state history is kept.",
+            ParamPrivacyInput::Public,
+        ));
         dumped_config
     }
 }
@@ -671,6 +765,7 @@ pub struct DbStats {
 // - CompiledClass <= Class <= State <= Header
 // - Body <= Header
 // - BaseLayerBlock <= Header
+// - StatePruning <= State
 // Event is currently unsupported.
 pub(crate) enum MarkerKind {
     Header,
@@ -684,6 +779,11 @@ pub(crate) enum MarkerKind {
     /// Marks the block beyond the last block that its classes can't be compiled with the current
     /// compiler version used in the class manager. Determined by starknet version.
     CompilerBackwardCompatibility,
+    /// Marks the first block whose state history wasn't pruned.
+    StatePruning,
+    /// Marks the first block whose events are indexed by their first key. Doesn't exist if the
+    /// event keys index is disabled.
+    EventKeysIndexStart,
 }
 
 pub(crate) type MarkersTable<'env> =
@@ -831,7 +931,7 @@ fn open_storage_files(
         table.get(&db_transaction, &OffsetKind::ThinStateDiff)?.unwrap_or_default();
     let (thin_state_diff_writer, thin_state_diff_reader) = open_file(
         mmap_file_config.clone(),
-        db_config.path().join("thin_state_diff.dat"),
+        db_config.path().join(OffsetKind::ThinStateDiff.file_name()),
         thin_state_diff_offset,
     )?;
 
@@ -839,19 +939,22 @@ fn open_storage_files(
         table.get(&db_transaction, &OffsetKind::ContractClass)?.unwrap_or_default();
     let (contract_class_writer, contract_class_reader) = open_file(
         mmap_file_config.clone(),
-        db_config.path().join("contract_class.dat"),
+        db_config.path().join(OffsetKind::ContractClass.file_name()),
         contract_class_offset,
     )?;
 
     let casm_offset = table.get(&db_transaction, &OffsetKind::Casm)?.unwrap_or_default();
-    let (casm_writer, casm_reader) =
-        open_file(mmap_file_config.clone(), db_config.path().join("casm.dat"), casm_offset)?;
+    let (casm_writer, casm_reader) = open_file(
+        mmap_file_config.clone(),
+        db_config.path().join(OffsetKind::Casm.file_name()),
+        casm_offset,
+    )?;
 
     let deprecated_contract_class_offset =
         table.get(&db_transaction, &OffsetKind::DeprecatedContractClass)?.unwrap_or_default();
     let (deprecated_contract_class_writer, deprecated_contract_class_reader) = open_file(
         mmap_file_config.clone(),
-        db_config.path().join("deprecated_contract_class.dat"),
+        db_config.path().join(OffsetKind::DeprecatedContractClass.file_name()),
         deprecated_contract_class_offset,
     )?;
 
@@ -859,14 +962,17 @@ fn open_storage_files(
         table.get(&db_transaction, &OffsetKind::TransactionOutput)?.unwrap_or_default();
     let (transaction_output_writer, transaction_output_reader) = open_file(
         mmap_file_config.clone(),
-        db_config.path().join("transaction_output.dat"),
+        db_config.path().join(OffsetKind::TransactionOutput.file_name()),
         transaction_output_offset,
     )?;
 
     let transaction_offset =
         table.get(&db_transaction, &OffsetKind::Transaction)?.unwrap_or_default();
-    let (transaction_writer, transaction_reader) =
-        open_file(mmap_file_config, db_config.path().join("transaction.dat"), transaction_offset)?;
+    let (transaction_writer, transaction_reader) = open_file(
+        mmap_file_config,
+        db_config.path().join(OffsetKind::Transaction.file_name()),
+        transaction_offset,
+    )?;
 
     Ok((
         FileHandlers {
@@ -905,6 +1011,20 @@ pub enum OffsetKind {
     Transaction,
 }
 
+impl OffsetKind {
+    // The name of the mmap file of this kind in the storage directory.
+    pub(crate) fn file_name(&self) -> &'static str {
+        match self {
+            OffsetKind::ThinStateDiff => "thin_state_diff.dat",
+            OffsetKind::ContractClass => "contract_class.dat",
+            OffsetKind::Casm => "casm.dat",
+            OffsetKind::DeprecatedContractClass => "deprecated_contract_class.dat",
+            OffsetKind::TransactionOutput => "transaction_output.dat",
+            OffsetKind::Transaction => "transaction.dat",
+        }
+    }
+}
+
 /// A storage query. Used for benchmarking in the storage_benchmark binary.
 // TODO(dvir): add more queries (especially get casm).
 // TODO(dvir): consider move this, maybe to test_utils.
@@ -24,8 +24,10 @@ use starknet_api::block::{
     BlockSignature,
     BlockStatus,
     BlockTimestamp,
+    CommitCertificate,
     GasPrice,
     GasPricePerToken,
+    SignedPrecommit,
     StarknetVersion,
 };
 use starknet_api::contract_class::EntryPointType;
@@ -186,6 +188,11 @@ auto_storage_serde! {
     }
     pub struct BlockTimestamp(pub u64);
     pub struct Calldata(pub Arc<Vec<Felt>>);
+    pub struct CommitCertificate {
+        pub round: u32,
+        pub block_hash: BlockHash,
+        pub precommits: Vec<SignedPrecommit>,
+    }
     pub struct CompiledClassHash(pub StarkHash);
     pub struct ClassHash(pub StarkHash);
     pub struct ContractAddressSalt(pub StarkHash);
@@ -329,6 +336,8 @@ auto_storage_serde! {
         BaseLayerBlock = 6,
         ClassManagerBlock = 7,
         CompilerBackwardCompatibility = 8,
+        StatePruning = 9,
+        EventKeysIndexStart = 10,
     }
     pub struct MessageToL1 {
         pub to_address: EthAddress,
@@ -381,6 +390,10 @@ auto_storage_serde! {
         pub r: Felt,
         pub s: Felt,
     }
+    pub struct SignedPrecommit {
+        pub voter: ContractAddress,
+        pub signature: Signature,
+    }
     pub struct StructAbiEntry {
         pub members: Vec<StructMember>,
         pub name: String,
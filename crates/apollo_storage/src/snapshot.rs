@@ -0,0 +1,403 @@
+//! Export and import of storage snapshots, for bootstrapping a node without syncing from genesis.
+//!
+//! A snapshot is a directory with a file for each database table, a copy of each mmap file up to
+//! its offset, and a manifest ([`SnapshotManifest`]) with the storage version, the markers and the
+//! checksums of the files.
+//!
+//! [`export_snapshot`] reads the storage in a single read transaction, so the snapshot is
+//! consistent even if the storage is written meanwhile. [`import_snapshot`] creates a new storage
+//! from a snapshot, after verifying the checksums and the compatibility of the storage version.
+//! Since the snapshot may come from an untrusted source, the checksums only detect corrupted
+//! files, and the imported storage is verified by recomputing the hashes of its header chain up to
+//! a block hash from a trusted source, and the state diff commitments of its state diffs.
+
+#[cfg(test)]
+#[path = "snapshot_test.rs"]
+mod snapshot_test;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use integer_encoding::{VarIntReader, VarIntWriter};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use starknet_api::block::{BlockHash, BlockHeader, BlockNumber};
+use starknet_api::block_hash::block_hash_calculator::{
+    calculate_block_hash,
+    concat_counts,
+    BlockHeaderCommitments,
+};
+use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
+use starknet_api::core::ChainId;
+use tracing::info;
+
+use crate::db::table_types::{DbCursorTrait, Table};
+use crate::db::{DbConfig, DbError, RO};
+use crate::header::HeaderStorageReader;
+use crate::state::StateStorageReader;
+use crate::version::{StorageVersionError, Version, VersionStorageReader};
+use crate::{
+    open_storage,
+    OffsetKind,
+    StorageConfig,
+    StorageError,
+    StorageReader,
+    StorageResult,
+    StorageScope,
+    StorageTxn,
+    StorageWriter,
+    Tables,
+    STORAGE_VERSION_BLOCKS,
+    STORAGE_VERSION_STATE,
+};
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+const MMAP_FILE_KINDS: [OffsetKind; 6] = [
+    OffsetKind::ThinStateDiff,
+    OffsetKind::ContractClass,
+    OffsetKind::Casm,
+    OffsetKind::DeprecatedContractClass,
+    OffsetKind::TransactionOutput,
+    OffsetKind::Transaction,
+];
+
+/// Errors that may be returned when exporting or importing a snapshot.
+#[allow(missing_docs)]
+#[derive(thiserror::Error, Debug)]
+pub enum SnapshotError {
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
+    #[error(transparent)]
+    InnerError(#[from] DbError),
+    #[error(transparent)]
+    IOError(#[from] io::Error),
+    #[error(transparent)]
+    SerdeError(#[from] serde_json::Error),
+    #[error("The directory {0:?} is not empty.")]
+    DirectoryNotEmpty(PathBuf),
+    #[error(
+        "The snapshot is of chain {snapshot_chain_id}, but the storage is of chain {chain_id}."
+    )]
+    ChainIdMismatch { snapshot_chain_id: ChainId, chain_id: ChainId },
+    #[error("The checksum of the snapshot file {file_name} is {actual}, expected {expected}.")]
+    ChecksumMismatch { file_name: String, expected: String, actual: String },
+    #[error("The header chain of the imported storage is broken at block {block_number}.")]
+    InvalidHeaderChain { block_number: BlockNumber },
+    #[error(
+        "The hash of block {block_number} can't be computed, since its header is missing \
+         commitments or its Starknet version precedes the supported block hash versions."
+    )]
+    UnverifiableBlockHash { block_number: BlockNumber },
+    #[error("The state diff of block {block_number} doesn't match its state diff commitment.")]
+    InvalidStateDiff { block_number: BlockNumber },
+}
+
+/// The description of a snapshot, saved in the snapshot directory.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotManifest {
+    /// The chain of the exported storage.
+    pub chain_id: ChainId,
+    /// The scope of the exported storage.
+    pub scope: StorageScope,
+    /// The state version of the exported storage.
+    pub state_version: Version,
+    /// The blocks version of the exported storage. Exists only for full archive storages.
+    pub blocks_version: Option<Version>,
+    /// The markers of the exported storage, by their names.
+    pub markers: BTreeMap<String, BlockNumber>,
+    /// The hash of the last header in the snapshot, if there are headers. Not used for verifying
+    /// the snapshot, since it's part of it.
+    pub last_block_hash: Option<BlockHash>,
+    /// The table files, by the table names.
+    pub tables: BTreeMap<String, SnapshotTableFile>,
+    /// The mmap files, by the file names.
+    pub mmap_files: BTreeMap<String, SnapshotMmapFile>,
+}
+
+/// A file with the entries of a table.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotTableFile {
+    /// The number of entries in the table.
+    pub entries: u64,
+    /// The SHA-256 of the file, as a hex string.
+    pub sha256: String,
+}
+
+/// A copy of an mmap file, up to its offset.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotMmapFile {
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The SHA-256 of the file, as a hex string.
+    pub sha256: String,
+}
+
+/// Exports a snapshot of the storage to the given directory, which must be empty or not exist.
+/// `db_config` is the config the storage was opened with.
+pub fn export_snapshot(
+    reader: &StorageReader,
+    db_config: &DbConfig,
+    snapshot_dir: &Path,
+) -> Result<SnapshotManifest, SnapshotError> {
+    create_empty_dir(snapshot_dir)?;
+    info!("Exporting a storage snapshot to {}.", snapshot_dir.display());
+    let txn = reader.begin_ro_txn()?;
+
+    let mut tables = BTreeMap::new();
+    for table_name in Tables::field_names() {
+        let path = snapshot_dir.join(table_file_name(table_name));
+        let mut file = BufWriter::new(File::create(&path)?);
+        let mut entries = 0;
+        txn.txn.for_each_raw_entry(table_name, |key, value| -> Result<(), SnapshotError> {
+            write_bytes(&mut file, key)?;
+            write_bytes(&mut file, value)?;
+            entries += 1;
+            Ok(())
+        })?;
+        file.flush()?;
+        tables
+            .insert(table_name.to_string(), SnapshotTableFile { entries, sha256: sha256(&path)? });
+    }
+
+    let file_offsets_table = txn.open_table(&txn.tables.file_offsets)?;
+    let mut mmap_files = BTreeMap::new();
+    for offset_kind in MMAP_FILE_KINDS {
+        let offset = file_offsets_table.get(&txn.txn, &offset_kind)?.unwrap_or_default();
+        let file_name = offset_kind.file_name();
+        let path = snapshot_dir.join(file_name);
+        // The data before the offset isn't modified, so it can be copied while the storage is
+        // written.
+        let mut storage_file = File::open(db_config.path().join(file_name))?
+            .take(u64::try_from(offset).expect("usize should fit in u64"));
+        let size = io::copy(&mut storage_file, &mut File::create(&path)?)?;
+        mmap_files.insert(file_name.to_string(), SnapshotMmapFile { size, sha256: sha256(&path)? });
+    }
+
+    let last_block_hash = match txn.get_header_marker()?.prev() {
+        Some(last_block_number) => {
+            txn.get_block_header(last_block_number)?.map(|header| header.block_hash)
+        }
+        None => None,
+    };
+    let manifest = SnapshotManifest {
+        chain_id: db_config.chain_id.clone(),
+        scope: txn.scope,
+        state_version: txn.get_state_version()?.expect("Storage should be initialized."),
+        blocks_version: txn.get_blocks_version()?,
+        markers: get_markers(&txn)?,
+        last_block_hash,
+        tables,
+        mmap_files,
+    };
+    let manifest_file = File::create(snapshot_dir.join(MANIFEST_FILE_NAME))?;
+    serde_json::to_writer_pretty(manifest_file, &manifest)?;
+    info!("Exported a storage snapshot with the markers {:?}.", manifest.markers);
+    Ok(manifest)
+}
+
+/// Creates a storage from the snapshot in the given directory and opens it. The storage directory
+/// must be empty or not exist. `expected_block_hash` is the hash of the last block of the snapshot,
+/// taken from a trusted source.
+pub fn import_snapshot(
+    snapshot_dir: &Path,
+    storage_config: StorageConfig,
+    expected_block_hash: BlockHash,
+) -> Result<(StorageReader, StorageWriter), SnapshotError> {
+    let manifest_file = BufReader::new(File::open(snapshot_dir.join(MANIFEST_FILE_NAME))?);
+    let manifest: SnapshotManifest = serde_json::from_reader(manifest_file)?;
+    if manifest.chain_id != storage_config.db_config.chain_id {
+        return Err(SnapshotError::ChainIdMismatch {
+            snapshot_chain_id: manifest.chain_id,
+            chain_id: storage_config.db_config.chain_id,
+        });
+    }
+    verify_snapshot_version(&manifest, storage_config.scope)?;
+    verify_checksums(snapshot_dir, &manifest)?;
+
+    let db_path = storage_config.db_config.path();
+    create_empty_dir(&db_path)?;
+    info!("Importing the storage snapshot from {}.", snapshot_dir.display());
+    for file_name in manifest.mmap_files.keys() {
+        fs::copy(snapshot_dir.join(file_name), db_path.join(file_name))?;
+    }
+    // The tables are written to a new storage, without the background tasks that write to it. The
+    // storage is then reopened, in order to load the offsets of the mmap files from the imported
+    // tables.
+    {
+        let (_, mut writer) = open_storage(StorageConfig {
+            state_history_length: None,
+            index_event_keys: false,
+            ..storage_config.clone()
+        })?;
+        let txn = writer.begin_rw_txn()?;
+        for (table_name, table_file) in &manifest.tables {
+            let mut file =
+                BufReader::new(File::open(snapshot_dir.join(table_file_name(table_name)))?);
+            for _ in 0..table_file.entries {
+                let key = read_bytes(&mut file)?;
+                let value = read_bytes(&mut file)?;
+                txn.txn.put_raw_entry(table_name, &key, &value)?;
+            }
+        }
+        txn.commit()?;
+    }
+
+    let (reader, writer) = open_storage(storage_config)?;
+    verify_header_chain(&reader.begin_ro_txn()?, expected_block_hash)?;
+    info!("Imported a storage snapshot with the markers {:?}.", manifest.markers);
+    Ok((reader, writer))
+}
+
+// Verifies that opening the imported storage won't fail due to its version, as in
+// `verify_storage_version`, before importing it.
+fn verify_snapshot_version(manifest: &SnapshotManifest, scope: StorageScope) -> StorageResult<()> {
+    verify_version(&STORAGE_VERSION_STATE, &manifest.state_version)?;
+    match (scope, &manifest.blocks_version) {
+        (StorageScope::FullArchive, Some(blocks_version)) => {
+            verify_version(&STORAGE_VERSION_BLOCKS, blocks_version)
+        }
+        (StorageScope::FullArchive, None) => Err(StorageError::StorageVersionInconsistency(
+            StorageVersionError::InconsistentStorageScope,
+        )),
+        (StorageScope::StateOnly, _) => Ok(()),
+    }
+}
+
+// A storage with a lower minor version is migrated when it's opened.
+fn verify_version(crate_version: &Version, snapshot_version: &Version) -> StorageResult<()> {
+    if crate_version.major != snapshot_version.major || crate_version.minor < snapshot_version.minor
+    {
+        return Err(StorageError::StorageVersionInconsistency(
+            StorageVersionError::InconsistentStorageVersion {
+                crate_version: crate_version.clone(),
+                storage_version: snapshot_version.clone(),
+            },
+        ));
+    }
+    Ok(())
+}
+
+fn verify_checksums(snapshot_dir: &Path, manifest: &SnapshotManifest) -> Result<(), SnapshotError> {
+    let table_files = manifest
+        .tables
+        .iter()
+        .map(|(table_name, table_file)| (table_file_name(table_name), &table_file.sha256));
+    let mmap_files = manifest
+        .mmap_files
+        .iter()
+        .map(|(file_name, mmap_file)| (file_name.clone(), &mmap_file.sha256));
+    for (file_name, expected) in table_files.chain(mmap_files) {
+        let actual = sha256(&snapshot_dir.join(&file_name))?;
+        if actual != *expected {
+            return Err(SnapshotError::ChecksumMismatch {
+                file_name,
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Verifies the header chain backwards from the trusted hash of the last block: the hash of each
+// header is recomputed and compared to the hash its child points to. The state diff of each block
+// is verified against the state diff commitment of its header. Blocks whose hash can't be
+// recomputed, such as blocks of Starknet versions before 0.13.2, fail the verification.
+fn verify_header_chain(
+    txn: &StorageTxn<'_, RO>,
+    expected_block_hash: BlockHash,
+) -> Result<(), SnapshotError> {
+    let header_marker = txn.get_header_marker()?;
+    if header_marker == BlockNumber(0) {
+        return Err(SnapshotError::InvalidHeaderChain { block_number: header_marker });
+    }
+    let state_marker = txn.get_state_marker()?;
+    let mut expected_block_hash = expected_block_hash;
+    for block_number in (0..header_marker.0).rev().map(BlockNumber) {
+        let header = txn
+            .get_block_header(block_number)?
+            .ok_or(SnapshotError::InvalidHeaderChain { block_number })?;
+        if header.block_hash != expected_block_hash
+            || calculate_header_hash(&header)? != expected_block_hash
+        {
+            return Err(SnapshotError::InvalidHeaderChain { block_number });
+        }
+        // State only storages don't keep the state diffs.
+        if txn.scope == StorageScope::FullArchive && block_number < state_marker {
+            let state_diff = txn
+                .get_state_diff(block_number)?
+                .ok_or(SnapshotError::InvalidStateDiff { block_number })?;
+            if header.state_diff_commitment != Some(calculate_state_diff_hash(&state_diff)) {
+                return Err(SnapshotError::InvalidStateDiff { block_number });
+            }
+        }
+        expected_block_hash = header.block_header_without_hash.parent_hash;
+    }
+    Ok(())
+}
+
+// Recomputes the hash of a header from its fields and commitments.
+fn calculate_header_hash(header: &BlockHeader) -> Result<BlockHash, SnapshotError> {
+    let unverifiable = || SnapshotError::UnverifiableBlockHash {
+        block_number: header.block_header_without_hash.block_number,
+    };
+    let commitments = BlockHeaderCommitments {
+        transaction_commitment: header.transaction_commitment.ok_or_else(unverifiable)?,
+        event_commitment: header.event_commitment.ok_or_else(unverifiable)?,
+        receipt_commitment: header.receipt_commitment.ok_or_else(unverifiable)?,
+        state_diff_commitment: header.state_diff_commitment.ok_or_else(unverifiable)?,
+        concatenated_counts: concat_counts(
+            header.n_transactions,
+            header.n_events,
+            header.state_diff_length.ok_or_else(unverifiable)?,
+            header.block_header_without_hash.l1_da_mode,
+        ),
+    };
+    calculate_block_hash(header.block_header_without_hash.clone(), commitments)
+        .map_err(|_| unverifiable())
+}
+
+fn get_markers(txn: &StorageTxn<'_, RO>) -> StorageResult<BTreeMap<String, BlockNumber>> {
+    let markers_table = txn.open_table(&txn.tables.markers)?;
+    let mut cursor = markers_table.cursor(&txn.txn)?;
+    let mut markers = BTreeMap::new();
+    while let Some((marker_kind, block_number)) = cursor.next()? {
+        markers.insert(format!("{marker_kind:?}"), block_number);
+    }
+    Ok(markers)
+}
+
+// Creates the directory if it doesn't exist, and verifies that it's empty.
+fn create_empty_dir(path: &Path) -> Result<(), SnapshotError> {
+    fs::create_dir_all(path)?;
+    if fs::read_dir(path)?.next().is_some() {
+        return Err(SnapshotError::DirectoryNotEmpty(path.to_path_buf()));
+    }
+    Ok(())
+}
+
+fn table_file_name(table_name: &str) -> String {
+    format!("{table_name}.table")
+}
+
+// The entries of a table file are written as length-prefixed byte arrays.
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    writer.write_varint(bytes.len())?;
+    writer.write_all(bytes)
+}
+
+fn read_bytes(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let len: usize = reader.read_varint()?;
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn sha256(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
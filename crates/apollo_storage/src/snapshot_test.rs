@@ -0,0 +1,256 @@
+use std::fs;
+
+use assert_matches::assert_matches;
+use indexmap::IndexMap;
+use pretty_assertions::assert_eq;
+use starknet_api::block::{
+    BlockHash,
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockNumber,
+    BlockTimestamp,
+    StarknetVersion,
+};
+use starknet_api::block_hash::block_hash_calculator::{
+    calculate_block_hash,
+    concat_counts,
+    BlockHeaderCommitments,
+};
+use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
+use starknet_api::core::{EventCommitment, Nonce, ReceiptCommitment, TransactionCommitment};
+use starknet_api::state::{StateNumber, ThinStateDiff};
+use starknet_api::{contract_address, storage_key};
+use starknet_types_core::felt::Felt;
+use tempfile::tempdir;
+
+use crate::header::{HeaderStorageReader, HeaderStorageWriter};
+use crate::snapshot::{export_snapshot, import_snapshot, SnapshotError, MANIFEST_FILE_NAME};
+use crate::state::{StateStorageReader, StateStorageWriter};
+use crate::test_utils::{get_test_config, get_test_storage_with_config_by_scope};
+use crate::{StorageError, StorageReader, StorageScope, StorageWriter, STORAGE_VERSION_STATE};
+
+const N_BLOCKS: u64 = 3;
+
+// Returns the headers of a chain of `n_blocks` blocks with the state diffs of `state_diff`, whose
+// hashes are computed from their fields.
+fn headers(n_blocks: u64) -> Vec<BlockHeader> {
+    let mut parent_hash = BlockHash::default();
+    (0..n_blocks)
+        .map(|block_number| {
+            let block_header_without_hash = BlockHeaderWithoutHash {
+                block_number: BlockNumber(block_number),
+                parent_hash,
+                starknet_version: StarknetVersion::V0_13_2,
+                ..Default::default()
+            };
+            let state_diff = state_diff(block_number);
+            let state_diff_commitment = calculate_state_diff_hash(&state_diff);
+            let commitments = BlockHeaderCommitments {
+                state_diff_commitment,
+                concatenated_counts: concat_counts(
+                    0,
+                    0,
+                    state_diff.len(),
+                    block_header_without_hash.l1_da_mode,
+                ),
+                ..Default::default()
+            };
+            let block_hash =
+                calculate_block_hash(block_header_without_hash.clone(), commitments).unwrap();
+            parent_hash = block_hash;
+            BlockHeader {
+                block_hash,
+                block_header_without_hash,
+                state_diff_commitment: Some(state_diff_commitment),
+                state_diff_length: Some(state_diff.len()),
+                transaction_commitment: Some(TransactionCommitment::default()),
+                event_commitment: Some(EventCommitment::default()),
+                receipt_commitment: Some(ReceiptCommitment::default()),
+                n_transactions: 0,
+                n_events: 0,
+            }
+        })
+        .collect()
+}
+
+fn state_diff(block_number: u64) -> ThinStateDiff {
+    let contract = contract_address!("0x11");
+    ThinStateDiff {
+        storage_diffs: IndexMap::from([(
+            contract,
+            IndexMap::from([(storage_key!("0x1"), Felt::from(block_number))]),
+        )]),
+        nonces: IndexMap::from([(contract, Nonce(Felt::from(block_number)))]),
+        ..Default::default()
+    }
+}
+
+// Writes the blocks with the given headers and state diffs to the storage.
+fn write_blocks(writer: &mut StorageWriter, blocks: Vec<(BlockHeader, ThinStateDiff)>) {
+    for (header, state_diff) in blocks {
+        let block_number = header.block_header_without_hash.block_number;
+        writer
+            .begin_rw_txn()
+            .unwrap()
+            .append_header(block_number, &header)
+            .unwrap()
+            .append_state_diff(block_number, state_diff)
+            .unwrap()
+            .commit()
+            .unwrap();
+    }
+}
+
+// Exports a snapshot of the given blocks, and returns the result of importing it with the hash of
+// the last block.
+fn export_and_import_blocks(
+    blocks: Vec<(BlockHeader, ThinStateDiff)>,
+) -> Result<(StorageReader, StorageWriter), SnapshotError> {
+    let ((reader, mut writer), config, _temp_dir) =
+        get_test_storage_with_config_by_scope(StorageScope::FullArchive);
+    let last_block_hash = blocks.last().unwrap().0.block_hash;
+    write_blocks(&mut writer, blocks);
+    let snapshot_dir = tempdir().unwrap();
+    export_snapshot(&reader, &config.db_config, snapshot_dir.path()).unwrap();
+
+    let (import_config, _import_temp_dir) = get_test_config(Some(StorageScope::FullArchive));
+    import_snapshot(snapshot_dir.path(), import_config, last_block_hash)
+}
+
+fn blocks(n_blocks: u64) -> Vec<(BlockHeader, ThinStateDiff)> {
+    headers(n_blocks).into_iter().zip((0..n_blocks).map(state_diff)).collect()
+}
+
+#[test]
+fn export_and_import_snapshot() {
+    let ((reader, mut writer), config, _temp_dir) =
+        get_test_storage_with_config_by_scope(StorageScope::FullArchive);
+    let mut blocks = blocks(N_BLOCKS + 1);
+    let block_after_export = blocks.pop().unwrap();
+    write_blocks(&mut writer, blocks);
+
+    let snapshot_dir = tempdir().unwrap();
+    let manifest = export_snapshot(&reader, &config.db_config, snapshot_dir.path()).unwrap();
+    let last_block_hash = headers(N_BLOCKS)[usize::try_from(N_BLOCKS - 1).unwrap()].block_hash;
+    assert_eq!(manifest.markers["Header"], BlockNumber(N_BLOCKS));
+    assert_eq!(manifest.last_block_hash, Some(last_block_hash));
+    // Data written after the export isn't in the snapshot.
+    write_blocks(&mut writer, vec![block_after_export]);
+
+    let (import_config, _import_temp_dir) = get_test_config(Some(StorageScope::FullArchive));
+    let (imported_reader, _) =
+        import_snapshot(snapshot_dir.path(), import_config, last_block_hash).unwrap();
+    let txn = imported_reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(N_BLOCKS));
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(N_BLOCKS));
+    for (block_number, header) in (0..N_BLOCKS).zip(headers(N_BLOCKS)) {
+        assert_eq!(txn.get_block_header(BlockNumber(block_number)).unwrap(), Some(header));
+        assert_eq!(
+            txn.get_state_diff(BlockNumber(block_number)).unwrap(),
+            Some(state_diff(block_number))
+        );
+    }
+    let state_number = StateNumber::unchecked_right_after_block(BlockNumber(N_BLOCKS - 1));
+    assert_eq!(
+        txn.get_state_reader()
+            .unwrap()
+            .get_storage_at(state_number, &contract_address!("0x11"), &storage_key!("0x1"))
+            .unwrap(),
+        Felt::from(N_BLOCKS - 1)
+    );
+}
+
+#[test]
+fn import_corrupted_snapshot() {
+    let ((reader, mut writer), config, _temp_dir) =
+        get_test_storage_with_config_by_scope(StorageScope::FullArchive);
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(BlockNumber(0), state_diff(0))
+        .unwrap()
+        .commit()
+        .unwrap();
+    let snapshot_dir = tempdir().unwrap();
+    export_snapshot(&reader, &config.db_config, snapshot_dir.path()).unwrap();
+
+    let thin_state_diff_file = snapshot_dir.path().join("thin_state_diff.dat");
+    let mut bytes = fs::read(&thin_state_diff_file).unwrap();
+    bytes[0] ^= 1;
+    fs::write(&thin_state_diff_file, bytes).unwrap();
+
+    let (import_config, _import_temp_dir) = get_test_config(Some(StorageScope::FullArchive));
+    assert_matches!(
+        import_snapshot(snapshot_dir.path(), import_config, BlockHash::default()),
+        Err(SnapshotError::ChecksumMismatch { file_name, .. })
+        if file_name == "thin_state_diff.dat"
+    );
+}
+
+#[test]
+fn import_snapshot_of_incompatible_version() {
+    let ((reader, _), config, _temp_dir) =
+        get_test_storage_with_config_by_scope(StorageScope::FullArchive);
+    let snapshot_dir = tempdir().unwrap();
+    export_snapshot(&reader, &config.db_config, snapshot_dir.path()).unwrap();
+
+    let manifest_path = snapshot_dir.path().join(MANIFEST_FILE_NAME);
+    let mut manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(&manifest_path).unwrap()).unwrap();
+    manifest["state_version"]["major"] = (STORAGE_VERSION_STATE.major + 1).into();
+    fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+    let (import_config, _import_temp_dir) = get_test_config(Some(StorageScope::FullArchive));
+    assert_matches!(
+        import_snapshot(snapshot_dir.path(), import_config, BlockHash::default()),
+        Err(SnapshotError::StorageError(StorageError::StorageVersionInconsistency(_)))
+    );
+}
+
+#[test]
+fn import_snapshot_with_untrusted_block_hash() {
+    let ((reader, mut writer), config, _temp_dir) =
+        get_test_storage_with_config_by_scope(StorageScope::FullArchive);
+    write_blocks(&mut writer, blocks(N_BLOCKS));
+    let snapshot_dir = tempdir().unwrap();
+    export_snapshot(&reader, &config.db_config, snapshot_dir.path()).unwrap();
+
+    let (import_config, _import_temp_dir) = get_test_config(Some(StorageScope::FullArchive));
+    assert_matches!(
+        import_snapshot(snapshot_dir.path(), import_config, BlockHash(Felt::ONE)),
+        Err(SnapshotError::InvalidHeaderChain { block_number })
+        if block_number == BlockNumber(N_BLOCKS - 1)
+    );
+}
+
+#[test]
+fn import_snapshot_with_tampered_header() {
+    // The header is modified without changing its hash.
+    let mut blocks = blocks(N_BLOCKS);
+    blocks[1].0.block_header_without_hash.timestamp = BlockTimestamp(1);
+    assert_matches!(
+        export_and_import_blocks(blocks),
+        Err(SnapshotError::InvalidHeaderChain { block_number }) if block_number == BlockNumber(1)
+    );
+}
+
+#[test]
+fn import_snapshot_with_tampered_state_diff() {
+    let mut blocks = blocks(N_BLOCKS);
+    blocks[1].1 = state_diff(N_BLOCKS);
+    assert_matches!(
+        export_and_import_blocks(blocks),
+        Err(SnapshotError::InvalidStateDiff { block_number }) if block_number == BlockNumber(1)
+    );
+}
+
+#[test]
+fn import_snapshot_with_unverifiable_block_hash() {
+    let mut blocks = blocks(N_BLOCKS);
+    blocks[1].0.transaction_commitment = None;
+    assert_matches!(
+        export_and_import_blocks(blocks),
+        Err(SnapshotError::UnverifiableBlockHash { block_number })
+        if block_number == BlockNumber(1)
+    );
+}
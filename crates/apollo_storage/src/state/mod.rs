@@ -51,6 +51,7 @@
 
 #[doc(hidden)]
 pub mod data;
+pub mod pruning;
 #[cfg(test)]
 mod state_test;
 
@@ -73,6 +74,7 @@ use crate::db::{DbTransaction, TableHandle, TransactionKind, RW};
 use crate::document_calls::{add_query, StorageQuery};
 use crate::mmap_file::LocationInFile;
 use crate::state::data::IndexedDeprecatedContractClass;
+use crate::state::pruning::verify_state_not_pruned;
 use crate::{
     FileHandlers,
     MarkerKind,
@@ -164,6 +166,7 @@ where
     ) -> StorageResult<Self>;
 
     /// Removes a state diff from the storage and returns the removed data.
+    /// Fails if the state history before the block was pruned.
     fn revert_state_diff(
         self,
         block_number: BlockNumber,
@@ -256,6 +259,9 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
     ///
     /// # Errors
     /// Returns [`StorageError`] if there was an error searching the table.
+    ///
+    /// Returns [`StorageError::StatePruned`] if the state history at the given state number was
+    /// pruned.
     pub fn get_class_hash_at(
         &self,
         state_number: StateNumber,
@@ -264,6 +270,7 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
         // TODO(dvir): create an attribute instead of this.
         #[cfg(feature = "document_calls")]
         add_query(StorageQuery::GetClassHashAt(state_number, *address));
+        verify_state_not_pruned(self.txn, &self.markers_table, state_number)?;
 
         let first_irrelevant_block: BlockNumber = state_number.block_after();
         let db_key = (*address, first_irrelevant_block);
@@ -287,6 +294,9 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
     ///
     /// # Errors
     /// Returns [`StorageError`] if there was an error searching the table.
+    ///
+    /// Returns [`StorageError::StatePruned`] if the state history at the given state number was
+    /// pruned.
     pub fn get_nonce_at(
         &self,
         state_number: StateNumber,
@@ -294,6 +304,7 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
     ) -> StorageResult<Option<Nonce>> {
         #[cfg(feature = "document_calls")]
         add_query(StorageQuery::GetNonceAt(state_number, *address));
+        verify_state_not_pruned(self.txn, &self.markers_table, state_number)?;
 
         // State diff updates are indexed by the block_number at which they occurred.
         let block_number: BlockNumber = state_number.block_after();
@@ -334,6 +345,9 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
     ///
     /// # Errors
     /// Returns [`StorageError`] if there was an error searching the table.
+    ///
+    /// Returns [`StorageError::StatePruned`] if the state history at the given state number was
+    /// pruned.
     pub fn get_storage_at(
         &self,
         state_number: StateNumber,
@@ -342,6 +356,7 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
     ) -> StorageResult<Felt> {
         #[cfg(feature = "document_calls")]
         add_query(StorageQuery::GetStorageAt(state_number, *address, *key));
+        verify_state_not_pruned(self.txn, &self.markers_table, state_number)?;
 
         // The updates to the storage key are indexed by the block_number at which they occurred.
         let first_irrelevant_block: BlockNumber = state_number.block_after();
@@ -574,6 +589,8 @@ impl StateStorageWriter for StorageTxn<'_, RW> {
             );
             return Ok((self, None));
         };
+        // Reverting the block requires the state before it.
+        verify_state_not_pruned(&self.txn, &markers_table, StateNumber(block_number))?;
 
         let thin_state_diff = self
             .get_state_diff(block_number)?
@@ -0,0 +1,172 @@
+//! Interface for pruning the history of the state.
+//!
+//! The contract storage, nonces and deployed contracts tables keep an entry for every block in
+//! which a value was written. Pruning the history before a block deletes every entry that was
+//! overwritten before that block, leaving for each key only the latest value. The state at the
+//! pruned blocks can no longer be queried, see [`StorageError::StatePruned`].
+//!
+//! Import [`StatePruningStorageReader`] and [`StatePruningStorageWriter`] to read the pruning
+//! marker and to prune the state history using a [`StorageTxn`]. When
+//! [`StorageConfig::state_history_length`](crate::StorageConfig::state_history_length) is set,
+//! the storage prunes the history in a background thread.
+
+#[cfg(test)]
+#[path = "pruning_test.rs"]
+mod pruning_test;
+
+use std::thread;
+use std::time::Duration;
+
+use starknet_api::block::BlockNumber;
+use starknet_api::state::StateNumber;
+use tracing::{debug, error};
+
+use crate::db::serialization::ValueSerde;
+use crate::db::table_types::{DbCursorTrait, Table};
+use crate::db::{DbTransaction, TransactionKind, RW};
+use crate::state::StateStorageReader;
+use crate::{MarkerKind, MarkersTable, StorageError, StorageResult, StorageTxn, StorageWriter};
+
+// The time to wait before checking again for blocks to prune, once the pruning caught up.
+const STATE_PRUNING_INTERVAL: Duration = Duration::from_secs(10);
+// Limits the size of the write transactions of the pruner, so they won't block the writer for
+// long.
+const MAX_BLOCKS_TO_PRUNE_PER_TXN: u64 = 100;
+
+/// Interface for reading the state pruning marker.
+pub trait StatePruningStorageReader {
+    /// The state pruning marker is the first block whose state history is kept. Querying the
+    /// state before this block returns [`StorageError::StatePruned`].
+    fn get_state_pruning_marker(&self) -> StorageResult<BlockNumber>;
+}
+
+/// Interface for pruning the state history.
+pub trait StatePruningStorageWriter
+where
+    Self: Sized,
+{
+    /// Deletes the state history before the given block. For each key, the last value written
+    /// before the block is kept, so the state at the block and after it can still be queried.
+    /// Blocks whose state diff doesn't exist yet are not pruned.
+    fn prune_state_history(self, block_number: BlockNumber) -> StorageResult<Self>;
+}
+
+impl<Mode: TransactionKind> StatePruningStorageReader for StorageTxn<'_, Mode> {
+    fn get_state_pruning_marker(&self) -> StorageResult<BlockNumber> {
+        let markers_table = self.open_table(&self.tables.markers)?;
+        Ok(markers_table.get(&self.txn, &MarkerKind::StatePruning)?.unwrap_or_default())
+    }
+}
+
+impl StatePruningStorageWriter for StorageTxn<'_, RW> {
+    fn prune_state_history(self, block_number: BlockNumber) -> StorageResult<Self> {
+        let markers_table = self.open_table(&self.tables.markers)?;
+        let deployed_contracts_table = self.open_table(&self.tables.deployed_contracts)?;
+        let nonces_table = self.open_table(&self.tables.nonces)?;
+        let storage_table = self.open_table(&self.tables.contract_storage)?;
+
+        let pruning_marker = self.get_state_pruning_marker()?;
+        let prune_up_to = block_number.min(self.get_state_marker()?);
+        if prune_up_to <= pruning_marker {
+            return Ok(self);
+        }
+
+        for current_block in pruning_marker.iter_up_to(prune_up_to) {
+            let thin_state_diff = self
+                .get_state_diff(current_block)?
+                .unwrap_or_else(|| panic!("Missing state diff for block {current_block}."));
+            for address in thin_state_diff.deployed_contracts.keys() {
+                let mut cursor = deployed_contracts_table.cursor(&self.txn)?;
+                let versions = get_versions_before(&mut cursor, *address, current_block)?;
+                for version in versions {
+                    deployed_contracts_table.delete(&self.txn, &(*address, version))?;
+                }
+            }
+            for address in thin_state_diff.nonces.keys() {
+                let mut cursor = nonces_table.cursor(&self.txn)?;
+                let versions = get_versions_before(&mut cursor, *address, current_block)?;
+                for version in versions {
+                    nonces_table.delete(&self.txn, &(*address, version))?;
+                }
+            }
+            for (address, storage_diffs) in &thin_state_diff.storage_diffs {
+                for key in storage_diffs.keys() {
+                    let mut cursor = storage_table.cursor(&self.txn)?;
+                    let versions =
+                        get_versions_before(&mut cursor, (*address, *key), current_block)?;
+                    for version in versions {
+                        storage_table.delete(&self.txn, &((*address, *key), version))?;
+                    }
+                }
+            }
+        }
+        markers_table.upsert(&self.txn, &MarkerKind::StatePruning, &prune_up_to)?;
+        Ok(self)
+    }
+}
+
+// Returns the blocks before `block_number` in which a value was written to `sub_key`.
+fn get_versions_before<SubKey: Copy + PartialEq, V: ValueSerde>(
+    cursor: &mut impl DbCursorTrait<Key = (SubKey, BlockNumber), Value = V>,
+    sub_key: SubKey,
+    block_number: BlockNumber,
+) -> StorageResult<Vec<BlockNumber>> {
+    let mut versions = Vec::new();
+    let mut current = cursor.lower_bound(&(sub_key, BlockNumber(0)))?;
+    while let Some(((got_sub_key, got_block_number), _)) = current {
+        if got_sub_key != sub_key || got_block_number >= block_number {
+            break;
+        }
+        versions.push(got_block_number);
+        current = cursor.next()?;
+    }
+    Ok(versions)
+}
+
+// Spawns a thread that keeps the state history of the last `history_length` blocks and prunes the
+// rest. The given writer shares the database environment with the main writer; the database
+// serializes their write transactions.
+pub(crate) fn spawn_state_pruner(mut writer: StorageWriter, history_length: u64) {
+    thread::Builder::new()
+        .name("state_pruner".to_string())
+        .spawn(move || loop {
+            match prune_next_blocks(&mut writer, history_length) {
+                Ok(true) => thread::sleep(STATE_PRUNING_INTERVAL),
+                Ok(false) => {}
+                Err(err) => {
+                    error!("Failed to prune the state history: {err}.");
+                    thread::sleep(STATE_PRUNING_INTERVAL);
+                }
+            }
+        })
+        .expect("Failed to spawn the state pruner thread");
+}
+
+// Prunes up to MAX_BLOCKS_TO_PRUNE_PER_TXN blocks. Returns whether the pruning caught up with the
+// state marker.
+fn prune_next_blocks(writer: &mut StorageWriter, history_length: u64) -> StorageResult<bool> {
+    let txn = writer.begin_rw_txn()?;
+    let pruning_target = BlockNumber(txn.get_state_marker()?.0.saturating_sub(history_length));
+    let pruning_marker = txn.get_state_pruning_marker()?;
+    if pruning_target <= pruning_marker {
+        return Ok(true);
+    }
+    let prune_up_to =
+        pruning_target.min(BlockNumber(pruning_marker.0 + MAX_BLOCKS_TO_PRUNE_PER_TXN));
+    debug!("Pruning the state history from block {pruning_marker} up to block {prune_up_to}.");
+    txn.prune_state_history(prune_up_to)?.commit()?;
+    Ok(prune_up_to == pruning_target)
+}
+
+// Returns an error if the history needed for reading the given state was pruned.
+pub(crate) fn verify_state_not_pruned<'env, Mode: TransactionKind>(
+    txn: &'env DbTransaction<'env, Mode>,
+    markers_table: &'env MarkersTable<'env>,
+    state_number: StateNumber,
+) -> StorageResult<()> {
+    let pruning_marker = markers_table.get(txn, &MarkerKind::StatePruning)?.unwrap_or_default();
+    if state_number.0 < pruning_marker {
+        return Err(StorageError::StatePruned { state_number, pruning_marker });
+    }
+    Ok(())
+}
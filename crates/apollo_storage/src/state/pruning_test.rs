@@ -0,0 +1,107 @@
+use assert_matches::assert_matches;
+use indexmap::IndexMap;
+use pretty_assertions::assert_eq;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::Nonce;
+use starknet_api::state::{StateNumber, ThinStateDiff};
+use starknet_api::{class_hash, contract_address, felt, storage_key};
+use starknet_types_core::felt::Felt;
+
+use crate::db::table_types::Table;
+use crate::state::pruning::{StatePruningStorageReader, StatePruningStorageWriter};
+use crate::state::{StateStorageReader, StateStorageWriter};
+use crate::test_utils::get_test_storage;
+use crate::StorageError;
+
+#[test]
+fn prune_state_history() {
+    let contract = contract_address!("0x11");
+    let class_0 = class_hash!("0x4");
+    let class_1 = class_hash!("0x5");
+    let key_0 = storage_key!("0x1001");
+    let key_1 = storage_key!("0x101");
+    let diff0 = ThinStateDiff {
+        deployed_contracts: IndexMap::from([(contract, class_0)]),
+        storage_diffs: IndexMap::from([(contract, IndexMap::from([(key_0, felt!("0x1"))]))]),
+        nonces: IndexMap::from([(contract, Nonce(Felt::ONE))]),
+        ..Default::default()
+    };
+    let diff1 = ThinStateDiff {
+        deployed_contracts: IndexMap::from([(contract, class_1)]),
+        storage_diffs: IndexMap::from([(contract, IndexMap::from([(key_0, felt!("0x2"))]))]),
+        nonces: IndexMap::from([(contract, Nonce(Felt::TWO))]),
+        ..Default::default()
+    };
+    let diff2 = ThinStateDiff {
+        storage_diffs: IndexMap::from([(contract, IndexMap::from([(key_1, felt!("0x3"))]))]),
+        ..Default::default()
+    };
+
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(BlockNumber(0), diff0)
+        .unwrap()
+        .append_state_diff(BlockNumber(1), diff1)
+        .unwrap()
+        .append_state_diff(BlockNumber(2), diff2)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(reader.begin_ro_txn().unwrap().get_state_pruning_marker().unwrap(), BlockNumber(0));
+
+    writer.begin_rw_txn().unwrap().prune_state_history(BlockNumber(2)).unwrap().commit().unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_state_pruning_marker().unwrap(), BlockNumber(2));
+    let storage_table = txn.open_table(&txn.tables.contract_storage).unwrap();
+    assert!(storage_table.get(&txn.txn, &((contract, key_0), BlockNumber(0))).unwrap().is_none());
+
+    let state_reader = txn.get_state_reader().unwrap();
+    let state_number = StateNumber::right_before_block(BlockNumber(2));
+    assert_eq!(state_reader.get_storage_at(state_number, &contract, &key_0).unwrap(), felt!("0x2"));
+    assert_eq!(state_reader.get_nonce_at(state_number, &contract).unwrap(), Some(Nonce(Felt::TWO)));
+    assert_eq!(state_reader.get_class_hash_at(state_number, &contract).unwrap(), Some(class_1));
+    let state_number = StateNumber::right_after_block(BlockNumber(2)).unwrap();
+    assert_eq!(state_reader.get_storage_at(state_number, &contract, &key_1).unwrap(), felt!("0x3"));
+
+    let pruned_state_number = StateNumber::right_before_block(BlockNumber(1));
+    assert_matches!(
+        state_reader.get_storage_at(pruned_state_number, &contract, &key_0),
+        Err(StorageError::StatePruned { state_number, pruning_marker: BlockNumber(2) })
+        if state_number == pruned_state_number
+    );
+    assert_matches!(
+        state_reader.get_nonce_at(pruned_state_number, &contract),
+        Err(StorageError::StatePruned { .. })
+    );
+    assert_matches!(
+        state_reader.get_class_hash_at(pruned_state_number, &contract),
+        Err(StorageError::StatePruned { .. })
+    );
+}
+
+#[test]
+fn revert_pruned_state_diff() {
+    let ((_, mut writer), _temp_dir) = get_test_storage();
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(BlockNumber(0), ThinStateDiff::default())
+        .unwrap()
+        .append_state_diff(BlockNumber(1), ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+    // Pruning beyond the state marker prunes only the existing blocks.
+    let txn = writer.begin_rw_txn().unwrap().prune_state_history(BlockNumber(10)).unwrap();
+    assert_eq!(txn.get_state_pruning_marker().unwrap(), BlockNumber(2));
+    txn.commit().unwrap();
+
+    // Reverting the last block requires the state before it.
+    assert_matches!(
+        writer.begin_rw_txn().unwrap().revert_state_diff(BlockNumber(1)),
+        Err(StorageError::StatePruned { .. })
+    );
+}
@@ -66,6 +66,8 @@ auto_impl_get_test_instance! {
         BaseLayerBlock = 6,
         ClassManagerBlock = 7,
         CompilerBackwardCompatibility = 8,
+        StatePruning = 9,
+        EventKeysIndexStart = 10,
     }
     pub enum OffsetKind {
         ThinStateDiff = 0,
@@ -24,6 +24,8 @@ fn build_storage_config(storage_scope: StorageScope, path_prefix: PathBuf) -> St
         },
         scope: storage_scope,
         mmap_file_config: get_mmap_file_test_config(),
+        state_history_length: None,
+        index_event_keys: false,
     }
 }
 
@@ -45,8 +45,10 @@ use starknet_api::block::{
     BlockSignature,
     BlockStatus,
     BlockTimestamp,
+    CommitCertificate,
     GasPrice,
     GasPricePerToken,
+    SignedPrecommit,
     StarknetVersion,
 };
 use starknet_api::consensus_transaction::ConsensusTransaction;
@@ -504,6 +506,11 @@ auto_impl_get_test_instance! {
 
     pub struct Calldata(pub Arc<Vec<Felt>>);
     pub struct ClassHash(pub StarkHash);
+    pub struct CommitCertificate {
+        pub round: u32,
+        pub block_hash: BlockHash,
+        pub precommits: Vec<SignedPrecommit>,
+    }
     pub struct CompiledClassHash(pub StarkHash);
     pub struct ContractAddressSalt(pub StarkHash);
     pub enum ConsensusTransaction {
@@ -845,6 +852,10 @@ auto_impl_get_test_instance! {
         pub r: Felt,
         pub s: Felt,
     }
+    pub struct SignedPrecommit {
+        pub voter: ContractAddress,
+        pub signature: Signature,
+    }
     pub struct StateDiff {
         pub deployed_contracts: IndexMap<ContractAddress, ClassHash>,
         pub storage_diffs: IndexMap<ContractAddress, IndexMap<StorageKey, Felt>>,
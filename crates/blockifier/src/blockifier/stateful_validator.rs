@@ -46,6 +46,13 @@ pub type StatefulValidatorResult<T> = Result<T, StatefulValidatorError>;
 pub trait StatefulValidatorTrait {
     #[allow(clippy::result_large_err)]
     fn validate(&mut self, account_tx: AccountTransaction) -> StatefulValidatorResult<()>;
+    /// Same as `validate`, and returns the receipt of the validation; [None] if `__validate__` was
+    /// skipped.
+    #[allow(clippy::result_large_err)]
+    fn validate_with_receipt(
+        &mut self,
+        account_tx: AccountTransaction,
+    ) -> StatefulValidatorResult<Option<TransactionReceipt>>;
     fn block_info(&self) -> &BlockInfo;
 }
 
@@ -55,6 +62,14 @@ impl<S: StateReader> StatefulValidatorTrait for StatefulValidator<S> {
         self.perform_validations(account_tx)
     }
 
+    #[allow(clippy::result_large_err)]
+    fn validate_with_receipt(
+        &mut self,
+        account_tx: AccountTransaction,
+    ) -> StatefulValidatorResult<Option<TransactionReceipt>> {
+        self.perform_validations_with_receipt(account_tx)
+    }
+
     fn block_info(&self) -> &BlockInfo {
         StatefulValidator::block_info(self)
     }
@@ -73,19 +88,30 @@ impl<S: StateReader> StatefulValidator<S> {
     }
 
     pub fn perform_validations(&mut self, tx: AccountTransaction) -> StatefulValidatorResult<()> {
+        self.perform_validations_with_receipt(tx)?;
+        Ok(())
+    }
+
+    /// Performs the validations of the transaction, and returns the receipt of the validation (of
+    /// the full execution, for deploy account and declare transactions); [None] if `__validate__`
+    /// was skipped.
+    pub fn perform_validations_with_receipt(
+        &mut self,
+        tx: AccountTransaction,
+    ) -> StatefulValidatorResult<Option<TransactionReceipt>> {
         // Deploy account transaction should be fully executed, since the constructor must run
         // before `__validate_deploy__`. The execution already includes all necessary validations,
         // so they are skipped here.
         // Declare transaction should also be fully executed - otherwise, if we only go through
         // the validate phase, we would miss the check that the class was not declared before.
         if let ApiTransaction::DeployAccount(_) | ApiTransaction::Declare(_) = tx.tx {
-            return self.execute(tx);
+            return Ok(Some(self.execute(tx)?));
         }
 
         let tx_context = Arc::new(self.tx_executor.block_context.to_tx_context(&tx));
         tx.perform_pre_validation_stage(self.state(), &tx_context)?;
         if !tx.execution_flags.validate {
-            return Ok(());
+            return Ok(None);
         }
 
         // `__validate__` call.
@@ -94,7 +120,7 @@ impl<S: StateReader> StatefulValidator<S> {
         // Post validations.
         PostValidationReport::verify(&tx_context, &actual_cost, tx.execution_flags.charge_fee)?;
 
-        Ok(())
+        Ok(Some(actual_cost))
     }
 
     pub fn block_info(&self) -> &BlockInfo {
@@ -105,9 +131,10 @@ impl<S: StateReader> StatefulValidator<S> {
         self.tx_executor.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR)
     }
 
-    fn execute(&mut self, tx: AccountTransaction) -> StatefulValidatorResult<()> {
-        self.tx_executor.execute(&Transaction::Account(tx))?;
-        Ok(())
+    fn execute(&mut self, tx: AccountTransaction) -> StatefulValidatorResult<TransactionReceipt> {
+        let (tx_execution_info, _state_diff) =
+            self.tx_executor.execute(&Transaction::Account(tx))?;
+        Ok(tx_execution_info.receipt)
     }
 
     fn validate(
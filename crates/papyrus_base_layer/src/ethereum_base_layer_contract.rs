@@ -5,12 +5,12 @@ use std::time::Duration;
 
 use alloy::dyn_abi::SolType;
 use alloy::eips::eip7840;
-use alloy::primitives::Address;
+use alloy::primitives::{Address, FixedBytes};
 use alloy::providers::{Provider, ProviderBuilder, RootProvider};
 use alloy::rpc::json_rpc::RpcError;
 use alloy::rpc::types::eth::Filter as EthEventFilter;
 use alloy::sol;
-use alloy::sol_types::sol_data;
+use alloy::sol_types::{sol_data, SolEvent};
 use alloy::transports::TransportErrorKind;
 use apollo_config::converters::deserialize_milliseconds_to_duration;
 use apollo_config::dumping::{ser_param, SerializeConfig};
@@ -19,6 +19,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockNumber};
 use starknet_api::hash::StarkHash;
+use starknet_api::transaction::L1HandlerTransaction;
 use starknet_api::StarknetApiError;
 use tokio::time::error::Elapsed;
 use tracing::{debug, error, instrument};
@@ -26,7 +27,14 @@ use url::Url;
 use validator::Validate;
 
 use crate::eth_events::parse_event;
-use crate::{BaseLayerContract, L1BlockHeader, L1BlockNumber, L1BlockReference, L1Event};
+use crate::{
+    BaseLayerContract,
+    EventData,
+    L1BlockHeader,
+    L1BlockNumber,
+    L1BlockReference,
+    L1Event,
+};
 
 pub type EthereumBaseLayerResult<T> = Result<T, EthereumBaseLayerError>;
 pub type EthereumContractAddress = Address;
@@ -66,6 +74,39 @@ impl EthereumBaseLayerContract {
             build_contract_instance(config.starknet_contract_address, current_node_url.clone());
         Self { contract, config }
     }
+
+    /// Returns the L1 handler transactions of the messages to L2 that the given L1 transaction
+    /// sent, in the order they were sent, or None if the L1 transaction wasn't found.
+    #[instrument(skip(self), err)]
+    pub async fn get_messages_to_l2(
+        &self,
+        l1_tx_hash: FixedBytes<32>,
+    ) -> EthereumBaseLayerResult<Option<Vec<L1HandlerTransaction>>> {
+        let receipt = tokio::time::timeout(
+            self.config.timeout_millis,
+            self.contract.provider().get_transaction_receipt(l1_tx_hash),
+        )
+        .await??;
+        let Some(receipt) = receipt else {
+            return Ok(None);
+        };
+
+        let validate = true;
+        receipt
+            .inner
+            .logs()
+            .iter()
+            .filter(|log| {
+                log.address() == self.config.starknet_contract_address
+                    && log.topic0() == Some(&Starknet::LogMessageToL2::SIGNATURE_HASH)
+            })
+            .map(|log| {
+                let event = Starknet::LogMessageToL2::decode_log(&log.inner, validate)?.data;
+                Ok(L1HandlerTransaction::from(EventData::try_from(event)?))
+            })
+            .collect::<EthereumBaseLayerResult<_>>()
+            .map(Some)
+    }
 }
 
 #[async_trait]
@@ -30,6 +30,8 @@ use apollo_storage::storage_metrics::update_storage_metrics;
 use apollo_storage::{open_storage, StorageReader, StorageWriter};
 use futures::StreamExt;
 use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
+#[cfg(feature = "rpc")]
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerContract;
 use papyrus_common::metrics::COLLECT_PROFILING_METRICS;
 use papyrus_common::pending_classes::PendingClasses;
 use papyrus_monitoring_gateway::MonitoringServer;
@@ -144,6 +146,7 @@ async fn spawn_rpc_server(
         storage_reader,
         VERSION_FULL,
         None,
+        Some(Arc::new(EthereumBaseLayerContract::new(config.base_layer.clone()))),
     )
     .await?;
     Ok(tokio::spawn(async move {
@@ -221,7 +224,7 @@ async fn spawn_sync_client(
     pending_classes: Arc<RwLock<PendingClasses>>,
     class_manager_client: SharedClassManagerClient,
 ) -> JoinHandle<anyhow::Result<()>> {
-    match (config.sync, config.p2p_sync) {
+    match (config.sync, config.p2p_sync.clone()) {
         (Some(_), Some(_)) => {
             panic!("One of --sync.#is_none or --p2p_sync.#is_none must be turned on");
         }
@@ -607,6 +607,25 @@ pub struct BlockInfo {
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub struct BlockSignature(pub Signature);
 
+/// A validator's signed precommit for a block, as part of a [`CommitCertificate`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct SignedPrecommit {
+    pub voter: ContractAddress,
+    /// The voter's signature on the precommit vote for the block hash.
+    pub signature: Signature,
+}
+
+/// The precommits with which consensus decided on a block. Precommits of a quorum of the
+/// validators prove that the block is final.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct CommitCertificate {
+    /// The consensus round in which the block was decided.
+    pub round: u32,
+    /// The proposal commitment the precommits voted for.
+    pub block_hash: BlockHash,
+    pub precommits: Vec<SignedPrecommit>,
+}
+
 /// The error type returned from the block verification functions.
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum BlockVerificationError {
@@ -153,6 +153,12 @@ impl From<starknet_crypto::ExtendedSignature> for RawSignature {
     }
 }
 
+impl From<Signature> for RawSignature {
+    fn from(signature: Signature) -> Self {
+        Self(vec![signature.r, signature.s])
+    }
+}
+
 #[derive(Clone, Debug, Error, Serialize, Deserialize, Eq, PartialEq)]
 pub enum SignatureConversionError {
     #[error("expected a 2-element signature, but got length {0}")]
@@ -172,3 +178,12 @@ impl TryFrom<RawSignature> for starknet_crypto::Signature {
         Ok(starknet_crypto::Signature { r, s })
     }
 }
+
+impl TryFrom<RawSignature> for Signature {
+    type Error = SignatureConversionError;
+
+    fn try_from(signature: RawSignature) -> Result<Self, Self::Error> {
+        let starknet_crypto::Signature { r, s } = signature.try_into()?;
+        Ok(Self { r, s })
+    }
+}
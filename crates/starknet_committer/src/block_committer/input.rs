@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 
 use starknet_api::core::{ClassHash, ContractAddress, Nonce, PatriciaKey};
-use starknet_api::state::StorageKey;
+use starknet_api::state::{StorageKey, ThinStateDiff};
 use starknet_patricia::hash::hash_trait::HashOutput;
 use starknet_patricia::patricia_merkle_tree::node_data::leaf::{LeafModifications, SkeletonLeaf};
 use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
@@ -110,6 +110,35 @@ pub struct Input<C: Config> {
     pub config: C,
 }
 
+// The deployed contracts of a thin state diff include the replaced classes, and both are changes of
+// the class hash of an address. Deprecated classes aren't committed to in the classes trie.
+impl From<ThinStateDiff> for StateDiff {
+    fn from(state_diff: ThinStateDiff) -> Self {
+        Self {
+            address_to_class_hash: state_diff.deployed_contracts.into_iter().collect(),
+            address_to_nonce: state_diff.nonces.into_iter().collect(),
+            class_hash_to_compiled_class_hash: state_diff
+                .declared_classes
+                .into_iter()
+                .map(|(class_hash, compiled_class_hash)| {
+                    (class_hash, CompiledClassHash(compiled_class_hash.0))
+                })
+                .collect(),
+            storage_updates: state_diff
+                .storage_diffs
+                .into_iter()
+                .map(|(address, storage_diffs)| {
+                    let updates = storage_diffs
+                        .into_iter()
+                        .map(|(key, value)| (StarknetStorageKey(key), StarknetStorageValue(value)))
+                        .collect();
+                    (address, updates)
+                })
+                .collect(),
+        }
+    }
+}
+
 impl StateDiff {
     pub(crate) fn accessed_addresses(&self) -> HashSet<&ContractAddress> {
         HashSet::from_iter(
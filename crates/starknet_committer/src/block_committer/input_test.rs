@@ -1,9 +1,19 @@
+use std::collections::HashMap;
+
 use rstest::rstest;
-use starknet_api::core::ContractAddress;
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::state::ThinStateDiff;
+use starknet_api::{class_hash, compiled_class_hash, contract_address, felt, storage_key};
 use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
 use starknet_types_core::felt::Felt;
 
-use crate::block_committer::input::try_node_index_into_contract_address;
+use crate::block_committer::input::{
+    try_node_index_into_contract_address,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff,
+};
+use crate::patricia_merkle_tree::types::CompiledClassHash;
 
 #[rstest]
 fn test_node_index_to_contract_address_conversion() {
@@ -30,3 +40,37 @@ fn test_node_index_to_contract_address_conversion() {
         Err("NodeIndex is not a leaf.".to_string())
     );
 }
+
+#[rstest]
+fn test_thin_state_diff_conversion() {
+    let address = contract_address!("0x11");
+    let thin_state_diff = ThinStateDiff {
+        deployed_contracts: [(address, class_hash!("0x1"))].into_iter().collect(),
+        storage_diffs: [(address, [(storage_key!("0x5"), felt!("0x7"))].into_iter().collect())]
+            .into_iter()
+            .collect(),
+        declared_classes: [(class_hash!("0x1"), compiled_class_hash!(0x31_u8))]
+            .into_iter()
+            .collect(),
+        deprecated_declared_classes: vec![class_hash!("0x2")],
+        nonces: [(address, Nonce(felt!("0x1")))].into_iter().collect(),
+    };
+    assert_eq!(
+        StateDiff::from(thin_state_diff),
+        StateDiff {
+            address_to_class_hash: HashMap::from([(address, class_hash!("0x1"))]),
+            address_to_nonce: HashMap::from([(address, Nonce(felt!("0x1")))]),
+            class_hash_to_compiled_class_hash: HashMap::from([(
+                class_hash!("0x1"),
+                CompiledClassHash(felt!("0x31"))
+            )]),
+            storage_updates: HashMap::from([(
+                address,
+                HashMap::from([(
+                    StarknetStorageKey(storage_key!("0x5")),
+                    StarknetStorageValue(felt!("0x7"))
+                )])
+            )]),
+        }
+    );
+}
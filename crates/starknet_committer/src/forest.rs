@@ -1,6 +1,9 @@
 pub mod filled_forest;
 pub mod forest_errors;
+pub mod forest_roots;
+pub mod garbage_collection;
 pub mod original_skeleton_forest;
 #[cfg(test)]
 pub mod skeleton_forest_test;
+pub mod storage_proof;
 pub mod updated_skeleton_forest;
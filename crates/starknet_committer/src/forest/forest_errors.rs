@@ -1,7 +1,9 @@
 use starknet_api::core::ContractAddress;
 use starknet_patricia::patricia_merkle_tree::filled_tree::errors::FilledTreeError;
 use starknet_patricia::patricia_merkle_tree::original_skeleton_tree::errors::OriginalSkeletonTreeError;
+use starknet_patricia::patricia_merkle_tree::traversal::{ProofVerificationError, TraversalError};
 use starknet_patricia::patricia_merkle_tree::updated_skeleton_tree::errors::UpdatedSkeletonTreeError;
+use starknet_patricia_storage::errors::DeserializationError;
 use thiserror::Error;
 use tokio::task::JoinError;
 
@@ -38,4 +40,12 @@ pub enum ForestError {
     JoinError(#[from] JoinError),
     #[error("Couldn't create Storage Trie: {0}")]
     StorageTrie(#[source] FilledTreeError),
+    #[error(transparent)]
+    Traversal(#[from] TraversalError),
+    #[error(transparent)]
+    Deserialization(#[from] DeserializationError),
+    #[error("The proof of the contract at address {0:?} is missing.")]
+    MissingContractProof(ContractAddress),
+    #[error(transparent)]
+    ProofVerification(#[from] ProofVerificationError),
 }
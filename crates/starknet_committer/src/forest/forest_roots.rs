@@ -0,0 +1,150 @@
+use std::sync::LazyLock;
+
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ascii_as_felt, GlobalRoot};
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia_storage::db_object::{DBObject, Deserializable, HasStaticPrefix};
+use starknet_patricia_storage::errors::DeserializationError;
+use starknet_patricia_storage::map_storage::MapStorage;
+use starknet_patricia_storage::storage_trait::{
+    create_db_key,
+    DbKey,
+    DbKeyPrefix,
+    DbValue,
+    Storage,
+};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+
+static STARKNET_STATE_V0: LazyLock<Felt> = LazyLock::new(|| {
+    ascii_as_felt("STARKNET_STATE_V0").expect("ascii_as_felt failed for 'STARKNET_STATE_V0'")
+});
+
+const SERIALIZED_HASH_BYTES: usize = 32;
+const SERIALIZED_BLOCK_NUMBER_BYTES: usize = 8;
+
+/// The roots of the contracts trie and the classes trie of a Starknet state.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ForestRoots {
+    pub contracts_trie_root_hash: HashOutput,
+    pub classes_trie_root_hash: HashOutput,
+}
+
+impl ForestRoots {
+    /// Returns the state root that is committed to in the block header:
+    /// Poseidon("STARKNET_STATE_V0", contracts_trie_root, classes_trie_root), or the contracts
+    /// trie root if the classes trie is empty.
+    pub fn global_root(&self) -> GlobalRoot {
+        if self.classes_trie_root_hash == HashOutput::ROOT_OF_EMPTY_TREE {
+            return GlobalRoot(self.contracts_trie_root_hash.0);
+        }
+        GlobalRoot(Poseidon::hash_array(&[
+            *STARKNET_STATE_V0,
+            self.contracts_trie_root_hash.0,
+            self.classes_trie_root_hash.0,
+        ]))
+    }
+}
+
+impl HasStaticPrefix for ForestRoots {
+    fn get_static_prefix() -> DbKeyPrefix {
+        DbKeyPrefix::new(b"forest_roots")
+    }
+}
+
+impl DBObject for ForestRoots {
+    /// Concatenates the contracts trie root and the classes trie root.
+    fn serialize(&self) -> DbValue {
+        DbValue(
+            [
+                self.contracts_trie_root_hash.0.to_bytes_be(),
+                self.classes_trie_root_hash.0.to_bytes_be(),
+            ]
+            .concat(),
+        )
+    }
+}
+
+impl Deserializable for ForestRoots {
+    fn deserialize(value: &DbValue) -> Result<Self, DeserializationError> {
+        if value.0.len() != 2 * SERIALIZED_HASH_BYTES {
+            return Err(DeserializationError::ValueError(
+                format!("Unexpected forest roots length {}.", value.0.len()).into(),
+            ));
+        }
+        Ok(Self {
+            contracts_trie_root_hash: HashOutput(Felt::from_bytes_be_slice(
+                &value.0[..SERIALIZED_HASH_BYTES],
+            )),
+            classes_trie_root_hash: HashOutput(Felt::from_bytes_be_slice(
+                &value.0[SERIALIZED_HASH_BYTES..],
+            )),
+        })
+    }
+}
+
+fn block_forest_roots_db_key(block_number: BlockNumber) -> DbKey {
+    ForestRoots::default().get_db_key(&block_number.0.to_be_bytes())
+}
+
+fn forest_roots_marker_db_key() -> DbKey {
+    create_db_key(DbKeyPrefix::new(b"forest_roots_marker"), b"")
+}
+
+/// Returns the writes that record the roots of the forest after the given block, and advance the
+/// marker past it.
+pub(crate) fn block_forest_roots_writes(
+    block_number: BlockNumber,
+    forest_roots: &ForestRoots,
+) -> MapStorage {
+    MapStorage::from([
+        (block_forest_roots_db_key(block_number), forest_roots.serialize()),
+        (
+            forest_roots_marker_db_key(),
+            DbValue(block_number.unchecked_next().0.to_be_bytes().to_vec()),
+        ),
+    ])
+}
+
+/// Records the roots of the forest after the given block, so the tries of the block can be found
+/// by its number.
+pub fn write_block_forest_roots(
+    storage: &mut impl Storage,
+    block_number: BlockNumber,
+    forest_roots: &ForestRoots,
+) {
+    storage.mset(block_forest_roots_writes(block_number, forest_roots));
+}
+
+/// Returns the first block whose forest roots weren't written, i.e., the block after the last
+/// written one.
+pub fn read_forest_roots_marker(
+    storage: &impl Storage,
+) -> Result<BlockNumber, DeserializationError> {
+    let Some(value) = storage.get(&forest_roots_marker_db_key()) else {
+        return Ok(BlockNumber(0));
+    };
+    let bytes: [u8; SERIALIZED_BLOCK_NUMBER_BYTES] =
+        value.0.as_slice().try_into().map_err(|_| {
+            DeserializationError::ValueError(
+                format!("Unexpected forest roots marker length {}.", value.0.len()).into(),
+            )
+        })?;
+    Ok(BlockNumber(u64::from_be_bytes(bytes)))
+}
+
+/// Returns the roots of the forest after the given block, if they were recorded.
+pub fn read_block_forest_roots(
+    storage: &impl Storage,
+    block_number: BlockNumber,
+) -> Result<Option<ForestRoots>, DeserializationError> {
+    storage
+        .get(&block_forest_roots_db_key(block_number))
+        .map(|value| ForestRoots::deserialize(&value))
+        .transpose()
+}
+
+/// Removes the record of the roots of the forest after the given block.
+pub fn delete_block_forest_roots(storage: &mut impl Storage, block_number: BlockNumber) {
+    storage.delete(&block_forest_roots_db_key(block_number));
+}
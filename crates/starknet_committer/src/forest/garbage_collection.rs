@@ -0,0 +1,348 @@
+//! Garbage collection of trie nodes that are no longer reachable from the retained blocks.
+//!
+//! Nodes are content addressed, so a single stored node may be shared by several parents, by
+//! several tries and by several blocks. Every stored node therefore has a reference count: the
+//! number of stored nodes pointing to it (a contract leaf points to the root of the contract's
+//! storage trie), plus the number of retained blocks whose forest roots are the node. When a block
+//! leaves the retention window, the references of its roots are released, and every node whose
+//! count drops to zero is deleted, releasing the references of its children in turn.
+//!
+//! The reference counts are only valid if the forests are written with [`write_block_forest`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU64;
+
+use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::filled_tree::node::FilledNode;
+use starknet_patricia::patricia_merkle_tree::filled_tree::tree::FilledTreeImpl;
+use starknet_patricia::patricia_merkle_tree::node_data::inner_node::NodeData;
+use starknet_patricia::patricia_merkle_tree::node_data::leaf::Leaf;
+use starknet_patricia::patricia_merkle_tree::traversal::{node_children, node_db_key};
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_patricia_storage::db_object::{DBObject, Deserializable};
+use starknet_patricia_storage::errors::DeserializationError;
+use starknet_patricia_storage::map_storage::MapStorage;
+use starknet_patricia_storage::storage_trait::{
+    create_db_key,
+    DbKey,
+    DbKeyPrefix,
+    DbValue,
+    Storage,
+};
+use tracing::info;
+
+use crate::block_committer::input::StarknetStorageValue;
+use crate::forest::filled_forest::FilledForest;
+use crate::forest::forest_errors::ForestResult;
+use crate::forest::forest_roots::{
+    block_forest_roots_writes,
+    delete_block_forest_roots,
+    read_block_forest_roots,
+    ForestRoots,
+};
+use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use crate::patricia_merkle_tree::types::CompiledClassHash;
+
+#[cfg(test)]
+#[path = "garbage_collection_test.rs"]
+pub mod garbage_collection_test;
+
+const REFERENCE_COUNT_BYTES: usize = 8;
+
+/// The retention window of the forest: the tries of the last `retained_blocks` blocks are kept,
+/// so proofs against them can still be served.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ForestRetentionConfig {
+    pub retained_blocks: NonZeroU64,
+}
+
+impl SerializeConfig for ForestRetentionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from([ser_param(
+            "retained_blocks",
+            &self.retained_blocks,
+            "The number of latest blocks whose tries are kept. The nodes that are only reachable \
+             from older blocks are deleted.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+impl Default for ForestRetentionConfig {
+    fn default() -> Self {
+        Self { retained_blocks: NonZeroU64::new(128).expect("128 is non-zero.") }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TrieKind {
+    Classes,
+    Contracts,
+    Storage,
+}
+
+/// A node of the forest, identified by its trie and position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct NodeReference {
+    trie: TrieKind,
+    index: NodeIndex,
+    hash: HashOutput,
+}
+
+impl NodeReference {
+    /// Returns a reference to the root of a trie, or None if the trie is empty.
+    fn root(trie: TrieKind, root_hash: HashOutput) -> Option<Self> {
+        (root_hash != HashOutput::ROOT_OF_EMPTY_TREE).then_some(Self {
+            trie,
+            index: NodeIndex::ROOT,
+            hash: root_hash,
+        })
+    }
+
+    fn forest_roots(forest_roots: &ForestRoots) -> impl Iterator<Item = Self> {
+        [
+            Self::root(TrieKind::Contracts, forest_roots.contracts_trie_root_hash),
+            Self::root(TrieKind::Classes, forest_roots.classes_trie_root_hash),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    fn db_key(&self) -> DbKey {
+        match self.trie {
+            TrieKind::Classes => node_db_key::<CompiledClassHash>(&self.index, &self.hash),
+            TrieKind::Contracts => node_db_key::<ContractState>(&self.index, &self.hash),
+            TrieKind::Storage => node_db_key::<StarknetStorageValue>(&self.index, &self.hash),
+        }
+    }
+
+    /// Returns the nodes referenced by the stored node.
+    fn children(&self, value: &DbValue) -> Result<Vec<Self>, DeserializationError> {
+        match self.trie {
+            TrieKind::Classes => self.stored_node_children::<CompiledClassHash>(value),
+            TrieKind::Contracts if self.index.is_leaf() => {
+                Ok(contract_leaf_children(&ContractState::deserialize(value)?))
+            }
+            TrieKind::Contracts => self.stored_node_children::<ContractState>(value),
+            TrieKind::Storage => self.stored_node_children::<StarknetStorageValue>(value),
+        }
+    }
+
+    fn stored_node_children<L: Leaf>(
+        &self,
+        value: &DbValue,
+    ) -> Result<Vec<Self>, DeserializationError> {
+        // Only inner nodes are deserialized, leaves other than contract leaves have no children.
+        if self.index.is_leaf() {
+            return Ok(vec![]);
+        }
+        let node = FilledNode::<L>::deserialize(self.hash, value, false)?;
+        Ok(inner_node_children(self.trie, self.index, &node.data))
+    }
+}
+
+fn inner_node_children<L: Leaf>(
+    trie: TrieKind,
+    index: NodeIndex,
+    node_data: &NodeData<L>,
+) -> Vec<NodeReference> {
+    node_children(index, node_data)
+        .into_iter()
+        .map(|(index, hash)| NodeReference { trie, index, hash })
+        .collect()
+}
+
+fn contract_leaf_children(contract_state: &ContractState) -> Vec<NodeReference> {
+    NodeReference::root(TrieKind::Storage, contract_state.storage_root_hash).into_iter().collect()
+}
+
+/// A node that is written to storage, and the nodes it references.
+struct NewNode {
+    value: DbValue,
+    children: Vec<NodeReference>,
+}
+
+fn collect_new_nodes<L: Leaf>(
+    trie: TrieKind,
+    tree: &FilledTreeImpl<L>,
+    new_nodes: &mut HashMap<DbKey, NewNode>,
+    leaf_children: impl Fn(&L) -> Vec<NodeReference>,
+) {
+    for (index, node) in &tree.tree_map {
+        let children = match &node.data {
+            NodeData::Leaf(leaf) => leaf_children(leaf),
+            node_data => inner_node_children(trie, *index, node_data),
+        };
+        new_nodes.insert(node.db_key(), NewNode { value: node.serialize(), children });
+    }
+}
+
+fn reference_count_db_key(node_db_key: &DbKey) -> DbKey {
+    create_db_key(DbKeyPrefix::new(b"node_reference_count"), &node_db_key.0)
+}
+
+fn first_retained_block_db_key() -> DbKey {
+    create_db_key(DbKeyPrefix::new(b"forest_retention"), b"first_retained_block")
+}
+
+fn serialize_u64(value: u64) -> DbValue {
+    DbValue(value.to_be_bytes().to_vec())
+}
+
+fn deserialize_u64(value: &DbValue) -> Result<u64, DeserializationError> {
+    let bytes: [u8; REFERENCE_COUNT_BYTES] = value.0.as_slice().try_into().map_err(|_| {
+        DeserializationError::ValueError(
+            format!("Unexpected reference count length {}.", value.0.len()).into(),
+        )
+    })?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+/// Reads the reference counts of the given nodes. A node without a recorded count isn't
+/// referenced.
+fn read_reference_counts(
+    storage: &impl Storage,
+    node_db_keys: &[DbKey],
+) -> Result<Vec<u64>, DeserializationError> {
+    let count_db_keys: Vec<DbKey> = node_db_keys.iter().map(reference_count_db_key).collect();
+    storage
+        .mget(&count_db_keys)
+        .into_iter()
+        .map(|value| value.map_or(Ok(0), |value| deserialize_u64(&value)))
+        .collect()
+}
+
+/// Writes the forest of the given block together with its roots, and updates the reference counts
+/// of the nodes it points to. Everything is written in a single `mset`, so a storage with atomic
+/// batches is never left with partial counts.
+pub fn write_block_forest(
+    storage: &mut impl Storage,
+    filled_forest: &FilledForest,
+    block_number: BlockNumber,
+) -> ForestResult<()> {
+    let mut new_nodes = HashMap::new();
+    for storage_trie in filled_forest.storage_tries.values() {
+        collect_new_nodes(TrieKind::Storage, storage_trie, &mut new_nodes, |_| vec![]);
+    }
+    collect_new_nodes(
+        TrieKind::Contracts,
+        &filled_forest.contracts_trie,
+        &mut new_nodes,
+        contract_leaf_children,
+    );
+    collect_new_nodes(TrieKind::Classes, &filled_forest.classes_trie, &mut new_nodes, |_| vec![]);
+
+    // A node that is already stored already holds references to its children.
+    let new_node_db_keys: Vec<DbKey> = new_nodes.keys().cloned().collect();
+    let stored_values = storage.mget(&new_node_db_keys);
+    for (db_key, stored_value) in new_node_db_keys.into_iter().zip(stored_values) {
+        if stored_value.is_some() {
+            new_nodes.remove(&db_key);
+        }
+    }
+
+    let forest_roots = ForestRoots {
+        contracts_trie_root_hash: filled_forest.get_contract_root_hash(),
+        classes_trie_root_hash: filled_forest.get_compiled_class_root_hash(),
+    };
+    let mut added_references: HashMap<DbKey, u64> = HashMap::new();
+    for referenced_node in new_nodes
+        .values()
+        .flat_map(|new_node| new_node.children.iter().copied())
+        .chain(NodeReference::forest_roots(&forest_roots))
+    {
+        *added_references.entry(referenced_node.db_key()).or_default() += 1;
+    }
+    let referenced_db_keys: Vec<DbKey> = added_references.keys().cloned().collect();
+    let reference_counts = read_reference_counts(storage, &referenced_db_keys)?;
+
+    let mut writes: MapStorage =
+        new_nodes.into_iter().map(|(db_key, new_node)| (db_key, new_node.value)).collect();
+    for (db_key, reference_count) in referenced_db_keys.into_iter().zip(reference_counts) {
+        let reference_count = reference_count + added_references[&db_key];
+        writes.insert(reference_count_db_key(&db_key), serialize_u64(reference_count));
+    }
+    writes.extend(block_forest_roots_writes(block_number, &forest_roots));
+    storage.mset(writes);
+    Ok(())
+}
+
+/// Deletes the nodes that are only reachable from blocks outside the retention window, given the
+/// latest written block. Returns the number of deleted nodes.
+///
+/// The reference counts are updated before anything is deleted, so a crash in the middle may leak
+/// unreachable nodes, but never deletes a reachable one.
+pub fn prune_forest(
+    storage: &mut impl Storage,
+    latest_block_number: BlockNumber,
+    config: &ForestRetentionConfig,
+) -> ForestResult<usize> {
+    let first_retained_block = storage
+        .get(&first_retained_block_db_key())
+        .map(|value| deserialize_u64(&value))
+        .transpose()?
+        .unwrap_or_default();
+    let new_first_retained_block =
+        (latest_block_number.0 + 1).saturating_sub(config.retained_blocks.get());
+    if new_first_retained_block <= first_retained_block {
+        return Ok(0);
+    }
+
+    let pruned_blocks: Vec<BlockNumber> =
+        (first_retained_block..new_first_retained_block).map(BlockNumber).collect();
+    let mut released_nodes = Vec::new();
+    for block_number in &pruned_blocks {
+        if let Some(forest_roots) = read_block_forest_roots(storage, *block_number)? {
+            released_nodes.extend(NodeReference::forest_roots(&forest_roots));
+        }
+    }
+
+    let mut reference_counts: HashMap<DbKey, u64> = HashMap::new();
+    let mut deleted_nodes = Vec::new();
+    while let Some(node) = released_nodes.pop() {
+        let db_key = node.db_key();
+        let reference_count = match reference_counts.get(&db_key) {
+            Some(reference_count) => *reference_count,
+            None => read_reference_counts(storage, std::slice::from_ref(&db_key))?[0],
+        };
+        // Nodes that were written without reference counts are never collected.
+        if reference_count == 0 {
+            continue;
+        }
+        reference_counts.insert(db_key.clone(), reference_count - 1);
+        if reference_count > 1 {
+            continue;
+        }
+        if let Some(value) = storage.get(&db_key) {
+            released_nodes.extend(node.children(&value)?);
+        }
+        deleted_nodes.push(db_key);
+    }
+
+    let mut writes: MapStorage = reference_counts
+        .iter()
+        .filter(|(_, reference_count)| **reference_count > 0)
+        .map(|(db_key, reference_count)| {
+            (reference_count_db_key(db_key), serialize_u64(*reference_count))
+        })
+        .collect();
+    writes.insert(first_retained_block_db_key(), serialize_u64(new_first_retained_block));
+    storage.mset(writes);
+
+    for block_number in pruned_blocks {
+        delete_block_forest_roots(storage, block_number);
+    }
+    for db_key in &deleted_nodes {
+        storage.delete(db_key);
+        storage.delete(&reference_count_db_key(db_key));
+    }
+    info!(
+        "Pruned the forest up to block {new_first_retained_block}, deleted {} nodes.",
+        deleted_nodes.len()
+    );
+    Ok(deleted_nodes.len())
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::{class_hash, contract_address, felt, storage_key};
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::filled_tree::tree::FilledTree;
+use starknet_patricia::patricia_merkle_tree::traversal::node_db_key;
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_patricia_storage::map_storage::MapStorage;
+use starknet_patricia_storage::storage_trait::DbKey;
+
+use crate::block_committer::commit::commit_block;
+use crate::block_committer::input::{
+    ConfigImpl,
+    Input,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff,
+};
+use crate::forest::filled_forest::FilledForest;
+use crate::forest::forest_roots::{read_block_forest_roots, read_forest_roots_marker, ForestRoots};
+use crate::forest::garbage_collection::{prune_forest, write_block_forest, ForestRetentionConfig};
+use crate::forest::storage_proof::fetch_storage_proof;
+use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use crate::patricia_merkle_tree::types::CompiledClassHash;
+
+fn test_contract() -> ContractAddress {
+    contract_address!("0x11")
+}
+
+fn storage_state_diff(storage_updates: &[(&str, &str)]) -> StateDiff {
+    StateDiff {
+        storage_updates: HashMap::from([(
+            test_contract(),
+            storage_updates
+                .iter()
+                .map(|(key, value)| {
+                    (StarknetStorageKey(storage_key!(*key)), StarknetStorageValue(felt!(*value)))
+                })
+                .collect(),
+        )]),
+        ..Default::default()
+    }
+}
+
+async fn commit_and_write(
+    storage: &mut MapStorage,
+    state_diff: StateDiff,
+    previous_roots: ForestRoots,
+    block_number: BlockNumber,
+) -> FilledForest {
+    let input = Input {
+        state_diff,
+        contracts_trie_root_hash: previous_roots.contracts_trie_root_hash,
+        classes_trie_root_hash: previous_roots.classes_trie_root_hash,
+        config: ConfigImpl::default(),
+    };
+    let filled_forest = commit_block(input, &*storage).await.unwrap();
+    write_block_forest(storage, &filled_forest, block_number).unwrap();
+    filled_forest
+}
+
+fn storage_leaf_db_key(key: &str, value: &str) -> DbKey {
+    node_db_key::<StarknetStorageValue>(
+        &NodeIndex::from(&StarknetStorageKey(storage_key!(key))),
+        &HashOutput(felt!(value)),
+    )
+}
+
+#[tokio::test]
+async fn prune_deletes_only_nodes_outside_the_retention_window() {
+    let mut storage = MapStorage::new();
+    let mut first_state_diff = storage_state_diff(&[("0x5", "0x7"), ("0x6", "0x8")]);
+    first_state_diff.address_to_class_hash = HashMap::from([(test_contract(), class_hash!("0x1"))]);
+    first_state_diff.address_to_nonce = HashMap::from([(test_contract(), Nonce(felt!("0x1")))]);
+    first_state_diff.class_hash_to_compiled_class_hash =
+        HashMap::from([(class_hash!("0x1"), CompiledClassHash(felt!("0x31")))]);
+    let first_forest =
+        commit_and_write(&mut storage, first_state_diff, ForestRoots::default(), BlockNumber(0))
+            .await;
+    let first_roots = read_block_forest_roots(&storage, BlockNumber(0)).unwrap().unwrap();
+    let second_forest = commit_and_write(
+        &mut storage,
+        storage_state_diff(&[("0x5", "0x9")]),
+        first_roots,
+        BlockNumber(1),
+    )
+    .await;
+    let second_roots = read_block_forest_roots(&storage, BlockNumber(1)).unwrap().unwrap();
+    assert_eq!(read_forest_roots_marker(&storage).unwrap(), BlockNumber(2));
+
+    let config = ForestRetentionConfig { retained_blocks: NonZeroU64::new(1).unwrap() };
+    assert!(prune_forest(&mut storage, BlockNumber(1), &config).unwrap() > 0);
+    assert_eq!(prune_forest(&mut storage, BlockNumber(1), &config).unwrap(), 0);
+
+    // The nodes that were replaced in the second block are deleted.
+    assert_eq!(read_block_forest_roots(&storage, BlockNumber(0)).unwrap(), None);
+    let first_contracts_root =
+        node_db_key::<ContractState>(&NodeIndex::ROOT, &first_roots.contracts_trie_root_hash);
+    let first_storage_root = node_db_key::<StarknetStorageValue>(
+        &NodeIndex::ROOT,
+        &first_forest.storage_tries[&test_contract()].get_root_hash(),
+    );
+    assert!(!storage.contains_key(&first_contracts_root));
+    assert!(!storage.contains_key(&first_storage_root));
+    assert!(!storage.contains_key(&storage_leaf_db_key("0x5", "0x7")));
+
+    // The nodes that are shared with the second block are kept.
+    assert_eq!(first_roots.classes_trie_root_hash, second_roots.classes_trie_root_hash);
+    assert!(storage.contains_key(&storage_leaf_db_key("0x6", "0x8")));
+    let storage_proof = fetch_storage_proof(
+        &storage,
+        &second_roots,
+        &[class_hash!("0x1")],
+        &[],
+        &HashMap::from([(test_contract(), vec![StarknetStorageKey(storage_key!("0x6"))])]),
+    )
+    .unwrap();
+    assert_eq!(
+        storage_proof.contracts_trie_leaves[&test_contract()].storage_root_hash,
+        second_forest.storage_tries[&test_contract()].get_root_hash()
+    );
+}
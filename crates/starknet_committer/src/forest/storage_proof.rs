@@ -0,0 +1,248 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_patricia::patricia_merkle_tree::node_data::inner_node::{BinaryData, EdgeData};
+use starknet_patricia::patricia_merkle_tree::traversal::{
+    fetch_patricia_paths,
+    verify_patricia_path,
+    Preimage,
+    PreimageMap,
+};
+use starknet_patricia::patricia_merkle_tree::types::{NodeIndex, SortedLeafIndices};
+use starknet_patricia_storage::storage_trait::Storage;
+use starknet_types_core::felt::Felt;
+
+use crate::block_committer::input::{
+    contract_address_into_node_index,
+    StarknetStorageKey,
+    StarknetStorageValue,
+};
+use crate::forest::forest_errors::{ForestError, ForestResult};
+use crate::forest::forest_roots::ForestRoots;
+use crate::hash_function::hash::TreeHashFunctionImpl;
+use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use crate::patricia_merkle_tree::types::{class_hash_into_node_index, CompiledClassHash};
+
+#[cfg(test)]
+#[path = "storage_proof_test.rs"]
+pub mod storage_proof_test;
+
+/// Merkle proofs of leaves of the forest, against the roots of the forest.
+#[derive(Debug, Default, PartialEq)]
+pub struct StorageProof {
+    pub classes_trie_proof: PreimageMap,
+    pub contracts_trie_proof: PreimageMap,
+    /// The leaves of the proven contracts. Contracts that aren't deployed have an empty leaf.
+    pub contracts_trie_leaves: HashMap<ContractAddress, ContractState>,
+    pub contracts_storage_proofs: HashMap<ContractAddress, PreimageMap>,
+}
+
+// The proof in the format of the `starknet_getStorageProof` RPC method.
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BinaryNode {
+    pub left: Felt,
+    pub right: Felt,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct EdgeNode {
+    pub path: Felt,
+    pub length: u8,
+    pub child: Felt,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MerkleNode {
+    Binary(BinaryNode),
+    Edge(EdgeNode),
+}
+
+impl From<Preimage> for MerkleNode {
+    fn from(preimage: Preimage) -> Self {
+        match preimage {
+            Preimage::Binary(BinaryData { left_hash, right_hash }) => {
+                Self::Binary(BinaryNode { left: left_hash.0, right: right_hash.0 })
+            }
+            Preimage::Edge(EdgeData { bottom_hash, path_to_bottom }) => Self::Edge(EdgeNode {
+                path: Felt::from(&path_to_bottom.path),
+                length: path_to_bottom.length.into(),
+                child: bottom_hash.0,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NodeHashToNode {
+    pub node_hash: Felt,
+    pub node: MerkleNode,
+}
+
+/// The nodes of a proof, sorted by their hashes.
+pub fn node_hash_to_node_mapping(nodes: PreimageMap) -> Vec<NodeHashToNode> {
+    let mut mapping: Vec<NodeHashToNode> = nodes
+        .into_iter()
+        .map(|(node_hash, preimage)| NodeHashToNode {
+            node_hash: node_hash.0,
+            node: preimage.into(),
+        })
+        .collect();
+    mapping.sort_by_key(|node_hash_to_node| node_hash_to_node.node_hash);
+    mapping
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ContractLeafData {
+    pub nonce: Nonce,
+    pub class_hash: ClassHash,
+    pub storage_root: Felt,
+}
+
+impl From<ContractState> for ContractLeafData {
+    fn from(contract_state: ContractState) -> Self {
+        Self {
+            nonce: contract_state.nonce,
+            class_hash: contract_state.class_hash,
+            storage_root: contract_state.storage_root_hash.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ContractsProof {
+    pub nodes: Vec<NodeHashToNode>,
+    /// The leaves of the requested contracts, in the order of the request.
+    pub contract_leaves_data: Vec<ContractLeafData>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct GlobalRoots {
+    pub contracts_tree_root: Felt,
+    pub classes_tree_root: Felt,
+}
+
+impl From<&ForestRoots> for GlobalRoots {
+    fn from(forest_roots: &ForestRoots) -> Self {
+        Self {
+            contracts_tree_root: forest_roots.contracts_trie_root_hash.0,
+            classes_tree_root: forest_roots.classes_trie_root_hash.0,
+        }
+    }
+}
+
+/// Fetches from storage the proofs of the given classes, contracts and storage keys. The proof of
+/// a contract's storage keys is against the contract's storage root, so the contract is proven as
+/// well.
+pub fn fetch_storage_proof(
+    storage: &impl Storage,
+    forest_roots: &ForestRoots,
+    class_hashes: &[ClassHash],
+    contract_addresses: &[ContractAddress],
+    contracts_storage_keys: &HashMap<ContractAddress, Vec<StarknetStorageKey>>,
+) -> ForestResult<StorageProof> {
+    let mut classes_trie_indices =
+        unique_indices(class_hashes.iter().map(class_hash_into_node_index));
+    let classes_trie_proof = fetch_patricia_paths::<CompiledClassHash>(
+        storage,
+        forest_roots.classes_trie_root_hash,
+        SortedLeafIndices::new(&mut classes_trie_indices),
+    )?
+    .nodes;
+
+    let proven_contracts: HashSet<ContractAddress> =
+        contract_addresses.iter().chain(contracts_storage_keys.keys()).copied().collect();
+    let mut contracts_trie_indices =
+        unique_indices(proven_contracts.iter().map(contract_address_into_node_index));
+    let contracts_trie_paths = fetch_patricia_paths::<ContractState>(
+        storage,
+        forest_roots.contracts_trie_root_hash,
+        SortedLeafIndices::new(&mut contracts_trie_indices),
+    )?;
+    let contracts_trie_leaves: HashMap<ContractAddress, ContractState> = proven_contracts
+        .into_iter()
+        .map(|address| {
+            let leaf = contracts_trie_paths
+                .leaves
+                .get(&contract_address_into_node_index(&address))
+                .cloned()
+                .unwrap_or_default();
+            (address, leaf)
+        })
+        .collect();
+
+    let mut contracts_storage_proofs = HashMap::new();
+    for (address, storage_keys) in contracts_storage_keys {
+        let mut storage_trie_indices = unique_indices(storage_keys.iter().map(NodeIndex::from));
+        let storage_trie_proof = fetch_patricia_paths::<StarknetStorageValue>(
+            storage,
+            contracts_trie_leaves[address].storage_root_hash,
+            SortedLeafIndices::new(&mut storage_trie_indices),
+        )?
+        .nodes;
+        contracts_storage_proofs.insert(*address, storage_trie_proof);
+    }
+
+    Ok(StorageProof {
+        classes_trie_proof,
+        contracts_trie_proof: contracts_trie_paths.nodes,
+        contracts_trie_leaves,
+        contracts_storage_proofs,
+    })
+}
+
+/// Verifies the given proof against the given roots: the classes have the given compiled class
+/// hashes, the proven contracts have the leaves in the proof, and the storage keys of the contracts
+/// have the given values. An undeclared class, an undeployed contract and an unset storage key have
+/// an empty leaf.
+pub fn verify_storage_proof(
+    storage_proof: &StorageProof,
+    forest_roots: &ForestRoots,
+    compiled_class_hashes: &HashMap<ClassHash, CompiledClassHash>,
+    contracts_storage_values: &HashMap<
+        ContractAddress,
+        HashMap<StarknetStorageKey, StarknetStorageValue>,
+    >,
+) -> ForestResult<()> {
+    for (class_hash, compiled_class_hash) in compiled_class_hashes {
+        verify_patricia_path::<CompiledClassHash, TreeHashFunctionImpl>(
+            &storage_proof.classes_trie_proof,
+            forest_roots.classes_trie_root_hash,
+            class_hash_into_node_index(class_hash),
+            compiled_class_hash,
+        )?;
+    }
+
+    for (address, contract_state) in &storage_proof.contracts_trie_leaves {
+        verify_patricia_path::<ContractState, TreeHashFunctionImpl>(
+            &storage_proof.contracts_trie_proof,
+            forest_roots.contracts_trie_root_hash,
+            contract_address_into_node_index(address),
+            contract_state,
+        )?;
+    }
+
+    for (address, storage_values) in contracts_storage_values {
+        let (Some(contract_state), Some(storage_trie_proof)) = (
+            storage_proof.contracts_trie_leaves.get(address),
+            storage_proof.contracts_storage_proofs.get(address),
+        ) else {
+            return Err(ForestError::MissingContractProof(*address));
+        };
+        for (storage_key, storage_value) in storage_values {
+            verify_patricia_path::<StarknetStorageValue, TreeHashFunctionImpl>(
+                storage_trie_proof,
+                contract_state.storage_root_hash,
+                NodeIndex::from(storage_key),
+                storage_value,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn unique_indices(indices: impl Iterator<Item = NodeIndex>) -> Vec<NodeIndex> {
+    indices.collect::<HashSet<_>>().into_iter().collect()
+}
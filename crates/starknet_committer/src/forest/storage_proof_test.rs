@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use starknet_api::core::Nonce;
+use starknet_api::{class_hash, contract_address, felt, storage_key};
+use starknet_patricia::patricia_merkle_tree::filled_tree::tree::FilledTree;
+use starknet_patricia::patricia_merkle_tree::node_data::inner_node::NodeData;
+use starknet_patricia::patricia_merkle_tree::node_data::leaf::Leaf;
+use starknet_patricia::patricia_merkle_tree::traversal::{PreimageMap, ProofVerificationError};
+use starknet_patricia::patricia_merkle_tree::updated_skeleton_tree::hash_function::TreeHashFunction;
+use starknet_patricia_storage::map_storage::MapStorage;
+
+use crate::block_committer::commit::commit_block;
+use crate::block_committer::input::{
+    ConfigImpl,
+    Input,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff,
+};
+use crate::forest::filled_forest::FilledForest;
+use crate::forest::forest_errors::ForestError;
+use crate::forest::forest_roots::ForestRoots;
+use crate::forest::storage_proof::{fetch_storage_proof, verify_storage_proof, StorageProof};
+use crate::hash_function::hash::TreeHashFunctionImpl;
+use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use crate::patricia_merkle_tree::types::CompiledClassHash;
+
+/// Asserts that the hash of each node is the hash of its pre-image.
+fn assert_consistent_nodes<L: Leaf>(nodes: &PreimageMap)
+where
+    TreeHashFunctionImpl: TreeHashFunction<L>,
+{
+    for (hash, preimage) in nodes {
+        let node_data: NodeData<L> = preimage.clone().into();
+        assert_eq!(TreeHashFunctionImpl::compute_node_hash(&node_data), *hash);
+    }
+}
+
+/// Commits a forest with two declared classes and two deployed contracts, one of them with
+/// storage.
+async fn commit_test_forest(storage: &mut MapStorage) -> (FilledForest, ForestRoots) {
+    let deployed_contract = contract_address!("0x11");
+    let state_diff = StateDiff {
+        address_to_class_hash: HashMap::from([
+            (deployed_contract, class_hash!("0x1")),
+            (contract_address!("0x12"), class_hash!("0x2")),
+        ]),
+        address_to_nonce: HashMap::from([(deployed_contract, Nonce(felt!("0x1")))]),
+        class_hash_to_compiled_class_hash: HashMap::from([
+            (class_hash!("0x1"), CompiledClassHash(felt!("0x31"))),
+            (class_hash!("0x2"), CompiledClassHash(felt!("0x32"))),
+        ]),
+        storage_updates: HashMap::from([(
+            deployed_contract,
+            HashMap::from([
+                (StarknetStorageKey(storage_key!("0x5")), StarknetStorageValue(felt!("0x7"))),
+                (StarknetStorageKey(storage_key!("0x6")), StarknetStorageValue(felt!("0x8"))),
+            ]),
+        )]),
+    };
+    let input = Input {
+        state_diff,
+        contracts_trie_root_hash: Default::default(),
+        classes_trie_root_hash: Default::default(),
+        config: ConfigImpl::default(),
+    };
+    let filled_forest = commit_block(input, &*storage).await.unwrap();
+    filled_forest.write_to_storage(storage);
+    let forest_roots = ForestRoots {
+        contracts_trie_root_hash: filled_forest.get_contract_root_hash(),
+        classes_trie_root_hash: filled_forest.get_compiled_class_root_hash(),
+    };
+    (filled_forest, forest_roots)
+}
+
+#[tokio::test]
+async fn fetch_storage_proof_of_committed_forest() {
+    let deployed_contract = contract_address!("0x11");
+    let mut storage = MapStorage::new();
+    let (filled_forest, forest_roots) = commit_test_forest(&mut storage).await;
+
+    let undeployed_contract = contract_address!("0x13");
+    let storage_proof = fetch_storage_proof(
+        &storage,
+        &forest_roots,
+        &[class_hash!("0x1"), class_hash!("0x3")],
+        &[contract_address!("0x12"), undeployed_contract],
+        &HashMap::from([(
+            deployed_contract,
+            vec![StarknetStorageKey(storage_key!("0x5")), StarknetStorageKey(storage_key!("0x9"))],
+        )]),
+    )
+    .unwrap();
+
+    assert!(storage_proof.classes_trie_proof.contains_key(&forest_roots.classes_trie_root_hash));
+    assert_consistent_nodes::<CompiledClassHash>(&storage_proof.classes_trie_proof);
+    assert!(storage_proof
+        .contracts_trie_proof
+        .contains_key(&forest_roots.contracts_trie_root_hash));
+    assert_consistent_nodes::<ContractState>(&storage_proof.contracts_trie_proof);
+
+    let storage_root_hash = filled_forest.storage_tries[&deployed_contract].get_root_hash();
+    assert_eq!(
+        storage_proof.contracts_trie_leaves,
+        HashMap::from([
+            (
+                deployed_contract,
+                ContractState {
+                    nonce: Nonce(felt!("0x1")),
+                    storage_root_hash,
+                    class_hash: class_hash!("0x1"),
+                }
+            ),
+            (
+                contract_address!("0x12"),
+                ContractState {
+                    nonce: Nonce::default(),
+                    storage_root_hash: Default::default(),
+                    class_hash: class_hash!("0x2"),
+                }
+            ),
+            (undeployed_contract, ContractState::default()),
+        ])
+    );
+    let contract_storage_proof = &storage_proof.contracts_storage_proofs[&deployed_contract];
+    assert!(contract_storage_proof.contains_key(&storage_root_hash));
+    assert_consistent_nodes::<StarknetStorageValue>(contract_storage_proof);
+}
+
+#[tokio::test]
+async fn verify_storage_proof_of_committed_forest() {
+    let deployed_contract = contract_address!("0x11");
+    let mut storage = MapStorage::new();
+    let (_, forest_roots) = commit_test_forest(&mut storage).await;
+    let storage_keys =
+        vec![StarknetStorageKey(storage_key!("0x5")), StarknetStorageKey(storage_key!("0x9"))];
+    let storage_proof = fetch_storage_proof(
+        &storage,
+        &forest_roots,
+        &[class_hash!("0x1"), class_hash!("0x3")],
+        &[contract_address!("0x13")],
+        &HashMap::from([(deployed_contract, storage_keys)]),
+    )
+    .unwrap();
+
+    let compiled_class_hashes = HashMap::from([
+        (class_hash!("0x1"), CompiledClassHash(felt!("0x31"))),
+        (class_hash!("0x3"), CompiledClassHash::default()),
+    ]);
+    let storage_values = |value: &str| {
+        HashMap::from([(
+            deployed_contract,
+            HashMap::from([
+                (StarknetStorageKey(storage_key!("0x5")), StarknetStorageValue(felt!(value))),
+                (StarknetStorageKey(storage_key!("0x9")), StarknetStorageValue::default()),
+            ]),
+        )])
+    };
+    verify_storage_proof(
+        &storage_proof,
+        &forest_roots,
+        &compiled_class_hashes,
+        &storage_values("0x7"),
+    )
+    .unwrap();
+
+    assert_matches!(
+        verify_storage_proof(
+            &storage_proof,
+            &forest_roots,
+            &compiled_class_hashes,
+            &storage_values("0x8")
+        ),
+        Err(ForestError::ProofVerification(ProofVerificationError::LeafMismatch(_)))
+    );
+    let wrong_compiled_class_hashes =
+        HashMap::from([(class_hash!("0x3"), CompiledClassHash(felt!("0x33")))]);
+    assert_matches!(
+        verify_storage_proof(
+            &storage_proof,
+            &forest_roots,
+            &wrong_compiled_class_hashes,
+            &HashMap::new()
+        ),
+        Err(ForestError::ProofVerification(ProofVerificationError::LeafMismatch(_)))
+    );
+}
+
+#[tokio::test]
+async fn verify_tampered_storage_proof() {
+    let mut storage = MapStorage::new();
+    let (_, forest_roots) = commit_test_forest(&mut storage).await;
+    let storage_proof =
+        fetch_storage_proof(&storage, &forest_roots, &[class_hash!("0x1")], &[], &HashMap::new())
+            .unwrap();
+    let compiled_class_hashes =
+        HashMap::from([(class_hash!("0x1"), CompiledClassHash(felt!("0x31")))]);
+    let root_hash = forest_roots.classes_trie_root_hash;
+
+    // A proof without the root.
+    let mut missing_node_proof = StorageProof {
+        classes_trie_proof: storage_proof.classes_trie_proof.clone(),
+        ..Default::default()
+    };
+    missing_node_proof.classes_trie_proof.remove(&root_hash);
+    assert_matches!(
+        verify_storage_proof(
+            &missing_node_proof,
+            &forest_roots,
+            &compiled_class_hashes,
+            &HashMap::new()
+        ),
+        Err(ForestError::ProofVerification(ProofVerificationError::MissingNode(hash)))
+            if hash == root_hash
+    );
+
+    // A proof whose root has the pre-image of another node.
+    let mut invalid_preimage_proof = StorageProof {
+        classes_trie_proof: storage_proof.classes_trie_proof.clone(),
+        ..Default::default()
+    };
+    let other_preimage = storage_proof
+        .classes_trie_proof
+        .iter()
+        .find_map(|(hash, preimage)| (*hash != root_hash).then(|| preimage.clone()))
+        .unwrap();
+    invalid_preimage_proof.classes_trie_proof.insert(root_hash, other_preimage);
+    assert_matches!(
+        verify_storage_proof(
+            &invalid_preimage_proof,
+            &forest_roots,
+            &compiled_class_hashes,
+            &HashMap::new()
+        ),
+        Err(ForestError::ProofVerification(ProofVerificationError::InvalidPreimage(hash)))
+            if hash == root_hash
+    );
+}
@@ -3,4 +3,5 @@ pub mod commands;
 pub mod filled_tree_output;
 pub mod parse_input;
 pub mod run_committer_cli;
+pub mod storage_proof_output;
 pub mod tests;
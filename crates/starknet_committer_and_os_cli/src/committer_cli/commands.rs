@@ -1,24 +1,37 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
+use starknet_api::core::ContractAddress;
 use starknet_committer::block_committer::commit::commit_block;
-use starknet_committer::block_committer::input::Config;
-use starknet_patricia_storage::map_storage::{BorrowedMapStorage, MapStorage};
+use starknet_committer::block_committer::input::{Config, StarknetStorageKey};
+use starknet_committer::forest::forest_roots::read_forest_roots_marker;
+use starknet_committer::forest::garbage_collection::{
+    prune_forest,
+    write_block_forest,
+    ForestRetentionConfig,
+};
+use starknet_committer::forest::storage_proof::fetch_storage_proof;
+use starknet_patricia_storage::map_storage::BorrowedMapStorage;
+use starknet_patricia_storage::mdbx_storage::{MdbxStorage, MdbxStorageConfig};
+use starknet_patricia_storage::storage_trait::Storage;
 use tracing::info;
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::reload::Handle;
 use tracing_subscriber::Registry;
 
 use crate::committer_cli::filled_tree_output::filled_forest::SerializedForest;
-use crate::committer_cli::parse_input::cast::{CommitterInputImpl, InputImpl};
-use crate::committer_cli::parse_input::raw_input::RawInput;
+use crate::committer_cli::parse_input::cast::{CommitterInputImpl, InputImpl, StorageProofInput};
+use crate::committer_cli::parse_input::raw_input::{RawInput, RawStorageProofInput};
+use crate::committer_cli::storage_proof_output::StorageProofOutput;
 use crate::shared_utils::read::{load_input, write_to_file};
 
 pub async fn parse_and_commit(
     input_path: String,
     output_path: String,
+    storage_path: Option<PathBuf>,
     log_filter_handle: Handle<LevelFilter, Registry>,
 ) {
-    let CommitterInputImpl { input, storage } = load_input::<RawInput>(input_path)
+    let CommitterInputImpl { input, mut storage } = load_input::<RawInput>(input_path)
         .try_into()
         .expect("Failed to convert RawInput to InputImpl.");
     info!(
@@ -30,21 +43,67 @@ pub async fn parse_and_commit(
     log_filter_handle
         .modify(|filter| *filter = input.config.logger_level())
         .expect("Failed to set the log level.");
-    commit(input, output_path, storage).await;
+    match storage_path {
+        None => commit(input, output_path, &mut storage).await,
+        Some(path) => {
+            let mut persistent_storage =
+                MdbxStorage::open(&MdbxStorageConfig { path, ..Default::default() })
+                    .expect("Failed to open the storage.");
+            // The facts of the input are added to the facts of the previously committed blocks.
+            persistent_storage.mset(storage);
+            commit(input, output_path, &mut persistent_storage).await;
+        }
+    }
 }
 
-pub async fn commit(input: InputImpl, output_path: String, storage: MapStorage) {
+/// Commits the block on top of the facts in the given storage, and writes the new facts to it, so
+/// the next block can be committed on top of this one. The tries of the blocks that left the
+/// retention window are then pruned.
+pub async fn commit(input: InputImpl, output_path: String, storage: &mut impl Storage) {
+    // The block follows the last block whose forest was written to the storage.
+    let block_number =
+        read_forest_roots_marker(&*storage).expect("Failed to read the last committed block.");
     let serialized_filled_forest = SerializedForest(
-        commit_block(input, &storage).await.expect("Failed to commit the given block."),
+        commit_block(input, &*storage).await.expect("Failed to commit the given block."),
     );
     // Create an empty storage for the new facts.
     let mut empty_storage = HashMap::new();
     let output_storage = BorrowedMapStorage { storage: &mut empty_storage };
-    let output = serialized_filled_forest.forest_to_output(output_storage);
+    let output = serialized_filled_forest.forest_to_output(output_storage, block_number);
     write_to_file(&output_path, &output);
+    write_block_forest(storage, &serialized_filled_forest.0, block_number)
+        .expect("Failed to write the forest of the block.");
+    prune_forest(storage, block_number, &ForestRetentionConfig::default())
+        .expect("Failed to prune the forest.");
     info!(
         "Successfully committed given block. Updated Contracts Trie Root Hash: {:?},
     Updated Classes Trie Root Hash: {:?}",
         output.contract_storage_root_hash, output.compiled_class_root_hash,
     );
 }
+
+pub fn parse_and_prove(input_path: String, output_path: String) {
+    let input: StorageProofInput = load_input::<RawStorageProofInput>(input_path)
+        .try_into()
+        .expect("Failed to convert RawStorageProofInput to StorageProofInput.");
+    let contracts_storage_keys: HashMap<ContractAddress, Vec<StarknetStorageKey>> =
+        input.contracts_storage_keys.iter().cloned().collect();
+    let storage_proof = fetch_storage_proof(
+        &input.storage,
+        &input.forest_roots,
+        &input.class_hashes,
+        &input.contract_addresses,
+        &contracts_storage_keys,
+    )
+    .expect("Failed to fetch the storage proof.");
+    let output = StorageProofOutput::new(storage_proof, &input)
+        .expect("Failed to convert the storage proof to the output format.");
+    write_to_file(&output_path, &output);
+    info!(
+        "Successfully fetched the storage proof of {} classes, {} contracts and the storage of {} \
+         contracts.",
+        input.class_hashes.len(),
+        input.contract_addresses.len(),
+        input.contracts_storage_keys.len(),
+    );
+}
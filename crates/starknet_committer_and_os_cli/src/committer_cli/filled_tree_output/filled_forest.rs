@@ -1,5 +1,7 @@
 use serde::Serialize;
+use starknet_api::block::BlockNumber;
 use starknet_committer::forest::filled_forest::FilledForest;
+use starknet_committer::forest::garbage_collection::write_block_forest;
 use starknet_patricia_storage::map_storage::BorrowedMapStorage;
 
 pub struct SerializedForest(pub FilledForest);
@@ -7,7 +9,7 @@ pub struct SerializedForest(pub FilledForest);
 #[derive(Debug, Serialize)]
 #[allow(dead_code)]
 pub struct Output<'a> {
-    // New fact storage.
+    // New fact storage: the nodes of the block, their reference counts and the roots of the block.
     storage: BorrowedMapStorage<'a>,
     // TODO(Amos, 1/8/2024): Rename to `contracts_trie_root_hash` & `classes_trie_root_hash`.
     // New contract storage root.
@@ -17,8 +19,13 @@ pub struct Output<'a> {
 }
 
 impl SerializedForest {
-    pub fn forest_to_output<'a>(&self, mut storage: BorrowedMapStorage<'a>) -> Output<'a> {
-        self.0.write_to_storage(&mut storage);
+    pub fn forest_to_output<'a>(
+        &self,
+        mut storage: BorrowedMapStorage<'a>,
+        block_number: BlockNumber,
+    ) -> Output<'a> {
+        write_block_forest(&mut storage, &self.0, block_number)
+            .expect("Failed to write the forest of the block.");
         let contract_storage_root_hash = self.0.get_contract_root_hash().0;
         let compiled_class_root_hash = self.0.get_compiled_class_root_hash().0;
         Output {
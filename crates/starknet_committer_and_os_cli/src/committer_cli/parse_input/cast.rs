@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_committer::block_committer::input::{
@@ -8,6 +8,7 @@ use starknet_committer::block_committer::input::{
     StarknetStorageValue,
     StateDiff,
 };
+use starknet_committer::forest::forest_roots::ForestRoots;
 use starknet_committer::patricia_merkle_tree::types::CompiledClassHash;
 use starknet_patricia::hash::hash_trait::HashOutput;
 use starknet_patricia_storage::errors::DeserializationError;
@@ -15,7 +16,7 @@ use starknet_patricia_storage::map_storage::MapStorage;
 use starknet_patricia_storage::storage_trait::{DbKey, DbValue};
 use starknet_types_core::felt::Felt;
 
-use crate::committer_cli::parse_input::raw_input::RawInput;
+use crate::committer_cli::parse_input::raw_input::{RawInput, RawStorageProofInput};
 
 pub type InputImpl = Input<ConfigImpl>;
 
@@ -101,6 +102,65 @@ impl TryFrom<RawInput> for CommitterInputImpl {
     }
 }
 
+/// The storage and the requested keys of a storage proof, in the order of the request.
+#[derive(Debug, PartialEq)]
+pub struct StorageProofInput {
+    pub storage: MapStorage,
+    pub forest_roots: ForestRoots,
+    pub class_hashes: Vec<ClassHash>,
+    pub contract_addresses: Vec<ContractAddress>,
+    pub contracts_storage_keys: Vec<(ContractAddress, Vec<StarknetStorageKey>)>,
+}
+
+impl TryFrom<RawStorageProofInput> for StorageProofInput {
+    type Error = DeserializationError;
+    fn try_from(raw_input: RawStorageProofInput) -> Result<Self, Self::Error> {
+        let mut storage = HashMap::new();
+        for entry in raw_input.storage {
+            add_unique(&mut storage, "storage", DbKey(entry.key), DbValue(entry.value))?;
+        }
+        let forest_roots = ForestRoots {
+            contracts_trie_root_hash: HashOutput(Felt::from_bytes_be_slice(
+                &raw_input.contracts_trie_root_hash,
+            )),
+            classes_trie_root_hash: HashOutput(Felt::from_bytes_be_slice(
+                &raw_input.classes_trie_root_hash,
+            )),
+        };
+        let class_hashes = raw_input
+            .class_hashes
+            .iter()
+            .map(|class_hash| ClassHash(Felt::from_bytes_be_slice(class_hash)))
+            .collect();
+        let contract_addresses: Vec<ContractAddress> = raw_input
+            .contract_addresses
+            .iter()
+            .map(|address| ContractAddress::try_from(Felt::from_bytes_be_slice(address)))
+            .collect::<Result<_, _>>()?;
+        check_unique("contract addresses", contract_addresses.iter())?;
+        let contracts_storage_keys: Vec<(ContractAddress, Vec<StarknetStorageKey>)> = raw_input
+            .contracts_storage_keys
+            .into_iter()
+            .map(|entry| {
+                let storage_keys = entry
+                    .storage_keys
+                    .iter()
+                    .map(|key| Ok(StarknetStorageKey(Felt::from_bytes_be_slice(key).try_into()?)))
+                    .collect::<Result<_, Self::Error>>()?;
+                Ok((
+                    ContractAddress::try_from(Felt::from_bytes_be_slice(&entry.address))?,
+                    storage_keys,
+                ))
+            })
+            .collect::<Result<_, Self::Error>>()?;
+        check_unique(
+            "contracts storage keys",
+            contracts_storage_keys.iter().map(|(address, _)| address),
+        )?;
+        Ok(Self { storage, forest_roots, class_hashes, contract_addresses, contracts_storage_keys })
+    }
+}
+
 pub(crate) fn add_unique<K, V>(
     map: &mut HashMap<K, V>,
     map_name: &str,
@@ -116,3 +176,21 @@ where
     map.insert(key, value);
     Ok(())
 }
+
+/// Fails if a key appears more than once.
+fn check_unique<K>(
+    list_name: &str,
+    keys: impl Iterator<Item = K>,
+) -> Result<(), DeserializationError>
+where
+    K: std::cmp::Eq + std::hash::Hash + std::fmt::Debug,
+{
+    let mut seen_keys = HashSet::new();
+    for key in keys {
+        if seen_keys.contains(&key) {
+            return Err(DeserializationError::KeyDuplicate(format!("{list_name}: {key:?}")));
+        }
+        seen_keys.insert(key);
+    }
+    Ok(())
+}
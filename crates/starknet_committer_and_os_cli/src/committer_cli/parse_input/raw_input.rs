@@ -15,6 +15,25 @@ pub(crate) struct RawInput {
     pub config: RawConfigImpl,
 }
 
+#[derive(Deserialize, Debug)]
+/// Input to the storage proof command.
+pub(crate) struct RawStorageProofInput {
+    /// Fact storage, in the same format as the committer input.
+    pub storage: Vec<RawStorageEntry>,
+    pub contracts_trie_root_hash: RawFelt,
+    pub classes_trie_root_hash: RawFelt,
+    pub class_hashes: Vec<RawFelt>,
+    pub contract_addresses: Vec<RawFelt>,
+    pub contracts_storage_keys: Vec<RawContractStorageKeys>,
+}
+
+#[derive(Deserialize, Debug)]
+/// The storage keys of a contract to prove.
+pub(crate) struct RawContractStorageKeys {
+    pub address: RawFelt,
+    pub storage_keys: Vec<RawFelt>,
+}
+
 #[derive(Deserialize, Debug)]
 /// Fact storage entry.
 pub(crate) struct RawStorageEntry {
@@ -1,7 +1,7 @@
 use starknet_patricia_storage::errors::DeserializationError;
 
-use crate::committer_cli::parse_input::cast::CommitterInputImpl;
-use crate::committer_cli::parse_input::raw_input::RawInput;
+use crate::committer_cli::parse_input::cast::{CommitterInputImpl, StorageProofInput};
+use crate::committer_cli::parse_input::raw_input::{RawInput, RawStorageProofInput};
 
 #[cfg(test)]
 #[path = "read_test.rs"]
@@ -12,3 +12,7 @@ type DeserializationResult<T> = Result<T, DeserializationError>;
 pub fn parse_input(input: &str) -> DeserializationResult<CommitterInputImpl> {
     serde_json::from_str::<RawInput>(input)?.try_into()
 }
+
+pub fn parse_storage_proof_input(input: &str) -> DeserializationResult<StorageProofInput> {
+    serde_json::from_str::<RawStorageProofInput>(input)?.try_into()
+}
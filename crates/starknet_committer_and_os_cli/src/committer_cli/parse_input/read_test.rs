@@ -17,7 +17,7 @@ use starknet_patricia_storage::storage_trait::{DbKey, DbValue};
 use starknet_types_core::felt::Felt;
 use tracing::level_filters::LevelFilter;
 
-use super::parse_input;
+use super::{parse_input, parse_storage_proof_input};
 use crate::committer_cli::parse_input::cast::CommitterInputImpl;
 
 #[test]
@@ -299,3 +299,56 @@ fn test_input_parsing_with_mapping_key_duplicate() {
         DeserializationError::KeyDuplicate(key) if key ==  expected_error
     );
 }
+
+#[test]
+fn test_storage_proof_input_parsing_with_contract_address_duplicate() {
+    let input = r#"
+[
+    [],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [],
+    [
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 17],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 18],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 17]
+    ],
+    []
+]
+
+"#;
+    let expected_error = "contract addresses: ContractAddress(PatriciaKey(0x11))";
+    assert_matches!(
+        parse_storage_proof_input(input).unwrap_err(),
+        DeserializationError::KeyDuplicate(key) if key == expected_error
+    );
+}
+
+#[test]
+fn test_storage_proof_input_parsing_with_contracts_storage_keys_duplicate() {
+    let input = r#"
+[
+    [],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [],
+    [],
+    [
+        [
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 17],
+            [[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5]]
+        ],
+        [
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 17],
+            [[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6]]
+        ]
+    ]
+]
+
+"#;
+    let expected_error = "contracts storage keys: ContractAddress(PatriciaKey(0x11))";
+    assert_matches!(
+        parse_storage_proof_input(input).unwrap_err(),
+        DeserializationError::KeyDuplicate(key) if key == expected_error
+    );
+}
@@ -1,10 +1,12 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 use tracing::info;
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::reload::Handle;
 use tracing_subscriber::Registry;
 
-use crate::committer_cli::commands::parse_and_commit;
+use crate::committer_cli::commands::{parse_and_commit, parse_and_prove};
 use crate::committer_cli::tests::python_tests::CommitterPythonTestRunner;
 use crate::shared_utils::types::{run_python_test, IoArgs, PythonTestArg};
 
@@ -20,8 +22,18 @@ enum Command {
     Commit {
         #[clap(flatten)]
         io_args: IoArgs,
+        /// The directory of a persistent fact storage. If given, the block is committed on top of
+        /// the facts stored there by previous commits, and its new facts are stored there.
+        #[clap(long)]
+        storage_path: Option<PathBuf>,
     },
     PythonTest(PythonTestArg),
+    /// Given the fact storage, the roots of a forest and classes, contracts and storage keys,
+    /// outputs their Merkle proofs in the format of `starknet_getStorageProof`.
+    StorageProof {
+        #[clap(flatten)]
+        io_args: IoArgs,
+    },
 }
 
 pub async fn run_committer_cli(
@@ -30,12 +42,16 @@ pub async fn run_committer_cli(
 ) {
     info!("Starting committer-cli with command: \n{:?}", committer_command);
     match committer_command.command {
-        Command::Commit { io_args: IoArgs { input_path, output_path } } => {
-            parse_and_commit(input_path, output_path, log_filter_handle).await;
+        Command::Commit { io_args: IoArgs { input_path, output_path }, storage_path } => {
+            parse_and_commit(input_path, output_path, storage_path, log_filter_handle).await;
         }
 
         Command::PythonTest(python_test_arg) => {
             run_python_test::<CommitterPythonTestRunner>(python_test_arg).await;
         }
+
+        Command::StorageProof { io_args: IoArgs { input_path, output_path } } => {
+            parse_and_prove(input_path, output_path);
+        }
     }
 }
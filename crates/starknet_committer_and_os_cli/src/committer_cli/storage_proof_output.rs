@@ -0,0 +1,69 @@
+//! The output of the storage proof command, in the format of the `starknet_getStorageProof` RPC
+//! method, without the block hash of the roots.
+
+use serde::Serialize;
+use starknet_committer::forest::forest_errors::ForestError;
+use starknet_committer::forest::storage_proof::{
+    node_hash_to_node_mapping,
+    ContractsProof,
+    GlobalRoots,
+    NodeHashToNode,
+    StorageProof,
+};
+
+use crate::committer_cli::parse_input::cast::StorageProofInput;
+
+#[cfg(test)]
+#[path = "storage_proof_output_test.rs"]
+pub mod storage_proof_output_test;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct StorageProofOutput {
+    pub classes_proof: Vec<NodeHashToNode>,
+    pub contracts_proof: ContractsProof,
+    /// The proofs of the storage keys of each requested contract, in the order of the request.
+    pub contracts_storage_proofs: Vec<Vec<NodeHashToNode>>,
+    pub global_roots: GlobalRoots,
+}
+
+impl StorageProofOutput {
+    /// Returns the output of the given storage proof of the given request. Fails if the proof is
+    /// missing a requested contract.
+    pub fn new(
+        mut storage_proof: StorageProof,
+        input: &StorageProofInput,
+    ) -> Result<Self, ForestError> {
+        let contract_leaves_data = input
+            .contract_addresses
+            .iter()
+            .map(|address| {
+                storage_proof
+                    .contracts_trie_leaves
+                    .get(address)
+                    .cloned()
+                    .map(Into::into)
+                    .ok_or(ForestError::MissingContractProof(*address))
+            })
+            .collect::<Result<_, _>>()?;
+        let contracts_storage_proofs = input
+            .contracts_storage_keys
+            .iter()
+            .map(|(address, _)| {
+                storage_proof
+                    .contracts_storage_proofs
+                    .remove(address)
+                    .map(node_hash_to_node_mapping)
+                    .ok_or(ForestError::MissingContractProof(*address))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            classes_proof: node_hash_to_node_mapping(storage_proof.classes_trie_proof),
+            contracts_proof: ContractsProof {
+                nodes: node_hash_to_node_mapping(storage_proof.contracts_trie_proof),
+                contract_leaves_data,
+            },
+            contracts_storage_proofs,
+            global_roots: (&input.forest_roots).into(),
+        })
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::fs;
+
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_committer::block_committer::commit::commit_block;
+use starknet_committer::block_committer::input::{
+    ConfigImpl,
+    Input,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff,
+};
+use starknet_committer::forest::forest_errors::ForestError;
+use starknet_committer::forest::forest_roots::ForestRoots;
+use starknet_committer::forest::storage_proof::StorageProof;
+use starknet_committer::patricia_merkle_tree::types::CompiledClassHash;
+use starknet_patricia_storage::map_storage::MapStorage;
+use starknet_types_core::felt::Felt;
+use tempfile::NamedTempFile;
+
+use crate::committer_cli::commands::parse_and_prove;
+use crate::committer_cli::parse_input::cast::StorageProofInput;
+use crate::committer_cli::storage_proof_output::StorageProofOutput;
+
+/// Commits a forest with a declared class and a deployed contract with storage.
+async fn commit_test_forest(storage: &mut MapStorage) -> ForestRoots {
+    let state_diff = StateDiff {
+        address_to_class_hash: HashMap::from([(
+            ContractAddress::from(0x11_u128),
+            ClassHash(Felt::ONE),
+        )]),
+        address_to_nonce: HashMap::from([(ContractAddress::from(0x11_u128), Nonce(Felt::ONE))]),
+        class_hash_to_compiled_class_hash: HashMap::from([(
+            ClassHash(Felt::ONE),
+            CompiledClassHash(Felt::from(0x31_u8)),
+        )]),
+        storage_updates: HashMap::from([(
+            ContractAddress::from(0x11_u128),
+            HashMap::from([(
+                StarknetStorageKey(StorageKey::from(0x5_u128)),
+                StarknetStorageValue(Felt::from(0x7_u8)),
+            )]),
+        )]),
+    };
+    let input = Input {
+        state_diff,
+        contracts_trie_root_hash: Default::default(),
+        classes_trie_root_hash: Default::default(),
+        config: ConfigImpl::default(),
+    };
+    let filled_forest = commit_block(input, &*storage).await.unwrap();
+    filled_forest.write_to_storage(storage);
+    ForestRoots {
+        contracts_trie_root_hash: filled_forest.get_contract_root_hash(),
+        classes_trie_root_hash: filled_forest.get_compiled_class_root_hash(),
+    }
+}
+
+/// Returns the sorted keys of the given JSON object.
+fn object_keys(value: &Value) -> Vec<&str> {
+    let mut keys: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+    keys.sort();
+    keys
+}
+
+/// Asserts that each node of the given proof is a binary node or an edge node.
+fn assert_node_shapes(proof: &Value) {
+    for node_hash_to_node in proof.as_array().unwrap() {
+        assert_eq!(object_keys(node_hash_to_node), vec!["node", "node_hash"]);
+        let node_keys = object_keys(&node_hash_to_node["node"]);
+        assert!(
+            node_keys == vec!["left", "right"] || node_keys == vec!["child", "length", "path"],
+            "Unexpected node: {node_hash_to_node}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn storage_proof_command_output() {
+    let mut storage = MapStorage::new();
+    let forest_roots = commit_test_forest(&mut storage).await;
+    let felt_bytes = |felt: Felt| felt.to_bytes_be();
+    let input = json!({
+        "storage": storage
+            .iter()
+            .map(|(key, value)| json!({ "key": key.0, "value": value.0 }))
+            .collect::<Vec<_>>(),
+        "contracts_trie_root_hash": felt_bytes(forest_roots.contracts_trie_root_hash.0),
+        "classes_trie_root_hash": felt_bytes(forest_roots.classes_trie_root_hash.0),
+        "class_hashes": [felt_bytes(Felt::ONE)],
+        "contract_addresses": [felt_bytes(Felt::from(0x11_u8)), felt_bytes(Felt::from(0x13_u8))],
+        "contracts_storage_keys": [{
+            "address": felt_bytes(Felt::from(0x11_u8)),
+            "storage_keys": [felt_bytes(Felt::from(0x5_u8)), felt_bytes(Felt::from(0x9_u8))],
+        }],
+    });
+    let input_file = NamedTempFile::new().unwrap();
+    fs::write(input_file.path(), input.to_string()).unwrap();
+    let output_file = NamedTempFile::new().unwrap();
+
+    parse_and_prove(
+        input_file.path().to_str().unwrap().to_string(),
+        output_file.path().to_str().unwrap().to_string(),
+    );
+
+    let output: Value = serde_json::from_slice(&fs::read(output_file.path()).unwrap()).unwrap();
+    assert_eq!(
+        object_keys(&output),
+        vec!["classes_proof", "contracts_proof", "contracts_storage_proofs", "global_roots"]
+    );
+    // Unlike the RPC method, the roots aren't of a block, so they have no block hash.
+    assert_eq!(
+        output["global_roots"],
+        json!({
+            "contracts_tree_root": forest_roots.contracts_trie_root_hash.0,
+            "classes_tree_root": forest_roots.classes_trie_root_hash.0,
+        })
+    );
+    assert_node_shapes(&output["classes_proof"]);
+    assert_eq!(object_keys(&output["contracts_proof"]), vec!["contract_leaves_data", "nodes"]);
+    assert_node_shapes(&output["contracts_proof"]["nodes"]);
+    let contract_leaves_data = output["contracts_proof"]["contract_leaves_data"].clone();
+    assert_eq!(contract_leaves_data.as_array().unwrap().len(), 2);
+    assert_eq!(object_keys(&contract_leaves_data[0]), vec!["class_hash", "nonce", "storage_root"]);
+    assert_eq!(contract_leaves_data[0]["class_hash"], json!(ClassHash(Felt::ONE)));
+    assert_eq!(contract_leaves_data[0]["nonce"], json!(Nonce(Felt::ONE)));
+    assert_eq!(
+        contract_leaves_data[1],
+        json!({
+            "class_hash": ClassHash::default(),
+            "nonce": Nonce::default(),
+            "storage_root": Felt::ZERO,
+        })
+    );
+    let contracts_storage_proofs = output["contracts_storage_proofs"].as_array().unwrap();
+    assert_eq!(contracts_storage_proofs.len(), 1);
+    assert_node_shapes(&contracts_storage_proofs[0]);
+    assert!(contracts_storage_proofs[0]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|node| node["node_hash"] == contract_leaves_data[0]["storage_root"]));
+}
+
+#[test]
+fn storage_proof_output_of_missing_contract() {
+    let address = ContractAddress::from(0x11_u128);
+    let input = StorageProofInput {
+        storage: MapStorage::new(),
+        forest_roots: ForestRoots::default(),
+        class_hashes: vec![],
+        contract_addresses: vec![address],
+        contracts_storage_keys: vec![],
+    };
+    assert_matches!(
+        StorageProofOutput::new(StorageProof::default(), &input),
+        Err(ForestError::MissingContractProof(missing_address)) if missing_address == address
+    );
+}
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 
 use ethnum::U256;
 use serde_json::json;
+use starknet_api::block::BlockNumber;
 use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_committer::block_committer::input::{
     StarknetStorageKey,
@@ -614,7 +615,7 @@ pub(crate) fn filled_forest_output_test() -> CommitterPythonTestResult {
     let dummy_forest = SerializedForest(FilledForest::dummy_random(&mut rand::thread_rng(), None));
     let mut storage = HashMap::new();
     let map_storage = BorrowedMapStorage { storage: &mut storage };
-    let output = dummy_forest.forest_to_output(map_storage);
+    let output = dummy_forest.forest_to_output(map_storage, BlockNumber(0));
     let output_string = serde_json::to_string(&output).expect("Failed to serialize");
     Ok(output_string)
 }
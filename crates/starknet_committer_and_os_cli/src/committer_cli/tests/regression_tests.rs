@@ -25,6 +25,8 @@ const SINGLE_TREE_FLOW_INPUT: &str = include_str!("../../../test_inputs/tree_flo
 const FLOW_TEST_INPUT: &str = include_str!("../../../test_inputs/committer_flow_inputs.json");
 const OUTPUT_PATH: &str = "benchmark_output.txt";
 const EXPECTED_NUMBER_OF_FILES: usize = 100;
+const GARBAGE_COLLECTION_KEY_PREFIXES: [&[u8]; 3] =
+    [b"node_reference_count:", b"forest_roots:", b"forest_roots_marker:"];
 
 #[derive(derive_more::Deref)]
 struct FactMap(Map<String, Value>);
@@ -132,6 +134,10 @@ pub async fn test_regression_single_tree() {
     assert!(execution_time.as_secs_f64() < MAX_TIME_FOR_SINGLE_TREE_BECHMARK_TEST);
 }
 
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
 pub async fn test_single_committer_flow(input: String, output_path: String) -> Result<(), Error> {
     let CommitterRegressionInput {
         committer_input,
@@ -140,17 +146,23 @@ pub async fn test_single_committer_flow(input: String, output_path: String) -> R
         expected_facts,
     } = serde_json::from_str(&input).unwrap();
     // Benchmark the committer flow test.
-    commit(committer_input.input, output_path.to_owned(), committer_input.storage).await;
+    let mut storage = committer_input.storage;
+    commit(committer_input.input, output_path.to_owned(), &mut storage).await;
 
     // Assert correctness of the output of the committer flow test.
     let CommitterRegressionOutput {
         contract_storage_root_hash,
         compiled_class_root_hash,
-        storage: StorageObject { storage: Value::Object(storage_changes) },
+        storage: StorageObject { storage: Value::Object(mut storage_changes) },
     } = serde_json::from_str(&std::fs::read_to_string(output_path).unwrap()).unwrap()
     else {
         panic!("Expected the storage to be an object.");
     };
+    // The expected facts are only the nodes of the tries, without the reference counts of the
+    // nodes and the roots of the block.
+    storage_changes.retain(|key, _| {
+        !GARBAGE_COLLECTION_KEY_PREFIXES.iter().any(|prefix| key.starts_with(&hex_encode(prefix)))
+    });
 
     assert_eq!(contract_storage_root_hash, expected_contract_states_root);
     assert_eq!(compiled_class_root_hash, expected_contract_classes_root);
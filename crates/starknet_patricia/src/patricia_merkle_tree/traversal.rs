@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use starknet_patricia_storage::errors::{DeserializationError, StorageError};
+use starknet_patricia_storage::storage_trait::{create_db_key, DbKey, Storage};
+use thiserror::Error;
+
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::filled_tree::node::FilledNode;
+use crate::patricia_merkle_tree::filled_tree::node_serde::PatriciaPrefix;
+use crate::patricia_merkle_tree::node_data::inner_node::{BinaryData, EdgeData, NodeData};
+use crate::patricia_merkle_tree::node_data::leaf::Leaf;
+use crate::patricia_merkle_tree::original_skeleton_tree::utils::{get_node_height, split_leaves};
+use crate::patricia_merkle_tree::types::{NodeIndex, SortedLeafIndices};
+use crate::patricia_merkle_tree::updated_skeleton_tree::hash_function::TreeHashFunction;
+
+#[cfg(test)]
+#[path = "traversal_test.rs"]
+pub mod traversal_test;
+
+#[derive(Debug, Error)]
+pub enum TraversalError {
+    #[error("Failed to deserialize the storage value: {0:?} while traversing the tree.")]
+    Deserialization(#[from] DeserializationError),
+    #[error("Unable to read from storage the storage key: {0:?} while traversing the tree.")]
+    StorageRead(#[from] StorageError),
+}
+
+pub type TraversalResult<T> = Result<T, TraversalError>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ProofVerificationError {
+    #[error("The pre-image of the node {0:?} is missing from the proof.")]
+    MissingNode(HashOutput),
+    #[error("The pre-image of the node {0:?} doesn't hash to it.")]
+    InvalidPreimage(HashOutput),
+    #[error("The proof doesn't match the value of the leaf at {0:?}.")]
+    LeafMismatch(NodeIndex),
+}
+
+pub type ProofVerificationResult<T> = Result<T, ProofVerificationError>;
+
+/// The pre-image of an inner node's hash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Preimage {
+    Binary(BinaryData),
+    Edge(EdgeData),
+}
+
+impl<L: Leaf> From<Preimage> for NodeData<L> {
+    fn from(preimage: Preimage) -> Self {
+        match preimage {
+            Preimage::Binary(binary_data) => Self::Binary(binary_data),
+            Preimage::Edge(edge_data) => Self::Edge(edge_data),
+        }
+    }
+}
+
+/// Maps the hashes of inner nodes to their pre-images.
+pub type PreimageMap = HashMap<HashOutput, Preimage>;
+
+/// The Patricia paths from the root of a tree to a set of leaves, i.e., the Merkle proofs of the
+/// leaves.
+#[derive(Debug, Default, PartialEq)]
+pub struct PatriciaPaths<L: Leaf> {
+    /// The inner nodes along the paths.
+    pub nodes: PreimageMap,
+    /// The non-empty leaves that were reached. A leaf that isn't reached is empty, and its path
+    /// ends in the edge node that diverges from it.
+    pub leaves: HashMap<NodeIndex, L>,
+}
+
+#[derive(Debug)]
+struct SubTree<'a> {
+    sorted_leaf_indices: SortedLeafIndices<'a>,
+    root_index: NodeIndex,
+    root_hash: HashOutput,
+}
+
+impl SubTree<'_> {
+    fn db_key<L: Leaf>(&self) -> DbKey {
+        node_db_key::<L>(&self.root_index, &self.root_hash)
+    }
+}
+
+/// Returns the storage key of the node with the given hash at the given index of a tree.
+pub fn node_db_key<L: Leaf>(index: &NodeIndex, hash: &HashOutput) -> DbKey {
+    let prefix = if index.is_leaf() {
+        PatriciaPrefix::Leaf(L::get_static_prefix())
+    } else {
+        PatriciaPrefix::InnerNode
+    };
+    create_db_key(prefix.into(), &hash.0.to_bytes_be())
+}
+
+/// Returns the indices and hashes of the children of the node at the given index. The child of an
+/// edge node is its bottom, and leaves have no children.
+pub fn node_children<L: Leaf>(
+    index: NodeIndex,
+    node_data: &NodeData<L>,
+) -> Vec<(NodeIndex, HashOutput)> {
+    match node_data {
+        NodeData::Binary(BinaryData { left_hash, right_hash }) => {
+            let [left_index, right_index] = index.get_children_indices();
+            vec![(left_index, *left_hash), (right_index, *right_hash)]
+        }
+        NodeData::Edge(EdgeData { bottom_hash, path_to_bottom }) => {
+            vec![(path_to_bottom.bottom_index(index), *bottom_hash)]
+        }
+        NodeData::Leaf(_) => vec![],
+    }
+}
+
+/// Fetches from storage the Patricia paths from the root with the given hash to the given leaves.
+/// Traverses the tree layer by layer, and reads each layer with a single `mget`.
+pub fn fetch_patricia_paths<L: Leaf>(
+    storage: &impl Storage,
+    root_hash: HashOutput,
+    sorted_leaf_indices: SortedLeafIndices<'_>,
+) -> TraversalResult<PatriciaPaths<L>> {
+    let mut paths = PatriciaPaths::default();
+    if sorted_leaf_indices.is_empty() || root_hash == HashOutput::ROOT_OF_EMPTY_TREE {
+        return Ok(paths);
+    }
+    let mut subtrees =
+        vec![SubTree { sorted_leaf_indices, root_index: NodeIndex::ROOT, root_hash }];
+    while !subtrees.is_empty() {
+        let db_keys: Vec<DbKey> = subtrees.iter().map(SubTree::db_key::<L>).collect();
+        let db_values = storage.mget(&db_keys);
+        let mut next_subtrees = Vec::new();
+        for ((subtree, optional_value), db_key) in subtrees.into_iter().zip(db_values).zip(db_keys)
+        {
+            let value = optional_value.ok_or(StorageError::MissingKey(db_key))?;
+            let node: FilledNode<L> =
+                FilledNode::deserialize(subtree.root_hash, &value, subtree.root_index.is_leaf())?;
+            match node.data {
+                NodeData::Binary(binary_data) => {
+                    let [left_leaves, right_leaves] =
+                        split_leaves(&subtree.root_index, &subtree.sorted_leaf_indices);
+                    let [left_index, right_index] = subtree.root_index.get_children_indices();
+                    for (sorted_leaf_indices, root_index, root_hash) in [
+                        (left_leaves, left_index, binary_data.left_hash),
+                        (right_leaves, right_index, binary_data.right_hash),
+                    ] {
+                        if !sorted_leaf_indices.is_empty() {
+                            next_subtrees.push(SubTree {
+                                sorted_leaf_indices,
+                                root_index,
+                                root_hash,
+                            });
+                        }
+                    }
+                    paths.nodes.insert(node.hash, Preimage::Binary(binary_data));
+                }
+                NodeData::Edge(edge_data) => {
+                    // Only the leaves under the bottom of the edge may be non-empty.
+                    let bottom_index = edge_data.path_to_bottom.bottom_index(subtree.root_index);
+                    let bottom_height = u8::from(get_node_height(&bottom_index));
+                    let first_leaf_in_bottom = bottom_index << bottom_height;
+                    let last_leaf_in_bottom = first_leaf_in_bottom
+                        + ((NodeIndex::ROOT << bottom_height) - NodeIndex::ROOT);
+                    let bottom_leaves = subtree.sorted_leaf_indices.subslice(
+                        subtree.sorted_leaf_indices.bisect_left(&first_leaf_in_bottom),
+                        subtree.sorted_leaf_indices.bisect_right(&last_leaf_in_bottom),
+                    );
+                    if !bottom_leaves.is_empty() {
+                        next_subtrees.push(SubTree {
+                            sorted_leaf_indices: bottom_leaves,
+                            root_index: bottom_index,
+                            root_hash: edge_data.bottom_hash,
+                        });
+                    }
+                    paths.nodes.insert(node.hash, Preimage::Edge(edge_data));
+                }
+                NodeData::Leaf(leaf) => {
+                    paths.leaves.insert(subtree.root_index, leaf);
+                }
+            }
+        }
+        subtrees = next_subtrees;
+    }
+    Ok(paths)
+}
+
+/// Verifies that the given nodes prove the value of the leaf at the given index, against the root
+/// with the given hash. An empty leaf is proven by a path that ends in an edge node diverging from
+/// it, or by an empty tree.
+pub fn verify_patricia_path<L: Leaf, TH: TreeHashFunction<L>>(
+    nodes: &PreimageMap,
+    root_hash: HashOutput,
+    leaf_index: NodeIndex,
+    leaf: &L,
+) -> ProofVerificationResult<()> {
+    let verify_empty_leaf = || {
+        if leaf.is_empty() {
+            Ok(())
+        } else {
+            Err(ProofVerificationError::LeafMismatch(leaf_index))
+        }
+    };
+    if root_hash == HashOutput::ROOT_OF_EMPTY_TREE {
+        return verify_empty_leaf();
+    }
+    let mut index = NodeIndex::ROOT;
+    let mut hash = root_hash;
+    while index != leaf_index {
+        let preimage = nodes.get(&hash).ok_or(ProofVerificationError::MissingNode(hash))?;
+        if TH::compute_node_hash(&NodeData::from(preimage.clone())) != hash {
+            return Err(ProofVerificationError::InvalidPreimage(hash));
+        }
+        match preimage {
+            Preimage::Binary(BinaryData { left_hash, right_hash }) => {
+                let child_index = leaf_index >> (u8::from(get_node_height(&index)) - 1);
+                let [left_index, _] = index.get_children_indices();
+                hash = if child_index == left_index { *left_hash } else { *right_hash };
+                index = child_index;
+            }
+            Preimage::Edge(EdgeData { bottom_hash, path_to_bottom }) => {
+                let bottom_index = path_to_bottom.bottom_index(index);
+                if leaf_index >> u8::from(get_node_height(&bottom_index)) != bottom_index {
+                    // The edge diverges from the leaf, so the leaf is empty.
+                    return verify_empty_leaf();
+                }
+                hash = *bottom_hash;
+                index = bottom_index;
+            }
+        }
+    }
+    if leaf.is_empty() || TH::compute_leaf_hash(leaf) != hash {
+        return Err(ProofVerificationError::LeafMismatch(leaf_index));
+    }
+    Ok(())
+}
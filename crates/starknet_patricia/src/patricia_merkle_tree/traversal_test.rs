@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use ethnum::U256;
+use pretty_assertions::assert_eq;
+use starknet_patricia_storage::map_storage::MapStorage;
+use starknet_types_core::felt::Felt;
+
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::external_test_utils::{
+    create_binary_entry,
+    create_edge_entry,
+    create_root_edge_entry,
+};
+use crate::patricia_merkle_tree::internal_test_utils::{small_tree_index_to_full, MockLeaf};
+use crate::patricia_merkle_tree::node_data::inner_node::{
+    BinaryData,
+    EdgeData,
+    EdgePathLength,
+    PathToBottom,
+};
+use crate::patricia_merkle_tree::original_skeleton_tree::create_tree::create_tree_test::create_mock_leaf_entry;
+use crate::patricia_merkle_tree::traversal::{fetch_patricia_paths, PatriciaPaths, Preimage};
+use crate::patricia_merkle_tree::types::{NodeIndex, SortedLeafIndices, SubTreeHeight};
+
+fn hash(value: u128) -> HashOutput {
+    HashOutput(Felt::from(value))
+}
+
+fn binary(left: u128, right: u128) -> (HashOutput, Preimage) {
+    (
+        hash(left + right),
+        Preimage::Binary(BinaryData { left_hash: hash(left), right_hash: hash(right) }),
+    )
+}
+
+fn edge(bottom: u128, path: u128, length: u8) -> (HashOutput, Preimage) {
+    (
+        hash(bottom + path + u128::from(length)),
+        Preimage::Edge(EdgeData {
+            bottom_hash: hash(bottom),
+            path_to_bottom: PathToBottom::new(path.into(), EdgePathLength::new(length).unwrap())
+                .unwrap(),
+        }),
+    )
+}
+
+// This test assumes for simplicity that hash is addition (i.e hash(a,b) = a + b).
+///                 Tree structure:
+///
+///                             50
+///                           /   \
+///                         30     20
+///                        /  \     \
+///                       17  13     *
+///                      /  \   \     \
+///                     8    9  11     15
+///
+///                   Requested leaves indices: [8, 10, 14]
+#[test]
+fn fetch_paths_of_present_and_absent_leaves() {
+    let height = SubTreeHeight::new(3);
+    let storage = MapStorage::from([
+        create_root_edge_entry(50, height),
+        create_binary_entry(8, 9),
+        create_edge_entry(11, 1, 1),
+        create_binary_entry(17, 13),
+        create_edge_entry(15, 3, 2),
+        create_binary_entry(30, 20),
+        create_mock_leaf_entry(8),
+        create_mock_leaf_entry(9),
+        create_mock_leaf_entry(11),
+        create_mock_leaf_entry(15),
+    ]);
+    let mut leaf_indices: Vec<NodeIndex> = [8_u8, 10, 14]
+        .into_iter()
+        .map(|index| small_tree_index_to_full(U256::from(index), height))
+        .collect();
+
+    let paths = fetch_patricia_paths::<MockLeaf>(
+        &storage,
+        hash(50 + 248),
+        SortedLeafIndices::new(&mut leaf_indices),
+    )
+    .unwrap();
+
+    let expected_paths = PatriciaPaths {
+        nodes: HashMap::from([
+            edge(50, 0, 248),
+            binary(30, 20),
+            binary(17, 13),
+            binary(8, 9),
+            edge(11, 1, 1),
+            edge(15, 3, 2),
+        ]),
+        leaves: HashMap::from([(
+            small_tree_index_to_full(U256::from(8_u8), height),
+            MockLeaf(Felt::from(8_u8)),
+        )]),
+    };
+    assert_eq!(paths, expected_paths);
+}
+
+#[test]
+fn fetch_paths_in_empty_tree() {
+    let mut leaf_indices = vec![NodeIndex::FIRST_LEAF];
+    let paths = fetch_patricia_paths::<MockLeaf>(
+        &MapStorage::default(),
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        SortedLeafIndices::new(&mut leaf_indices),
+    )
+    .unwrap();
+    assert_eq!(paths, PatriciaPaths::default());
+}
@@ -9,6 +9,10 @@ use crate::storage_trait::DbKey;
 pub enum StorageError {
     #[error("The key {0:?} does not exist in storage.")]
     MissingKey(DbKey),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Mdbx(#[from] libmdbx::Error),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -26,12 +26,12 @@ impl Storage for MapStorage {
         self.remove(key)
     }
 
-    fn get(&self, key: &DbKey) -> Option<&DbValue> {
-        self.get(key)
+    fn get(&self, key: &DbKey) -> Option<DbValue> {
+        self.get(key).cloned()
     }
 
-    fn mget(&self, keys: &[DbKey]) -> Vec<Option<&DbValue>> {
-        keys.iter().map(|key| self.get(key)).collect()
+    fn mget(&self, keys: &[DbKey]) -> Vec<Option<DbValue>> {
+        keys.iter().map(|key| self.get(key).cloned()).collect()
     }
 }
 
@@ -48,11 +48,11 @@ impl<S: Storage> Storage for BorrowedStorage<'_, S> {
         self.storage.delete(key)
     }
 
-    fn get(&self, key: &DbKey) -> Option<&DbValue> {
+    fn get(&self, key: &DbKey) -> Option<DbValue> {
         self.storage.get(key)
     }
 
-    fn mget(&self, keys: &[DbKey]) -> Vec<Option<&DbValue>> {
+    fn mget(&self, keys: &[DbKey]) -> Vec<Option<DbValue>> {
         self.storage.mget(keys)
     }
 }
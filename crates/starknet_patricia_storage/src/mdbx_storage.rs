@@ -0,0 +1,199 @@
+//! A durable [`Storage`] implementation on top of libmdbx.
+//!
+//! All the key-value pairs are kept in the unnamed table of a single MDBX environment. Every
+//! mutating call is performed in its own write transaction, so in particular a whole
+//! [`Storage::mset`] (e.g., the output of a single block commitment) is either persisted entirely
+//! or not at all. Recently read and written values are kept in an LRU cache.
+
+#[cfg(test)]
+#[path = "mdbx_storage_test.rs"]
+mod mdbx_storage_test;
+
+use std::borrow::Cow;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use libmdbx::{DatabaseFlags, Geometry, WriteFlags, WriteMap};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::StorageError;
+use crate::map_storage::MapStorage;
+use crate::storage_trait::{DbKey, DbValue, Storage};
+
+type Environment = libmdbx::Database<WriteMap>;
+
+pub type MdbxStorageResult<T> = Result<T, StorageError>;
+
+/// The configuration of an [`MdbxStorage`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MdbxStorageConfig {
+    /// The directory of the database files. It is created if it does not exist.
+    pub path: PathBuf,
+    /// The minimum size of the database in bytes.
+    pub min_size: usize,
+    /// The maximum size of the database in bytes.
+    pub max_size: usize,
+    /// The growth step of the database in bytes.
+    pub growth_step: isize,
+    /// The maximal number of values kept in the read cache.
+    pub cache_size: NonZeroUsize,
+}
+
+impl Default for MdbxStorageConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("./patricia_storage"),
+            min_size: 1 << 20,    // 1MB
+            max_size: 1 << 40,    // 1TB
+            growth_step: 1 << 32, // 4GB
+            cache_size: NonZeroUsize::new(1 << 20).expect("Cache size must be positive."),
+        }
+    }
+}
+
+/// A [`Storage`] persisted in an MDBX environment.
+///
+/// Since the [`Storage`] trait is infallible, a failure of the underlying database while reading
+/// or writing is considered unrecoverable and causes a panic.
+pub struct MdbxStorage {
+    env: Environment,
+    cache: Mutex<LruCache<DbKey, DbValue>>,
+}
+
+impl MdbxStorage {
+    /// Opens (or creates) the database at the configured path.
+    pub fn open(config: &MdbxStorageConfig) -> MdbxStorageResult<Self> {
+        std::fs::create_dir_all(&config.path)?;
+        let env = Environment::new()
+            .set_geometry(Geometry {
+                size: Some(config.min_size..config.max_size),
+                growth_step: Some(config.growth_step),
+                ..Default::default()
+            })
+            .set_flags(DatabaseFlags {
+                // Trie nodes are accessed by their hash, so there is no locality to exploit.
+                no_rdahead: true,
+                liforeclaim: true,
+                ..Default::default()
+            })
+            .open(&config.path)?;
+        Ok(Self { env, cache: Mutex::new(LruCache::new(config.cache_size)) })
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, LruCache<DbKey, DbValue>> {
+        self.cache.lock().expect("The read cache lock is poisoned.")
+    }
+
+    fn read(&self, keys: &[&DbKey]) -> MdbxStorageResult<Vec<Option<DbValue>>> {
+        let txn = self.env.begin_ro_txn()?;
+        let table = txn.open_table(None)?;
+        keys.iter()
+            .map(|key| {
+                Ok(txn
+                    .get::<Cow<'_, [u8]>>(&table, &key.0)?
+                    .map(|value| DbValue(value.into_owned())))
+            })
+            .collect()
+    }
+
+    fn write_batch(&self, key_to_value: &MapStorage) -> MdbxStorageResult<()> {
+        let txn = self.env.begin_rw_txn()?;
+        {
+            let table = txn.open_table(None)?;
+            for (key, value) in key_to_value {
+                txn.put(&table, &key.0, &value.0, WriteFlags::UPSERT)?;
+            }
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn replace(&self, key: &DbKey, value: &DbValue) -> MdbxStorageResult<Option<DbValue>> {
+        let txn = self.env.begin_rw_txn()?;
+        let old_value = {
+            let table = txn.open_table(None)?;
+            let old_value =
+                txn.get::<Cow<'_, [u8]>>(&table, &key.0)?.map(|value| DbValue(value.into_owned()));
+            txn.put(&table, &key.0, &value.0, WriteFlags::UPSERT)?;
+            old_value
+        };
+        txn.commit()?;
+        Ok(old_value)
+    }
+
+    fn remove(&self, key: &DbKey) -> MdbxStorageResult<Option<DbValue>> {
+        let txn = self.env.begin_rw_txn()?;
+        let old_value = {
+            let table = txn.open_table(None)?;
+            let old_value =
+                txn.get::<Cow<'_, [u8]>>(&table, &key.0)?.map(|value| DbValue(value.into_owned()));
+            if old_value.is_some() {
+                txn.del(&table, &key.0, None)?;
+            }
+            old_value
+        };
+        txn.commit()?;
+        Ok(old_value)
+    }
+}
+
+impl Storage for MdbxStorage {
+    fn get(&self, key: &DbKey) -> Option<DbValue> {
+        self.mget(std::slice::from_ref(key)).pop().flatten()
+    }
+
+    fn set(&mut self, key: DbKey, value: DbValue) -> Option<DbValue> {
+        let old_value =
+            self.replace(&key, &value).unwrap_or_else(|error| panic!("Failed to write: {error}."));
+        self.lock_cache().put(key, value);
+        old_value
+    }
+
+    fn mget(&self, keys: &[DbKey]) -> Vec<Option<DbValue>> {
+        let mut values: Vec<Option<DbValue>> = {
+            let mut cache = self.lock_cache();
+            keys.iter().map(|key| cache.get(key).cloned()).collect()
+        };
+        let missing_keys: Vec<&DbKey> = keys
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_none())
+            .map(|(key, _)| key)
+            .collect();
+        if missing_keys.is_empty() {
+            return values;
+        }
+
+        let read_values =
+            self.read(&missing_keys).unwrap_or_else(|error| panic!("Failed to read: {error}."));
+        let mut cache = self.lock_cache();
+        let mut read_values = read_values.into_iter();
+        for (key, value) in keys.iter().zip(values.iter_mut()) {
+            if value.is_some() {
+                continue;
+            }
+            *value = read_values.next().expect("A value is read for every missing key.");
+            if let Some(read_value) = value {
+                cache.put(key.clone(), read_value.clone());
+            }
+        }
+        values
+    }
+
+    fn mset(&mut self, key_to_value: MapStorage) {
+        self.write_batch(&key_to_value).unwrap_or_else(|error| panic!("Failed to write: {error}."));
+        let mut cache = self.lock_cache();
+        for (key, value) in key_to_value {
+            cache.put(key, value);
+        }
+    }
+
+    fn delete(&mut self, key: &DbKey) -> Option<DbValue> {
+        let old_value =
+            self.remove(key).unwrap_or_else(|error| panic!("Failed to delete: {error}."));
+        self.lock_cache().pop(key);
+        old_value
+    }
+}
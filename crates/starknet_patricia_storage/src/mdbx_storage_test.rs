@@ -0,0 +1,60 @@
+use std::num::NonZeroUsize;
+
+use tempfile::TempDir;
+
+use crate::map_storage::MapStorage;
+use crate::mdbx_storage::{MdbxStorage, MdbxStorageConfig};
+use crate::storage_trait::{DbKey, DbValue, Storage};
+
+fn test_config(dir: &TempDir) -> MdbxStorageConfig {
+    MdbxStorageConfig {
+        path: dir.path().to_path_buf(),
+        growth_step: 1 << 20,
+        cache_size: NonZeroUsize::new(2).unwrap(),
+        ..Default::default()
+    }
+}
+
+fn key(byte: u8) -> DbKey {
+    DbKey(vec![byte])
+}
+
+fn value(byte: u8) -> DbValue {
+    DbValue(vec![byte, byte])
+}
+
+#[test]
+fn set_get_and_delete() {
+    let dir = TempDir::new().unwrap();
+    let mut storage = MdbxStorage::open(&test_config(&dir)).unwrap();
+
+    assert_eq!(storage.get(&key(1)), None);
+    assert_eq!(storage.set(key(1), value(1)), None);
+    assert_eq!(storage.set(key(1), value(2)), Some(value(1)));
+    assert_eq!(storage.get(&key(1)), Some(value(2)));
+    assert_eq!(storage.delete(&key(1)), Some(value(2)));
+    assert_eq!(storage.delete(&key(1)), None);
+    assert_eq!(storage.get(&key(1)), None);
+}
+
+#[test]
+fn mset_is_persisted_across_reopening() {
+    let dir = TempDir::new().unwrap();
+    let config = test_config(&dir);
+    let keys: Vec<DbKey> = (0..5).map(key).collect();
+    {
+        let mut storage = MdbxStorage::open(&config).unwrap();
+        storage.mset(MapStorage::from_iter((0..4).map(|i| (key(i), value(i)))));
+        // The cache holds only two values, so the rest are read from the database.
+        assert_eq!(
+            storage.mget(&keys),
+            vec![Some(value(0)), Some(value(1)), Some(value(2)), Some(value(3)), None]
+        );
+    }
+
+    let storage = MdbxStorage::open(&config).unwrap();
+    assert_eq!(
+        storage.mget(&keys),
+        vec![Some(value(0)), Some(value(1)), Some(value(2)), Some(value(3)), None]
+    );
+}
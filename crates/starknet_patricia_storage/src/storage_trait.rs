@@ -3,17 +3,19 @@ use starknet_types_core::felt::Felt;
 
 use crate::map_storage::MapStorage;
 
-#[derive(Debug, Eq, Hash, PartialEq)]
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct DbKey(pub Vec<u8>);
 
-#[derive(Debug, Eq, PartialEq, Serialize)]
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct DbValue(pub Vec<u8>);
 
+/// A key-value store of the facts of Patricia tries.
+///
+/// Values are returned owned rather than borrowed: a storage that is backed by a database reads
+/// its values through short-lived transactions, and can't return references that outlive them.
 pub trait Storage {
     /// Returns value from storage, if it exists.
-    fn get(&self, key: &DbKey) -> Option<&DbValue>;
+    fn get(&self, key: &DbKey) -> Option<DbValue>;
 
     /// Sets value in storage. If key already exists, its value is overwritten and the old value is
     /// returned.
@@ -21,7 +23,7 @@ pub trait Storage {
 
     /// Returns values from storage in same order of given keys. Value is None for keys that do not
     /// exist.
-    fn mget(&self, keys: &[DbKey]) -> Vec<Option<&DbValue>>;
+    fn mget(&self, keys: &[DbKey]) -> Vec<Option<DbValue>>;
 
     /// Sets values in storage.
     fn mset(&mut self, key_to_value: MapStorage);